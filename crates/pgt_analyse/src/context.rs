@@ -5,6 +5,7 @@ use crate::{
 
 pub struct RuleContext<'a, R: Rule> {
     stmt: &'a pgt_query_ext::NodeEnum,
+    text: &'a str,
     options: &'a R::Options,
 }
 
@@ -13,8 +14,12 @@ where
     R: Rule + Sized + 'static,
 {
     #[allow(clippy::too_many_arguments)]
-    pub fn new(stmt: &'a pgt_query_ext::NodeEnum, options: &'a R::Options) -> Self {
-        Self { stmt, options }
+    pub fn new(stmt: &'a pgt_query_ext::NodeEnum, text: &'a str, options: &'a R::Options) -> Self {
+        Self {
+            stmt,
+            text,
+            options,
+        }
     }
 
     /// Returns the group that belongs to the current rule
@@ -32,6 +37,11 @@ where
         self.stmt
     }
 
+    /// Returns the text of the statement, which the locations of the AST nodes point into
+    pub fn text(&self) -> &str {
+        self.text
+    }
+
     /// Returns the metadata of the rule
     ///
     /// The metadata contains information about the rule, such as the name, version, language, and whether it is recommended.
@@ -19,5 +19,6 @@ pub use crate::registry::{
     MetadataRegistry, RegistryRuleParams, RegistryVisitor, RuleRegistry, RuleRegistryBuilder,
 };
 pub use crate::rule::{
-    GroupCategory, Rule, RuleDiagnostic, RuleGroup, RuleMeta, RuleMetadata, RuleSource,
+    FixEdit, FixSafety, GroupCategory, Rule, RuleDiagnostic, RuleFix, RuleGroup, RuleMeta,
+    RuleMetadata, RuleSource,
 };
@@ -157,6 +157,7 @@ impl RuleRegistry {
 
 pub struct RegistryRuleParams<'a> {
     pub root: &'a pgt_query_ext::NodeEnum,
+    pub text: &'a str,
     pub options: &'a AnalyserOptions,
 }
 
@@ -174,7 +175,7 @@ impl RegistryRule {
             R: Rule<Options: Default> + 'static,
         {
             let options = params.options.rule_options::<R>().unwrap_or_default();
-            let ctx = RuleContext::new(params.root, &options);
+            let ctx = RuleContext::new(params.root, params.text, &options);
             R::run(&ctx)
         }
 
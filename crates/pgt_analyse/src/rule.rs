@@ -111,6 +111,35 @@ pub struct RuleDiagnostic {
     pub(crate) tags: DiagnosticTags,
     #[advice]
     pub(crate) rule_advice: RuleAdvice,
+    pub(crate) fix: Option<RuleFix>,
+}
+
+/// A fix for a [RuleDiagnostic], made of edits to the statement the diagnostic was reported for
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuleFix {
+    /// A short description of the fix, e.g. shown as the title of a code action
+    pub title: String,
+    /// The edits of the fix, which must not overlap
+    pub edits: Vec<FixEdit>,
+    /// Whether the fix can be applied without review
+    pub safety: FixSafety,
+}
+
+/// Replaces a range of the statement, relative to the start of the statement
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixEdit {
+    pub range: TextRange,
+    pub new_text: String,
+}
+
+/// Whether a [RuleFix] keeps the meaning of the statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixSafety {
+    /// The fix keeps the meaning of the statement and can be applied without review, e.g. by
+    /// `check --write`
+    Safe,
+    /// The fix changes the meaning or the formatting of the statement, so it has to be reviewed
+    Unsafe,
 }
 
 #[derive(Debug, Default, PartialEq)]
@@ -182,6 +211,7 @@ impl RuleDiagnostic {
             message: MessageAndDescription::from(message),
             tags: DiagnosticTags::empty(),
             rule_advice: RuleAdvice::default(),
+            fix: None,
         }
     }
 
@@ -209,6 +239,17 @@ impl RuleDiagnostic {
         self
     }
 
+    /// Attaches a fix to this [`RuleDiagnostic`] and marks it as fixable.
+    pub fn fix(mut self, title: impl Into<String>, edits: Vec<FixEdit>, safety: FixSafety) -> Self {
+        self.tags |= DiagnosticTags::FIXABLE;
+        self.fix = Some(RuleFix {
+            title: title.into(),
+            edits,
+            safety,
+        });
+        self
+    }
+
     /// Attaches a label to this [`RuleDiagnostic`].
     ///
     /// The given span has to be in the file that was provided while creating this [`RuleDiagnostic`].
@@ -264,6 +305,11 @@ impl RuleDiagnostic {
         &self.rule_advice
     }
 
+    /// Returns the fix of this diagnostic, if the rule provides one
+    pub fn get_fix(&self) -> Option<&RuleFix> {
+        self.fix.as_ref()
+    }
+
     /// Will return the rule's category name as defined via `define_categories! { .. }`.
     pub fn get_category_name(&self) -> &'static str {
         self.category.name()
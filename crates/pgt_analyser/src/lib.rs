@@ -5,10 +5,12 @@ use pgt_analyse::{
     RuleRegistry,
 };
 pub use registry::visit_registry;
+pub use rules::{RuleInfo, rules_metadata};
 
 mod lint;
 pub mod options;
 mod registry;
+mod rules;
 
 pub static METADATA: LazyLock<MetadataRegistry> = LazyLock::new(|| {
     let mut metadata = MetadataRegistry::default();
@@ -32,6 +34,8 @@ pub struct Analyser<'a> {
 
 pub struct AnalyserContext<'a> {
     pub root: &'a pgt_query_ext::NodeEnum,
+    /// The text `root` was parsed from
+    pub text: &'a str,
 }
 
 pub struct AnalyserConfig<'a> {
@@ -55,6 +59,7 @@ impl<'a> Analyser<'a> {
     pub fn run(&self, ctx: AnalyserContext) -> Vec<RuleDiagnostic> {
         let params = RegistryRuleParams {
             root: ctx.root,
+            text: ctx.text,
             options: self.options,
         };
 
@@ -86,7 +91,7 @@ mod tests {
     fn debug_test() {
         fn markup_to_string(markup: Markup) -> String {
             let mut buffer = Vec::new();
-            let mut write = Termcolor(NoColor::new(&mut buffer));
+            let mut write = Termcolor::new(NoColor::new(&mut buffer));
             let mut fmt = Formatter::new(&mut write);
             fmt.write_markup(markup).unwrap();
 
@@ -110,7 +115,10 @@ mod tests {
             filter,
         });
 
-        let results = analyser.run(crate::AnalyserContext { root: &ast });
+        let results = analyser.run(crate::AnalyserContext {
+            root: &ast,
+            text: SQL,
+        });
 
         println!("*******************");
         for result in &results {
@@ -1,4 +1,5 @@
 //! Generated file, do not edit by hand, see `xtask/codegen`
 
 pub mod safety;
-::pgt_analyse::declare_category! { pub Lint { kind : Lint , groups : [self :: safety :: Safety ,] } }
+pub mod style;
+::pgt_analyse::declare_category! { pub Lint { kind : Lint , groups : [self :: safety :: Safety , self :: style :: Style ,] } }
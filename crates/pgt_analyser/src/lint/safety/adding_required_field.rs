@@ -1,5 +1,8 @@
-use pgt_analyse::{Rule, RuleDiagnostic, RuleSource, context::RuleContext, declare_lint_rule};
+use pgt_analyse::{
+    FixEdit, FixSafety, Rule, RuleDiagnostic, RuleSource, context::RuleContext, declare_lint_rule,
+};
 use pgt_console::markup;
+use pgt_text_size::{TextRange, TextSize};
 
 declare_lint_rule! {
     /// Adding a new column that is NOT NULL and has no default value to an existing table effectively makes it required.
@@ -43,20 +46,28 @@ impl Rule for AddingRequiredField {
                     if alter_table_cmd.subtype()
                         == pgt_query_ext::protobuf::AlterTableType::AtAddColumn
                     {
-                        diagnostics.push(
-                            RuleDiagnostic::new(
-                                rule_category!(),
-                                None,
-                                markup! {
-                                    "Adding a new column that is NOT NULL and has no default value to an existing table effectively makes it required."
-                                },
-                            )
-                            .detail(
-                                None,
-                                "Make new columns optional initially by omitting the NOT NULL constraint until all existing data and application code has been updated. Once no NULL values are written to or persisted in the database, set it to NOT NULL. Alternatively, if using Postgres version 11 or later, add a DEFAULT value that is not volatile. This allows the column to keep its NOT NULL constraint.
+                        let diagnostic = RuleDiagnostic::new(
+                            rule_category!(),
+                            None,
+                            markup! {
+                                "Adding a new column that is NOT NULL and has no default value to an existing table effectively makes it required."
+                            },
+                        )
+                        .detail(
+                            None,
+                            "Make new columns optional initially by omitting the NOT NULL constraint until all existing data and application code has been updated. Once no NULL values are written to or persisted in the database, set it to NOT NULL. Alternatively, if using Postgres version 11 or later, add a DEFAULT value that is not volatile. This allows the column to keep its NOT NULL constraint.
                                 ",
-                        ),
-                    );
+                        );
+
+                        diagnostics.push(match omit_not_null(alter_table_cmd, ctx.text()) {
+                            // the column is no longer required
+                            Some(edits) => diagnostic.fix(
+                                "Omit the NOT NULL constraint",
+                                edits,
+                                FixSafety::Unsafe,
+                            ),
+                            None => diagnostic,
+                        });
                     }
                 }
             }
@@ -65,3 +76,70 @@ impl Rule for AddingRequiredField {
         diagnostics
     }
 }
+
+/// Returns the edits that remove the NOT NULL constraints of the column added by `cmd`
+/// from `text`, the text of the statement.
+fn omit_not_null(cmd: &pgt_query_ext::protobuf::AlterTableCmd, text: &str) -> Option<Vec<FixEdit>> {
+    let Some(pgt_query_ext::NodeEnum::ColumnDef(def)) =
+        cmd.def.as_ref().and_then(|d| d.node.as_ref())
+    else {
+        return None;
+    };
+
+    let edits = def
+        .constraints
+        .iter()
+        .filter_map(|c| match &c.node {
+            Some(pgt_query_ext::NodeEnum::Constraint(c))
+                if c.contype() == pgt_query_ext::protobuf::ConstrType::ConstrNotnull =>
+            {
+                Some(c)
+            }
+            _ => None,
+        })
+        .map(|c| {
+            not_null_range(text, usize::try_from(c.location).ok()?).map(|range| FixEdit {
+                range,
+                new_text: String::new(),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (!edits.is_empty()).then_some(edits)
+}
+
+/// Returns the range of the NOT NULL constraint starting at `location`, including its name
+/// and the whitespace in front of it, e.g. ` not null` of `age int not null`.
+fn not_null_range(text: &str, location: usize) -> Option<TextRange> {
+    let mut rest = text.get(location..)?;
+
+    // `constraint age_not_null not null`
+    if let Some(after) = strip_keyword(rest, "constraint") {
+        let after = after.trim_start();
+        let name_len = match after.strip_prefix('"') {
+            Some(quoted) => quoted.find('"')? + 2,
+            None => after
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(after.len()),
+        };
+        rest = &after[name_len..];
+    }
+
+    let rest = strip_keyword(rest.trim_start(), "not")?;
+    let rest = strip_keyword(rest.trim_start(), "null")?;
+
+    let start = text[..location].trim_end().len();
+    let end = text.len() - rest.len();
+
+    Some(TextRange::new(
+        TextSize::try_from(start).ok()?,
+        TextSize::try_from(end).ok()?,
+    ))
+}
+
+/// Strips the case insensitive `keyword` from the start of `text`
+fn strip_keyword<'a>(text: &'a str, keyword: &str) -> Option<&'a str> {
+    text.get(..keyword.len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case(keyword))
+        .map(|_| &text[keyword.len()..])
+}
@@ -0,0 +1,5 @@
+//! Generated file, do not edit by hand, see `xtask/codegen`
+
+use pgt_analyse::declare_lint_group;
+pub mod reserved_identifier;
+declare_lint_group! { pub Style { name : "style" , rules : [self :: reserved_identifier :: ReservedIdentifier ,] } }
@@ -0,0 +1,291 @@
+use pgt_analyse::{Rule, RuleDiagnostic, context::RuleContext, declare_lint_rule};
+use pgt_console::markup;
+use pgt_query_ext::{ChildrenIterator, NodeEnum};
+use pgt_text_size::{TextRange, TextSize};
+
+declare_lint_rule! {
+    /// Object names should not be reserved words.
+    ///
+    /// Names like `user` or `order` are keywords of SQL. A table or column with such a name has to be
+    /// quoted wherever it is referenced, and forgetting the quotes either fails to parse or, as for
+    /// `user`, silently means something else: `select user` returns the name of the current role.
+    ///
+    /// Rename the object instead, e.g. to `users` or `app_user`.
+    ///
+    /// ## Examples
+    ///
+    /// ### Invalid
+    ///
+    /// ```sql,expect_diagnostic
+    /// create table "user" (id int);
+    /// ```
+    ///
+    /// ### Valid
+    ///
+    /// ```sql
+    /// create table users (id int);
+    /// ```
+    pub ReservedIdentifier {
+        version: "next",
+        name: "reservedIdentifier",
+        recommended: false,
+    }
+}
+
+/// The keywords that are reserved in Postgres, including those that can be function or type names.
+/// Sorted, so that they can be searched.
+const RESERVED_WORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "binary",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "deferrable",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "false",
+    "fetch",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "natural",
+    "not",
+    "notnull",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "outer",
+    "overlaps",
+    "placing",
+    "primary",
+    "references",
+    "returning",
+    "right",
+    "select",
+    "session_user",
+    "similar",
+    "some",
+    "symmetric",
+    "system_user",
+    "table",
+    "tablesample",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+impl Rule for ReservedIdentifier {
+    type Options = ();
+
+    fn run(ctx: &RuleContext<Self>) -> Vec<RuleDiagnostic> {
+        let text = ctx.text();
+
+        // the names with the range of the identifier, if it can be found in the text
+        let mut names: Vec<(String, Option<TextRange>)> = Vec::new();
+
+        for node in ChildrenIterator::new(ctx.stmt().clone()) {
+            match node {
+                // the relation is the last part of a qualified name like `public."user"`
+                NodeEnum::RangeVar(n) => {
+                    let range = name_parts(text, n.location).last().copied();
+                    names.push((n.relname, range));
+                }
+                NodeEnum::ColumnDef(n) => {
+                    let range = name_parts(text, n.location).first().copied();
+                    names.push((n.colname, range));
+                }
+                NodeEnum::ColumnRef(n) => {
+                    let ranges = name_parts(text, n.location);
+                    names.extend(n.fields.into_iter().enumerate().filter_map(
+                        |(idx, f)| match f.node {
+                            Some(NodeEnum::String(s)) => Some((s.sval, ranges.get(idx).copied())),
+                            _ => None,
+                        },
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        // every name is reported once, at its first occurrence
+        let mut reported: Vec<(String, Option<TextRange>)> = Vec::new();
+        for (name, range) in names {
+            if RESERVED_WORDS.binary_search(&name.as_str()).is_ok()
+                && !reported.iter().any(|(r, _)| *r == name)
+            {
+                reported.push((name, range));
+            }
+        }
+
+        reported
+            .into_iter()
+            .map(|(name, range)| {
+                RuleDiagnostic::new(
+                    rule_category!(),
+                    range,
+                    markup! {
+                        "The name "<Emphasis>{name}</Emphasis>" is a reserved word."
+                    },
+                )
+                .detail(
+                    None,
+                    "It has to be quoted wherever the object is referenced. Consider renaming the object.",
+                )
+            })
+            .collect()
+    }
+}
+
+/// Returns the ranges of the parts of the possibly qualified name that starts at `location`,
+/// e.g. of `public` and `"user"` in `public."user"`.
+fn name_parts(text: &str, location: i32) -> Vec<TextRange> {
+    let mut parts = Vec::new();
+    let Ok(mut start) = usize::try_from(location) else {
+        return parts;
+    };
+
+    while let Some(rest) = text.get(start..) {
+        let len = match rest.strip_prefix('"') {
+            Some(quoted) => match quoted_len(quoted) {
+                Some(len) => len + 1,
+                None => break,
+            },
+            None => rest
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(rest.len()),
+        };
+
+        if len == 0 {
+            break;
+        }
+
+        let (Ok(offset), Ok(len_size)) = (TextSize::try_from(start), TextSize::try_from(len))
+        else {
+            break;
+        };
+        parts.push(TextRange::at(offset, len_size));
+
+        // the next part follows a dot
+        let after = rest[len..].trim_start();
+        match after.strip_prefix('.') {
+            Some(next) => start = text.len() - next.trim_start().len(),
+            None => break,
+        }
+    }
+
+    parts
+}
+
+/// Returns the length of the rest of a quoted identifier, including its closing quote,
+/// e.g. `a""b"` of `"a""b"`.
+fn quoted_len(quoted: &str) -> Option<usize> {
+    let mut idx = 0;
+    loop {
+        let end = idx + quoted[idx..].find('"')?;
+
+        // a doubled quote is an escaped one
+        if quoted[end + 1..].starts_with('"') {
+            idx = end + 2;
+        } else {
+            return Some(end + 1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_text_size::{TextRange, TextSize};
+
+    use super::{RESERVED_WORDS, name_parts};
+
+    #[test]
+    fn reserved_words_are_sorted() {
+        // they are searched with a binary search
+        assert!(RESERVED_WORDS.is_sorted());
+    }
+
+    #[test]
+    fn finds_the_parts_of_qualified_names() {
+        let text = r#"select * from public . "us""er" u"#;
+        let parts: Vec<&str> = name_parts(text, 14)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect();
+
+        assert_eq!(parts, vec!["public", r#""us""er""#]);
+        assert_eq!(name_parts(text, 7), vec![]);
+        assert_eq!(
+            name_parts("drop table \"user", 11),
+            vec![],
+            "An unclosed identifier has no range"
+        );
+        assert_eq!(
+            name_parts("select id", 7),
+            vec![TextRange::new(TextSize::new(7), TextSize::new(9))]
+        );
+    }
+}
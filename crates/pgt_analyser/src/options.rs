@@ -8,3 +8,5 @@ pub type BanDropColumn =
 pub type BanDropNotNull =
     <lint::safety::ban_drop_not_null::BanDropNotNull as pgt_analyse::Rule>::Options;
 pub type BanDropTable = <lint::safety::ban_drop_table::BanDropTable as pgt_analyse::Rule>::Options;
+pub type ReservedIdentifier =
+    <lint::style::reserved_identifier::ReservedIdentifier as pgt_analyse::Rule>::Options;
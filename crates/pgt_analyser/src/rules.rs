@@ -0,0 +1,93 @@
+use pgt_analyse::{GroupCategory, RegistryVisitor, Rule, RuleCategory, RuleGroup, RuleMetadata};
+use pgt_diagnostics::Severity;
+
+use crate::visit_registry;
+
+/// Static information about a rule known to the analyser
+#[derive(Clone, Debug)]
+pub struct RuleInfo {
+    /// The category of the group the rule belongs to
+    pub category: RuleCategory,
+    /// The name of the group the rule belongs to, e.g. `safety`
+    pub group: &'static str,
+    /// The metadata declared alongside the rule
+    pub metadata: RuleMetadata,
+}
+
+impl RuleInfo {
+    /// The severity of the rule's diagnostics if the configuration doesn't override it:
+    /// [Severity::Error] for recommended rules and [Severity::Warning] for other rules.
+    pub fn default_severity(&self) -> Severity {
+        if self.metadata.recommended {
+            Severity::Error
+        } else {
+            Severity::Warning
+        }
+    }
+}
+
+/// Returns the metadata of all rules known to the analyser, sorted by group and name
+pub fn rules_metadata() -> Vec<RuleInfo> {
+    let mut visitor = RuleInfoVisitor::default();
+    visit_registry(&mut visitor);
+
+    let mut rules = visitor.rules;
+    rules.sort_by(|a, b| {
+        a.group
+            .cmp(b.group)
+            .then_with(|| a.metadata.name.cmp(b.metadata.name))
+    });
+    rules
+}
+
+#[derive(Default)]
+struct RuleInfoVisitor {
+    /// The category currently being recorded
+    category: Option<RuleCategory>,
+    rules: Vec<RuleInfo>,
+}
+
+impl RegistryVisitor for RuleInfoVisitor {
+    fn record_category<C: GroupCategory>(&mut self) {
+        self.category = Some(C::CATEGORY);
+        C::record_groups(self);
+    }
+
+    fn record_rule<R>(&mut self)
+    where
+        R: Rule + 'static,
+    {
+        let category = self
+            .category
+            .expect("Rules are always recorded as part of a category");
+
+        self.rules.push(RuleInfo {
+            category,
+            group: <R::Group as RuleGroup>::NAME,
+            metadata: R::METADATA,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_analyse::RuleCategory;
+    use pgt_diagnostics::Severity;
+
+    use super::rules_metadata;
+
+    #[test]
+    fn lists_known_rules_with_their_category() {
+        let rules = rules_metadata();
+
+        let rule = rules
+            .iter()
+            .find(|r| r.metadata.name == "banDropColumn")
+            .expect("banDropColumn should be listed");
+
+        assert_eq!(rule.group, "safety");
+        assert_eq!(rule.category, RuleCategory::Lint);
+        assert!(rule.metadata.recommended);
+        assert_eq!(rule.default_severity(), Severity::Error);
+    }
+}
@@ -32,7 +32,10 @@ fn rule_test(full_path: &'static str, _: &str, _: &str) {
         filter,
     });
 
-    let results = analyser.run(AnalyserContext { root: &ast });
+    let results = analyser.run(AnalyserContext {
+        root: &ast,
+        text: &query,
+    });
 
     let mut snapshot = String::new();
     write_snapshot(&mut snapshot, query.as_str(), results.as_slice());
@@ -0,0 +1,2 @@
+-- expect_only_lint/style/reservedIdentifier
+create table "user" (id int);
\ No newline at end of file
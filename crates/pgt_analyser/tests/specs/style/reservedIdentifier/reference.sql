@@ -0,0 +1,2 @@
+-- expect_only_lint/style/reservedIdentifier
+select "order".id from "order";
\ No newline at end of file
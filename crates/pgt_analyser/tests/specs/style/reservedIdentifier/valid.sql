@@ -0,0 +1,2 @@
+-- expect_no_diagnostics
+create table users (id int);
\ No newline at end of file
@@ -25,11 +25,11 @@ pub(crate) fn get_changed_files(
         }
     };
 
+    // files the traversal cannot handle, e.g. without one of the `files.sqlExtensions`, are
+    // skipped by it
     let changed_files = fs.get_changed_files(base)?;
 
-    let filtered_changed_files = changed_files.iter().map(OsString::from).collect::<Vec<_>>();
-
-    Ok(filtered_changed_files)
+    Ok(changed_files.iter().map(OsString::from).collect())
 }
 
 pub(crate) fn get_staged_files(
@@ -37,7 +37,5 @@ pub(crate) fn get_staged_files(
 ) -> Result<Vec<OsString>, CliDiagnostic> {
     let staged_files = fs.get_staged_files()?;
 
-    let filtered_staged_files = staged_files.iter().map(OsString::from).collect::<Vec<_>>();
-
-    Ok(filtered_staged_files)
+    Ok(staged_files.iter().map(OsString::from).collect())
 }
@@ -14,6 +14,10 @@ pub struct CliOptions {
     #[bpaf(long("colors"), argument("off|force"))]
     pub colors: Option<ColorsArg>,
 
+    /// Set the colors of the markup: "dark" suits terminals with a dark background, "light" uses darker shades that stay readable on a light background
+    #[bpaf(long("colors-theme"), argument("dark|light"))]
+    pub colors_theme: Option<ColorsThemeArg>,
+
     /// Connect to a running instance of the daemon server.
     #[bpaf(long("use-server"), switch, fallback(false))]
     pub use_server: bool,
@@ -48,10 +52,19 @@ pub struct CliOptions {
     #[bpaf(long("error-on-warnings"), switch)]
     pub error_on_warnings: bool,
 
+    /// The lowest severity of the emitted diagnostics that makes Postgres Tools exit with an error code. Passing `--fail-on=none` will only report the diagnostics.
+    #[bpaf(
+        long("fail-on"),
+        argument("error|warning|none"),
+        fallback(FailOn::default()),
+        display_fallback
+    )]
+    pub fail_on: FailOn,
+
     /// Allows to change how diagnostics and summary are reported.
     #[bpaf(
         long("reporter"),
-        argument("json|json-pretty|github|junit|summary|gitlab"),
+        argument("default|json|ndjson|sarif|github|junit|gitlab"),
         fallback(CliReporter::default())
     )]
     pub reporter: CliReporter,
@@ -95,6 +108,15 @@ impl CliOptions {
             Some(path) => ConfigurationPathHint::FromUser(PathBuf::from(path)),
         }
     }
+
+    /// Computes the [FailOn] threshold, `--error-on-warnings` is a shorthand for `--fail-on=warning`
+    pub(crate) fn fail_on(&self) -> FailOn {
+        if self.error_on_warnings {
+            FailOn::Warning
+        } else {
+            self.fail_on
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -117,6 +139,62 @@ impl FromStr for ColorsArg {
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum ColorsThemeArg {
+    Dark,
+    Light,
+}
+
+impl FromStr for ColorsThemeArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            _ => Err(format!(
+                "value {s:?} is not valid for the --colors-theme argument"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FailOn {
+    /// Exit with an error code if some diagnostics emit errors
+    #[default]
+    Error,
+    /// Exit with an error code if some diagnostics emit errors or warnings
+    Warning,
+    /// Never exit with an error code because of the emitted diagnostics
+    None,
+}
+
+impl FromStr for FailOn {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Self::Error),
+            "warning" => Ok(Self::Warning),
+            "none" => Ok(Self::None),
+            _ => Err(format!(
+                "value {s:?} is not valid for the --fail-on argument"
+            )),
+        }
+    }
+}
+
+impl Display for FailOn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FailOn::Error => f.write_str("error"),
+            FailOn::Warning => f.write_str("warning"),
+            FailOn::None => f.write_str("none"),
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub enum CliReporter {
     /// The default reporter
@@ -128,6 +206,12 @@ pub enum CliReporter {
     Junit,
     /// Reports linter diagnostics using the [GitLab Code Quality report](https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool).
     GitLab,
+    /// Diagnostics and summary are printed as a single JSON document
+    Json,
+    /// Diagnostics and summary are printed as newline delimited JSON, one line each
+    Ndjson,
+    /// Diagnostics are printed as a [SARIF](https://sarifweb.azurewebsites.net/) log
+    Sarif,
 }
 
 impl CliReporter {
@@ -141,9 +225,13 @@ impl FromStr for CliReporter {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            "default" => Ok(Self::Default),
             "github" => Ok(Self::GitHub),
             "junit" => Ok(Self::Junit),
             "gitlab" => Ok(Self::GitLab),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            "sarif" => Ok(Self::Sarif),
             _ => Err(format!(
                 "value {s:?} is not valid for the --reporter argument"
             )),
@@ -158,6 +246,9 @@ impl Display for CliReporter {
             CliReporter::GitHub => f.write_str("github"),
             CliReporter::Junit => f.write_str("junit"),
             CliReporter::GitLab => f.write_str("gitlab"),
+            CliReporter::Json => f.write_str("json"),
+            CliReporter::Ndjson => f.write_str("ndjson"),
+            CliReporter::Sarif => f.write_str("sarif"),
         }
     }
 }
@@ -1,7 +1,7 @@
 use crate::cli_options::CliOptions;
-use crate::{CliDiagnostic, Execution, TraversalMode};
+use crate::{CliDiagnostic, Execution, FixFileMode, TraversalMode};
 use biome_deserialize::Merge;
-use pgt_configuration::PartialConfiguration;
+use pgt_configuration::{PartialConfiguration, RuleSelector};
 use pgt_console::Console;
 use pgt_fs::FileSystem;
 use pgt_workspace::{DynRef, Workspace, WorkspaceError, configuration::LoadedConfiguration};
@@ -16,6 +16,10 @@ pub(crate) struct CheckCommandPayload {
     pub(crate) staged: bool,
     pub(crate) changed: bool,
     pub(crate) since: Option<String>,
+    pub(crate) only: Vec<RuleSelector>,
+    pub(crate) skip: Vec<RuleSelector>,
+    pub(crate) fix_file_mode: Option<FixFileMode>,
+    pub(crate) unsafe_fixes: bool,
 }
 
 impl CommandRunner for CheckCommandPayload {
@@ -68,8 +72,12 @@ impl CommandRunner for CheckCommandPayload {
         _workspace: &dyn Workspace,
     ) -> Result<Execution, CliDiagnostic> {
         Ok(Execution::new(TraversalMode::Check {
+            fix_file_mode: self.fix_file_mode,
+            unsafe_fixes: self.unsafe_fixes,
             stdin: self.get_stdin(console)?,
             vcs_targeted: (self.staged, self.changed).into(),
+            only: self.only.clone(),
+            skip: self.skip.clone(),
         })
         .set_report(cli_options))
     }
@@ -0,0 +1,70 @@
+use crate::cli_options::CliOptions;
+use crate::{CliDiagnostic, Execution, TraversalMode};
+use biome_deserialize::Merge;
+use pgt_configuration::PartialConfiguration;
+use pgt_console::Console;
+use pgt_fs::FileSystem;
+use pgt_text_size::TextSize;
+use pgt_workspace::{DynRef, Workspace, WorkspaceError, configuration::LoadedConfiguration};
+use std::ffi::OsString;
+
+use super::CommandRunner;
+
+pub(crate) struct CompleteCommandPayload {
+    pub(crate) configuration: Option<PartialConfiguration>,
+    pub(crate) stdin_file_path: String,
+    pub(crate) cursor: u32,
+}
+
+impl CommandRunner for CompleteCommandPayload {
+    const COMMAND_NAME: &'static str = "complete";
+
+    fn merge_configuration(
+        &mut self,
+        loaded_configuration: LoadedConfiguration,
+        _fs: &DynRef<'_, dyn FileSystem>,
+        _console: &mut dyn Console,
+    ) -> Result<PartialConfiguration, WorkspaceError> {
+        let LoadedConfiguration {
+            configuration: mut fs_configuration,
+            ..
+        } = loaded_configuration;
+
+        if let Some(configuration) = self.configuration.clone() {
+            // overwrite fs config with cli args
+            fs_configuration.merge_with(configuration);
+        }
+
+        Ok(fs_configuration)
+    }
+
+    fn get_files_to_process(
+        &self,
+        _fs: &DynRef<'_, dyn FileSystem>,
+        _configuration: &PartialConfiguration,
+    ) -> Result<Vec<OsString>, CliDiagnostic> {
+        // completions only ever run against the content piped via stdin
+        Ok(vec![])
+    }
+
+    fn get_stdin_file_path(&self) -> Option<&str> {
+        Some(self.stdin_file_path.as_str())
+    }
+
+    fn get_execution(
+        &self,
+        cli_options: &CliOptions,
+        console: &mut dyn Console,
+        _workspace: &dyn Workspace,
+    ) -> Result<Execution, CliDiagnostic> {
+        let stdin = self
+            .get_stdin(console)?
+            .ok_or_else(|| CliDiagnostic::missing_argument("stdin", Self::COMMAND_NAME))?;
+
+        Ok(Execution::new(TraversalMode::Complete {
+            stdin,
+            cursor: TextSize::from(self.cursor),
+        })
+        .set_report(cli_options))
+    }
+}
@@ -1,12 +1,12 @@
 use crate::changed::{get_changed_files, get_staged_files};
-use crate::cli_options::{CliOptions, CliReporter, ColorsArg, cli_options};
-use crate::execute::Stdin;
+use crate::cli_options::{CliOptions, CliReporter, ColorsArg, ColorsThemeArg, cli_options};
+use crate::execute::{FixFileMode, Stdin, fix_file_mode};
 use crate::logging::LoggingKind;
 use crate::{
     CliDiagnostic, CliSession, Execution, LoggingLevel, VERSION, execute_mode, setup_cli_subscriber,
 };
 use bpaf::Bpaf;
-use pgt_configuration::{PartialConfiguration, partial_configuration};
+use pgt_configuration::{PartialConfiguration, RuleSelector, partial_configuration};
 use pgt_console::Console;
 use pgt_fs::FileSystem;
 use pgt_workspace::configuration::{LoadedConfiguration, load_configuration};
@@ -17,6 +17,7 @@ use std::ffi::OsString;
 use std::path::PathBuf;
 pub(crate) mod check;
 pub(crate) mod clean;
+pub(crate) mod complete;
 pub(crate) mod daemon;
 pub(crate) mod init;
 pub(crate) mod version;
@@ -62,11 +63,54 @@ pub enum PgtCommand {
         #[bpaf(long("since"), argument("REF"))]
         since: Option<String>,
 
+        /// Run only the given rule or group of rules.
+        ///
+        /// Example: `postgrestools check --only=safety/banDropColumn --only=safety`
+        #[bpaf(long("only"), argument("GROUP|RULE"))]
+        only: Vec<RuleSelector>,
+
+        /// Skip the given rule or group of rules. This option takes precedence over `--only`.
+        ///
+        /// Example: `postgrestools check --skip=safety/banDropColumn --skip=safety`
+        #[bpaf(long("skip"), argument("GROUP|RULE"))]
+        skip: Vec<RuleSelector>,
+
+        #[bpaf(external(fix_file_mode), optional)]
+        fix_file_mode: Option<FixFileMode>,
+
+        /// Applies the unsafe fixes too when used with `--write` or `--dry-run`.
+        ///
+        /// Unsafe fixes change the meaning of the statements, so review them before committing.
+        #[bpaf(long("unsafe"), switch)]
+        unsafe_fixes: bool,
+
         /// Single file, single path or list of paths
         #[bpaf(positional("PATH"), many)]
         paths: Vec<OsString>,
     },
 
+    /// Prints the completion items for the given cursor position as JSON.
+    #[bpaf(command)]
+    Complete {
+        #[bpaf(external(partial_configuration), hide_usage, optional)]
+        configuration: Option<PartialConfiguration>,
+
+        #[bpaf(external, hide_usage)]
+        cli_options: CliOptions,
+
+        /// The path of the file whose content is piped via `stdin`.
+        ///
+        /// The file doesn't need to exist on disk.
+        ///
+        /// Example: `echo 'select * from ' | postgrestools complete --stdin-file-path=test.sql --cursor=14`
+        #[bpaf(long("stdin-file-path"), argument("PATH"))]
+        stdin_file_path: String,
+
+        /// The byte offset of the cursor within the content piped via `stdin`.
+        #[bpaf(long("cursor"), argument("OFFSET"))]
+        cursor: u32,
+    },
+
     /// Starts the daemon server process.
     #[bpaf(command)]
     Start {
@@ -194,9 +238,9 @@ pub enum PgtCommand {
 impl PgtCommand {
     const fn cli_options(&self) -> Option<&CliOptions> {
         match self {
-            PgtCommand::Version(cli_options) | PgtCommand::Check { cli_options, .. } => {
-                Some(cli_options)
-            }
+            PgtCommand::Version(cli_options)
+            | PgtCommand::Check { cli_options, .. }
+            | PgtCommand::Complete { cli_options, .. } => Some(cli_options),
             PgtCommand::LspProxy { .. }
             | PgtCommand::Start { .. }
             | PgtCommand::Stop
@@ -210,8 +254,14 @@ impl PgtCommand {
     pub const fn get_color(&self) -> Option<&ColorsArg> {
         match self.cli_options() {
             Some(cli_options) => {
-                // To properly display GitHub annotations we need to disable colors
-                if matches!(cli_options.reporter, CliReporter::GitHub) {
+                // To properly display GitHub annotations and keep JSON parseable we need to disable colors
+                if matches!(
+                    cli_options.reporter,
+                    CliReporter::GitHub
+                        | CliReporter::Json
+                        | CliReporter::Ndjson
+                        | CliReporter::Sarif
+                ) {
                     return Some(&ColorsArg::Off);
                 }
                 // We want force colors in CI, to give e better UX experience
@@ -226,6 +276,11 @@ impl PgtCommand {
         }
     }
 
+    pub fn get_color_theme(&self) -> Option<&ColorsThemeArg> {
+        self.cli_options()
+            .and_then(|cli_options| cli_options.colors_theme.as_ref())
+    }
+
     pub const fn should_use_server(&self) -> bool {
         match self.cli_options() {
             Some(cli_options) => cli_options.use_server,
@@ -301,6 +356,11 @@ pub(crate) trait CommandRunner: Sized {
         let (vcs_base_path, gitignore_matches) =
             configuration.retrieve_gitignore_matches(fs, vcs_base_path.as_deref())?;
         let paths = self.get_files_to_process(fs, &configuration)?;
+        let sql_extensions = configuration
+            .files
+            .as_ref()
+            .and_then(|files| files.sql_extensions.as_ref())
+            .map(|extensions| extensions.iter().cloned().collect());
 
         workspace.update_settings(UpdateSettingsParams {
             workspace_directory: fs.working_directory(),
@@ -309,7 +369,9 @@ pub(crate) trait CommandRunner: Sized {
             gitignore_matches,
         })?;
 
-        let execution = self.get_execution(cli_options, console, workspace)?;
+        let execution = self
+            .get_execution(cli_options, console, workspace)?
+            .set_sql_extensions(sql_extensions);
         Ok((execution, paths))
     }
 
@@ -3,18 +3,26 @@ mod process_file;
 mod std_in;
 pub(crate) mod traverse;
 
-use crate::cli_options::{CliOptions, CliReporter};
-use crate::execute::traverse::{TraverseResult, traverse};
+pub use traverse::TraverseResult;
+
+use crate::cli_options::{CliOptions, CliReporter, FailOn};
+use crate::execute::traverse::traverse;
 use crate::reporter::github::{GithubReporter, GithubReporterVisitor};
 use crate::reporter::gitlab::{GitLabReporter, GitLabReporterVisitor};
+use crate::reporter::json::{JsonReporter, JsonReporterVisitor};
 use crate::reporter::junit::{JunitReporter, JunitReporterVisitor};
+use crate::reporter::sarif::{SarifReporter, SarifReporterVisitor};
 use crate::reporter::terminal::{ConsoleReporter, ConsoleReporterVisitor};
-use crate::{CliDiagnostic, CliSession, DiagnosticsPayload, Reporter};
-use pgt_diagnostics::{Category, category};
+use crate::{CliDiagnostic, CliSession, DiagnosticsPayload, Reporter, TraversalSummary};
+use bpaf::Bpaf;
+use pgt_configuration::RuleSelector;
+use pgt_configuration::files::DEFAULT_SQL_EXTENSIONS;
+use pgt_diagnostics::{Category, Severity, category};
+use pgt_text_size::TextSize;
 use std::borrow::Borrow;
 use std::ffi::OsString;
 use std::fmt::{Display, Formatter};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
 /// Useful information during the traversal of files and virtual content
@@ -28,6 +36,9 @@ pub struct Execution {
 
     /// The maximum number of diagnostics that can be printed in console
     max_diagnostics: u32,
+
+    /// The extensions of the files that are traversed
+    sql_extensions: Vec<String>,
 }
 
 impl Execution {
@@ -39,7 +50,6 @@ impl Execution {
 /// A type that holds the information to execute the CLI via `stdin
 #[derive(Debug, Clone)]
 pub struct Stdin(
-    #[allow(unused)]
     /// The virtual path to the file
     PathBuf,
     /// The content of the file
@@ -47,6 +57,10 @@ pub struct Stdin(
 );
 
 impl Stdin {
+    fn as_path(&self) -> &Path {
+        self.0.as_path()
+    }
+
     fn as_content(&self) -> &str {
         self.1.as_str()
     }
@@ -76,17 +90,29 @@ pub enum TraversalMode {
     Dummy,
     /// This mode is enabled when running the command `check`
     Check {
-        // The type of fixes that should be applied when analyzing a file.
-        //
-        // It's [None] if the `check` command is called without `--apply` or `--apply-suggested`
-        // arguments.
-        // fix_file_mode: Option<FixFileMode>,
+        /// How the fixes of the diagnostics should be applied when analyzing a file.
+        ///
+        /// It's [None] if the `check` command is called without `--write` or `--dry-run`.
+        fix_file_mode: Option<FixFileMode>,
+        /// Whether the unsafe fixes are applied too, which is the case with `--unsafe`
+        unsafe_fixes: bool,
         /// An optional tuple.
         /// 1. The virtual path to the file
         /// 2. The content of the file
         stdin: Option<Stdin>,
         /// A flag to know vcs integrated options such as `--staged` or `--changed` are enabled
         vcs_targeted: VcsTargeted,
+        /// Run only the given rules or groups of rules
+        only: Vec<RuleSelector>,
+        /// Skip the given rules or groups of rules
+        skip: Vec<RuleSelector>,
+    },
+    /// This mode is enabled when running the command `complete`
+    Complete {
+        /// The virtual path and the content of the file, piped via `stdin`
+        stdin: Stdin,
+        /// The byte offset of the cursor within the content
+        cursor: TextSize,
     },
 }
 
@@ -95,10 +121,22 @@ impl Display for TraversalMode {
         match self {
             TraversalMode::Dummy => write!(f, "dummy"),
             TraversalMode::Check { .. } => write!(f, "check"),
+            TraversalMode::Complete { .. } => write!(f, "complete"),
         }
     }
 }
 
+/// How the fixes suggested by the diagnostics are applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Bpaf)]
+pub enum FixFileMode {
+    /// Applies the safe fixes of the diagnostics and writes the fixed content back to the files.
+    #[bpaf(long("write"))]
+    Write,
+    /// Computes the fixes like `--write`, but only reports them without changing any file.
+    #[bpaf(long("dry-run"))]
+    DryRun,
+}
+
 /// Tells to the execution of the traversal how the information should be reported
 #[derive(Copy, Clone, Debug)]
 pub enum ReportMode {
@@ -111,6 +149,12 @@ pub enum ReportMode {
     Junit,
     /// Reports information in the [GitLab Code Quality](https://docs.gitlab.com/ee/ci/testing/code_quality.html#implement-a-custom-tool) format.
     GitLab,
+    /// Reports information as JSON
+    Json,
+    /// Reports information as newline delimited JSON
+    Ndjson,
+    /// Reports information in the [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) format.
+    Sarif,
 }
 
 impl Default for ReportMode {
@@ -126,6 +170,9 @@ impl From<CliReporter> for ReportMode {
             CliReporter::GitHub => Self::GitHub,
             CliReporter::Junit => Self::Junit,
             CliReporter::GitLab => Self::GitLab {},
+            CliReporter::Json => Self::Json,
+            CliReporter::Ndjson => Self::Ndjson,
+            CliReporter::Sarif => Self::Sarif,
         }
     }
 }
@@ -136,9 +183,25 @@ impl Execution {
             report_mode: ReportMode::default(),
             traversal_mode: mode,
             max_diagnostics: 20,
+            sql_extensions: DEFAULT_SQL_EXTENSIONS.map(String::from).to_vec(),
         }
     }
 
+    /// Sets the extensions of the files that are traversed, if configured
+    pub(crate) fn set_sql_extensions(mut self, extensions: Option<Vec<String>>) -> Self {
+        if let Some(extensions) = extensions {
+            self.sql_extensions = extensions;
+        }
+        self
+    }
+
+    /// Returns true if the extension of `path` is one of the configured SQL extensions
+    pub(crate) fn is_sql_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| self.sql_extensions.iter().any(|e| e == ext))
+    }
+
     /// It sets the reporting mode by reading the [CliOptions]
     pub(crate) fn set_report(mut self, cli_options: &CliOptions) -> Self {
         self.report_mode = cli_options.reporter.clone().into();
@@ -157,21 +220,20 @@ impl Execution {
         match self.traversal_mode {
             TraversalMode::Dummy => category!("dummy"),
             TraversalMode::Check { .. } => category!("check"),
+            TraversalMode::Complete { .. } => category!("complete"),
         }
     }
 
     /// Whether the traversal mode requires write access to files
-    pub(crate) const fn requires_write_access(&self) -> bool {
-        match self.traversal_mode {
-            TraversalMode::Dummy => false,
-            TraversalMode::Check { .. } => false,
-        }
+    pub(crate) fn requires_write_access(&self) -> bool {
+        self.is_write()
     }
 
     pub(crate) fn as_stdin_file(&self) -> Option<&Stdin> {
         match &self.traversal_mode {
             TraversalMode::Dummy => None,
             TraversalMode::Check { stdin, .. } => stdin.as_ref(),
+            TraversalMode::Complete { stdin, .. } => Some(stdin),
         }
     }
 
@@ -181,20 +243,26 @@ impl Execution {
             TraversalMode::Check { vcs_targeted, .. } => {
                 vcs_targeted.staged || vcs_targeted.changed
             }
+            TraversalMode::Complete { .. } => false,
         }
     }
 
-    pub(crate) const fn is_check_apply(&self) -> bool {
-        false
+    /// Returns how the fixes of the diagnostics are applied, if at all
+    pub(crate) fn fix_file_mode(&self) -> Option<FixFileMode> {
+        match self.traversal_mode {
+            TraversalMode::Dummy => None,
+            TraversalMode::Check { fix_file_mode, .. } => fix_file_mode,
+            TraversalMode::Complete { .. } => None,
+        }
+    }
+
+    pub(crate) fn is_check_apply(&self) -> bool {
+        self.fix_file_mode().is_some()
     }
 
-    #[allow(unused)]
-    /// Returns [true] if the user used the `--write`/`--fix` option
+    /// Returns [true] if the user used the `--write` option
     pub(crate) fn is_write(&self) -> bool {
-        match self.traversal_mode {
-            TraversalMode::Dummy => false,
-            TraversalMode::Check { .. } => false,
-        }
+        self.fix_file_mode() == Some(FixFileMode::Write)
     }
 }
 
@@ -219,18 +287,22 @@ pub fn execute_mode(
 
     // don't do any traversal if there's some content coming from stdin
     if let Some(stdin) = execution.as_stdin_file() {
-        std_in::run(session, stdin.as_content())
+        std_in::run(session, execution.traversal_mode(), stdin)
     } else {
+        let result = traverse(&execution, &mut session, cli_options, paths)?;
+        if let Some(on_traverse) = session.on_traverse.take() {
+            on_traverse(&result);
+        }
+
         let TraverseResult {
             summary,
             evaluated_paths,
             diagnostics,
-        } = traverse(&execution, &mut session, cli_options, paths)?;
+        } = result;
         let console = session.app.console;
-        let errors = summary.errors;
         let skipped = summary.skipped;
         let processed = summary.changed + summary.unchanged;
-        let should_exit_on_warnings = summary.warnings > 0 && cli_options.error_on_warnings;
+        let failing_severity = failing_severity(&summary, cli_options.fail_on());
 
         match execution.report_mode {
             ReportMode::Terminal => {
@@ -283,14 +355,41 @@ pub fn execute_mode(
                 };
                 reporter.write(&mut JunitReporterVisitor::new(console))?;
             }
+            ReportMode::Json | ReportMode::Ndjson => {
+                let reporter = JsonReporter {
+                    summary,
+                    diagnostics_payload: DiagnosticsPayload {
+                        verbose: cli_options.verbose,
+                        diagnostic_level: cli_options.diagnostic_level,
+                        diagnostics,
+                    },
+                    execution: execution.clone(),
+                };
+                let mut visitor = match execution.report_mode {
+                    ReportMode::Ndjson => JsonReporterVisitor::delimited(console),
+                    _ => JsonReporterVisitor::new(console),
+                };
+                reporter.write(&mut visitor)?;
+            }
+            ReportMode::Sarif => {
+                let reporter = SarifReporter {
+                    diagnostics_payload: DiagnosticsPayload {
+                        verbose: cli_options.verbose,
+                        diagnostic_level: cli_options.diagnostic_level,
+                        diagnostics,
+                    },
+                    execution: execution.clone(),
+                };
+                reporter.write(&mut SarifReporterVisitor(console))?;
+            }
         }
 
         // Processing emitted error diagnostics, exit with a non-zero code
         if processed.saturating_sub(skipped) == 0 && !cli_options.no_errors_on_unmatched {
             Err(CliDiagnostic::no_files_processed())
-        } else if errors > 0 || should_exit_on_warnings {
+        } else if let Some(severity) = failing_severity {
             let category = execution.as_diagnostic_category();
-            if should_exit_on_warnings {
+            if severity == Severity::Warning {
                 if execution.is_check_apply() {
                     Err(CliDiagnostic::apply_warnings(category))
                 } else {
@@ -306,3 +405,72 @@ pub fn execute_mode(
         }
     }
 }
+
+/// Returns the severity of the diagnostics that should make the process exit with an error code,
+/// or [None] if the [TraversalSummary] doesn't reach the [FailOn] threshold.
+fn failing_severity(summary: &TraversalSummary, fail_on: FailOn) -> Option<Severity> {
+    match fail_on {
+        FailOn::None => None,
+        FailOn::Error | FailOn::Warning if summary.errors > 0 => Some(Severity::Error),
+        FailOn::Warning if summary.warnings > 0 => Some(Severity::Warning),
+        FailOn::Error | FailOn::Warning => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_diagnostics::Severity;
+
+    use super::failing_severity;
+    use crate::TraversalSummary;
+    use crate::cli_options::FailOn;
+
+    fn summary(errors: u32, warnings: u32) -> TraversalSummary {
+        TraversalSummary {
+            errors,
+            warnings,
+            ..TraversalSummary::default()
+        }
+    }
+
+    #[test]
+    fn fails_on_errors_by_default() {
+        assert_eq!(FailOn::default(), FailOn::Error);
+
+        assert_eq!(failing_severity(&summary(0, 0), FailOn::Error), None);
+        assert_eq!(failing_severity(&summary(0, 3), FailOn::Error), None);
+        assert_eq!(
+            failing_severity(&summary(1, 0), FailOn::Error),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            failing_severity(&summary(1, 3), FailOn::Error),
+            Some(Severity::Error)
+        );
+    }
+
+    #[test]
+    fn fails_on_warnings() {
+        assert_eq!(failing_severity(&summary(0, 0), FailOn::Warning), None);
+        assert_eq!(
+            failing_severity(&summary(0, 3), FailOn::Warning),
+            Some(Severity::Warning)
+        );
+        assert_eq!(
+            failing_severity(&summary(1, 0), FailOn::Warning),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            failing_severity(&summary(1, 3), FailOn::Warning),
+            Some(Severity::Error)
+        );
+    }
+
+    #[test]
+    fn never_fails_in_report_only_mode() {
+        assert_eq!(failing_severity(&summary(0, 0), FailOn::None), None);
+        assert_eq!(failing_severity(&summary(0, 3), FailOn::None), None);
+        assert_eq!(failing_severity(&summary(1, 0), FailOn::None), None);
+        assert_eq!(failing_severity(&summary(1, 3), FailOn::None), None);
+    }
+}
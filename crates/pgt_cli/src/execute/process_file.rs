@@ -36,12 +36,16 @@ pub(crate) enum FileStatus {
 /// Wrapper type for messages that can be printed during the traversal process
 #[derive(Debug)]
 pub(crate) enum Message {
-    #[allow(unused)]
     SkippedFixes {
         /// Suggested fixes skipped during the lint traversal
         skipped_suggested_fixes: u32,
     },
 
+    AppliedFixes {
+        /// Suggested fixes applied during the lint traversal
+        applied_suggested_fixes: u32,
+    },
+
     #[allow(unused)]
     Failure,
     Error(Error),
@@ -112,6 +116,9 @@ pub(crate) fn process_file(ctx: &TraversalOptions, pgt_path: &PgTPath) -> FileRe
                 unreachable!("The dummy mode should not be called for this file")
             }
             TraversalMode::Check { .. } => check_file(shared_context, pgt_path),
+            TraversalMode::Complete { .. } => {
+                unreachable!("The complete mode only runs against stdin")
+            }
         }
     })
 }
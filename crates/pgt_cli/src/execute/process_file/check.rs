@@ -1,6 +1,10 @@
 use pgt_analyse::RuleCategoriesBuilder;
 use pgt_diagnostics::{Error, category};
+use pgt_text_size::TextRange;
+use pgt_workspace::features::code_actions::{CodeAction, CodeActionKind, TextEdit};
 
+use crate::TraversalMode;
+use crate::execute::FixFileMode;
 use crate::execute::diagnostics::ResultExt;
 use crate::execute::process_file::workspace_file::WorkspaceFile;
 use crate::execute::process_file::{FileResult, FileStatus, Message, SharedTraversalOptions};
@@ -22,25 +26,65 @@ pub(crate) fn check_with_guard<'ctx>(
 ) -> FileResult {
     tracing::info_span!("Processes check", path =? workspace_file.path.display()).in_scope(
         move || {
-            let input = workspace_file.input()?;
-            let changed = false;
+            let mut changed = false;
 
-            let (only, skip) = (Vec::new(), Vec::new());
+            let (only, skip, unsafe_fixes) = match ctx.execution.traversal_mode() {
+                TraversalMode::Check {
+                    only,
+                    skip,
+                    unsafe_fixes,
+                    ..
+                } => (only.clone(), skip.clone(), *unsafe_fixes),
+                _ => (Vec::new(), Vec::new(), false),
+            };
 
             let max_diagnostics = ctx.remaining_diagnostics.load(Ordering::Relaxed);
 
-            let pull_diagnostics_result = workspace_file
-                .guard()
-                .pull_diagnostics(
-                    RuleCategoriesBuilder::default().all().build(),
-                    max_diagnostics,
-                    only,
-                    skip,
-                )
-                .with_file_path_and_code(
-                    workspace_file.path.display().to_string(),
-                    category!("check"),
-                )?;
+            let pull_diagnostics = |workspace_file: &WorkspaceFile| {
+                workspace_file
+                    .guard()
+                    .pull_diagnostics(
+                        RuleCategoriesBuilder::default().all().build(),
+                        max_diagnostics,
+                        only.clone(),
+                        skip.clone(),
+                    )
+                    .with_file_path_and_code(
+                        workspace_file.path.display().to_string(),
+                        category!("check"),
+                    )
+            };
+
+            let mut pull_diagnostics_result = pull_diagnostics(workspace_file)?;
+
+            if let Some(fix_file_mode) = ctx.execution.fix_file_mode() {
+                let fixes = collect_fixes(
+                    std::mem::take(&mut pull_diagnostics_result.fixes),
+                    unsafe_fixes,
+                );
+
+                if fixes.applied() > 0 {
+                    ctx.push_message(Message::AppliedFixes {
+                        applied_suggested_fixes: fixes.applied(),
+                    });
+                }
+                if fixes.skipped > 0 {
+                    ctx.push_message(Message::SkippedFixes {
+                        skipped_suggested_fixes: fixes.skipped,
+                    });
+                }
+
+                if fix_file_mode == FixFileMode::Write && fixes.applied() > 0 {
+                    let fixed = fixes.apply(&workspace_file.input()?);
+                    workspace_file.update_file(fixed)?;
+                    changed = true;
+
+                    // the diagnostics are reported for the fixed content
+                    pull_diagnostics_result = pull_diagnostics(workspace_file)?;
+                }
+            }
+
+            let input = workspace_file.input()?;
 
             let no_diagnostics = pull_diagnostics_result.diagnostics.is_empty()
                 && pull_diagnostics_result.skipped_diagnostics == 0;
@@ -66,3 +110,77 @@ pub(crate) fn check_with_guard<'ctx>(
         },
     )
 }
+
+/// The fixes of the diagnostics of a file
+#[derive(Debug, Default)]
+struct Fixes {
+    edits: Vec<TextEdit>,
+    /// The number of fixes whose edits are applied
+    applied: u32,
+    /// The number of fixes that were not applied because they are unsafe or
+    /// another fix already changes the same range
+    skipped: u32,
+}
+
+impl Fixes {
+    fn applied(&self) -> u32 {
+        self.applied
+    }
+
+    /// Applies the edits to `content`, which must be the content they were computed for
+    fn apply(mut self, content: &str) -> String {
+        let mut content = content.to_string();
+
+        // back to front, so the ranges of the remaining edits stay valid
+        self.edits.sort_by_key(|edit| edit.range.start());
+        for edit in self.edits.into_iter().rev() {
+            content.replace_range(
+                usize::from(edit.range.start())..usize::from(edit.range.end()),
+                &edit.new_text,
+            );
+        }
+
+        content
+    }
+}
+
+/// Collects the fixes to apply from the fixes of the diagnostics of the file.
+///
+/// Unsafe fixes are skipped unless `unsafe_fixes` is set. A fix is applied as a whole, so it is
+/// skipped if any of its edits overlaps an edit of a fix that is already applied.
+fn collect_fixes(actions: Vec<CodeAction>, unsafe_fixes: bool) -> Fixes {
+    let mut fixes = Fixes::default();
+
+    for action in actions {
+        let CodeActionKind::Edit(edit_action) = action.kind else {
+            continue;
+        };
+
+        if !edit_action.is_safe && !unsafe_fixes {
+            fixes.skipped += 1;
+            continue;
+        }
+
+        let overlaps = edit_action.edits.iter().any(|edit| {
+            fixes
+                .edits
+                .iter()
+                .any(|applied| overlap(applied.range, edit.range))
+        });
+        if overlaps {
+            fixes.skipped += 1;
+            continue;
+        }
+
+        fixes.edits.extend(edit_action.edits);
+        fixes.applied += 1;
+    }
+
+    fixes
+}
+
+/// Returns `true` if the edits of both ranges can't be applied together, e.g. because they
+/// replace the same text or insert at the same offset
+fn overlap(a: TextRange, b: TextRange) -> bool {
+    (a.start() < b.end() && b.start() < a.end()) || a.start() == b.start()
+}
@@ -59,7 +59,6 @@ impl<'ctx, 'app> WorkspaceFile<'ctx, 'app> {
     }
 
     /// It updates the workspace file with `new_content`
-    #[allow(dead_code)]
     pub(crate) fn update_file(&mut self, new_content: impl Into<String>) -> Result<(), Error> {
         let new_content = new_content.into();
 
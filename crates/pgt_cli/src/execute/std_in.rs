@@ -1,11 +1,50 @@
 //! In here, there are the operations that run via standard input
 //!
+use crate::diagnostics::ReportDiagnostic;
+use crate::execute::{Stdin, TraversalMode};
 use crate::{CliDiagnostic, CliSession};
 use pgt_console::{ConsoleExt, markup};
+use pgt_diagnostics::adapters::SerdeJsonError;
+use pgt_fs::PgTPath;
+use pgt_workspace::WorkspaceError;
+use pgt_workspace::workspace::{FileGuard, OpenFileParams};
 
-pub(crate) fn run(session: CliSession, content: &str) -> Result<(), CliDiagnostic> {
+pub(crate) fn run(
+    session: CliSession,
+    mode: &TraversalMode,
+    stdin: &Stdin,
+) -> Result<(), CliDiagnostic> {
     let console = &mut *session.app.console;
+    let workspace = &*session.app.workspace;
+
+    match mode {
+        TraversalMode::Complete { cursor, .. } => {
+            let file = FileGuard::open(
+                workspace,
+                OpenFileParams {
+                    path: PgTPath::new(stdin.as_path()),
+                    content: stdin.as_content().to_string(),
+                    version: 0,
+                },
+            )?;
+
+            let items = match file.get_completions(*cursor) {
+                Ok(result) => result.into_iter().collect::<Vec<_>>(),
+                // without a database there is nothing to complete
+                Err(WorkspaceError::DatabaseConnectionError(_)) => vec![],
+                Err(err) => return Err(err.into()),
+            };
+
+            let serialized = serde_json::to_string(&items).map_err(|err| {
+                CliDiagnostic::Report(ReportDiagnostic::Serialization(SerdeJsonError::from(err)))
+            })?;
+
+            console.log(markup! {{serialized}});
+        }
+        TraversalMode::Dummy | TraversalMode::Check { .. } => {
+            console.append(markup! {{stdin.as_content()}});
+        }
+    }
 
-    console.append(markup! {{content}});
     Ok(())
 }
@@ -5,6 +5,7 @@ use crate::execute::diagnostics::PanicDiagnostic;
 use crate::reporter::TraversalSummary;
 use crate::{CliDiagnostic, CliSession};
 use crossbeam::channel::{Receiver, Sender, unbounded};
+use pgt_console::{ConsoleExt, markup};
 use pgt_diagnostics::DiagnosticTags;
 use pgt_diagnostics::{DiagnosticExt, Error, Resource, Severity};
 use pgt_fs::{FileSystem, PathInterner, PgTPath};
@@ -14,12 +15,12 @@ use pgt_workspace::workspace::IsPathIgnoredParams;
 use pgt_workspace::{Workspace, WorkspaceError};
 use rustc_hash::FxHashSet;
 use std::collections::BTreeSet;
-use std::sync::RwLock;
 use std::sync::atomic::AtomicU32;
+use std::sync::{Mutex, RwLock};
 use std::{
     env::current_dir,
     ffi::OsString,
-    panic::catch_unwind,
+    panic::{RefUnwindSafe, catch_unwind},
     path::PathBuf,
     sync::{
         Once,
@@ -29,10 +30,23 @@ use std::{
     time::{Duration, Instant},
 };
 
-pub(crate) struct TraverseResult {
-    pub(crate) summary: TraversalSummary,
-    pub(crate) evaluated_paths: BTreeSet<PgTPath>,
-    pub(crate) diagnostics: Vec<Error>,
+/// Snapshot of how far a traversal got, reported each time a file completes
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct TraversalProgress {
+    /// Number of files that were fully processed so far
+    pub(crate) processed: usize,
+    /// Number of files discovered by the traversal
+    pub(crate) total: usize,
+}
+
+/// Callback invoked by the traversal every time a file completes
+pub(crate) type ProgressCallback<'a> = &'a (dyn Fn(TraversalProgress) + Sync + RefUnwindSafe);
+
+/// The outcome of a traversal, before it is handed to a reporter
+pub struct TraverseResult {
+    pub summary: TraversalSummary,
+    pub evaluated_paths: BTreeSet<PgTPath>,
+    pub diagnostics: Vec<Error>,
 }
 
 pub(crate) fn traverse(
@@ -76,6 +90,16 @@ pub(crate) fn traverse(
     let fs = &*session.app.fs;
     let workspace = &*session.app.workspace;
 
+    // the progress is printed in verbose mode, other reporters expect their own format only
+    let console = Mutex::new(&mut *session.app.console);
+    let print_progress = |progress: TraversalProgress| {
+        console.lock().unwrap().log(markup! {
+            <Dim>"Processed "{progress.processed}" of "{progress.total}" files"</Dim>
+        });
+    };
+    let on_progress = (cli_options.verbose && cli_options.reporter.is_default())
+        .then_some(&print_progress as ProgressCallback);
+
     let max_diagnostics = execution.get_max_diagnostics();
     let remaining_diagnostics = AtomicU32::new(max_diagnostics);
 
@@ -107,6 +131,9 @@ pub(crate) fn traverse(
                 messages: sender,
                 remaining_diagnostics: &remaining_diagnostics,
                 evaluated_paths: RwLock::default(),
+                processed: Mutex::default(),
+                discovered: AtomicUsize::new(0),
+                on_progress,
             },
         );
         // wait for the main thread to finish
@@ -122,6 +149,7 @@ pub(crate) fn traverse(
     let matches = matches.load(Ordering::Relaxed);
     let skipped = skipped.load(Ordering::Relaxed);
     let suggested_fixes_skipped = printer.skipped_fixes();
+    let suggested_fixes_applied = printer.applied_fixes();
     let diagnostics_not_printed = printer.not_printed_diagnostics();
 
     Ok(TraverseResult {
@@ -134,6 +162,7 @@ pub(crate) fn traverse(
             warnings,
             skipped,
             suggested_fixes_skipped,
+            suggested_fixes_applied,
             diagnostics_not_printed,
         },
         evaluated_paths,
@@ -169,6 +198,7 @@ fn traverse_inputs(
     }));
 
     let paths = ctx.evaluated_paths();
+    ctx.discovered.store(paths.len(), Ordering::Relaxed);
     let dome = Dome::new(paths);
     let mut iter = dome.iter();
     fs.traversal(Box::new(|scope: &dyn TraversalScope| {
@@ -209,6 +239,8 @@ struct DiagnosticsPrinter<'ctx> {
     not_printed_diagnostics: AtomicU32,
     printed_diagnostics: AtomicU32,
     total_skipped_suggested_fixes: AtomicU32,
+    /// The number of fixes that were applied, or would have been in a dry run
+    total_applied_suggested_fixes: AtomicU32,
 }
 
 impl<'ctx> DiagnosticsPrinter<'ctx> {
@@ -224,6 +256,7 @@ impl<'ctx> DiagnosticsPrinter<'ctx> {
             not_printed_diagnostics: AtomicU32::new(0),
             printed_diagnostics: AtomicU32::new(0),
             total_skipped_suggested_fixes: AtomicU32::new(0),
+            total_applied_suggested_fixes: AtomicU32::new(0),
         }
     }
 
@@ -258,6 +291,10 @@ impl<'ctx> DiagnosticsPrinter<'ctx> {
         self.total_skipped_suggested_fixes.load(Ordering::Relaxed)
     }
 
+    fn applied_fixes(&self) -> u32 {
+        self.total_applied_suggested_fixes.load(Ordering::Relaxed)
+    }
+
     /// Checks if the diagnostic we received from the thread should be considered or not. Logic:
     /// - it should not be considered if its severity level is lower than the one provided via CLI;
     /// - it should not be considered if it's a verbose diagnostic and the CLI **didn't** request a `--verbose` option.
@@ -304,6 +341,13 @@ impl<'ctx> DiagnosticsPrinter<'ctx> {
                         .fetch_add(skipped_suggested_fixes, Ordering::Relaxed);
                 }
 
+                Message::AppliedFixes {
+                    applied_suggested_fixes,
+                } => {
+                    self.total_applied_suggested_fixes
+                        .fetch_add(applied_suggested_fixes, Ordering::Relaxed);
+                }
+
                 Message::Failure => {
                     self.errors.fetch_add(1, Ordering::Relaxed);
                 }
@@ -384,10 +428,33 @@ impl<'ctx> DiagnosticsPrinter<'ctx> {
             }
         }
 
+        sort_diagnostics(&mut diagnostics_to_print);
         diagnostics_to_print
     }
 }
 
+/// Orders diagnostics by file, position and category, since the traversal threads
+/// report them in no particular order.
+fn sort_diagnostics(diagnostics: &mut [Error]) {
+    diagnostics.sort_by(|a, b| {
+        let key = |diagnostic: &Error| {
+            let location = diagnostic.location();
+            let file = match location.resource {
+                Some(Resource::File(file)) => Some(file),
+                _ => None,
+            };
+
+            (
+                file,
+                location.span.map(|span| span.start()),
+                diagnostic.category().map(|category| category.name()),
+            )
+        };
+
+        key(a).cmp(&key(b))
+    });
+}
+
 /// Context object shared between directory traversal tasks
 pub(crate) struct TraversalOptions<'ctx, 'app> {
     /// Shared instance of [FileSystem]
@@ -414,6 +481,14 @@ pub(crate) struct TraversalOptions<'ctx, 'app> {
 
     /// List of paths that should be processed
     pub(crate) evaluated_paths: RwLock<BTreeSet<PgTPath>>,
+
+    /// Number of files that completed processing. Guarded by a lock so that the
+    /// progress callback observes the counts in increasing order
+    processed: Mutex<usize>,
+    /// Number of files discovered during the evaluation phase of the traversal
+    discovered: AtomicUsize,
+    /// Optional callback notified every time a file completes processing
+    on_progress: Option<ProgressCallback<'ctx>>,
 }
 
 impl TraversalOptions<'_, '_> {
@@ -432,6 +507,18 @@ impl TraversalOptions<'_, '_> {
         self.matches.fetch_add(num_matches, Ordering::Relaxed);
     }
 
+    /// Marks a file as completed and notifies the progress callback, if any
+    fn increment_processed(&self) {
+        let mut processed = self.processed.lock().unwrap();
+        *processed += 1;
+        if let Some(on_progress) = self.on_progress {
+            on_progress(TraversalProgress {
+                processed: *processed,
+                total: self.discovered.load(Ordering::Relaxed),
+            });
+        }
+    }
+
     /// Send a message to the display thread
     pub(crate) fn push_message(&self, msg: impl Into<Message>) {
         self.messages.send(msg.into()).ok();
@@ -454,10 +541,7 @@ impl TraversalContext for TraversalOptions<'_, '_> {
     fn can_handle(&self, pgt_path: &PgTPath) -> bool {
         let path = pgt_path.as_path();
 
-        let is_valid_file = self.fs.path_is_file(path)
-            && path
-                .extension()
-                .is_some_and(|ext| ext == "sql" || ext == "pg");
+        let is_valid_file = self.fs.path_is_file(path) && self.execution.is_sql_file(path);
 
         if self.fs.path_is_dir(path) || self.fs.path_is_symlink(path) || is_valid_file {
             // handle:
@@ -486,11 +570,13 @@ impl TraversalContext for TraversalOptions<'_, '_> {
         match self.execution.traversal_mode() {
             TraversalMode::Dummy => true,
             TraversalMode::Check { .. } => true,
+            TraversalMode::Complete { .. } => false,
         }
     }
 
     fn handle_path(&self, path: PgTPath) {
-        handle_file(self, &path)
+        handle_file(self, &path);
+        self.increment_processed();
     }
 
     fn store_path(&self, path: PgTPath) {
@@ -546,3 +632,133 @@ fn handle_file(ctx: &TraversalOptions, path: &PgTPath) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{DiagnosticsPrinter, TraversalOptions, TraversalProgress, traverse_inputs};
+    use crate::execute::diagnostics::PanicDiagnostic;
+    use crate::execute::process_file::Message;
+    use crate::execute::{Execution, TraversalMode, VcsTargeted};
+    use crossbeam::channel::unbounded;
+    use pgt_diagnostics::{DiagnosticExt, Error, Resource};
+    use pgt_fs::{MemoryFileSystem, PathInterner};
+    use pgt_text_size::{TextRange, TextSize};
+    use pgt_workspace::workspace::server;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicU32, AtomicUsize};
+
+    #[test]
+    fn reports_progress_for_every_file() {
+        const FILES: usize = 5;
+
+        let mut fs = MemoryFileSystem::default();
+        for i in 0..FILES {
+            fs.insert(format!("src/file_{i}.sql").into(), "select 1;".as_bytes());
+        }
+
+        let workspace = server();
+        let execution = Execution::new(TraversalMode::Check {
+            fix_file_mode: None,
+            unsafe_fixes: false,
+            stdin: None,
+            vcs_targeted: VcsTargeted::from((false, false)),
+            only: vec![],
+            skip: vec![],
+        });
+
+        let (interner, _recv_files) = PathInterner::new();
+        let (sender, _receiver) = unbounded();
+        let counter = AtomicUsize::new(0);
+        let remaining_diagnostics = AtomicU32::new(20);
+
+        let reported = Mutex::new(Vec::new());
+        let on_progress = |progress: TraversalProgress| reported.lock().unwrap().push(progress);
+
+        traverse_inputs(
+            &fs,
+            vec![OsString::from("src")],
+            &TraversalOptions {
+                fs: &fs,
+                workspace: &*workspace,
+                execution: &execution,
+                interner,
+                matches: &counter,
+                changed: &counter,
+                unchanged: &counter,
+                skipped: &counter,
+                messages: sender,
+                remaining_diagnostics: &remaining_diagnostics,
+                evaluated_paths: Default::default(),
+                processed: Mutex::default(),
+                discovered: AtomicUsize::new(0),
+                on_progress: Some(&on_progress),
+            },
+        );
+
+        let reported = reported.into_inner().unwrap();
+        assert_eq!(reported.len(), FILES);
+        for (i, progress) in reported.iter().enumerate() {
+            assert_eq!(progress.processed, i + 1);
+            assert_eq!(progress.total, FILES);
+        }
+    }
+
+    #[test]
+    fn prints_diagnostics_sorted_by_file_and_position() {
+        let execution = Execution::new(TraversalMode::Check {
+            fix_file_mode: None,
+            unsafe_fixes: false,
+            stdin: None,
+            vcs_targeted: VcsTargeted::from((false, false)),
+            only: vec![],
+            skip: vec![],
+        });
+        let printer = DiagnosticsPrinter::new(&execution);
+
+        let diagnostic = |start: u32| -> Error {
+            PanicDiagnostic {
+                message: "failed".into(),
+            }
+            .with_file_span(TextRange::at(TextSize::new(start), TextSize::new(1)))
+        };
+
+        let (sender, receiver) = unbounded();
+        let (_interner, interner_receiver) = unbounded::<PathBuf>();
+
+        for (name, starts) in [("b.sql", vec![5, 0]), ("a.sql", vec![3])] {
+            sender
+                .send(Message::Diagnostics {
+                    name: name.into(),
+                    content: "select 1;".into(),
+                    diagnostics: starts.into_iter().map(diagnostic).collect(),
+                    skipped_diagnostics: 0,
+                })
+                .unwrap();
+        }
+        drop(sender);
+
+        let printed: Vec<(String, u32)> = printer
+            .run(receiver, interner_receiver)
+            .iter()
+            .map(|d| {
+                let location = d.location();
+                let file = match location.resource {
+                    Some(Resource::File(file)) => file.to_string(),
+                    _ => panic!("Expected a file"),
+                };
+                (file, location.span.unwrap().start().into())
+            })
+            .collect();
+
+        assert_eq!(
+            printed,
+            vec![
+                ("a.sql".to_string(), 3),
+                ("b.sql".to_string(), 0),
+                ("b.sql".to_string(), 5),
+            ]
+        );
+    }
+}
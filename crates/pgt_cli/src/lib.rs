@@ -7,6 +7,8 @@
 use cli_options::CliOptions;
 use commands::CommandRunner;
 use commands::check::CheckCommandPayload;
+use commands::complete::CompleteCommandPayload;
+use pgt_console::fmt::ColorTheme;
 use pgt_console::{ColorMode, Console};
 use pgt_fs::OsFileSystem;
 use pgt_workspace::{App, DynRef, Workspace, WorkspaceRef};
@@ -23,11 +25,13 @@ mod panic;
 mod reporter;
 mod service;
 
-use crate::cli_options::ColorsArg;
+use crate::cli_options::{ColorsArg, ColorsThemeArg};
 pub use crate::commands::{PgtCommand, pgt_command};
 pub use crate::logging::{LoggingLevel, setup_cli_subscriber};
 pub use diagnostics::CliDiagnostic;
-pub use execute::{Execution, TraversalMode, VcsTargeted, execute_mode};
+pub use execute::{
+    Execution, FixFileMode, TraversalMode, TraverseResult, VcsTargeted, execute_mode,
+};
 pub use panic::setup_panic_handler;
 pub use reporter::{DiagnosticsPayload, Reporter, ReporterVisitor, TraversalSummary};
 pub use service::{SocketTransport, open_transport};
@@ -37,10 +41,16 @@ pub(crate) const VERSION: &str = match option_env!("PGT_VERSION") {
     None => env!("CARGO_PKG_VERSION"),
 };
 
+/// Callback that receives the result of a traversal before it is reported
+pub type TraverseHook<'app> = Box<dyn FnOnce(&TraverseResult) + 'app>;
+
 /// Global context for an execution of the CLI
 pub struct CliSession<'app> {
     /// Instance of [App] used by this run of the CLI
     pub app: App<'app>,
+
+    /// Inspects the result of the traversal, if the command traverses any files
+    pub on_traverse: Option<TraverseHook<'app>>,
 }
 
 impl<'app> CliSession<'app> {
@@ -54,6 +64,7 @@ impl<'app> CliSession<'app> {
                 console,
                 WorkspaceRef::Borrowed(workspace),
             ),
+            on_traverse: None,
         })
     }
 
@@ -74,6 +85,10 @@ impl<'app> CliSession<'app> {
                 staged,
                 changed,
                 since,
+                only,
+                skip,
+                fix_file_mode,
+                unsafe_fixes,
             } => run_command(
                 self,
                 &cli_options,
@@ -84,6 +99,24 @@ impl<'app> CliSession<'app> {
                     staged,
                     changed,
                     since,
+                    only,
+                    skip,
+                    fix_file_mode,
+                    unsafe_fixes,
+                },
+            ),
+            PgtCommand::Complete {
+                cli_options,
+                configuration,
+                stdin_file_path,
+                cursor,
+            } => run_command(
+                self,
+                &cli_options,
+                CompleteCommandPayload {
+                    configuration,
+                    stdin_file_path,
+                    cursor,
                 },
             ),
             PgtCommand::Clean => commands::clean::clean(self),
@@ -134,6 +167,13 @@ pub fn to_color_mode(color: Option<&ColorsArg>) -> ColorMode {
     }
 }
 
+pub fn to_color_theme(theme: Option<&ColorsThemeArg>) -> ColorTheme {
+    match theme {
+        Some(ColorsThemeArg::Light) => ColorTheme::LIGHT,
+        Some(ColorsThemeArg::Dark) | None => ColorTheme::DARK,
+    }
+}
+
 pub(crate) fn run_command(
     session: CliSession,
     cli_options: &CliOptions,
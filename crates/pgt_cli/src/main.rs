@@ -2,7 +2,7 @@
 
 use pgt_cli::{
     CliDiagnostic, CliSession, PgtCommand, open_transport, pgt_command, setup_panic_handler,
-    to_color_mode,
+    to_color_mode, to_color_theme,
 };
 use pgt_console::{ConsoleExt, EnvConsole, markup};
 use pgt_diagnostics::{Diagnostic, PrintDiagnostic, set_bottom_frame};
@@ -34,6 +34,7 @@ fn main() -> ExitCode {
     let command = pgt_command().fallback_to_usage().run();
 
     console.set_color(to_color_mode(command.get_color()));
+    console.set_theme(to_color_theme(command.get_color_theme()));
 
     let is_verbose = command.is_verbose();
     let result = run_workspace(&mut console, command);
@@ -0,0 +1,153 @@
+use crate::{DiagnosticsPayload, Execution, Reporter, ReporterVisitor, TraversalSummary};
+use pgt_console::{Console, ConsoleExt, markup};
+use pgt_diagnostics::display::SourceFile;
+use pgt_diagnostics::{Error, PrintDescription, Resource, Severity};
+use serde::Serialize;
+use std::io;
+
+pub(crate) struct JsonReporter {
+    pub(crate) diagnostics_payload: DiagnosticsPayload,
+    pub(crate) execution: Execution,
+    pub(crate) summary: TraversalSummary,
+}
+
+impl Reporter for JsonReporter {
+    fn write(self, visitor: &mut dyn ReporterVisitor) -> io::Result<()> {
+        visitor.report_diagnostics(&self.execution, self.diagnostics_payload)?;
+        visitor.report_summary(&self.execution, self.summary)?;
+        Ok(())
+    }
+}
+
+/// Prints the diagnostics and the summary either as a single JSON document, or as
+/// [newline delimited JSON](https://github.com/ndjson/ndjson-spec) with one line per diagnostic
+/// and a final line for the summary.
+pub(crate) struct JsonReporterVisitor<'a> {
+    console: &'a mut dyn Console,
+    delimited: bool,
+    diagnostics: Vec<JsonDiagnostic>,
+}
+
+impl<'a> JsonReporterVisitor<'a> {
+    pub(crate) fn new(console: &'a mut dyn Console) -> Self {
+        Self {
+            console,
+            delimited: false,
+            diagnostics: vec![],
+        }
+    }
+
+    pub(crate) fn delimited(console: &'a mut dyn Console) -> Self {
+        Self {
+            console,
+            delimited: true,
+            diagnostics: vec![],
+        }
+    }
+}
+
+impl ReporterVisitor for JsonReporterVisitor<'_> {
+    fn report_summary(
+        &mut self,
+        _execution: &Execution,
+        summary: TraversalSummary,
+    ) -> io::Result<()> {
+        let serialized = if self.delimited {
+            serde_json::to_string(&JsonSummaryLine { summary })?
+        } else {
+            serde_json::to_string(&JsonReport {
+                summary,
+                diagnostics: &self.diagnostics,
+            })?
+        };
+
+        self.console.log(markup! {{serialized}});
+        Ok(())
+    }
+
+    fn report_diagnostics(
+        &mut self,
+        _execution: &Execution,
+        payload: DiagnosticsPayload,
+    ) -> io::Result<()> {
+        for diagnostic in reported_diagnostics(&payload) {
+            let diagnostic = JsonDiagnostic::from_diagnostic(diagnostic);
+
+            if self.delimited {
+                let serialized = serde_json::to_string(&diagnostic)?;
+                self.console.log(markup! {{serialized}});
+            } else {
+                self.diagnostics.push(diagnostic);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The diagnostics of the payload that pass its severity and verbosity filters
+pub(super) fn reported_diagnostics(payload: &DiagnosticsPayload) -> impl Iterator<Item = &Error> {
+    payload
+        .diagnostics
+        .iter()
+        .filter(|d| d.severity() >= payload.diagnostic_level)
+        .filter(|d| !d.tags().is_verbose() || payload.verbose)
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    summary: TraversalSummary,
+    diagnostics: &'a [JsonDiagnostic],
+}
+
+#[derive(Serialize)]
+struct JsonSummaryLine {
+    summary: TraversalSummary,
+}
+
+/// A diagnostic, detached from the source it was emitted for
+#[derive(Debug, Serialize)]
+pub(super) struct JsonDiagnostic {
+    pub(super) category: Option<String>,
+    pub(super) severity: Severity,
+    pub(super) message: String,
+    pub(super) path: Option<String>,
+    /// The one-based position where the diagnostic starts, if it points into a file
+    pub(super) start: Option<JsonPosition>,
+}
+
+#[derive(Debug, Serialize)]
+pub(super) struct JsonPosition {
+    pub(super) line: usize,
+    pub(super) column: usize,
+}
+
+impl JsonDiagnostic {
+    pub(super) fn from_diagnostic(diagnostic: &Error) -> Self {
+        let location = diagnostic.location();
+
+        let path = match location.resource {
+            Some(Resource::File(file)) => Some(file.to_string()),
+            _ => None,
+        };
+
+        let start = location
+            .span
+            .zip(location.source_code)
+            .and_then(|(span, source_code)| {
+                SourceFile::new(source_code).location(span.start()).ok()
+            })
+            .map(|start| JsonPosition {
+                line: start.line_number.get(),
+                column: start.column_number.get(),
+            });
+
+        Self {
+            category: diagnostic.category().map(|c| c.name().to_string()),
+            severity: diagnostic.severity(),
+            message: PrintDescription(diagnostic).to_string(),
+            path,
+            start,
+        }
+    }
+}
@@ -1,6 +1,8 @@
 pub(crate) mod github;
 pub(crate) mod gitlab;
+pub(crate) mod json;
 pub(crate) mod junit;
+pub(crate) mod sarif;
 pub(crate) mod terminal;
 
 use crate::execute::Execution;
@@ -30,6 +32,7 @@ pub struct TraversalSummary {
     pub warnings: u32,
     pub skipped: usize,
     pub suggested_fixes_skipped: u32,
+    pub suggested_fixes_applied: u32,
     pub diagnostics_not_printed: u32,
 }
 
@@ -0,0 +1,165 @@
+use crate::reporter::json::{JsonDiagnostic, reported_diagnostics};
+use crate::{DiagnosticsPayload, Execution, Reporter, ReporterVisitor, TraversalSummary};
+use pgt_console::{Console, ConsoleExt, markup};
+use pgt_diagnostics::Severity;
+use serde::Serialize;
+use std::io;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+pub(crate) struct SarifReporter {
+    pub(crate) diagnostics_payload: DiagnosticsPayload,
+    pub(crate) execution: Execution,
+}
+
+impl Reporter for SarifReporter {
+    fn write(self, visitor: &mut dyn ReporterVisitor) -> io::Result<()> {
+        visitor.report_diagnostics(&self.execution, self.diagnostics_payload)?;
+        Ok(())
+    }
+}
+
+/// Prints the diagnostics as a [SARIF](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html) log,
+/// e.g. to upload them to GitHub code scanning.
+pub(crate) struct SarifReporterVisitor<'a>(pub(crate) &'a mut dyn Console);
+
+impl ReporterVisitor for SarifReporterVisitor<'_> {
+    fn report_summary(
+        &mut self,
+        _execution: &Execution,
+        _summary: TraversalSummary,
+    ) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn report_diagnostics(
+        &mut self,
+        _execution: &Execution,
+        payload: DiagnosticsPayload,
+    ) -> io::Result<()> {
+        let results = reported_diagnostics(&payload)
+            .map(|diagnostic| SarifResult::from(JsonDiagnostic::from_diagnostic(diagnostic)))
+            .collect();
+
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "postgrestools",
+                        information_uri: "https://pgtools.dev",
+                    },
+                },
+                results,
+            }],
+        };
+
+        let serialized = serde_json::to_string_pretty(&log)?;
+        self.0.log(markup! {{serialized}});
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    information_uri: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_id: Option<String>,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: usize,
+    start_column: usize,
+}
+
+impl From<JsonDiagnostic> for SarifResult {
+    fn from(diagnostic: JsonDiagnostic) -> Self {
+        let region = diagnostic.start.map(|start| SarifRegion {
+            start_line: start.line,
+            start_column: start.column,
+        });
+
+        // sarif results without a location are valid, but an artifact needs a uri
+        let locations = diagnostic
+            .path
+            .map(|uri| SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation { uri },
+                    region,
+                },
+            })
+            .into_iter()
+            .collect();
+
+        Self {
+            rule_id: diagnostic.category,
+            level: match diagnostic.severity {
+                Severity::Hint | Severity::Information => "note",
+                Severity::Warning => "warning",
+                Severity::Error | Severity::Fatal => "error",
+            },
+            message: SarifMessage {
+                text: diagnostic.message,
+            },
+            locations,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::Reporter;
-use crate::execute::{Execution, TraversalMode};
+use crate::execute::{Execution, FixFileMode, TraversalMode};
 use crate::reporter::{DiagnosticsPayload, ReporterVisitor, TraversalSummary};
 use pgt_console::fmt::Formatter;
 use pgt_console::{Console, ConsoleExt, fmt, markup};
@@ -145,6 +145,45 @@ impl fmt::Display for SummaryDetail {
         }
     }
 }
+struct Fixes(u32);
+
+impl fmt::Display for Fixes {
+    fn fmt(&self, fmt: &mut Formatter) -> io::Result<()> {
+        fmt.write_markup(markup!({self.0} " "))?;
+        if self.0 == 1 {
+            fmt.write_str("fix")
+        } else {
+            fmt.write_str("fixes")
+        }
+    }
+}
+
+/// The fixes of a `check --write` or `check --dry-run`
+struct FixesDetail<'a>(Option<FixFileMode>, &'a TraversalSummary);
+
+impl fmt::Display for FixesDetail<'_> {
+    fn fmt(&self, fmt: &mut Formatter) -> io::Result<()> {
+        let applied = Fixes(self.1.suggested_fixes_applied);
+        match self.0 {
+            None => return Ok(()),
+            Some(FixFileMode::Write) => fmt.write_markup(markup! {
+                "\nApplied "{applied}"."
+            })?,
+            Some(FixFileMode::DryRun) => fmt.write_markup(markup! {
+                "\nWould apply "{applied}"."
+            })?,
+        }
+
+        if self.1.suggested_fixes_skipped > 0 {
+            fmt.write_markup(markup! {
+                " Skipped "{Fixes(self.1.suggested_fixes_skipped)}" that are unsafe or change an already fixed statement."
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
 struct SummaryTotal<'a>(&'a TraversalMode, usize, &'a Duration);
 
 impl fmt::Display for SummaryTotal<'_> {
@@ -157,6 +196,9 @@ impl fmt::Display for SummaryTotal<'_> {
             TraversalMode::Check { .. } => fmt.write_markup(markup! {
                 "Checked "{files}" in "{self.2}"."
             }),
+            TraversalMode::Complete { .. } => fmt.write_markup(markup! {
+                "Completed "{files}" in "{self.2}"."
+            }),
         }
     }
 }
@@ -165,11 +207,21 @@ pub(crate) struct ConsoleTraversalSummary<'a>(
     pub(crate) &'a TraversalMode,
     pub(crate) &'a TraversalSummary,
 );
+
+impl ConsoleTraversalSummary<'_> {
+    fn fix_file_mode(&self) -> Option<FixFileMode> {
+        match self.0 {
+            TraversalMode::Check { fix_file_mode, .. } => *fix_file_mode,
+            _ => None,
+        }
+    }
+}
 impl fmt::Display for ConsoleTraversalSummary<'_> {
     fn fmt(&self, fmt: &mut Formatter) -> io::Result<()> {
         let summary = SummaryTotal(self.0, self.1.changed + self.1.unchanged, &self.1.duration);
         let detail = SummaryDetail(self.1.changed);
-        fmt.write_markup(markup!(<Info>{summary}{detail}</Info>))?;
+        let fixes = FixesDetail(self.fix_file_mode(), self.1);
+        fmt.write_markup(markup!(<Info>{summary}{detail}{fixes}</Info>))?;
 
         if self.1.errors > 0 {
             if self.1.errors == 1 {
@@ -1,9 +1,10 @@
 use bpaf::Args;
 use std::path::Path;
 
-use crate::run_cli;
+use crate::{ReportedDiagnostic, run_cli, run_cli_captured};
 use pgt_console::BufferConsole;
-use pgt_fs::MemoryFileSystem;
+use pgt_diagnostics::Severity;
+use pgt_fs::{FileSystem, MemoryFileSystem, OpenOptions};
 use pgt_workspace::DynRef;
 
 #[test]
@@ -22,3 +23,409 @@ fn syntax_error() {
 
     assert!(result.is_ok(), "run_cli returned {result:?}");
 }
+
+#[test]
+fn only_checks_changed_files() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    fs.insert(Path::new("changed.sql").into(), "select 1;".as_bytes());
+    fs.insert(Path::new("unchanged.sql").into(), "select 1;".as_bytes());
+    fs.insert(Path::new("README.md").into(), "# readme".as_bytes());
+
+    // simulates `git diff --name-only main...HEAD`
+    fs.set_on_get_changed_files(Box::new(|| {
+        vec![String::from("changed.sql"), String::from("README.md")]
+    }));
+
+    let result = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(["check", "--changed", "--since=main"].as_slice()),
+    );
+
+    assert!(result.is_ok(), "run_cli returned {result:?}");
+
+    let output = console.to_snapshot();
+    assert!(
+        output.contains("Checked 1 file"),
+        "Expected only the changed file to be checked, got {output}"
+    );
+}
+
+#[test]
+fn checks_changed_files_with_configured_extensions() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    fs.insert(
+        Path::new("postgrestools.jsonc").into(),
+        r#"{ "files": { "sqlExtensions": ["psql"] } }"#.as_bytes(),
+    );
+    fs.insert(Path::new("changed.psql").into(), "select 1;".as_bytes());
+    fs.insert(Path::new("changed.sql").into(), "select 1;".as_bytes());
+
+    fs.set_on_get_changed_files(Box::new(|| {
+        vec![String::from("changed.psql"), String::from("changed.sql")]
+    }));
+
+    let result = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(["check", "--changed", "--since=main"].as_slice()),
+    );
+
+    assert!(result.is_ok(), "run_cli returned {result:?}");
+
+    let output = console.to_snapshot();
+    assert!(
+        output.contains("Checked 1 file"),
+        "Expected only the file with a configured extension to be checked, got {output}"
+    );
+}
+
+#[test]
+fn only_reports_selected_rules() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    let file_path = Path::new("test.sql");
+    fs.insert(
+        file_path.into(),
+        "alter table users drop column email;\ndrop table accounts;".as_bytes(),
+    );
+
+    let _ = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(
+            [
+                "check",
+                "--only=safety/banDropColumn",
+                file_path.as_os_str().to_str().unwrap(),
+            ]
+            .as_slice(),
+        ),
+    );
+
+    let output = console.to_snapshot();
+    assert!(
+        output.contains("lint/safety/banDropColumn"),
+        "Expected the selected rule to fire, got {output}"
+    );
+    assert!(
+        !output.contains("lint/safety/banDropTable"),
+        "Expected other rules to be skipped, got {output}"
+    );
+}
+
+#[test]
+fn checks_files_with_configured_extensions() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    fs.insert(
+        Path::new("postgrestools.jsonc").into(),
+        r#"{ "files": { "sqlExtensions": ["psql"] } }"#.as_bytes(),
+    );
+    fs.insert(Path::new("src/test.psql").into(), "select 1;".as_bytes());
+    fs.insert(Path::new("src/test.sql").into(), "select 1;".as_bytes());
+
+    let result = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(["check", "src"].as_slice()),
+    );
+
+    assert!(result.is_ok(), "run_cli returned {result:?}");
+
+    let output = console.to_snapshot();
+    assert!(
+        output.contains("Checked 1 file"),
+        "Expected only the .psql file to be checked, got {output}"
+    );
+}
+
+#[test]
+fn captures_printed_output_and_traversal_result() {
+    let mut fs = MemoryFileSystem::default();
+    fs.insert(
+        Path::new("test.sql").into(),
+        "alter table users drop column email;".as_bytes(),
+    );
+
+    let run = run_cli_captured(fs, Args::from(["check", "test.sql"].as_slice()));
+
+    // the rule is recommended, so its diagnostics are errors
+    assert!(run.result.is_err(), "Expected the check to fail");
+
+    let output = run.console.to_snapshot();
+    assert!(
+        output.contains("lint/safety/banDropColumn"),
+        "Expected the diagnostic to be printed, got {output}"
+    );
+    assert!(
+        output.contains("Checked 1 file"),
+        "Expected the summary to be printed, got {output}"
+    );
+
+    let summary = run.summary.expect("Expected the check to traverse files");
+    assert_eq!(summary.changed + summary.unchanged, 1);
+    assert_eq!(summary.errors, 1);
+    assert_eq!(
+        run.diagnostics,
+        vec![ReportedDiagnostic {
+            category: Some("lint/safety/banDropColumn".to_string()),
+            severity: Severity::Error,
+        }]
+    );
+}
+
+#[test]
+fn reports_with_each_reporter() {
+    for (reporter, expected) in [
+        ("default", "lint/safety/banDropColumn"),
+        ("github", "::error"),
+    ] {
+        let output = check_with_reporter(reporter).to_snapshot();
+        assert!(
+            output.contains(expected),
+            "Expected `{expected}` in the output of the {reporter} reporter, got {output}"
+        );
+    }
+
+    let json = parse_json(&printed_text(&check_with_reporter("json")));
+    let diagnostics = json["diagnostics"]
+        .as_array()
+        .expect("Expected the json report to list the diagnostics");
+    assert_eq!(diagnostics.len(), 1, "{json}");
+    assert_eq!(diagnostics[0]["category"], "lint/safety/banDropColumn");
+    assert!(json["summary"].is_object(), "{json}");
+
+    let ndjson = printed_text(&check_with_reporter("ndjson"));
+    let lines: Vec<serde_json::Value> = ndjson.lines().map(parse_json).collect();
+    assert!(
+        lines.iter().all(|line| line.is_object()),
+        "Expected one object per line, got {ndjson}"
+    );
+    assert_eq!(lines.len(), 2, "{ndjson}");
+    assert_eq!(lines[0]["category"], "lint/safety/banDropColumn");
+    assert!(lines[1]["summary"].is_object(), "{ndjson}");
+
+    let sarif = parse_json(&printed_text(&check_with_reporter("sarif")));
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"]
+        .as_array()
+        .expect("Expected the sarif log to list the results of a run");
+    assert_eq!(results.len(), 1, "{sarif}");
+    assert_eq!(results[0]["ruleId"], "lint/safety/banDropColumn");
+}
+
+#[test]
+fn prints_progress_in_verbose_mode() {
+    let mut fs = MemoryFileSystem::default();
+    fs.insert(Path::new("a.sql").into(), "select 1;".as_bytes());
+    fs.insert(Path::new("b.sql").into(), "select 2;".as_bytes());
+
+    let run = run_cli_captured(
+        fs,
+        Args::from(["check", "--verbose", "a.sql", "b.sql"].as_slice()),
+    );
+
+    let output = run.console.to_snapshot();
+    assert!(
+        output.contains("Processed 2 of 2 files"),
+        "Expected the progress in the output, got {output}"
+    );
+
+    // other reporters print their own format only
+    let mut fs = MemoryFileSystem::default();
+    fs.insert(Path::new("a.sql").into(), "select 1;".as_bytes());
+
+    let run = run_cli_captured(
+        fs,
+        Args::from(["check", "--verbose", "--reporter=json", "a.sql"].as_slice()),
+    );
+    let output = run.console.to_snapshot();
+    assert!(!output.contains("Processed"), "{output}");
+}
+
+fn check_with_reporter(reporter: &str) -> BufferConsole {
+    let mut fs = MemoryFileSystem::default();
+    fs.insert(
+        Path::new("test.sql").into(),
+        "alter table users drop column email;".as_bytes(),
+    );
+
+    let reporter_arg = format!("--reporter={reporter}");
+    run_cli_captured(
+        fs,
+        Args::from(["check", reporter_arg.as_str(), "test.sql"].as_slice()),
+    )
+    .console
+}
+
+/// The printed messages as plain text, one message per line
+fn printed_text(console: &BufferConsole) -> String {
+    console
+        .out_buffer
+        .iter()
+        .map(|message| {
+            message
+                .content
+                .0
+                .iter()
+                .map(|node| &*node.content)
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn parse_json(text: &str) -> serde_json::Value {
+    serde_json::from_str(text)
+        .unwrap_or_else(|err| panic!("Expected valid JSON ({err}), got {text}"))
+}
+
+fn read_file(fs: &MemoryFileSystem, path: &Path) -> String {
+    let mut file = fs
+        .open_with_options(path, OpenOptions::default().read(true))
+        .expect("the file should exist in the memory file system");
+
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .expect("the file should be read without error");
+    content
+}
+
+#[test]
+fn skips_unsafe_fixes_with_write() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    let file_path = Path::new("test.sql");
+    let content = "alter table users add column age int not null;";
+    fs.insert(file_path.into(), content.as_bytes());
+
+    let _ = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(
+            [
+                "check",
+                "--write",
+                "--only=safety/addingRequiredField",
+                file_path.as_os_str().to_str().unwrap(),
+            ]
+            .as_slice(),
+        ),
+    );
+
+    assert_eq!(read_file(&fs, file_path), content);
+
+    let output = console.to_snapshot();
+    assert!(
+        output.contains("Applied 0 fixes.") && output.contains("Skipped 1 fix"),
+        "Expected the unsafe fix to be skipped, got {output}"
+    );
+}
+
+#[test]
+fn applies_unsafe_fixes_with_write_and_unsafe() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    let file_path = Path::new("test.sql");
+    let content = "alter table users add column age int not null;";
+    fs.insert(file_path.into(), content.as_bytes());
+
+    let result = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(
+            [
+                "check",
+                "--write",
+                "--unsafe",
+                "--only=safety/addingRequiredField",
+                file_path.as_os_str().to_str().unwrap(),
+            ]
+            .as_slice(),
+        ),
+    );
+
+    assert!(result.is_ok(), "run_cli returned {result:?}");
+
+    let fixed = read_file(&fs, file_path);
+    assert_ne!(fixed, content);
+    assert!(
+        !fixed.to_lowercase().contains("not null"),
+        "Expected the fix to be written, got {fixed}"
+    );
+
+    let output = console.to_snapshot();
+    assert!(
+        output.contains("Fixed 1 file") && output.contains("Applied 1 fix."),
+        "Expected the applied fix to be reported, got {output}"
+    );
+}
+
+#[test]
+fn previews_fixes_with_dry_run() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    let file_path = Path::new("test.sql");
+    let content = "alter table users add column age int not null;";
+    fs.insert(file_path.into(), content.as_bytes());
+
+    let _ = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(
+            [
+                "check",
+                "--dry-run",
+                "--unsafe",
+                "--only=safety/addingRequiredField",
+                file_path.as_os_str().to_str().unwrap(),
+            ]
+            .as_slice(),
+        ),
+    );
+
+    assert_eq!(read_file(&fs, file_path), content);
+
+    let output = console.to_snapshot();
+    assert!(
+        output.contains("Would apply 1 fix."),
+        "Expected the fix to be previewed, got {output}"
+    );
+}
+
+#[test]
+fn rejects_write_with_dry_run() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    let file_path = Path::new("test.sql");
+    let content = "alter table users add column age int not null;";
+    fs.insert(file_path.into(), content.as_bytes());
+
+    let result = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(
+            [
+                "check",
+                "--write",
+                "--dry-run",
+                file_path.as_os_str().to_str().unwrap(),
+            ]
+            .as_slice(),
+        ),
+    );
+
+    assert!(result.is_err(), "run_cli returned {result:?}");
+    assert_eq!(read_file(&fs, file_path), content);
+}
@@ -0,0 +1,54 @@
+use bpaf::Args;
+
+use crate::run_cli;
+use pgt_console::BufferConsole;
+use pgt_fs::MemoryFileSystem;
+use pgt_workspace::DynRef;
+
+#[test]
+fn prints_completions_as_json() {
+    let mut fs = MemoryFileSystem::default();
+    let mut console = BufferConsole::default();
+
+    let sql = "select * from ";
+    console.in_buffer.push(sql.to_string());
+
+    let cursor = format!("--cursor={}", sql.len());
+
+    let result = run_cli(
+        DynRef::Borrowed(&mut fs),
+        &mut console,
+        Args::from(["complete", "--stdin-file-path=test.sql", cursor.as_str()].as_slice()),
+    );
+
+    assert!(result.is_ok(), "run_cli returned {result:?}");
+
+    let message = console
+        .out_buffer
+        .last()
+        .expect("Expected the completion items to be printed");
+
+    let content: String = message
+        .content
+        .0
+        .iter()
+        .map(|node| &*node.content)
+        .collect();
+    let items: serde_json::Value =
+        serde_json::from_str(&content).expect("Expected the output to be valid JSON");
+
+    let items = items
+        .as_array()
+        .expect("Expected a list of completion items");
+
+    // there is no database to complete the tables from, but keywords don't need one
+    assert!(
+        items.iter().any(|item| item["label"] == "only"),
+        "Expected the `only` keyword to be offered, got {items:?}"
+    );
+
+    for item in items {
+        assert!(item.get("label").is_some_and(|l| l.is_string()));
+        assert!(item.get("kind").is_some_and(|k| k.is_string()));
+    }
+}
@@ -1,9 +1,12 @@
 mod commands;
 
+use std::cell::RefCell;
+
 use bpaf::ParseFailure;
-use pgt_cli::{CliDiagnostic, CliSession, pgt_command};
-use pgt_console::{Console, ConsoleExt, markup};
-use pgt_fs::FileSystem;
+use pgt_cli::{CliDiagnostic, CliSession, TraversalSummary, TraverseHook, pgt_command};
+use pgt_console::{BufferConsole, Console, ConsoleExt, markup};
+use pgt_diagnostics::Severity;
+use pgt_fs::{FileSystem, MemoryFileSystem};
 use pgt_workspace::{App, DynRef};
 
 /// Create an [App] instance using the provided [FileSystem] and [Console]
@@ -12,6 +15,70 @@ pub(crate) fn run_cli<'app>(
     fs: DynRef<'app, dyn FileSystem>,
     console: &'app mut dyn Console,
     args: bpaf::Args,
+) -> Result<(), CliDiagnostic> {
+    run_cli_session(fs, console, args, None)
+}
+
+/// A diagnostic as reported by the traversal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReportedDiagnostic {
+    pub(crate) category: Option<String>,
+    pub(crate) severity: Severity,
+}
+
+/// The outcome of a CLI run, captured by [run_cli_captured]
+pub(crate) struct CapturedRun {
+    pub(crate) result: Result<(), CliDiagnostic>,
+    /// Everything the command printed
+    pub(crate) console: BufferConsole,
+    /// The summary of the traversal, `None` if the command did not traverse any files
+    pub(crate) summary: Option<TraversalSummary>,
+    /// The diagnostics of the traversal, regardless of what the reporter printed
+    pub(crate) diagnostics: Vec<ReportedDiagnostic>,
+}
+
+/// Runs the CLI like [run_cli] with a [BufferConsole], and captures the result of the
+/// traversal next to the printed output
+pub(crate) fn run_cli_captured(fs: MemoryFileSystem, args: bpaf::Args) -> CapturedRun {
+    let mut console = BufferConsole::default();
+    let captured = RefCell::new(None);
+
+    let result = run_cli_session(
+        DynRef::Owned(Box::new(fs)),
+        &mut console,
+        args,
+        Some(Box::new(|traversal| {
+            let diagnostics = traversal
+                .diagnostics
+                .iter()
+                .map(|d| ReportedDiagnostic {
+                    category: d.category().map(|c| c.name().to_string()),
+                    severity: d.severity(),
+                })
+                .collect::<Vec<_>>();
+
+            *captured.borrow_mut() = Some((traversal.summary, diagnostics));
+        })),
+    );
+
+    let (summary, diagnostics) = match captured.into_inner() {
+        Some((summary, diagnostics)) => (Some(summary), diagnostics),
+        None => (None, vec![]),
+    };
+
+    CapturedRun {
+        result,
+        console,
+        summary,
+        diagnostics,
+    }
+}
+
+fn run_cli_session<'app>(
+    fs: DynRef<'app, dyn FileSystem>,
+    console: &'app mut dyn Console,
+    args: bpaf::Args,
+    on_traverse: Option<TraverseHook<'app>>,
 ) -> Result<(), CliDiagnostic> {
     use pgt_cli::SocketTransport;
     use pgt_lsp::ServerFactory;
@@ -36,7 +103,7 @@ pub(crate) fn run_cli<'app>(
     let workspace = workspace::client(transport).unwrap();
     let app = App::new(fs, console, WorkspaceRef::Owned(workspace));
 
-    let mut session = CliSession { app };
+    let mut session = CliSession { app, on_traverse };
     let command = pgt_command().run_inner(args);
     match command {
         Ok(command) => session.run(command),
@@ -28,6 +28,12 @@ fn to_params<'a>(
         schema: cache,
         text,
         tree,
+        min_chars: 0,
+        only_kinds: None,
+        include_system_schemas: true,
+        hidden_objects: None,
+        limit: None,
+        preceding_statements: &[],
     }
 }
 
@@ -12,22 +12,34 @@ pub(crate) struct PossibleCompletionItem<'a> {
     pub score: CompletionScore<'a>,
     pub filter: CompletionFilter<'a>,
     pub completion_text: Option<CompletionText>,
+    pub documentation: Option<String>,
 }
 
 pub(crate) struct CompletionBuilder<'a> {
     items: Vec<PossibleCompletionItem<'a>>,
     ctx: &'a CompletionContext<'a>,
+    /// The maximum number of items that [CompletionBuilder::finish] returns.
+    limit: usize,
 }
 
 impl<'a> CompletionBuilder<'a> {
-    pub fn new(ctx: &'a CompletionContext) -> Self {
-        CompletionBuilder { items: vec![], ctx }
+    pub fn new(ctx: &'a CompletionContext, limit: usize) -> Self {
+        CompletionBuilder {
+            items: vec![],
+            ctx,
+            limit,
+        }
     }
 
     pub fn add_item(&mut self, item: PossibleCompletionItem<'a>) {
         self.items.push(item);
     }
 
+    /// Drops all items that are not of one of the given kinds.
+    pub fn retain_kinds(&mut self, kinds: &[CompletionItemKind]) {
+        self.items.retain(|item| kinds.contains(&item.kind));
+    }
+
     pub fn finish(self) -> Vec<CompletionItem> {
         let mut items: Vec<PossibleCompletionItem> = self
             .items
@@ -37,6 +49,13 @@ impl<'a> CompletionBuilder<'a> {
 
         for item in items.iter_mut() {
             item.score.calc_score(self.ctx);
+
+            tracing::trace!(
+                label = item.label,
+                score = item.score.get_score(),
+                breakdown = ?item.score.get_breakdown(),
+                "Scored completion item"
+            );
         }
 
         items.sort_by(|a, b| {
@@ -47,7 +66,7 @@ impl<'a> CompletionBuilder<'a> {
         });
 
         items.dedup_by(|a, b| a.label == b.label);
-        items.truncate(crate::LIMIT);
+        items.truncate(self.limit);
 
         let should_preselect_first_item = should_preselect_first_item(&items);
 
@@ -65,6 +84,11 @@ impl<'a> CompletionBuilder<'a> {
             .map(|(idx, item)| {
                 let preselected = idx == 0 && should_preselect_first_item;
 
+                let completion_text = item.completion_text.unwrap_or_else(|| CompletionText {
+                    text: item.label.clone(),
+                    range: self.ctx.replace_range,
+                });
+
                 CompletionItem {
                     description: item.description,
                     kind: item.kind,
@@ -73,7 +97,8 @@ impl<'a> CompletionBuilder<'a> {
 
                     // wonderous Rust syntax ftw
                     sort_text: format!("{:0>padding$}", idx, padding = max_padding),
-                    completion_text: item.completion_text,
+                    completion_text,
+                    documentation: item.documentation,
                 }
             })
             .collect()
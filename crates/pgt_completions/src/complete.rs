@@ -3,8 +3,17 @@ use pgt_text_size::TextSize;
 use crate::{
     builder::CompletionBuilder,
     context::CompletionContext,
-    item::CompletionItem,
-    providers::{complete_columns, complete_functions, complete_schemas, complete_tables},
+    hidden_objects::HiddenObjects,
+    item::{CompletionItem, CompletionItemKind},
+    providers::{
+        complete_aliases, complete_array_types, complete_collations, complete_column_defaults,
+        complete_columns, complete_constraints, complete_ctes, complete_defined_columns,
+        complete_enum_values, complete_extensions, complete_fields, complete_functions,
+        complete_keywords, complete_literals, complete_meta_commands, complete_operator_classes,
+        complete_policies, complete_returning, complete_schemas, complete_sequence_options,
+        complete_settings, complete_storage_parameters, complete_tables, complete_time_zones,
+        complete_transactions, complete_types, complete_windows,
+    },
     sanitization::SanitizedCompletionParams,
 };
 
@@ -16,6 +25,19 @@ pub struct CompletionParams<'a> {
     pub schema: &'a pgt_schema_cache::SchemaCache,
     pub text: String,
     pub tree: &'a tree_sitter::Tree,
+    /// Completions are only offered once the word under the cursor has at least this many characters.
+    pub min_chars: usize,
+    /// If set, only items of these kinds are completed and all other providers are skipped.
+    pub only_kinds: Option<Vec<CompletionItemKind>>,
+    /// Whether objects in system schemas like `pg_catalog` are suggested at all.
+    pub include_system_schemas: bool,
+    /// Objects that are never suggested, e.g. deprecated ones.
+    pub hidden_objects: Option<&'a HiddenObjects>,
+    /// The maximum number of items that are returned, [LIMIT] if not set.
+    pub limit: Option<usize>,
+    /// The statements of the document before the statement, e.g. to find the savepoints
+    /// declared there.
+    pub preceding_statements: &'a [&'a str],
 }
 
 #[tracing::instrument(level = "debug", skip_all, fields(
@@ -23,16 +45,234 @@ pub struct CompletionParams<'a> {
     position = params.position.to_string()
 ))]
 pub fn complete(params: CompletionParams) -> Vec<CompletionItem> {
+    let min_chars = params.min_chars;
+    let limit = params.limit.unwrap_or(LIMIT);
+    let only_kinds = params.only_kinds.clone();
     let sanitized_params = SanitizedCompletionParams::from(params);
 
     let ctx = CompletionContext::new(&sanitized_params);
 
-    let mut builder = CompletionBuilder::new(&ctx);
+    let typed_chars = ctx
+        .get_node_under_cursor_content()
+        .map_or(0, |content| content.chars().count());
+    if typed_chars < min_chars {
+        return vec![];
+    }
 
-    complete_tables(&ctx, &mut builder);
-    complete_functions(&ctx, &mut builder);
-    complete_columns(&ctx, &mut builder);
-    complete_schemas(&ctx, &mut builder);
+    // whether a provider that produces items of the given kinds should run
+    let wants = |kinds: &[CompletionItemKind]| {
+        only_kinds
+            .as_ref()
+            .is_none_or(|only| kinds.iter().any(|kind| only.contains(kind)))
+    };
+
+    let mut builder = CompletionBuilder::new(&ctx, limit);
+
+    // psql meta-commands are not sql, so nothing else can be completed there
+    if ctx.meta_command_range.is_some() {
+        if wants(&[CompletionItemKind::Keyword]) {
+            complete_meta_commands(&ctx, &mut builder);
+        }
+        return builder.finish();
+    }
+
+    // without a database connection, there are no objects to complete
+    if !ctx.schema_cache.is_empty() {
+        if wants(&[CompletionItemKind::Table]) {
+            complete_tables(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Function]) {
+            complete_functions(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Column]) {
+            complete_columns(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Field]) {
+            complete_fields(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Schema]) {
+            complete_schemas(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Setting]) {
+            complete_settings(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Collation]) {
+            complete_collations(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Extension]) {
+            complete_extensions(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::OperatorClass]) {
+            complete_operator_classes(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Constraint]) {
+            complete_constraints(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Type]) {
+            complete_types(&ctx, &mut builder);
+            complete_array_types(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Policy]) {
+            complete_policies(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::Keyword]) {
+            complete_time_zones(&ctx, &mut builder);
+        }
+        if wants(&[CompletionItemKind::EnumValue]) {
+            complete_enum_values(&ctx, &mut builder);
+        }
+    }
+
+    if wants(&[CompletionItemKind::Function, CompletionItemKind::Keyword]) {
+        complete_column_defaults(&ctx, &mut builder);
+    }
+    if wants(&[CompletionItemKind::Parameter]) {
+        complete_storage_parameters(&ctx, &mut builder);
+    }
+    if wants(&[CompletionItemKind::Keyword]) {
+        complete_sequence_options(&ctx, &mut builder);
+        complete_transactions(&ctx, &mut builder);
+    }
+
+    if wants(&[CompletionItemKind::Table]) {
+        complete_ctes(&ctx, &mut builder);
+    }
+    if wants(&[CompletionItemKind::Column]) {
+        complete_defined_columns(&ctx, &mut builder);
+    }
+    if wants(&[CompletionItemKind::Keyword]) {
+        complete_returning(&ctx, &mut builder);
+    }
+    if wants(&[CompletionItemKind::Keyword]) {
+        complete_keywords(&ctx, &mut builder);
+        complete_literals(&ctx, &mut builder);
+    }
+    if wants(&[CompletionItemKind::Alias]) {
+        complete_aliases(&ctx, &mut builder);
+    }
+    if wants(&[CompletionItemKind::Window]) {
+        complete_windows(&ctx, &mut builder);
+    }
+
+    // some providers produce items of several kinds
+    if let Some(only) = &only_kinds {
+        builder.retain_kinds(only);
+    }
 
     builder.finish()
 }
+
+#[cfg(test)]
+mod tests {
+    use pgt_schema_cache::SchemaCache;
+
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[test]
+    fn completes_keywords_without_schema() {
+        let query = format!("drop table users {}", CURSOR_POS);
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        let tree = parser.parse("drop table users", None).unwrap();
+
+        let cache = SchemaCache::default();
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(!items.is_empty());
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Keyword),
+            "Expected only keywords without a schema, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+        assert!(items.iter().any(|i| i.label == "cascade"));
+    }
+
+    #[test]
+    fn waits_for_min_chars() {
+        let query = format!("drop table users c{}", CURSOR_POS);
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        let tree = parser.parse("drop table users c", None).unwrap();
+
+        let cache = SchemaCache::default();
+
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.min_chars = 2;
+        assert!(complete(params).is_empty());
+
+        let labels = |min_chars| {
+            let mut params = get_test_params(&tree, &cache, query.as_str().into());
+            params.min_chars = min_chars;
+            complete(params)
+                .into_iter()
+                .map(|i| i.label)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(labels(1), labels(0));
+    }
+
+    #[test]
+    fn truncates_to_the_limit() {
+        let query = format!("drop table users {}", CURSOR_POS);
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        let tree = parser.parse("drop table users ", None).unwrap();
+
+        let cache = SchemaCache::default();
+
+        let labels = |limit| {
+            let mut params = get_test_params(&tree, &cache, query.as_str().into());
+            params.limit = limit;
+            complete(params)
+                .into_iter()
+                .map(|i| i.label)
+                .collect::<Vec<_>>()
+        };
+
+        let all = labels(None);
+        assert!(all.len() > 1 && all.len() <= crate::LIMIT);
+
+        // the best items are kept
+        assert_eq!(labels(Some(1)), all[..1].to_vec());
+    }
+
+    #[tokio::test]
+    async fn completes_only_the_requested_kinds() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+
+            create function active_users() returns setof users language sql as 'select * from users';
+        "#;
+
+        let query = format!("select * from {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.only_kinds = Some(vec![CompletionItemKind::Table]);
+        let items = complete(params);
+
+        assert!(items.iter().any(|i| i.label == "users"));
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Table),
+            "Expected only tables, got {:?}",
+            items.iter().map(|i| (&i.label, i.kind)).collect::<Vec<_>>()
+        );
+    }
+}
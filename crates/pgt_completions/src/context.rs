@@ -6,15 +6,32 @@ use pgt_treesitter_queries::{
     queries::{self, QueryResult},
 };
 
-use crate::sanitization::SanitizedCompletionParams;
+use pgt_text_size::{TextRange, TextSize};
 
-#[derive(Debug, PartialEq, Eq)]
+use crate::{
+    hidden_objects::HiddenObjects,
+    sanitization::{SANITIZED_TOKEN, SanitizedCompletionParams},
+};
+
+mod column_definitions;
+mod ctes;
+mod lexical;
+mod windows;
+
+use lexical::TextScan;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClauseType {
     Select,
     Where,
     From,
     Update,
     Delete,
+    PartitionBy,
+    OrderBy,
+    Limit,
+    Offset,
+    Returning,
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -23,6 +40,23 @@ pub(crate) enum NodeText<'a> {
     Original(&'a str),
 }
 
+impl NodeText<'_> {
+    /// Returns the text as the identifier it names, e.g. `My"Col` for `"My""Col"`.
+    /// The closing quote may be missing, since the user might still be typing the identifier.
+    pub(crate) fn identifier(&self) -> Option<String> {
+        match self {
+            NodeText::Replaced => None,
+            NodeText::Original(txt) => match txt.strip_prefix('"') {
+                Some(quoted) => {
+                    let quoted = quoted.strip_suffix('"').unwrap_or(quoted);
+                    Some(quoted.replace("\"\"", "\""))
+                }
+                None => Some(txt.to_string()),
+            },
+        }
+    }
+}
+
 impl TryFrom<&str> for ClauseType {
     type Error = String;
 
@@ -33,6 +67,11 @@ impl TryFrom<&str> for ClauseType {
             "from" => Ok(Self::From),
             "update" => Ok(Self::Update),
             "delete" => Ok(Self::Delete),
+            "partition_by" => Ok(Self::PartitionBy),
+            "order_by" => Ok(Self::OrderBy),
+            "limit" => Ok(Self::Limit),
+            "offset" => Ok(Self::Offset),
+            "returning" => Ok(Self::Returning),
             _ => {
                 let message = format!("Unimplemented ClauseType: {}", value);
 
@@ -67,107 +106,1952 @@ pub enum WrappingNode {
     Relation,
     BinaryExpression,
     Assignment,
+    /// The parenthesized part of an `OVER (...)` clause.
+    WindowSpecification,
 }
 
 impl TryFrom<&str> for WrappingNode {
     type Error = String;
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        match value {
-            "relation" => Ok(Self::Relation),
-            "assignment" => Ok(Self::Assignment),
-            "binary_expression" => Ok(Self::BinaryExpression),
-            _ => {
-                let message = format!("Unimplemented Relation: {}", value);
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "relation" => Ok(Self::Relation),
+            "assignment" => Ok(Self::Assignment),
+            "binary_expression" => Ok(Self::BinaryExpression),
+            "window_specification" => Ok(Self::WindowSpecification),
+            _ => {
+                let message = format!("Unimplemented Relation: {}", value);
+
+                // Err on tests, so we notice that we're lacking an implementation immediately.
+                if cfg!(test) {
+                    panic!("{}", message);
+                }
+
+                Err(message)
+            }
+        }
+    }
+}
+
+impl TryFrom<String> for WrappingNode {
+    type Error = String;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::try_from(value.as_str())
+    }
+}
+
+/// The part of a `GRANT` or `REVOKE` statement the cursor is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrantPosition {
+    /// `grant | on users to admin`
+    Privileges,
+    /// `grant select on | to admin`
+    Object,
+}
+
+/// The part of a `CREATE FUNCTION` or `CREATE PROCEDURE` statement the cursor is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionDefinitionPosition {
+    /// `create function f() returns int language |`
+    Language,
+    /// `create function f() returns int language sql as 'select 1' |`
+    Attributes,
+}
+
+/// The part of a `SET`, `SHOW` or `RESET` statement the cursor is in.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SettingPosition {
+    /// `set |`
+    Name,
+    /// `set search_path |`
+    Assignment,
+    /// `set client_min_messages to |`
+    Value(String),
+}
+
+/// The part of a transaction control statement the cursor is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionPosition {
+    /// `begin |`, `start transaction |` or `set transaction read only |`
+    Modes { after_begin: bool },
+    /// `set transaction isolation level |`
+    IsolationLevel,
+    /// `commit |`, `end |`, `abort |` or `rollback |`
+    End { is_rollback: bool },
+    /// `rollback to |`, `rollback to savepoint |`, `release |` or `release savepoint |`
+    Savepoint { after_savepoint_keyword: bool },
+}
+
+/// The part of a `CREATE EXTENSION` statement the cursor is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionPosition {
+    /// `create extension |` or `create extension if not exists |`
+    Name { if_not_exists: bool },
+    /// `create extension pgcrypto |`
+    Options,
+}
+
+/// The clause of a `CREATE TABLE` statement that names a parent table the cursor is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentTablePosition {
+    /// `create table child () inherits (|)`
+    Inherits,
+    /// `create table child partition of |`
+    PartitionOf,
+}
+
+/// The part of a `COPY` statement the cursor is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyPosition {
+    /// `copy users from |`
+    Source,
+    /// `copy users to |`
+    Target,
+    /// `copy users from stdin |`
+    AfterSource,
+    /// `copy users from stdin with (|`
+    Options,
+    /// `copy users from stdin with (format |`
+    Format,
+}
+
+/// A position where a plain value is expected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValuePosition {
+    /// `where active = |` or `update users set active = |`
+    Comparison,
+    /// `insert into users (id, active) values (1, |)`
+    ValuesSlot,
+    /// `create table users (active bool default |)`
+    Default,
+}
+
+/// The column definition of a `CREATE TABLE` statement whose default the cursor is at,
+/// e.g. `create table users (id int default |)`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ColumnDefaultPosition {
+    /// The first word of the type of the column, e.g. `timestamp` for `timestamp with time zone`.
+    pub data_type: String,
+}
+
+/// The column of a `CREATE INDEX` statement whose operator class the cursor is at,
+/// e.g. `create index on users using gin (name |)`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct OperatorClassPosition {
+    pub table: String,
+    pub column: String,
+    /// The type of the column, if the column is known.
+    pub column_type_id: Option<i64>,
+    /// The index access method, `btree` if none is given.
+    pub access_method: String,
+}
+
+/// The name of an existing policy in an `ALTER POLICY` or `DROP POLICY` statement,
+/// e.g. `alter policy | on users`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PolicyPosition {
+    /// The unqualified name of the table the policy is defined on, if it was typed already.
+    pub table: Option<String>,
+}
+
+/// The part of a `CREATE TRIGGER` statement the cursor is in.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TriggerPosition {
+    /// `create trigger t | on users`
+    Timing,
+    /// `create trigger t before | on users` or `create trigger t before insert or | on users`
+    Event {
+        /// `truncate` cannot be used with `instead of` triggers.
+        instead_of: bool,
+    },
+    /// `create trigger t before update of | on users`
+    UpdateColumns {
+        /// The unqualified name of the table the trigger is created on, if it was typed already.
+        table: Option<String>,
+    },
+}
+
+/// Where the cursor is within the sequence options of an identity column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityOptionsPosition {
+    /// `id int generated always as identity (|` or `(start with 10 |`
+    Option,
+    /// `id int generated always as identity (no |`
+    AfterNo,
+}
+
+/// The part of the `GENERATED` clause of a column definition the cursor is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneratedPosition {
+    /// `create table t (id int generated |`
+    Kind,
+    /// `create table t (id int generated always |`
+    Always,
+    /// `create table t (id int generated by default |`
+    ByDefault,
+    /// `create table t (total int generated always as (price * amount) |`
+    AfterExpression,
+}
+
+/// Access to the fields of a composite-typed column, e.g. `(address).|` or `addresses[1].|`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CompositeFieldAccess {
+    /// The table or alias qualifying the column, e.g. `u` in `(u.address).|`.
+    pub qualifier: Option<String>,
+    pub column: String,
+    /// Whether an element of an array column is accessed, e.g. `addresses[1].|`.
+    pub is_subscripted: bool,
+}
+
+/// A statement that declares or destroys savepoints.
+enum SavepointCommand {
+    /// `savepoint sp1`
+    Declare(String),
+    /// `rollback to savepoint sp1`
+    RollbackTo(String),
+    /// `release savepoint sp1`
+    Release(String),
+    /// Starts or ends a transaction, e.g. `begin` or `commit`
+    TransactionBoundary,
+}
+
+impl SavepointCommand {
+    /// Only looks at the leading words of the statement, so that string literals and
+    /// comments later on are never mistaken for a command.
+    fn parse(statement: &str) -> Option<Self> {
+        let words = leading_words(statement);
+        let mut words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+        // `rollback work` and `rollback transaction` are the same as `rollback`
+        if words.first() == Some(&"rollback")
+            && matches!(words.get(1), Some(&"work") | Some(&"transaction"))
+        {
+            words.remove(1);
+        }
+
+        match words.as_slice() {
+            ["savepoint", name, ..] => Some(Self::Declare(name.to_string())),
+            ["rollback", "to", "savepoint", name, ..] | ["rollback", "to", name, ..] => {
+                Some(Self::RollbackTo(name.to_string()))
+            }
+            ["release", "savepoint", name, ..] | ["release", name, ..] => {
+                Some(Self::Release(name.to_string()))
+            }
+            [
+                "begin" | "start" | "commit" | "end" | "abort" | "rollback",
+                ..,
+            ] => Some(Self::TransactionBoundary),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the first lowercased words of a statement after its leading comments,
+/// e.g. `["rollback", "to", "savepoint", "sp1"]`.
+fn leading_words(statement: &str) -> Vec<String> {
+    let mut rest = statement.trim_start();
+    loop {
+        if let Some(comment) = rest.strip_prefix("--") {
+            rest = comment.split_once('\n').map_or("", |(_, after)| after);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            rest = comment.split_once("*/").map_or("", |(_, after)| after);
+        } else {
+            break;
+        }
+        rest = rest.trim_start();
+    }
+
+    rest.split(|c: char| c.is_whitespace() || c == ';')
+        .filter(|w| !w.is_empty())
+        .take(5)
+        .map(|w| w.trim_matches('"').to_lowercase())
+        .collect()
+}
+
+/// The table written to by a statement, e.g. `auth.users` in
+/// `update auth.users set name = 'a' returning |` or
+/// `insert into auth.users values (1) on conflict on constraint |`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReturningTable {
+    pub schema: Option<String>,
+    pub name: String,
+}
+
+impl ReturningTable {
+    /// Whether this is the table `name` in `schema`. An unqualified table is looked up in `public`.
+    pub fn is(&self, schema: &str, name: &str) -> bool {
+        self.name == name && self.schema.as_deref().unwrap_or("public") == schema
+    }
+}
+
+/// A column compared to the string literal the cursor is in, e.g. `status` in `where status = '|'`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ComparedColumn {
+    /// The table or alias qualifying the column, e.g. `u` in `where u.status = '|'`.
+    pub qualifier: Option<String>,
+    pub column: String,
+    /// The range of the value within its string literal, without the quotes.
+    pub value_range: TextRange,
+}
+
+/// The positions of the cursor within the statement, which many completion items are checked against.
+#[derive(Debug, Default)]
+struct CursorPositions {
+    grant: Option<GrantPosition>,
+    setting: Option<SettingPosition>,
+    transaction: Option<TransactionPosition>,
+    extension: Option<ExtensionPosition>,
+    parent_table: Option<ParentTablePosition>,
+    copy: Option<CopyPosition>,
+    value: Option<ValuePosition>,
+    column_default: Option<ColumnDefaultPosition>,
+    storage_parameter: bool,
+    identity_options: Option<IdentityOptionsPosition>,
+    conflict_constraint_table: Option<ReturningTable>,
+    policy: Option<PolicyPosition>,
+    operator_class: Option<OperatorClassPosition>,
+    trigger: Option<TriggerPosition>,
+    generated: Option<GeneratedPosition>,
+    collation: bool,
+    check_expression: bool,
+    table_reference: bool,
+    data_type: bool,
+    window_reference: bool,
+    row_count_clause: Option<ClauseType>,
+    using_join_relations: Option<(String, String)>,
+    filter_where: bool,
+    function_definition: Option<FunctionDefinitionPosition>,
+}
+
+pub(crate) struct CompletionContext<'a> {
+    pub node_under_cursor: Option<tree_sitter::Node<'a>>,
+
+    pub tree: &'a tree_sitter::Tree,
+    pub text: &'a str,
+    pub schema_cache: &'a SchemaCache,
+    pub position: usize,
+
+    /// Whether objects in system schemas like `pg_catalog` may be suggested.
+    pub include_system_schemas: bool,
+
+    /// Objects that must not be suggested, e.g. deprecated ones.
+    pub hidden_objects: Option<&'a HiddenObjects>,
+
+    pub schema_name: Option<String>,
+    pub wrapping_clause_type: Option<ClauseType>,
+
+    pub wrapping_node_kind: Option<WrappingNode>,
+
+    pub is_invocation: bool,
+    pub wrapping_statement_range: Option<tree_sitter::Range>,
+
+    pub mentioned_relations: HashMap<Option<String>, HashSet<String>>,
+
+    /// The lowercased words of the current statement before the cursor,
+    /// excluding the word under the cursor.
+    pub words_before_cursor: Vec<String>,
+    /// The lowercased words of the current statement after the cursor,
+    /// excluding the word under the cursor.
+    pub words_after_cursor: Vec<String>,
+
+    /// The range of the double-quoted identifier under the cursor, including its quotes,
+    /// e.g. `"na` in `select "na| from users`.
+    pub quoted_identifier_range: Option<TextRange>,
+
+    /// The range of the token that is being completed, e.g. `us` in `select * from us|`.
+    /// If nothing was typed yet, the range is empty and starts at the cursor.
+    pub replace_range: TextRange,
+
+    /// The range of the psql meta-command before the cursor, e.g. `\d` in `\d|`.
+    pub meta_command_range: Option<TextRange>,
+
+    /// The range of the time zone name within its string literal, without the quotes,
+    /// e.g. `Eur` in `select now() at time zone 'Eur|'`.
+    pub time_zone_range: Option<TextRange>,
+
+    /// The names of the common table expressions that can be referenced at the cursor.
+    pub ctes: Vec<String>,
+
+    /// The names of the windows defined in the `WINDOW` clause of the statement.
+    pub named_windows: Vec<String>,
+
+    /// The names of the columns defined by the `CREATE TABLE` statement the cursor is in.
+    pub defined_columns: Vec<String>,
+
+    /// The aliases that may be given to the table or column before the cursor,
+    /// e.g. `u` in `from users |` or `as created` in `select created_at |`.
+    pub alias_suggestions: Vec<String>,
+
+    pub composite_field_access: Option<CompositeFieldAccess>,
+
+    pub compared_column: Option<ComparedColumn>,
+
+    /// The savepoints of the current transaction that are declared before the cursor.
+    pub savepoints: Vec<String>,
+
+    /// The table the statement writes to, if the cursor is in its `RETURNING` clause.
+    pub returning_table: Option<ReturningTable>,
+
+    /// The statement around the cursor and the literal or comment the cursor is in.
+    text_scan: TextScan<'a>,
+
+    positions: CursorPositions,
+}
+
+impl<'a> CompletionContext<'a> {
+    pub fn new(params: &'a SanitizedCompletionParams) -> Self {
+        let mut ctx = Self {
+            tree: params.tree.as_ref(),
+            text: &params.text,
+            schema_cache: params.schema,
+            include_system_schemas: params.include_system_schemas,
+            hidden_objects: params.hidden_objects,
+            position: usize::from(params.position),
+            node_under_cursor: None,
+            schema_name: None,
+            wrapping_clause_type: None,
+            wrapping_node_kind: None,
+            wrapping_statement_range: None,
+            is_invocation: false,
+            mentioned_relations: HashMap::new(),
+            words_before_cursor: vec![],
+            words_after_cursor: vec![],
+            quoted_identifier_range: None,
+            replace_range: TextRange::empty(params.position),
+            meta_command_range: None,
+            time_zone_range: None,
+            ctes: vec![],
+            named_windows: vec![],
+            defined_columns: vec![],
+            alias_suggestions: vec![],
+            composite_field_access: None,
+            compared_column: None,
+            savepoints: vec![],
+            returning_table: None,
+            text_scan: TextScan::new(&params.text, usize::from(params.position)),
+            positions: CursorPositions::default(),
+        };
+
+        // these operate on the original cursor position,
+        // which the tree context might adjust
+        ctx.gather_statement_words();
+        ctx.gather_quoted_identifier();
+        ctx.gather_meta_command();
+        ctx.gather_time_zone();
+        ctx.gather_compared_column();
+        ctx.gather_ctes();
+        ctx.gather_named_windows();
+        ctx.gather_defined_columns();
+        ctx.gather_alias_suggestions();
+        ctx.gather_composite_field_access();
+        ctx.gather_savepoints(params.preceding_statements);
+        ctx.gather_returning_table();
+
+        let position = ctx.position;
+        ctx.gather_tree_context();
+        ctx.gather_replace_range(position);
+        if ctx.position != position {
+            ctx.text_scan = TextScan::new(ctx.text, ctx.position);
+        }
+
+        // tree-sitter can't always make sense of an incomplete `returning` clause
+        if ctx.returning_table.is_some() {
+            ctx.wrapping_clause_type = Some(ClauseType::Returning);
+        }
+        ctx.gather_info_from_ts_queries();
+        ctx.gather_positions();
+
+        ctx
+    }
+
+    /// Computes the positions once, since the filters check them for every item.
+    fn gather_positions(&mut self) {
+        let transaction = self.find_transaction_position();
+        // `set transaction |` is not a setting
+        let setting = match transaction {
+            Some(_) => None,
+            None => self.find_setting_position(),
+        };
+        // `set search_path = |` assigns a setting, not a value
+        let value = match setting {
+            Some(_) => None,
+            None => self.find_value_position(),
+        };
+
+        self.positions = CursorPositions {
+            grant: self.find_grant_position(),
+            setting,
+            transaction,
+            extension: self.find_extension_position(),
+            parent_table: self.find_parent_table_position(),
+            copy: self.find_copy_position(),
+            value,
+            column_default: self.find_column_default_position(),
+            storage_parameter: self.detect_storage_parameter_position(),
+            identity_options: self.find_identity_options_position(),
+            conflict_constraint_table: self.find_conflict_constraint_table(),
+            policy: self.find_policy_position(),
+            operator_class: self.find_operator_class_position(),
+            trigger: self.find_trigger_position(),
+            generated: self.find_generated_position(),
+            collation: self.detect_collation_position(),
+            check_expression: self.detect_check_expression_position(),
+            table_reference: self.detect_table_reference_position(),
+            data_type: self.detect_type_position(),
+            window_reference: self.detect_window_reference_position(),
+            row_count_clause: self.find_row_count_clause(),
+            using_join_relations: self.find_using_join_relations(),
+            filter_where: self.detect_filter_where_position(),
+            function_definition: self.find_function_definition_position(),
+        };
+    }
+
+    fn gather_info_from_ts_queries(&mut self) {
+        let stmt_range = self.wrapping_statement_range.as_ref();
+        let sql = self.text;
+
+        let mut executor = TreeSitterQueriesExecutor::new(self.tree.root_node(), sql);
+
+        executor.add_query_results::<queries::RelationMatch>();
+
+        for relation_match in executor.get_iter(stmt_range) {
+            match relation_match {
+                QueryResult::Relation(r) => {
+                    let schema_name = r.get_schema(sql);
+                    let table_name = r.get_table(sql);
+
+                    let current = self.mentioned_relations.get_mut(&schema_name);
+
+                    match current {
+                        Some(c) => {
+                            c.insert(table_name);
+                        }
+                        None => {
+                            let mut new = HashSet::new();
+                            new.insert(table_name);
+                            self.mentioned_relations.insert(schema_name, new);
+                        }
+                    };
+                }
+            };
+        }
+    }
+
+    /// Splits the statement around the cursor into words.
+    ///
+    /// This does not rely on the tree, since tree-sitter can't make sense of many incomplete
+    /// statements, but some completions only depend on the keywords that were typed so far.
+    fn gather_statement_words(&mut self) {
+        let text = self.text;
+        let position = self.position.min(text.len());
+        if !text.is_char_boundary(position) {
+            return;
+        }
+
+        let statement = &self.text_scan.statement;
+        let before = &text[statement.start..position];
+        let after = &text[position..statement.end];
+
+        self.words_before_cursor = split_words(before.trim_end_matches(is_word_char));
+        self.words_after_cursor = split_words(after.trim_start_matches(is_word_char));
+    }
+
+    /// Returns the text of the statement the cursor is in and the offset of its start.
+    fn current_statement(&self) -> Option<(usize, &'a str)> {
+        let text = self.text;
+        let position = self.position.min(text.len());
+        if !text.is_char_boundary(position) {
+            return None;
+        }
+
+        let statement = self.text_scan.statement.clone();
+
+        Some((statement.start, &text[statement]))
+    }
+
+    fn gather_ctes(&mut self) {
+        if let Some((start, statement)) = self.current_statement() {
+            self.ctes = ctes::visible_ctes(statement, self.position.min(self.text.len()) - start);
+        }
+    }
+
+    fn gather_named_windows(&mut self) {
+        if let Some((_, statement)) = self.current_statement() {
+            self.named_windows = windows::named_windows(statement);
+        }
+    }
+
+    fn gather_defined_columns(&mut self) {
+        if let Some((_, statement)) = self.current_statement() {
+            self.defined_columns = column_definitions::defined_columns(statement);
+        }
+    }
+
+    fn gather_savepoints(&mut self, preceding_statements: &[&str]) {
+        // savepoints only exist within the transaction they were declared in
+        let transaction_start = preceding_statements
+            .iter()
+            .rposition(|statement| {
+                matches!(
+                    SavepointCommand::parse(statement),
+                    Some(SavepointCommand::TransactionBoundary)
+                )
+            })
+            .map_or(0, |idx| idx + 1);
+
+        for statement in &preceding_statements[transaction_start..] {
+            match SavepointCommand::parse(statement) {
+                Some(SavepointCommand::Declare(name)) => {
+                    if !self.savepoints.contains(&name) {
+                        self.savepoints.push(name);
+                    }
+                }
+                // the savepoints declared after the one rolled back to are destroyed
+                Some(SavepointCommand::RollbackTo(name)) => {
+                    if let Some(idx) = self.savepoints.iter().position(|s| *s == name) {
+                        self.savepoints.truncate(idx + 1);
+                    }
+                }
+                Some(SavepointCommand::Release(name)) => {
+                    if let Some(idx) = self.savepoints.iter().position(|s| *s == name) {
+                        self.savepoints.truncate(idx);
+                    }
+                }
+                Some(SavepointCommand::TransactionBoundary) | None => {}
+            }
+        }
+    }
+
+    fn gather_alias_suggestions(&mut self) {
+        let text = self.text;
+        let position = self.position.min(text.len());
+        if !text.is_char_boundary(position) {
+            return;
+        }
+
+        let before = &text[self.text_scan.statement.start..position];
+
+        // an alias is separated from what it names by whitespace only, e.g. `users |` but not `users, |`
+        let before = before.trim_end_matches(is_word_char);
+        if !before.ends_with(char::is_whitespace)
+            || !before
+                .trim_end()
+                .ends_with(|c: char| c.is_alphanumeric() || c == '_' || c == '"' || c == '*')
+        {
+            return;
+        }
+
+        let Some((named, rest)) = self.words_before_cursor.split_last() else {
+            return;
+        };
+
+        if NON_ALIASABLE_WORDS.contains(&named.as_str()) {
+            return;
+        }
+
+        // `copy users from stdin |` names a source, not a relation
+        if self.find_copy_position().is_some() {
+            return;
+        }
+
+        if let Some(table) = referenced_table(named, rest) {
+            if let Some(alias) = derive_table_alias(table) {
+                self.alias_suggestions.push(alias);
+            }
+            return;
+        }
+
+        // a `*` that doesn't follow a table is an operator or a wildcard
+        if named.ends_with('*') {
+            return;
+        }
+
+        match rest.last().map(|w| w.as_str()) {
+            Some("as") => {}
+            _ if rest.first().is_some_and(|w| w == "select")
+                && !rest.iter().any(|w| w == "from") =>
+            {
+                if let Some(alias) = derive_column_alias(named) {
+                    self.alias_suggestions.push(format!("as {}", alias));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn gather_composite_field_access(&mut self) {
+        let text = self.text;
+        let position = self.position.min(text.len());
+        if !text.is_char_boundary(position) {
+            return;
+        }
+
+        let before = &text[self.text_scan.statement.start..position];
+
+        // the field the user started typing
+        let before = before.trim_end_matches(|c: char| c.is_alphanumeric() || c == '_');
+
+        let before = match before.strip_suffix('.') {
+            Some(b) => b,
+            None => return,
+        };
+
+        let (mut expression, mut is_subscripted) = strip_subscripts(before);
+
+        if let Some(inner) = expression.strip_suffix(')') {
+            let open = match matching_open_paren(inner) {
+                Some(idx) => idx,
+                None => return,
+            };
+
+            // `f(x).field` accesses the result of a function, which we cannot resolve
+            if inner[..open].ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                return;
+            }
+
+            let (inner, inner_is_subscripted) = strip_subscripts(inner[open + 1..].trim());
+            expression = inner;
+            is_subscripted |= inner_is_subscripted;
+        } else if !is_subscripted {
+            // `a.b.|` qualifies objects, only `(a.b).|` accesses fields
+            return;
+        }
+
+        let expression = expression.trim();
+        let start = expression
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.' || c == '"'))
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+
+        let mut parts = expression[start..].rsplit('.').map(|part| {
+            match part.strip_prefix('"').and_then(|p| p.strip_suffix('"')) {
+                Some(quoted) => quoted.to_string(),
+                None => part.to_lowercase(),
+            }
+        });
+
+        let column = match parts.next() {
+            Some(c) if !c.is_empty() => c,
+            _ => return,
+        };
+
+        self.composite_field_access = Some(CompositeFieldAccess {
+            qualifier: parts.next(),
+            column,
+            is_subscripted,
+        });
+    }
+
+    fn gather_meta_command(&mut self) {
+        let text = self.text;
+        let position = self.position.min(text.len());
+        if !text.is_char_boundary(position) {
+            return;
+        }
+
+        // a backslash in a literal is an escape, e.g. `E'\n'`
+        if self.text_scan.is_in_string() || self.text_scan.quoted_identifier_start().is_some() {
+            return;
+        }
+
+        let before = &text[..position];
+        let start = before
+            .rfind(char::is_whitespace)
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+
+        let is_meta_command = before[start..]
+            .strip_prefix('\\')
+            .is_some_and(|cmd| cmd.chars().all(|c| c.is_ascii_alphabetic() || c == '+'));
+
+        if is_meta_command {
+            self.meta_command_range = Some(TextRange::new(
+                TextSize::try_from(start).unwrap(),
+                TextSize::try_from(position).unwrap(),
+            ));
+        }
+    }
+
+    fn gather_quoted_identifier(&mut self) {
+        let text = self.text;
+        let position = self.position.min(text.len());
+        if !text.is_char_boundary(position) {
+            return;
+        }
+
+        let Some(start) = self.text_scan.quoted_identifier_start() else {
+            return;
+        };
+
+        let after = &text[position..];
+
+        // the sanitized token is not part of what the user typed
+        let after = after.strip_prefix(SANITIZED_TOKEN).unwrap_or(after);
+
+        let rest_len = after
+            .find(|c: char| c == '"' || c.is_whitespace())
+            .unwrap_or(after.len());
+        let is_closed = after[rest_len..].starts_with('"');
+        let end = position + rest_len + usize::from(is_closed);
+
+        self.quoted_identifier_range = Some(TextRange::new(
+            TextSize::try_from(start).unwrap(),
+            TextSize::try_from(end).unwrap(),
+        ));
+    }
+
+    /// Returns the statement text before the string literal the cursor is in,
+    /// and the range of the literal's value without the quotes.
+    fn string_literal_at_cursor(&self) -> Option<(&'a str, TextRange)> {
+        let text = self.text;
+        let position = self.position.min(text.len());
+        if !text.is_char_boundary(position) {
+            return None;
+        }
+
+        let quote = self.text_scan.string_start()?;
+        let after = &text[position..];
+
+        // the sanitized token is not part of what the user typed
+        let after = after.strip_prefix(SANITIZED_TOKEN).unwrap_or(after);
+        let rest_len = after
+            .find(|c: char| c == '\'' || c.is_whitespace())
+            .unwrap_or(after.len());
+
+        let range = TextRange::new(
+            TextSize::try_from(quote + 1).unwrap(),
+            TextSize::try_from(position + rest_len).unwrap(),
+        );
+
+        Some((&text[self.text_scan.statement.start..quote], range))
+    }
+
+    fn gather_time_zone(&mut self) {
+        let Some((before, range)) = self.string_literal_at_cursor() else {
+            return;
+        };
+
+        // `at time zone '|'` and `set time zone '|'`
+        let words = split_words(&before.to_lowercase());
+        if matches!(words.as_slice(), [.., at_or_set, time, zone]
+            if (at_or_set == "at" || at_or_set == "set") && time == "time" && zone == "zone")
+        {
+            self.time_zone_range = Some(range);
+        }
+    }
+
+    /// `where status = '|'` or `where u.status <> '|'`
+    fn gather_compared_column(&mut self) {
+        let Some((before, value_range)) = self.string_literal_at_cursor() else {
+            return;
+        };
+
+        // only the conditions of a `where` clause are considered
+        if !split_words(&before.to_lowercase())
+            .iter()
+            .any(|w| w == "where")
+        {
+            return;
+        }
+
+        let before = before.trim_end();
+        let Some(before) = ["<>", "!=", "="]
+            .iter()
+            .find_map(|op| before.strip_suffix(op))
+        else {
+            return;
+        };
+
+        let before = before.trim_end();
+        let start = before
+            .rfind(|c: char| !is_word_char(c))
+            .map(|idx| idx + 1)
+            .unwrap_or(0);
+
+        let mut parts = before[start..].rsplit('.').map(|part| {
+            match part.strip_prefix('"').and_then(|p| p.strip_suffix('"')) {
+                Some(quoted) => quoted.to_string(),
+                None => part.to_lowercase(),
+            }
+        });
+
+        let column = match parts.next() {
+            Some(c) if !c.is_empty() => c,
+            _ => return,
+        };
+
+        self.compared_column = Some(ComparedColumn {
+            qualifier: parts.next(),
+            column,
+            value_range,
+        });
+    }
+
+    /// Determines the range of the token under the original cursor `position`.
+    fn gather_replace_range(&mut self, position: usize) {
+        if let Some(range) = self
+            .quoted_identifier_range
+            .or(self.meta_command_range)
+            .or(self.time_zone_range)
+            .or(self.compared_column.as_ref().map(|c| c.value_range))
+        {
+            self.replace_range = range;
+            return;
+        }
+
+        let Some(node) = self
+            .node_under_cursor
+            .filter(|n| n.start_byte() <= position && position <= n.end_byte())
+        else {
+            return;
+        };
+
+        // the sanitized token is not part of the document, so there is nothing to replace
+        let is_typed_word = matches!(
+            self.get_ts_node_content(node),
+            Some(NodeText::Original(txt))
+                if !txt.is_empty() && txt.chars().all(|c| c.is_alphanumeric() || c == '_')
+        );
+
+        if is_typed_word {
+            self.replace_range = TextRange::new(
+                TextSize::try_from(node.start_byte()).unwrap(),
+                TextSize::try_from(node.end_byte()).unwrap(),
+            );
+        }
+    }
+
+    pub fn get_grant_position(&self) -> Option<GrantPosition> {
+        self.positions.grant
+    }
+
+    pub fn get_setting_position(&self) -> Option<&SettingPosition> {
+        self.positions.setting.as_ref()
+    }
+
+    pub fn get_transaction_position(&self) -> Option<TransactionPosition> {
+        self.positions.transaction
+    }
+
+    pub fn get_extension_position(&self) -> Option<ExtensionPosition> {
+        self.positions.extension
+    }
+
+    pub fn get_parent_table_position(&self) -> Option<ParentTablePosition> {
+        self.positions.parent_table
+    }
+
+    pub fn get_copy_position(&self) -> Option<CopyPosition> {
+        self.positions.copy
+    }
+
+    pub fn get_value_position(&self) -> Option<ValuePosition> {
+        self.positions.value
+    }
+
+    pub fn get_column_default_position(&self) -> Option<&ColumnDefaultPosition> {
+        self.positions.column_default.as_ref()
+    }
+
+    pub fn is_storage_parameter_position(&self) -> bool {
+        self.positions.storage_parameter
+    }
+
+    pub fn is_identity_options_position(&self) -> bool {
+        self.positions.identity_options.is_some()
+    }
+
+    pub fn get_identity_options_position(&self) -> Option<IdentityOptionsPosition> {
+        self.positions.identity_options
+    }
+
+    pub fn get_conflict_constraint_table(&self) -> Option<&ReturningTable> {
+        self.positions.conflict_constraint_table.as_ref()
+    }
+
+    pub fn get_policy_position(&self) -> Option<&PolicyPosition> {
+        self.positions.policy.as_ref()
+    }
+
+    pub fn get_operator_class_position(&self) -> Option<&OperatorClassPosition> {
+        self.positions.operator_class.as_ref()
+    }
+
+    pub fn get_trigger_position(&self) -> Option<&TriggerPosition> {
+        self.positions.trigger.as_ref()
+    }
+
+    pub fn get_generated_position(&self) -> Option<GeneratedPosition> {
+        self.positions.generated
+    }
+
+    pub fn is_collation_position(&self) -> bool {
+        self.positions.collation
+    }
+
+    pub fn is_check_expression_position(&self) -> bool {
+        self.positions.check_expression
+    }
+
+    pub fn is_table_reference_position(&self) -> bool {
+        self.positions.table_reference
+    }
+
+    pub fn is_type_position(&self) -> bool {
+        self.positions.data_type
+    }
+
+    pub fn is_window_reference_position(&self) -> bool {
+        self.positions.window_reference
+    }
+
+    pub fn get_row_count_clause(&self) -> Option<ClauseType> {
+        self.positions.row_count_clause
+    }
+
+    pub fn get_using_join_relations(&self) -> Option<(&str, &str)> {
+        self.positions
+            .using_join_relations
+            .as_ref()
+            .map(|(left, right)| (left.as_str(), right.as_str()))
+    }
+
+    pub fn is_filter_where_position(&self) -> bool {
+        self.positions.filter_where
+    }
+
+    pub fn get_function_definition_position(&self) -> Option<FunctionDefinitionPosition> {
+        self.positions.function_definition
+    }
+
+    /// Returns where the cursor is within a `GRANT` or `REVOKE` statement, if it is in one.
+    fn find_grant_position(&self) -> Option<GrantPosition> {
+        let is_grant = self
+            .words_before_cursor
+            .first()
+            .is_some_and(|w| w == "grant" || w == "revoke");
+
+        if !is_grant {
+            return None;
+        }
+
+        match self
+            .words_before_cursor
+            .iter()
+            .rposition(|w| w == "on" || w == "to" || w == "from")
+        {
+            None => Some(GrantPosition::Privileges),
+            Some(idx) if self.words_before_cursor[idx] == "on" => Some(GrantPosition::Object),
+            Some(_) => None,
+        }
+    }
+
+    /// Returns where the cursor is within a `SET`, `SHOW` or `RESET` statement, if it is in one.
+    ///
+    /// `set transaction |` is excluded by the caller.
+    fn find_setting_position(&self) -> Option<SettingPosition> {
+        let (command, rest) = self.words_before_cursor.split_first()?;
+
+        let rest = match command.as_str() {
+            "set" => match rest.first().map(|w| w.as_str()) {
+                // `set session authorization |` takes a role, not a setting
+                Some("session") if rest.get(1).is_some_and(|w| w == "authorization") => {
+                    return None;
+                }
+                Some("session" | "local") => &rest[1..],
+                _ => rest,
+            },
+            "show" | "reset" => rest,
+            _ => return None,
+        };
+
+        let is_set = command == "set";
+
+        match rest {
+            [] => Some(SettingPosition::Name),
+            // `set role |` takes a role without `to` or `=`
+            [name] if is_set && name == "role" => None,
+            [_] if is_set => Some(SettingPosition::Assignment),
+            [name, assignment] if is_set && (assignment == "to" || assignment == "=") => {
+                Some(SettingPosition::Value(name.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns where the cursor is within a transaction control statement, if it is in one.
+    fn find_transaction_position(&self) -> Option<TransactionPosition> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        let modes = match words.as_slice() {
+            ["begin"] => return Some(TransactionPosition::Modes { after_begin: true }),
+            ["begin", "work" | "transaction", modes @ ..]
+            | ["begin", modes @ ..]
+            | ["start", "transaction", modes @ ..]
+            | ["set", "transaction", modes @ ..]
+            | [
+                "set",
+                "session",
+                "characteristics",
+                "as",
+                "transaction",
+                modes @ ..,
+            ] => modes,
+            [
+                command @ ("commit" | "end" | "abort" | "rollback"),
+                rest @ ..,
+            ] => {
+                let is_rollback = *command == "rollback";
+                let rest = match rest {
+                    ["work" | "transaction", rest @ ..] => rest,
+                    _ => rest,
+                };
+
+                return match rest {
+                    [] => Some(TransactionPosition::End { is_rollback }),
+                    ["to"] if is_rollback => Some(TransactionPosition::Savepoint {
+                        after_savepoint_keyword: false,
+                    }),
+                    ["to", "savepoint"] if is_rollback => Some(TransactionPosition::Savepoint {
+                        after_savepoint_keyword: true,
+                    }),
+                    _ => None,
+                };
+            }
+            ["release"] => {
+                return Some(TransactionPosition::Savepoint {
+                    after_savepoint_keyword: false,
+                });
+            }
+            ["release", "savepoint"] => {
+                return Some(TransactionPosition::Savepoint {
+                    after_savepoint_keyword: true,
+                });
+            }
+            _ => return None,
+        };
+
+        match modes {
+            [.., "isolation", "level"] => Some(TransactionPosition::IsolationLevel),
+            // still typing a mode, e.g. `isolation level repeatable |` or `read |`
+            [.., "isolation" | "read" | "not" | "repeatable"] => None,
+            _ => Some(TransactionPosition::Modes { after_begin: false }),
+        }
+    }
+
+    /// Returns where the cursor is within a `CREATE EXTENSION` statement, if it is in one.
+    fn find_extension_position(&self) -> Option<ExtensionPosition> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        let rest = match words.as_slice() {
+            ["create", "extension", rest @ ..] => rest,
+            _ => return None,
+        };
+
+        let (rest, if_not_exists) = match rest {
+            ["if", "not", "exists", rest @ ..] => (rest, true),
+            _ => (rest, false),
+        };
+
+        match rest {
+            [] => Some(ExtensionPosition::Name { if_not_exists }),
+            // still typing `if not exists`
+            ["if"] | ["if", "not"] => None,
+            // the schema to install the extension into goes here
+            [.., "schema"] => None,
+            _ => Some(ExtensionPosition::Options),
+        }
+    }
+
+    /// Returns the clause of a `CREATE TABLE` statement naming a parent table the cursor is in,
+    /// if it is in one.
+    fn find_parent_table_position(&self) -> Option<ParentTablePosition> {
+        let mut words = self.words_before_cursor.iter().map(|w| w.as_str());
+
+        if words.next() != Some("create") || !words.any(|w| w == "table") {
+            return None;
+        }
+
+        if self
+            .words_before_cursor
+            .ends_with(&["partition".into(), "of".into()])
+        {
+            return Some(ParentTablePosition::PartitionOf);
+        }
+
+        if !self.words_before_cursor.iter().any(|w| w == "inherits") {
+            return None;
+        }
+
+        // the list of parents has to be opened, but not closed yet
+        let before = self.text.get(..self.position)?;
+        let start = before.to_ascii_lowercase().rfind("inherits")?;
+        matching_open_paren(&before[start..]).map(|_| ParentTablePosition::Inherits)
+    }
+
+    /// Returns where the cursor is within a `COPY` statement, if it is in one.
+    fn find_copy_position(&self) -> Option<CopyPosition> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        if words.first() != Some(&"copy") {
+            return None;
+        }
+
+        // the cursor is inside of a file name or command
+        if self.is_in_string() {
+            return None;
+        }
+
+        // parentheses of preceding statements are not part of the `copy`
+        let before = self
+            .text
+            .get(self.text_scan.statement.start..self.position)?;
+
+        // `copy (select * from |` is still typing the query
+        let query = before.trim_start().get("copy".len()..)?.trim_start();
+        if query.starts_with('(') && matching_close_paren(query).is_none() {
+            return None;
+        }
+
+        let direction = words.iter().rposition(|w| *w == "from" || *w == "to")?;
+        let is_in_parens = matching_open_paren(before).is_some();
+
+        match &words[direction + 1..] {
+            [] if words[direction] == "from" => Some(CopyPosition::Source),
+            [] => Some(CopyPosition::Target),
+            // the command to run goes here
+            ["program"] => None,
+            [_] | ["program", _] if !is_in_parens => Some(CopyPosition::AfterSource),
+            [_, "with", ..] | ["program", _, "with", ..] if is_in_parens => {
+                if words.last() == Some(&"format") {
+                    Some(CopyPosition::Format)
+                } else if before.trim_end().ends_with(['(', ',']) {
+                    Some(CopyPosition::Options)
+                } else {
+                    // the value of an option goes here
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of value position the cursor is in, if it is in one.
+    ///
+    /// The value of a setting is excluded by the caller.
+    fn find_value_position(&self) -> Option<ValuePosition> {
+        let before = self.text.get(..self.position)?;
+        // disregard the word under the cursor
+        let before = before.trim_end_matches(is_word_char).trim_end();
+
+        // the cursor is inside of a string
+        if self.is_in_string() {
+            return None;
+        }
+
+        match self.words_before_cursor.last().map(|w| w.as_str()) {
+            Some("default") => return Some(ValuePosition::Default),
+            Some("is") => return Some(ValuePosition::Comparison),
+            Some("not")
+                if self
+                    .words_before_cursor
+                    .iter()
+                    .nth_back(1)
+                    .is_some_and(|w| w == "is") =>
+            {
+                return Some(ValuePosition::Comparison);
+            }
+            _ => {}
+        }
+
+        if before.ends_with(['=', '<', '>']) {
+            return Some(ValuePosition::Comparison);
+        }
+
+        if !before.ends_with(['(', ',']) {
+            return None;
+        }
+
+        // a row of a `VALUES` list has to be opened, but not closed yet,
+        // e.g. `values (1, |` or `values (1, true), (|`
+        let open = matching_open_paren(before)?;
+        let preceding = before[..open].trim_end().to_ascii_lowercase();
+        let is_values_row = preceding.ends_with("values")
+            || (preceding.ends_with(',') && self.words_before_cursor.iter().any(|w| w == "values"));
+
+        is_values_row.then_some(ValuePosition::ValuesSlot)
+    }
+
+    /// Returns the column definition whose default the cursor is at, if it is at one.
+    fn find_column_default_position(&self) -> Option<ColumnDefaultPosition> {
+        let mut words = self.words_before_cursor.iter().map(|w| w.as_str());
+
+        if words.next() != Some("create") || !words.any(|w| w == "table") {
+            return None;
+        }
+
+        if self
+            .words_before_cursor
+            .last()
+            .is_none_or(|w| w != "default")
+        {
+            return None;
+        }
+
+        // the list of column definitions has to be opened, but not closed yet
+        let before = self.text.get(..self.position)?;
+        let open = matching_open_paren(before)?;
+
+        // the column definition starts after the last comma that separates definitions,
+        // commas within a type like `numeric(10, 2)` do not count
+        let definitions = &before[open + 1..];
+        let mut depth = 0;
+        let mut start = 0;
+        for (idx, c) in definitions.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                ',' if depth == 0 => start = idx + 1,
+                _ => {}
+            }
+        }
+
+        match split_words(&definitions[start..]).as_slice() {
+            [_column, data_type, .., _] => Some(ColumnDefaultPosition {
+                data_type: data_type.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the cursor is where the name of a storage parameter of a table goes,
+    /// e.g. `create table users (id int) with (|` or `alter table users set (|`.
+    fn detect_storage_parameter_position(&self) -> bool {
+        let mut words = self.words_before_cursor.iter().map(|w| w.as_str());
+
+        if !matches!(words.next(), Some("create" | "alter")) || !words.any(|w| w == "table") {
+            return false;
+        }
+
+        let Some(before) = self.text.get(..self.position) else {
+            return false;
+        };
+        // disregard the word under the cursor
+        let before = before.trim_end_matches(is_word_char).trim_end();
+
+        if !before.ends_with(['(', ',']) {
+            return false;
+        }
+
+        // the option list has to be opened, but not closed yet
+        let Some(open) = matching_open_paren(before) else {
+            return false;
+        };
+
+        let preceding = before[..open].trim_end().to_ascii_lowercase();
+        ["with", " set", " reset"]
+            .iter()
+            .any(|keyword| preceding.ends_with(keyword))
+    }
+
+    /// Returns where the cursor is within the sequence options of an identity column, if it is
+    /// in them, e.g. `id int generated always as identity (|` or `(start with 10 |`.
+    fn find_identity_options_position(&self) -> Option<IdentityOptionsPosition> {
+        let before = self.text.get(..self.position)?;
+        // disregard the word under the cursor
+        let before = before.trim_end_matches(is_word_char).trim_end();
+
+        // the option list has to be opened, but not closed yet
+        let open = matching_open_paren(before)?;
+
+        let preceding = split_words(&before[..open]);
+        if !preceding.ends_with(&["as".to_string(), "identity".to_string()]) {
+            return None;
+        }
+
+        let options = split_words(&before[open + 1..]);
+        let mut options = options.iter().rev().map(|w| w.as_str());
+        match (options.next(), options.next()) {
+            (Some("no"), _) => Some(IdentityOptionsPosition::AfterNo),
+            // `no minvalue` and `no maxvalue` take no value
+            (Some("minvalue" | "maxvalue"), Some("no")) => Some(IdentityOptionsPosition::Option),
+            // the values of the options, e.g. `start with |`, are not options themselves
+            (
+                Some(
+                    "start" | "with" | "increment" | "by" | "minvalue" | "maxvalue" | "cache"
+                    | "as" | "sequence" | "name" | "restart",
+                ),
+                _,
+            ) => None,
+            _ => Some(IdentityOptionsPosition::Option),
+        }
+    }
+
+    /// Returns the target table of an `INSERT` if the cursor is at the constraint of its
+    /// conflict target, e.g. `insert into users (email) values ('a') on conflict on constraint |`.
+    fn find_conflict_constraint_table(&self) -> Option<ReturningTable> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        if !words.ends_with(&["on", "conflict", "on", "constraint"]) {
+            return None;
+        }
+
+        // the insert might follow `with` queries
+        let into = words
+            .windows(2)
+            .position(|pair| pair == ["insert", "into"])?;
+
+        let table = words.get(into + 2)?;
+        let schema = table
+            .rsplit_once('.')
+            .map(|(schema, _)| schema.trim_matches('"').to_string());
+
+        Some(ReturningTable {
+            schema,
+            name: unqualified(table),
+        })
+    }
+
+    /// Returns the policy position if the cursor names an existing policy,
+    /// e.g. `alter policy | on users` or `drop policy if exists |`.
+    fn find_policy_position(&self) -> Option<PolicyPosition> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        if !matches!(
+            words.as_slice(),
+            ["alter", "policy"] | ["drop", "policy"] | ["drop", "policy", "if", "exists"]
+        ) {
+            return None;
+        }
+
+        let table = match self.words_after_cursor.as_slice() {
+            [on, table, ..] if on == "on" => Some(unqualified(table)),
+            _ => None,
+        };
+
+        Some(PolicyPosition { table })
+    }
+
+    /// Returns the indexed column whose operator class the cursor is at, if it is at one.
+    fn find_operator_class_position(&self) -> Option<OperatorClassPosition> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        if words.first() != Some(&"create") || !words.contains(&"index") {
+            return None;
+        }
+
+        let on = words.iter().position(|w| *w == "on")?;
+        let table = match words.get(on + 1..)? {
+            ["only", table, ..] | [table, ..] => unqualified(table),
+            [] => return None,
+        };
+
+        let access_method = match words.iter().position(|w| *w == "using") {
+            Some(idx) => words.get(idx + 1)?.to_string(),
+            None => "btree".to_string(),
+        };
+
+        // the list of indexed columns has to be opened, but not closed yet
+        let before = self.text.get(..self.position)?;
+        let before = before.trim_end_matches(is_word_char);
+        let open = matching_open_paren(before)?;
+
+        // the operator class directly follows the column, e.g. `(name |` or `(id, name |`
+        let element = before[open + 1..].rsplit(',').next()?;
+        match split_words(element).as_slice() {
+            [column] if element.ends_with(char::is_whitespace) => {
+                let column = unqualified(column);
+                let column_type_id = self
+                    .schema_cache
+                    .find_col(&column, &table, None)
+                    .map(|c| c.type_id);
+
+                Some(OperatorClassPosition {
+                    table,
+                    column,
+                    column_type_id,
+                    access_method,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns where the cursor is within the timing and events of a `CREATE TRIGGER` statement,
+    /// if it is in them.
+    fn find_trigger_position(&self) -> Option<TriggerPosition> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        let rest = match words.as_slice() {
+            ["create", "or", "replace", rest @ ..] | ["create", rest @ ..] => rest,
+            _ => return None,
+        };
+        let rest = rest.strip_prefix(&["constraint"]).unwrap_or(rest);
+
+        // the name of the trigger has to be typed already
+        let [_name, rest @ ..] = rest.strip_prefix(&["trigger"])? else {
+            return None;
+        };
+
+        // once the table is named, the timing and events are complete
+        if rest.contains(&"on") {
+            return None;
+        }
+
+        let instead_of = rest.starts_with(&["instead", "of"]);
+        let is_update_of = rest.windows(2).any(|w| w == ["update", "of"]);
+
+        match rest {
+            [] => Some(TriggerPosition::Timing),
+            [.., "update", "of"] => Some(self.trigger_update_columns()),
+            ["before" | "after"] | ["instead", "of"] | [.., "or"] => {
+                Some(TriggerPosition::Event { instead_of })
+            }
+            _ if is_update_of => {
+                // commas are not part of the words, e.g. `update of name, |`
+                let before = self.text.get(..self.position)?;
+                let before = before.trim_end_matches(is_word_char).trim_end();
+                before.ends_with(',').then(|| self.trigger_update_columns())
+            }
+            _ => None,
+        }
+    }
+
+    fn trigger_update_columns(&self) -> TriggerPosition {
+        let table = self
+            .words_after_cursor
+            .iter()
+            .skip_while(|w| *w != "on")
+            .nth(1)
+            .map(|t| unqualified(t));
+
+        TriggerPosition::UpdateColumns { table }
+    }
+
+    /// Returns where the cursor is within the `GENERATED` clause of a column definition,
+    /// if it is in one.
+    fn find_generated_position(&self) -> Option<GeneratedPosition> {
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        if !matches!(words.first(), Some(&"create" | &"alter")) || !words.contains(&"table") {
+            return None;
+        }
+
+        match words.as_slice() {
+            [.., "generated"] => Some(GeneratedPosition::Kind),
+            [.., "generated", "always"] => Some(GeneratedPosition::Always),
+            [.., "generated", "by", "default"] => Some(GeneratedPosition::ByDefault),
+            _ => self
+                .is_after_generation_expression(&words)
+                .then_some(GeneratedPosition::AfterExpression),
+        }
+    }
+
+    /// Whether the cursor directly follows the closed expression of a generated column,
+    /// e.g. `total int generated always as (price * amount) |`.
+    fn is_after_generation_expression(&self, words: &[&str]) -> bool {
+        let Some(generated) = words.iter().rposition(|w| *w == "generated") else {
+            return false;
+        };
+
+        if !matches!(
+            words[generated + 1..],
+            ["always", "as", expression, ..] if expression != "identity"
+        ) {
+            return false;
+        }
+
+        let Some(before) = self.text.get(self.text_scan.statement.start..self.position) else {
+            return false;
+        };
+        let before = before.trim_end_matches(is_word_char).trim_end();
+
+        let Some(start) = before.to_ascii_lowercase().rfind("generated") else {
+            return false;
+        };
+        let expression = &before[start..];
+
+        expression.find('(').is_some_and(|open| {
+            matching_close_paren(&expression[open..])
+                .is_some_and(|close| open + close + 1 == expression.len())
+        })
+    }
+
+    /// Returns true if the cursor is within the expression of a `CHECK` constraint of a
+    /// `CREATE TABLE` statement, e.g. `create table t (a int, check (|))`.
+    fn detect_check_expression_position(&self) -> bool {
+        if self
+            .words_before_cursor
+            .first()
+            .is_none_or(|w| w != "create")
+            || !self.words_before_cursor.iter().any(|w| w == "table")
+        {
+            return false;
+        }
+
+        let Some(mut before) = self.text.get(..self.position) else {
+            return false;
+        };
+
+        // the expression might be nested, e.g. `check (length(|) > 0)`
+        while let Some(open) = matching_open_paren(before) {
+            before = &before[..open];
+
+            let word_start = before
+                .trim_end()
+                .rfind(|c: char| !is_word_char(c))
+                .map_or(0, |idx| idx + 1);
+            if before.trim_end()[word_start..].eq_ignore_ascii_case("check") {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Returns true if the cursor is where `from`, `join`, `update` or `delete from` name a table,
+    /// e.g. `select * from |` or `update only |`.
+    fn detect_table_reference_position(&self) -> bool {
+        let Some(before) = self.text.get(..self.position) else {
+            return false;
+        };
+        let before = before
+            .get(self.text_scan.statement.start..)
+            .unwrap_or_default();
+
+        // a subquery is a statement of its own, but function arguments like
+        // `extract(year from |)` aren't
+        let words = match matching_open_paren(before) {
+            Some(open) => split_words(&before[open + 1..]),
+            None => self.words_before_cursor.clone(),
+        };
+
+        let mut words = words.as_slice();
+        if words.last().is_some_and(|w| w == "only") {
+            words = &words[..words.len() - 1];
+        }
+
+        let Some((last, rest)) = words.split_last() else {
+            return false;
+        };
+
+        let is_query = |w: &String| {
+            matches!(
+                w.as_str(),
+                "select" | "with" | "insert" | "update" | "delete"
+            )
+        };
+
+        match last.as_str() {
+            "from" | "join" => {
+                rest.first().is_some_and(is_query) && rest.last().is_none_or(|w| w != "distinct")
+            }
+            "update" => rest.first().is_none_or(|w| w == "with"),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the cursor is where a data type is named, e.g. `select id::|`,
+    /// `cast(id as |)`, `create table users (id |)` or `alter table users add column id |`.
+    fn detect_type_position(&self) -> bool {
+        let Some(before) = self.text.get(..self.position) else {
+            return false;
+        };
+        let before = before
+            .get(self.text_scan.statement.start..)
+            .unwrap_or_default();
+        let before = before.trim_end_matches(is_word_char);
+
+        if before.ends_with("::") {
+            return true;
+        }
+
+        let words: Vec<&str> = self
+            .words_before_cursor
+            .iter()
+            .map(|w| w.as_str())
+            .collect();
+
+        match words.as_slice() {
+            [.., "add", "column", _] | [.., "set", "data", "type"] => return true,
+            [.., "alter", "column", _, "type"] => return true,
+            [.., "add", name] if *name != "column" && !TABLE_CONSTRAINT_WORDS.contains(name) => {
+                return words.first() == Some(&"alter");
+            }
+            _ => {}
+        }
+
+        let Some(open) = matching_open_paren(before) else {
+            return false;
+        };
+
+        let outer = split_words(&before[..open]);
+
+        // `cast(id as |)`
+        if outer.last().is_some_and(|w| w == "cast") {
+            return split_words(&before[open + 1..])
+                .last()
+                .is_some_and(|w| w == "as");
+        }
+
+        // the type follows the name of a column in the element list of `create table`,
+        // which directly follows the name of the table
+        if outer.first().is_none_or(|w| w != "create") {
+            return false;
+        }
+
+        let Some(table_idx) = outer.iter().position(|w| w == "table") else {
+            return false;
+        };
+
+        let table_name: Vec<&String> = outer[table_idx + 1..]
+            .iter()
+            .filter(|w| !matches!(w.as_str(), "if" | "not" | "exists"))
+            .collect();
+
+        let element = before[open + 1..].rsplit(',').next().unwrap_or_default();
+
+        table_name.len() == 1
+            && matches!(
+                split_words(element).as_slice(),
+                [name] if !TABLE_CONSTRAINT_WORDS.contains(&name.as_str())
+            )
+    }
+
+    /// Returns true if the cursor is where a named window is referenced, e.g. `sum(x) over |`,
+    /// as opposed to an inline window definition like `sum(x) over (|`.
+    fn detect_window_reference_position(&self) -> bool {
+        if self.words_before_cursor.last().is_none_or(|w| w != "over") {
+            return false;
+        }
+
+        self.text.get(..self.position).is_some_and(|before| {
+            !before
+                .trim_end_matches(is_word_char)
+                .trim_end()
+                .ends_with('(')
+        })
+    }
+
+    /// Returns `Limit` or `Offset` if the cursor is at the row count of such a clause,
+    /// e.g. `select * from users limit |`.
+    fn find_row_count_clause(&self) -> Option<ClauseType> {
+        match self.wrapping_clause_type {
+            Some(ClauseType::Limit) => return Some(ClauseType::Limit),
+            Some(ClauseType::Offset) => return Some(ClauseType::Offset),
+            _ => {}
+        }
+
+        // tree-sitter can't always make sense of an incomplete clause
+        match self.words_before_cursor.last().map(|w| w.as_str()) {
+            Some("limit") => Some(ClauseType::Limit),
+            Some("offset") => Some(ClauseType::Offset),
+            _ => None,
+        }
+    }
+
+    /// Gathers the table an `INSERT`, `UPDATE` or `DELETE` writes to if the cursor is in its
+    /// `RETURNING` clause, e.g. `users` for `update users set name = 'a' returning |`.
+    fn gather_returning_table(&mut self) {
+        self.returning_table = self.find_returning_table();
+    }
 
-                // Err on tests, so we notice that we're lacking an implementation immediately.
-                if cfg!(test) {
-                    panic!("{}", message);
-                }
+    fn find_returning_table(&self) -> Option<ReturningTable> {
+        let words = &self.words_before_cursor;
 
-                Err(message)
-            }
+        if !words.iter().any(|w| w == "returning") {
+            return None;
         }
+
+        let target_keyword = match words.first()?.as_str() {
+            "insert" => "into",
+            "update" => "update",
+            "delete" => "from",
+            _ => return None,
+        };
+
+        let idx = words.iter().position(|w| w == target_keyword)?;
+        let table = words[idx + 1..].iter().find(|w| *w != "only")?;
+        let table = table.trim_end_matches('*');
+
+        let schema = table
+            .rsplit_once('.')
+            .map(|(schema, _)| schema.trim_matches('"').to_string());
+
+        Some(ReturningTable {
+            schema,
+            name: unqualified(table),
+        })
     }
-}
 
-impl TryFrom<String> for WrappingNode {
-    type Error = String;
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        Self::try_from(value.as_str())
+    /// Returns true if the cursor is where the new table of a `SELECT ... INTO` goes,
+    /// e.g. `select * into |`.
+    pub fn is_select_into_target(&self) -> bool {
+        self.words_before_cursor
+            .first()
+            .is_some_and(|w| w == "select")
+            && self.words_before_cursor.last().is_some_and(|w| w == "into")
     }
-}
 
-pub(crate) struct CompletionContext<'a> {
-    pub node_under_cursor: Option<tree_sitter::Node<'a>>,
+    /// Returns the relations joined by the `USING` list the cursor is in,
+    /// e.g. `("a", "b")` for `select * from a join b using (|`.
+    fn find_using_join_relations(&self) -> Option<(String, String)> {
+        let using_idx = self
+            .words_before_cursor
+            .iter()
+            .rposition(|w| w == "using")?;
+
+        // the column list has to be opened, but not closed yet
+        let before = self.text.get(..self.position)?.to_ascii_lowercase();
+        let list = before[before.rfind("using")? + "using".len()..].trim_start();
+        if !list.starts_with('(') || list.contains(')') {
+            return None;
+        }
 
-    pub tree: &'a tree_sitter::Tree,
-    pub text: &'a str,
-    pub schema_cache: &'a SchemaCache,
-    pub position: usize,
+        // `only` and a trailing `*` only control whether descendant tables are included
+        let words: Vec<&str> = self.words_before_cursor[..using_idx]
+            .iter()
+            .map(|w| w.as_str())
+            .filter(|w| *w != "only" && *w != "*")
+            .collect();
 
-    pub schema_name: Option<String>,
-    pub wrapping_clause_type: Option<ClauseType>,
+        let mut relations = words
+            .windows(2)
+            .filter(|w| w[0] == "from" || w[0] == "join")
+            .map(|w| w[1].trim_end_matches('*').replace('"', ""));
 
-    pub wrapping_node_kind: Option<WrappingNode>,
+        let right = relations.next_back()?;
+        let left = relations.next_back()?;
 
-    pub is_invocation: bool,
-    pub wrapping_statement_range: Option<tree_sitter::Range>,
+        Some((left, right))
+    }
 
-    pub mentioned_relations: HashMap<Option<String>, HashSet<String>>,
-}
+    /// Returns true if the cursor is in the predicate of an aggregate's `FILTER` clause,
+    /// e.g. `select count(*) filter (where |) from orders`.
+    fn detect_filter_where_position(&self) -> bool {
+        let Some(idx) = self.words_before_cursor.iter().rposition(|w| w == "filter") else {
+            return false;
+        };
 
-impl<'a> CompletionContext<'a> {
-    pub fn new(params: &'a SanitizedCompletionParams) -> Self {
-        let mut ctx = Self {
-            tree: params.tree.as_ref(),
-            text: &params.text,
-            schema_cache: params.schema,
-            position: usize::from(params.position),
-            node_under_cursor: None,
-            schema_name: None,
-            wrapping_clause_type: None,
-            wrapping_node_kind: None,
-            wrapping_statement_range: None,
-            is_invocation: false,
-            mentioned_relations: HashMap::new(),
+        if self
+            .words_before_cursor
+            .get(idx + 1)
+            .is_none_or(|w| w != "where")
+        {
+            return false;
+        }
+
+        // the clause has to be still open at the cursor
+        let Some(before) = self.text.get(..self.position) else {
+            return false;
         };
 
-        ctx.gather_tree_context();
-        ctx.gather_info_from_ts_queries();
+        before
+            .to_ascii_lowercase()
+            .rfind("filter")
+            .is_some_and(|start| matching_open_paren(&before[start..]).is_some())
+    }
 
-        ctx
+    /// Returns true if the cursor is where the collation of a `COLLATE` clause goes,
+    /// e.g. `order by name collate "|"`.
+    fn detect_collation_position(&self) -> bool {
+        self.words_before_cursor
+            .last()
+            .is_some_and(|w| w == "collate")
     }
 
-    fn gather_info_from_ts_queries(&mut self) {
-        let stmt_range = self.wrapping_statement_range.as_ref();
-        let sql = self.text;
+    /// Returns true if the cursor is where the sampling method of a `TABLESAMPLE` clause goes,
+    /// e.g. `select * from t tablesample |`.
+    pub fn is_tablesample_method_position(&self) -> bool {
+        self.words_before_cursor
+            .last()
+            .is_some_and(|w| w == "tablesample")
+    }
 
-        let mut executor = TreeSitterQueriesExecutor::new(self.tree.root_node(), sql);
+    /// Returns where the cursor is within a `CREATE FUNCTION` or `CREATE PROCEDURE` statement,
+    /// if it is in one.
+    fn find_function_definition_position(&self) -> Option<FunctionDefinitionPosition> {
+        let mut words = self.words_before_cursor.iter().map(|w| w.as_str());
 
-        executor.add_query_results::<queries::RelationMatch>();
+        if words.next() != Some("create") {
+            return None;
+        }
 
-        for relation_match in executor.get_iter(stmt_range) {
-            match relation_match {
-                QueryResult::Relation(r) => {
-                    let schema_name = r.get_schema(sql);
-                    let table_name = r.get_table(sql);
+        let routine_kind = match words.next() {
+            Some("or") => words.nth(1),
+            kind => kind,
+        };
 
-                    let current = self.mentioned_relations.get_mut(&schema_name);
+        if !matches!(routine_kind, Some("function" | "procedure")) {
+            return None;
+        }
 
-                    match current {
-                        Some(c) => {
-                            c.insert(table_name);
-                        }
-                        None => {
-                            let mut new = HashSet::new();
-                            new.insert(table_name);
-                            self.mentioned_relations.insert(schema_name, new);
-                        }
-                    };
-                }
-            };
+        // the cursor is inside of the function body
+        if self.is_in_string() {
+            return None;
+        }
+
+        match self.words_before_cursor.last().map(|w| w.as_str()) {
+            Some("language") => Some(FunctionDefinitionPosition::Language),
+            // these always need a value
+            Some("returns" | "setof" | "as" | "security" | "parallel" | "cost" | "rows") => None,
+            _ if routine_kind == Some("function")
+                && self.words_before_cursor.iter().any(|w| w == "returns") =>
+            {
+                Some(FunctionDefinitionPosition::Attributes)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns true if the cursor is inside of a string literal, including dollar-quoted ones.
+    fn is_in_string(&self) -> bool {
+        if self.text_scan.is_in_string() {
+            return true;
         }
+
+        // tree-sitter also knows about escapes like `E'it\'s'`
+        self.node_under_cursor.is_some_and(|node| {
+            node.kind() == "literal"
+                && node.start_byte() < self.position
+                && self.position < node.end_byte()
+                && self
+                    .text
+                    .get(node.start_byte()..)
+                    .is_some_and(|txt| txt.trim_start_matches(['e', 'E']).starts_with(['\'', '$']))
+        })
     }
 
     pub fn get_ts_node_content(&self, ts_node: tree_sitter::Node<'a>) -> Option<NodeText<'a>> {
@@ -190,6 +2074,38 @@ impl<'a> CompletionContext<'a> {
             })
     }
 
+    /// Like [Self::get_node_under_cursor_content], but without the quotes of a quoted identifier,
+    /// so it can be compared to the names of the schema objects.
+    pub fn get_node_under_cursor_identifier(&self) -> Option<String> {
+        self.node_under_cursor
+            .and_then(|n| self.get_ts_node_content(n))
+            .and_then(|txt| txt.identifier())
+    }
+
+    /// Returns the top-level node of the statement the cursor is in.
+    ///
+    /// If the text under the cursor could not be parsed as part of the statement,
+    /// tree-sitter moves it into an `ERROR` node right after the statement, e.g. for
+    /// `drop table users cas|`. In that case, the preceding statement is returned.
+    pub fn get_statement_node(&self) -> Option<tree_sitter::Node<'a>> {
+        let mut node = self.node_under_cursor?;
+
+        while let Some(parent) = node.parent() {
+            if parent.kind() == "program" {
+                break;
+            }
+            node = parent;
+        }
+
+        if node.kind() == "ERROR" && !first_leaf(node).kind().starts_with("keyword_") {
+            if let Some(statement) = node.prev_sibling().filter(|n| n.kind() == "statement") {
+                return Some(statement);
+            }
+        }
+
+        Some(node)
+    }
+
     fn gather_tree_context(&mut self) {
         let mut cursor = self.tree.root_node().walk();
 
@@ -204,10 +2120,12 @@ impl<'a> CompletionContext<'a> {
          *
          * We'll therefore adjust the cursor position such that it meets the last node of the AST.
          * `select * from use           {}` becomes `select * from use{}`.
+         *
+         * The cursor steps back a whole character at a time, so it never lands within a multi-byte one.
          */
         let current_node = cursor.node();
         while cursor.goto_first_child_for_byte(self.position).is_none() && self.position > 0 {
-            self.position -= 1;
+            self.position = previous_char_boundary(self.text, self.position);
         }
 
         self.gather_context_from_node(cursor, current_node);
@@ -255,11 +2173,12 @@ impl<'a> CompletionContext<'a> {
                 }
             }
 
-            "where" | "update" | "select" | "delete" | "from" => {
+            "where" | "update" | "select" | "delete" | "from" | "partition_by" | "order_by"
+            | "limit" | "offset" | "returning" => {
                 self.wrapping_clause_type = current_node_kind.try_into().ok();
             }
 
-            "relation" | "binary_expression" | "assignment" => {
+            "relation" | "binary_expression" | "assignment" | "window_specification" => {
                 self.wrapping_node_kind = current_node_kind.try_into().ok();
             }
 
@@ -277,13 +2196,164 @@ impl<'a> CompletionContext<'a> {
     }
 }
 
+/// Keywords that may precede the cursor, but cannot be given an alias.
+const NON_ALIASABLE_WORDS: &[&str] = &[
+    "select", "distinct", "all", "from", "where", "join", "on", "using", "as", "and", "or", "not",
+    "by", "case", "when", "then", "else", "end", "null", "true", "false", "lateral", "only",
+    "inner", "left", "right", "full", "cross", "outer", "natural",
+];
+
+/// Words that start a table constraint rather than a column in the element list of `create table`.
+const TABLE_CONSTRAINT_WORDS: &[&str] = &[
+    "constraint",
+    "primary",
+    "unique",
+    "check",
+    "foreign",
+    "exclude",
+    "like",
+];
+
+/// Keywords that a derived alias must not collide with.
+const RESERVED_ALIASES: &[&str] = &["as", "by", "do", "in", "is", "of", "on", "or", "to"];
+
+/// Returns the table named by `from` or `join`, given the word before the cursor and the ones preceding it.
+/// The table may be marked with `only` or `*` to exclude or include its descendants, e.g. `from only users`
+/// or `join teams *`.
+fn referenced_table<'w>(named: &'w str, rest: &'w [String]) -> Option<&'w str> {
+    let (named, rest) = match (named, rest.split_last()) {
+        ("*", Some((table, rest))) => (table.as_str(), rest),
+        _ => (named, rest),
+    };
+
+    let rest = match rest.split_last() {
+        Some((only, rest)) if only == "only" => rest,
+        _ => rest,
+    };
+
+    let table = named.trim_end_matches('*');
+    matches!(rest.last().map(|w| w.as_str()), Some("from" | "join"))
+        .then_some(table)
+        .filter(|t| !t.is_empty())
+}
+
+/// Derives an alias from the initials of a table name, e.g. `oi` for `public.order_items`.
+fn derive_table_alias(name: &str) -> Option<String> {
+    let name = name.rsplit('.').next()?.trim_matches('"');
+
+    let alias: String = name
+        .split('_')
+        .filter_map(|segment| segment.chars().next())
+        .collect::<String>()
+        .to_lowercase();
+
+    (!alias.is_empty() && alias != name && !RESERVED_ALIASES.contains(&alias.as_str()))
+        .then_some(alias)
+}
+
+/// Derives an alias by dropping the last segment of a column name, e.g. `created` for `created_at`.
+fn derive_column_alias(name: &str) -> Option<String> {
+    let name = name.rsplit('.').next()?.trim_matches('"');
+    let (alias, _) = name.rsplit_once('_')?;
+
+    (!alias.is_empty() && !RESERVED_ALIASES.contains(&alias)).then(|| alias.to_string())
+}
+
+/// Strips the schema and the quotes from a name, e.g. `users` for `public."users"`.
+fn unqualified(name: &str) -> String {
+    name.rsplit('.')
+        .next()
+        .unwrap_or(name)
+        .trim_matches('"')
+        .to_string()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.' || c == '"'
+}
+
+fn split_words(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Strips trailing array subscripts, e.g. `addresses[1][2]`.
+fn strip_subscripts(text: &str) -> (&str, bool) {
+    let mut text = text.trim_end();
+    let mut is_subscripted = false;
+
+    while text.ends_with(']') {
+        match text.rfind('[') {
+            Some(idx) => {
+                text = text[..idx].trim_end();
+                is_subscripted = true;
+            }
+            None => break,
+        }
+    }
+
+    (text, is_subscripted)
+}
+
+/// Returns the start of the character before `position`, or `0` if there is none.
+fn previous_char_boundary(text: &str, position: usize) -> usize {
+    (0..position)
+        .rev()
+        .find(|idx| text.is_char_boundary(*idx))
+        .unwrap_or(0)
+}
+
+/// Returns the index of the `(` matching the `)` that was stripped from the end of `text`.
+fn matching_open_paren(text: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (idx, c) in text.char_indices().rev() {
+        match c {
+            ')' => depth += 1,
+            '(' if depth == 0 => return Some(idx),
+            '(' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the index of the `)` matching the `(` at the start of `text`.
+fn matching_close_paren(text: &str) -> Option<usize> {
+    let mut depth = 0;
+
+    for (idx, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 1 => return Some(idx),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the first token of the given node, e.g. `keyword_drop` for a `drop table` statement.
+pub(crate) fn first_leaf(node: tree_sitter::Node) -> tree_sitter::Node {
+    let mut leaf = node;
+    while let Some(child) = leaf.child(0) {
+        leaf = child;
+    }
+    leaf
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        context::{ClauseType, CompletionContext, NodeText},
+        context::{ClauseType, CompletionContext, CopyPosition, NodeText, WrappingNode},
         sanitization::SanitizedCompletionParams,
         test_helper::{CURSOR_POS, get_text_and_position},
     };
+    use pgt_text_size::TextRange;
 
     fn get_tree(input: &str) -> tree_sitter::Tree {
         let mut parser = tree_sitter::Parser::new();
@@ -333,11 +2403,145 @@ mod tests {
                 text,
                 tree: std::borrow::Cow::Owned(tree),
                 schema: &pgt_schema_cache::SchemaCache::default(),
+                include_system_schemas: true,
+                hidden_objects: None,
+                preceding_statements: &[],
+            };
+
+            let ctx = CompletionContext::new(&params);
+
+            assert_eq!(ctx.wrapping_clause_type, expected_clause.try_into().ok());
+        }
+    }
+
+    #[test]
+    fn identifies_copy_positions() {
+        let test_cases = vec![
+            (
+                format!("copy users from {}", CURSOR_POS),
+                Some(CopyPosition::Source),
+            ),
+            (
+                format!("copy (select * from users) to {}", CURSOR_POS),
+                Some(CopyPosition::Target),
+            ),
+            // the cursor is inside of the query
+            (format!("copy (select * from {}", CURSOR_POS), None),
+            (format!("copy (select count(*) from {}", CURSOR_POS), None),
+            // the open parenthesis of the preceding statement is ignored
+            (
+                format!("select (1;\ncopy users from stdin {}", CURSOR_POS),
+                Some(CopyPosition::AfterSource),
+            ),
+        ];
+
+        for (query, expected) in test_cases {
+            let (position, text) = get_text_and_position(query.as_str().into());
+
+            let tree = get_tree(text.as_str());
+
+            let params = SanitizedCompletionParams {
+                position: (position as u32).into(),
+                text,
+                tree: std::borrow::Cow::Owned(tree),
+                schema: &pgt_schema_cache::SchemaCache::default(),
+                include_system_schemas: true,
+                hidden_objects: None,
+                preceding_statements: &[],
+            };
+
+            let ctx = CompletionContext::new(&params);
+
+            assert_eq!(ctx.get_copy_position(), expected, "{query}");
+        }
+    }
+
+    #[test]
+    fn identifies_window_specification() {
+        let test_cases = vec![
+            (
+                format!(
+                    "select sum(amount) over (partition by us{}er_id) from orders;",
+                    CURSOR_POS
+                ),
+                "partition_by",
+            ),
+            (
+                format!(
+                    "select sum(amount) over (partition by user_id order by cr{}eated_at) from orders;",
+                    CURSOR_POS
+                ),
+                "order_by",
+            ),
+        ];
+
+        for (query, expected_clause) in test_cases {
+            let (position, text) = get_text_and_position(query.as_str().into());
+
+            let tree = get_tree(text.as_str());
+            let params = SanitizedCompletionParams {
+                position: (position as u32).into(),
+                text,
+                tree: std::borrow::Cow::Owned(tree),
+                schema: &pgt_schema_cache::SchemaCache::default(),
+                include_system_schemas: true,
+                hidden_objects: None,
+                preceding_statements: &[],
             };
 
             let ctx = CompletionContext::new(&params);
 
             assert_eq!(ctx.wrapping_clause_type, expected_clause.try_into().ok());
+            assert_eq!(
+                ctx.wrapping_node_kind,
+                Some(WrappingNode::WindowSpecification)
+            );
+        }
+    }
+
+    #[test]
+    fn identifies_quoted_identifiers() {
+        let test_cases = vec![
+            (
+                format!(r#"select "na{} from users;"#, CURSOR_POS),
+                Some((7, 10)),
+            ),
+            (
+                format!(r#"select "na{}" from users;"#, CURSOR_POS),
+                Some((7, 11)),
+            ),
+            (
+                format!(r#"select "na{}me" from users;"#, CURSOR_POS),
+                Some((7, 13)),
+            ),
+            (
+                format!(r#"select "id", na{} from users;"#, CURSOR_POS),
+                None,
+            ),
+            (format!("select na{} from users;", CURSOR_POS), None),
+        ];
+
+        for (query, expected_range) in test_cases {
+            let (position, text) = get_text_and_position(query.as_str().into());
+
+            let tree = get_tree(text.as_str());
+            let params = SanitizedCompletionParams {
+                position: (position as u32).into(),
+                text,
+                tree: std::borrow::Cow::Owned(tree),
+                schema: &pgt_schema_cache::SchemaCache::default(),
+                include_system_schemas: true,
+                hidden_objects: None,
+                preceding_statements: &[],
+            };
+
+            let ctx = CompletionContext::new(&params);
+
+            assert_eq!(
+                ctx.quoted_identifier_range,
+                expected_range
+                    .map(|(start, end): (u32, u32)| TextRange::new(start.into(), end.into()))
+            );
         }
     }
 
@@ -365,6 +2569,9 @@ mod tests {
                 text,
                 tree: std::borrow::Cow::Owned(tree),
                 schema: &pgt_schema_cache::SchemaCache::default(),
+                include_system_schemas: true,
+                hidden_objects: None,
+                preceding_statements: &[],
             };
 
             let ctx = CompletionContext::new(&params);
@@ -399,6 +2606,9 @@ mod tests {
                 text,
                 tree: std::borrow::Cow::Owned(tree),
                 schema: &pgt_schema_cache::SchemaCache::default(),
+                include_system_schemas: true,
+                hidden_objects: None,
+                preceding_statements: &[],
             };
 
             let ctx = CompletionContext::new(&params);
@@ -424,6 +2634,9 @@ mod tests {
                 text,
                 tree: std::borrow::Cow::Owned(tree),
                 schema: &pgt_schema_cache::SchemaCache::default(),
+                include_system_schemas: true,
+                hidden_objects: None,
+                preceding_statements: &[],
             };
 
             let ctx = CompletionContext::new(&params);
@@ -455,6 +2668,9 @@ mod tests {
             text,
             tree: std::borrow::Cow::Owned(tree),
             schema: &pgt_schema_cache::SchemaCache::default(),
+            include_system_schemas: true,
+            hidden_objects: None,
+            preceding_statements: &[],
         };
 
         let ctx = CompletionContext::new(&params);
@@ -480,6 +2696,9 @@ mod tests {
             text,
             tree: std::borrow::Cow::Owned(tree),
             schema: &pgt_schema_cache::SchemaCache::default(),
+            include_system_schemas: true,
+            hidden_objects: None,
+            preceding_statements: &[],
         };
 
         let ctx = CompletionContext::new(&params);
@@ -505,6 +2724,9 @@ mod tests {
             text,
             tree: std::borrow::Cow::Owned(tree),
             schema: &pgt_schema_cache::SchemaCache::default(),
+            include_system_schemas: true,
+            hidden_objects: None,
+            preceding_statements: &[],
         };
 
         let ctx = CompletionContext::new(&params);
@@ -517,4 +2739,31 @@ mod tests {
         );
         assert_eq!(ctx.wrapping_clause_type, Some(ClauseType::Select));
     }
+
+    #[test]
+    fn does_not_move_the_cursor_into_a_multi_byte_character() {
+        let query = format!("select * from ñandú {}", CURSOR_POS);
+
+        let (position, text) = get_text_and_position(query.as_str().into());
+
+        let tree = get_tree(text.as_str());
+
+        let params = SanitizedCompletionParams {
+            position: (position as u32).into(),
+            text,
+            tree: std::borrow::Cow::Owned(tree),
+            schema: &pgt_schema_cache::SchemaCache::default(),
+            include_system_schemas: true,
+            hidden_objects: None,
+            preceding_statements: &[],
+        };
+
+        let ctx = CompletionContext::new(&params);
+
+        assert!(
+            ctx.text.is_char_boundary(ctx.position),
+            "The cursor was moved to byte {}, within a character",
+            ctx.position
+        );
+    }
 }
@@ -0,0 +1,86 @@
+//! Gathers the names of the columns a `CREATE TABLE` statement defines,
+//! e.g. `a` and `b` in `create table t (a int, b text, check (a > 0))`.
+
+use super::ctes::{TokenKind, tokenize};
+
+/// Keywords that start a table constraint rather than a column definition.
+const TABLE_CONSTRAINT_KEYWORDS: &[&str] = &[
+    "constraint",
+    "check",
+    "primary",
+    "unique",
+    "foreign",
+    "exclude",
+    "like",
+];
+
+/// Returns the names of the columns defined in the column list of a `CREATE TABLE` statement.
+pub(crate) fn defined_columns(sql: &str) -> Vec<String> {
+    let tokens = tokenize(sql);
+
+    if !tokens.first().is_some_and(|t| t.is_word("create")) {
+        return vec![];
+    }
+
+    let Some(table) = tokens.iter().position(|t| t.is_word("table")) else {
+        return vec![];
+    };
+
+    // `create table t as select ...` has no column list
+    let Some(open) = tokens
+        .iter()
+        .skip(table)
+        .take_while(|t| !t.is_word("as"))
+        .position(|t| t.kind == TokenKind::Open)
+        .map(|idx| table + idx)
+    else {
+        return vec![];
+    };
+
+    let mut columns = vec![];
+    let mut idx = open + 1;
+
+    loop {
+        // every element of the list starts with the name of its column, unless it is a constraint
+        if let Some(TokenKind::Word(name)) = tokens.get(idx).map(|t| &t.kind) {
+            if !TABLE_CONSTRAINT_KEYWORDS
+                .iter()
+                .any(|keyword| name.eq_ignore_ascii_case(keyword))
+            {
+                columns.push(name.to_string());
+            }
+        }
+
+        let mut depth = 0;
+        loop {
+            match tokens.get(idx).map(|t| &t.kind) {
+                None => return columns,
+                Some(TokenKind::Close) if depth == 0 => return columns,
+                Some(TokenKind::Close) => depth -= 1,
+                Some(TokenKind::Open) => depth += 1,
+                Some(TokenKind::Comma) if depth == 0 => break,
+                _ => {}
+            }
+            idx += 1;
+        }
+
+        idx += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::defined_columns;
+
+    #[test]
+    fn finds_the_columns_of_the_definition() {
+        assert_eq!(
+            defined_columns(
+                "create table if not exists public.t (a numeric(10, 2), \"B\" text check (length(\"B\") > 0), constraint c unique (a), check ("
+            ),
+            vec!["a", "B"]
+        );
+        assert!(defined_columns("create table t as select (1) as a").is_empty());
+        assert!(defined_columns("select a from t").is_empty());
+    }
+}
@@ -0,0 +1,218 @@
+//! A lightweight scanner for the common table expressions of a statement.
+//!
+//! tree-sitter does not reliably parse incomplete CTEs, so their names are
+//! gathered from the statement text instead.
+
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum TokenKind<'a> {
+    Word(&'a str),
+    Open,
+    Close,
+    Comma,
+}
+
+#[derive(Debug)]
+pub(super) struct Token<'a> {
+    pub(super) kind: TokenKind<'a>,
+    start: usize,
+    end: usize,
+}
+
+impl Token<'_> {
+    pub(super) fn is_word(&self, word: &str) -> bool {
+        matches!(self.kind, TokenKind::Word(w) if w.eq_ignore_ascii_case(word))
+    }
+}
+
+/// Returns the names of the CTEs that may be referenced at the given position, e.g.
+/// all previously defined CTEs within the body of a CTE, or all CTEs within the
+/// body of a `with recursive` CTE, including itself.
+pub(crate) fn visible_ctes(sql: &str, position: usize) -> Vec<String> {
+    let tokens = tokenize(sql);
+
+    if !tokens.first().is_some_and(|t| t.is_word("with")) {
+        return vec![];
+    }
+
+    let mut idx = 1;
+
+    let is_recursive = tokens.get(idx).is_some_and(|t| t.is_word("recursive"));
+    if is_recursive {
+        idx += 1;
+    }
+
+    let mut ctes = vec![];
+
+    while let Some(name_token) = tokens.get(idx) {
+        let name = match name_token.kind {
+            TokenKind::Word(name) => name,
+            _ => break,
+        };
+        idx += 1;
+
+        // `with t(a, b) as (...)`
+        if tokens.get(idx).is_some_and(|t| t.kind == TokenKind::Open) {
+            idx = skip_parens(&tokens, idx);
+        }
+
+        if !tokens.get(idx).is_some_and(|t| t.is_word("as")) {
+            break;
+        }
+        idx += 1;
+
+        // `as not materialized (...)`
+        if tokens.get(idx).is_some_and(|t| t.is_word("not")) {
+            idx += 1;
+        }
+        if tokens.get(idx).is_some_and(|t| t.is_word("materialized")) {
+            idx += 1;
+        }
+
+        if !tokens.get(idx).is_some_and(|t| t.kind == TokenKind::Open) {
+            break;
+        }
+
+        idx = skip_parens(&tokens, idx);
+
+        // an unterminated body extends to the end of the statement
+        let body_end = match tokens.get(idx - 1) {
+            Some(t) if t.kind == TokenKind::Close => t.end,
+            _ => sql.len(),
+        };
+
+        let is_typing_name = name_token.start <= position && position <= name_token.end;
+        let is_defined_before = body_end <= position;
+
+        if !is_typing_name && (is_recursive || is_defined_before) {
+            ctes.push(name.to_string());
+        }
+
+        if !tokens.get(idx).is_some_and(|t| t.kind == TokenKind::Comma) {
+            break;
+        }
+        idx += 1;
+    }
+
+    ctes
+}
+
+/// Expects an opening parenthesis at `idx` and returns the index after its matching closing one.
+pub(super) fn skip_parens(tokens: &[Token], idx: usize) -> usize {
+    let mut depth = 0;
+
+    for (i, token) in tokens.iter().enumerate().skip(idx) {
+        match token.kind {
+            TokenKind::Open => depth += 1,
+            TokenKind::Close => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tokens.len()
+}
+
+pub(super) fn tokenize(sql: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut chars = sql.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = match c {
+            '(' => TokenKind::Open,
+            ')' => TokenKind::Close,
+            ',' => TokenKind::Comma,
+            '"' => {
+                let end = sql[start + 1..]
+                    .find('"')
+                    .map(|i| start + 1 + i)
+                    .unwrap_or(sql.len());
+                while chars.next_if(|(i, _)| *i <= end).is_some() {}
+
+                tokens.push(Token {
+                    kind: TokenKind::Word(&sql[start + 1..end]),
+                    start,
+                    end: (end + 1).min(sql.len()),
+                });
+                continue;
+            }
+            '\'' => {
+                // string literals are skipped entirely
+                let end = sql[start + 1..]
+                    .find('\'')
+                    .map(|i| start + 1 + i)
+                    .unwrap_or(sql.len());
+                while chars.next_if(|(i, _)| *i <= end).is_some() {}
+                continue;
+            }
+            '-' if chars.peek().is_some_and(|(_, c)| *c == '-') => {
+                while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+                continue;
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                while let Some((i, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    end = i + c.len_utf8();
+                }
+
+                tokens.push(Token {
+                    kind: TokenKind::Word(&sql[start..end]),
+                    start,
+                    end,
+                });
+                continue;
+            }
+            _ => continue,
+        };
+
+        tokens.push(Token {
+            kind,
+            start,
+            end: start + c.len_utf8(),
+        });
+    }
+
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::visible_ctes;
+
+    fn ctes_at_cursor(input: &str) -> Vec<String> {
+        let position = input.find('|').unwrap();
+        let sql = input.replace('|', "");
+        visible_ctes(&sql, position)
+    }
+
+    #[test]
+    fn finds_previously_defined_ctes() {
+        assert_eq!(
+            ctes_at_cursor("with a as (select 1), b (x) as (select * from |) select 1"),
+            vec!["a"]
+        );
+        assert_eq!(
+            ctes_at_cursor("with a as (select 1), b as materialized (select 2) select * from |"),
+            vec!["a", "b"]
+        );
+    }
+
+    #[test]
+    fn finds_recursive_ctes_within_their_own_body() {
+        assert_eq!(
+            ctes_at_cursor(
+                "with recursive t as (select 1 as n union all select n + 1 from | where n < 5) select * from t"
+            ),
+            vec!["t"]
+        );
+    }
+
+    #[test]
+    fn ignores_statements_without_ctes() {
+        assert!(ctes_at_cursor("select * from |").is_empty());
+        assert!(ctes_at_cursor("select ')' from \"with\" where |").is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+//! Finds the statement around the cursor and whether the cursor is inside of a string literal,
+//! a quoted identifier, a dollar-quoted body or a comment.
+//!
+//! A `;` or a quote within any of these does not end a statement or a literal, so simply
+//! splitting the text or counting quotes is not enough.
+
+use std::ops::{ControlFlow, Range};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LexState<'a> {
+    Sql,
+    /// A string literal, starting at the offset of its opening quote.
+    String(usize),
+    /// A string literal with backslash escapes, e.g. `E'it\'s'`, starting at the offset of its
+    /// opening quote.
+    EscapeString(usize),
+    /// A quoted identifier, starting at the offset of its opening quote.
+    QuotedIdentifier(usize),
+    /// A dollar-quoted string with the given tag, e.g. `$$` or `$body$`.
+    DollarQuoted(&'a str),
+    LineComment,
+    /// A block comment, which may be nested.
+    BlockComment(usize),
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct TextScan<'a> {
+    /// The range of the statement around the position, without its terminating `;`.
+    pub(super) statement: Range<usize>,
+    /// The lexical state at the position.
+    pub(super) state: LexState<'a>,
+}
+
+impl<'a> TextScan<'a> {
+    pub(super) fn new(text: &'a str, position: usize) -> Self {
+        let position = position.min(text.len());
+
+        let mut start = 0;
+        let state = scan(text, 0..position, LexState::Sql, |idx| {
+            start = idx + 1;
+            ControlFlow::Continue(())
+        });
+
+        let mut end = text.len();
+        scan(text, position..text.len(), state, |idx| {
+            end = idx;
+            ControlFlow::Break(())
+        });
+
+        Self {
+            statement: start..end,
+            state,
+        }
+    }
+
+    /// Returns the offset of the opening quote if the position is inside of a string literal.
+    pub(super) fn string_start(&self) -> Option<usize> {
+        match self.state {
+            LexState::String(start) | LexState::EscapeString(start) => Some(start),
+            _ => None,
+        }
+    }
+
+    /// Returns the offset of the opening quote if the position is inside of a quoted identifier.
+    pub(super) fn quoted_identifier_start(&self) -> Option<usize> {
+        match self.state {
+            LexState::QuotedIdentifier(start) => Some(start),
+            _ => None,
+        }
+    }
+
+    /// Whether the position is inside of a string literal, including dollar-quoted ones.
+    pub(super) fn is_in_string(&self) -> bool {
+        matches!(
+            self.state,
+            LexState::String(_) | LexState::EscapeString(_) | LexState::DollarQuoted(_)
+        )
+    }
+}
+
+/// Scans `text[range]` starting in `state` and returns the state at its end.
+///
+/// `on_terminator` is called with the offset of every `;` that terminates a statement.
+fn scan<'a>(
+    text: &'a str,
+    range: Range<usize>,
+    mut state: LexState<'a>,
+    mut on_terminator: impl FnMut(usize) -> ControlFlow<()>,
+) -> LexState<'a> {
+    // nothing after the range is looked at, e.g. to tell an escaped quote from a closing one
+    let bytes = &text.as_bytes()[..range.end];
+    let mut idx = range.start;
+
+    while idx < bytes.len() {
+        let rest = &bytes[idx..];
+
+        match state {
+            LexState::Sql => match rest[0] {
+                b';' => {
+                    if on_terminator(idx).is_break() {
+                        return state;
+                    }
+                }
+                b'\'' => {
+                    state = if is_escape_string_prefix(bytes, idx) {
+                        LexState::EscapeString(idx)
+                    } else {
+                        LexState::String(idx)
+                    }
+                }
+                b'"' => state = LexState::QuotedIdentifier(idx),
+                b'-' if rest.starts_with(b"--") => {
+                    state = LexState::LineComment;
+                    idx += 1;
+                }
+                b'/' if rest.starts_with(b"/*") => {
+                    state = LexState::BlockComment(1);
+                    idx += 1;
+                }
+                b'$' => {
+                    if let Some(tag) = dollar_quote_tag(text, idx, range.end) {
+                        state = LexState::DollarQuoted(tag);
+                        idx += tag.len() - 1;
+                    }
+                }
+                _ => {}
+            },
+            LexState::String(_) | LexState::EscapeString(_) | LexState::QuotedIdentifier(_) => {
+                let quote = match state {
+                    LexState::QuotedIdentifier(_) => b'"',
+                    _ => b'\'',
+                };
+
+                if rest[0] == b'\\' && matches!(state, LexState::EscapeString(_)) {
+                    // the escaped character, e.g. a quote, does not end the literal
+                    idx += 1;
+                } else if rest[0] == quote {
+                    // a doubled quote is an escaped one
+                    if rest.get(1) == Some(&quote) {
+                        idx += 1;
+                    } else {
+                        state = LexState::Sql;
+                    }
+                }
+            }
+            LexState::DollarQuoted(tag) => {
+                if rest.starts_with(tag.as_bytes()) {
+                    state = LexState::Sql;
+                    idx += tag.len() - 1;
+                }
+            }
+            LexState::LineComment => {
+                if rest[0] == b'\n' {
+                    state = LexState::Sql;
+                }
+            }
+            LexState::BlockComment(depth) => {
+                if rest.starts_with(b"/*") {
+                    state = LexState::BlockComment(depth + 1);
+                    idx += 1;
+                } else if rest.starts_with(b"*/") {
+                    state = match depth {
+                        1 => LexState::Sql,
+                        _ => LexState::BlockComment(depth - 1),
+                    };
+                    idx += 1;
+                }
+            }
+        }
+
+        idx += 1;
+    }
+
+    state
+}
+
+/// Returns true if the quote at `idx` opens an escape string, i.e. follows an `E` that is not
+/// the end of an identifier like `name`.
+fn is_escape_string_prefix(bytes: &[u8], idx: usize) -> bool {
+    let Some(prefix) = idx.checked_sub(1) else {
+        return false;
+    };
+
+    matches!(bytes[prefix], b'e' | b'E')
+        && prefix
+            .checked_sub(1)
+            .is_none_or(|before| !(bytes[before].is_ascii_alphanumeric() || bytes[before] == b'_'))
+}
+
+/// Returns the tag of the dollar quote that starts at `idx`, e.g. `$$` or `$body$`.
+fn dollar_quote_tag(text: &str, idx: usize, end: usize) -> Option<&str> {
+    let bytes = &text.as_bytes()[..end];
+    let is_tag_char = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_';
+
+    // `$` is part of identifiers like `a$b`, and `$1` is a parameter
+    if idx > 0 && (is_tag_char(&bytes[idx - 1]) || bytes[idx - 1] == b'$') {
+        return None;
+    }
+    if bytes.get(idx + 1).is_some_and(u8::is_ascii_digit) {
+        return None;
+    }
+
+    let len = bytes[idx + 1..].iter().position(|b| !is_tag_char(b))?;
+
+    (bytes[idx + 1 + len] == b'$').then(|| &text[idx..idx + len + 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LexState, TextScan};
+
+    #[test]
+    fn ignores_terminators_in_strings_bodies_and_comments() {
+        let text = "select ';'; -- ;\nselect $$ ; $$, \"a;b\" /* ; */ from t; select 1";
+        let position = text.find(" from").unwrap();
+
+        let scan = TextScan::new(text, position);
+
+        assert_eq!(
+            &text[scan.statement],
+            " -- ;\nselect $$ ; $$, \"a;b\" /* ; */ from t"
+        );
+        assert_eq!(scan.state, LexState::Sql);
+    }
+
+    #[test]
+    fn finds_the_open_literal() {
+        let text = "select 'it''s";
+        assert_eq!(TextScan::new(text, text.len()).string_start(), Some(7));
+
+        let text = "select 'done' ";
+        assert_eq!(TextScan::new(text, text.len()).string_start(), None);
+
+        // a backslash escapes the quote in escape strings only
+        let text = r"select E'it\'s', 'C:\', '";
+        assert_eq!(
+            TextScan::new(text, text.len()).string_start(),
+            Some(text.len() - 1)
+        );
+
+        let text = r"select name'a\', '";
+        assert_eq!(
+            TextScan::new(text, text.len()).string_start(),
+            Some(text.len() - 1)
+        );
+
+        let text = "create function f() returns int as $body$ select 'a";
+        assert!(TextScan::new(text, text.len()).is_in_string());
+        assert_eq!(TextScan::new(text, text.len()).string_start(), None);
+
+        let text = "select $1, \"My";
+        assert_eq!(
+            TextScan::new(text, text.len()).quoted_identifier_start(),
+            Some(11)
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Gathers the names of the windows defined in the `WINDOW` clause of a statement,
+//! e.g. `w` in `select sum(x) over w from t window w as (order by x)`.
+
+use super::ctes::{TokenKind, skip_parens, tokenize};
+
+/// Returns the names of all windows defined in the `WINDOW` clauses of the statement.
+pub(crate) fn named_windows(sql: &str) -> Vec<String> {
+    let tokens = tokenize(sql);
+    let mut windows = vec![];
+
+    for start in tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| t.is_word("window"))
+        .map(|(idx, _)| idx + 1)
+    {
+        let mut idx = start;
+
+        // `window a as (...), b as (...)`
+        while let Some(TokenKind::Word(name)) = tokens.get(idx).map(|t| &t.kind) {
+            if !tokens.get(idx + 1).is_some_and(|t| t.is_word("as"))
+                || !tokens
+                    .get(idx + 2)
+                    .is_some_and(|t| t.kind == TokenKind::Open)
+            {
+                break;
+            }
+
+            windows.push(name.to_string());
+
+            idx = skip_parens(&tokens, idx + 2);
+            if !tokens.get(idx).is_some_and(|t| t.kind == TokenKind::Comma) {
+                break;
+            }
+            idx += 1;
+        }
+    }
+
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::named_windows;
+
+    #[test]
+    fn finds_all_windows_of_the_clause() {
+        assert_eq!(
+            named_windows(
+                "select sum(x) over w from t window w as (order by x), \"W2\" as (w rows 2 preceding)"
+            ),
+            vec!["w", "W2"]
+        );
+        assert!(named_windows("select sum(x) over (order by x) from t").is_empty());
+    }
+}
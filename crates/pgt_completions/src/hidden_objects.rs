@@ -0,0 +1,60 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+/// Objects that are not suggested, e.g. deprecated or internal ones.
+#[derive(Debug, Clone, Default)]
+pub struct HiddenObjects {
+    patterns: GlobSet,
+    comment_marker: Option<String>,
+}
+
+impl HiddenObjects {
+    /// Hides the objects whose name matches one of the glob `patterns`, e.g. `_deprecated*`,
+    /// and the tables and columns whose comment contains the `comment_marker`, e.g. `@deprecated`.
+    pub fn new<'p>(
+        patterns: impl IntoIterator<Item = &'p str>,
+        comment_marker: Option<String>,
+    ) -> Result<Self, globset::Error> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(Glob::new(pattern)?);
+        }
+
+        Ok(Self {
+            patterns: builder.build()?,
+            comment_marker: comment_marker.filter(|marker| !marker.is_empty()),
+        })
+    }
+
+    /// Returns true if neither a pattern nor a comment marker was given.
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty() && self.comment_marker.is_none()
+    }
+
+    pub(crate) fn hides(&self, name: &str, comment: Option<&str>) -> bool {
+        if self.patterns.is_match(name) {
+            return true;
+        }
+
+        match (self.comment_marker.as_deref(), comment) {
+            (Some(marker), Some(comment)) => comment.contains(marker),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HiddenObjects;
+
+    #[test]
+    fn hides_by_name_and_comment() {
+        let hidden =
+            HiddenObjects::new(["_deprecated*", "tmp_?"], Some("@internal".into())).unwrap();
+
+        assert!(hidden.hides("_deprecated_users", None));
+        assert!(hidden.hides("tmp_a", None));
+        assert!(!hidden.hides("tmp_ab", None));
+        assert!(!hidden.hides("users", Some("All users")));
+        assert!(hidden.hides("users", Some("@internal: do not use")));
+    }
+}
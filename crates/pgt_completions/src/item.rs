@@ -3,7 +3,7 @@ use std::fmt::Display;
 use pgt_text_size::TextRange;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub enum CompletionItemKind {
@@ -11,6 +11,19 @@ pub enum CompletionItemKind {
     Function,
     Column,
     Schema,
+    Keyword,
+    Setting,
+    Field,
+    Collation,
+    Extension,
+    Alias,
+    Parameter,
+    OperatorClass,
+    Window,
+    Constraint,
+    Type,
+    Policy,
+    EnumValue,
 }
 
 impl Display for CompletionItemKind {
@@ -20,6 +33,19 @@ impl Display for CompletionItemKind {
             CompletionItemKind::Function => "Function",
             CompletionItemKind::Column => "Column",
             CompletionItemKind::Schema => "Schema",
+            CompletionItemKind::Keyword => "Keyword",
+            CompletionItemKind::Setting => "Setting",
+            CompletionItemKind::Field => "Field",
+            CompletionItemKind::Collation => "Collation",
+            CompletionItemKind::Extension => "Extension",
+            CompletionItemKind::Alias => "Alias",
+            CompletionItemKind::Parameter => "Parameter",
+            CompletionItemKind::OperatorClass => "Operator Class",
+            CompletionItemKind::Window => "Window",
+            CompletionItemKind::Constraint => "Constraint",
+            CompletionItemKind::Type => "Type",
+            CompletionItemKind::Policy => "Policy",
+            CompletionItemKind::EnumValue => "Enum Value",
         };
 
         write!(f, "{txt}")
@@ -29,7 +55,7 @@ impl Display for CompletionItemKind {
 #[derive(Debug, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 /// The text that the editor should fill in.
-/// If `None`, the `label` should be used.
+/// Unless a provider sets a different text, this is the `label`.
 /// Tables, for example, might have different completion_texts:
 ///
 /// label: "users", description: "Schema: auth", completion_text: "auth.users".
@@ -51,5 +77,9 @@ pub struct CompletionItem {
     /// String used for sorting by LSP clients.
     pub sort_text: String,
 
-    pub completion_text: Option<CompletionText>,
+    /// Replaces the token that is being completed, so editors don't duplicate the typed prefix.
+    pub completion_text: CompletionText,
+
+    /// The comment of the object, e.g. from `COMMENT ON TABLE`, if it has one.
+    pub documentation: Option<String>,
 }
@@ -1,6 +1,7 @@
 mod builder;
 mod complete;
 mod context;
+mod hidden_objects;
 mod item;
 mod providers;
 mod relevance;
@@ -10,5 +11,6 @@ mod sanitization;
 mod test_helper;
 
 pub use complete::*;
+pub use hidden_objects::HiddenObjects;
 pub use item::*;
 pub use sanitization::*;
@@ -0,0 +1,84 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+pub fn complete_aliases<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
+    for alias in &ctx.alias_suggestions {
+        let relevance = CompletionRelevanceData::Alias(alias);
+
+        let item = PossibleCompletionItem {
+            label: alias.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Alias".into(),
+            kind: CompletionItemKind::Alias,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_table_alias_after_table_in_from() {
+        let setup = r#"
+            create table order_items (
+                id serial primary key,
+                created_at timestamp
+            );
+        "#;
+
+        let queries = [
+            format!("select * from order_items {}", CURSOR_POS),
+            format!("select * from only order_items {}", CURSOR_POS),
+            format!("select * from order_items * {}", CURSOR_POS),
+        ];
+
+        for query in queries {
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            assert!(
+                items
+                    .iter()
+                    .any(|i| i.label == "oi" && i.kind == CompletionItemKind::Alias),
+                "Expected the alias `oi` for `{query}`, got {:?}",
+                items.iter().map(|i| &i.label).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn suggests_column_alias_after_column_in_select() {
+        let setup = r#"
+            create table order_items (
+                id serial primary key,
+                created_at timestamp
+            );
+        "#;
+
+        let query = format!("select created_at {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "as created" && i.kind == CompletionItemKind::Alias)
+        );
+    }
+}
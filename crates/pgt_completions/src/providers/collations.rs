@@ -0,0 +1,74 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::get_completion_text_for_case_sensitive_identifier;
+
+pub fn complete_collations<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    if !ctx.is_collation_position() {
+        return;
+    }
+
+    for collation in &ctx.schema_cache.collations {
+        let relevance = CompletionRelevanceData::Collation(collation);
+
+        let item = PossibleCompletionItem {
+            label: collation.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("Collation: {}", collation.provider),
+            kind: CompletionItemKind::Collation,
+            completion_text: get_completion_text_for_case_sensitive_identifier(
+                ctx,
+                &collation.name,
+            ),
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_collations_inside_quotes() {
+        let setup = r#"
+            create table public.users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!(
+            r#"select name from users order by name collate "{}""#,
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let c = items
+            .iter()
+            .find(|i| i.label == "C")
+            .expect("Expected the C collation");
+
+        assert_eq!(c.kind, CompletionItemKind::Collation);
+        assert_eq!(c.completion_text.text, r#""C""#);
+        assert!(
+            items
+                .iter()
+                .all(|i| i.kind == CompletionItemKind::Collation),
+            "Only collations should be suggested after `collate`"
+        );
+    }
+}
@@ -5,9 +5,23 @@ use crate::{
     relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
 };
 
+use std::collections::HashMap;
+
+use pgt_schema_cache::Column;
+
+use super::helper::{get_completion_text_for_quoted_identifier, get_documentation};
+
 pub fn complete_columns<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
     let available_columns = &ctx.schema_cache.columns;
 
+    // the names of the column types, looked up by id
+    let type_names: HashMap<i64, &str> = ctx
+        .schema_cache
+        .types
+        .iter()
+        .map(|t| (t.id, t.format.as_str()))
+        .collect();
+
     for col in available_columns {
         let relevance = CompletionRelevanceData::Column(col);
 
@@ -15,9 +29,47 @@ pub fn complete_columns<'a>(ctx: &CompletionContext<'a>, builder: &mut Completio
             label: col.name.clone(),
             score: CompletionScore::from(relevance.clone()),
             filter: CompletionFilter::from(relevance),
-            description: format!("Table: {}.{}", col.schema_name, col.table_name),
+            description: column_description(&type_names, col),
             kind: CompletionItemKind::Column,
-            completion_text: None,
+            completion_text: get_completion_text_for_quoted_identifier(ctx, &col.name),
+            documentation: get_documentation(col.comment.as_ref()),
+        };
+
+        builder.add_item(item);
+    }
+}
+
+/// Describes a column by its type, nullability and table, e.g. `text, not null · public.users`.
+fn column_description(type_names: &HashMap<i64, &str>, col: &Column) -> String {
+    let nullability = if col.is_nullable {
+        "nullable"
+    } else {
+        "not null"
+    };
+    let table = format!("{}.{}", col.schema_name, col.table_name);
+
+    match type_names.get(&col.type_id) {
+        Some(type_name) => format!("{}, {} · {}", type_name, nullability, table),
+        None => format!("{} · {}", nullability, table),
+    }
+}
+
+/// Completes the columns a `CREATE TABLE` statement defines, which are not in the schema cache yet.
+pub fn complete_defined_columns<'a>(
+    ctx: &'a CompletionContext,
+    builder: &mut CompletionBuilder<'a>,
+) {
+    for col in &ctx.defined_columns {
+        let relevance = CompletionRelevanceData::DefinedColumn(col);
+
+        let item = PossibleCompletionItem {
+            label: col.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Defined in this statement".into(),
+            kind: CompletionItemKind::Column,
+            completion_text: get_completion_text_for_quoted_identifier(ctx, col),
+            documentation: None,
         };
 
         builder.add_item(item);
@@ -26,6 +78,9 @@ pub fn complete_columns<'a>(ctx: &CompletionContext<'a>, builder: &mut Completio
 
 #[cfg(test)]
 mod tests {
+    use pgt_schema_cache::SchemaCache;
+    use pgt_text_size::{TextRange, TextSize};
+
     use crate::{
         CompletionItem, CompletionItemKind, complete,
         test_helper::{CURSOR_POS, InputQuery, get_test_deps, get_test_params},
@@ -71,7 +126,7 @@ mod tests {
                 message: "correctly prefers the columns of present tables",
                 query: format!(r#"select na{} from public.audio_books;"#, CURSOR_POS),
                 label: "narrator",
-                description: "Table: public.audio_books",
+                description: "text, nullable · public.audio_books",
             },
             TestCase {
                 message: "correctly handles nested queries",
@@ -89,13 +144,13 @@ mod tests {
                     CURSOR_POS
                 ),
                 label: "narrator_id",
-                description: "Table: private.audio_books",
+                description: "text, nullable · private.audio_books",
             },
             TestCase {
                 message: "works without a schema",
                 query: format!(r#"select na{} from users;"#, CURSOR_POS),
                 label: "name",
-                description: "Table: public.users",
+                description: "text, nullable · public.users",
             },
         ];
 
@@ -165,9 +220,9 @@ mod tests {
             .collect();
 
         let expected = vec![
-            ("name", "Table: public.users"),
-            ("narrator", "Table: public.audio_books"),
-            ("narrator_id", "Table: private.audio_books"),
+            ("name", "text, nullable · public.users"),
+            ("narrator", "text, nullable · public.audio_books"),
+            ("narrator_id", "text, nullable · private.audio_books"),
             ("name", "Schema: pg_catalog"),
             ("nameconcatoid", "Schema: pg_catalog"),
             ("nameeq", "Schema: pg_catalog"),
@@ -182,6 +237,39 @@ mod tests {
         assert_eq!(labels, expected);
     }
 
+    #[tokio::test]
+    async fn describes_column_type_and_nullability() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                email varchar(255) not null,
+                nickname text
+            );
+        "#;
+
+        let query = format!("select {} from users", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        let description = |label: &str| {
+            items
+                .iter()
+                .find(|i| i.label == label && i.kind == CompletionItemKind::Column)
+                .map(|i| i.description.as_str())
+        };
+
+        assert_eq!(description("id"), Some("integer, not null · public.users"));
+        assert_eq!(
+            description("email"),
+            Some("character varying, not null · public.users")
+        );
+        assert_eq!(
+            description("nickname"),
+            Some("text, nullable · public.users")
+        );
+    }
+
     #[tokio::test]
     async fn suggests_relevant_columns_without_letters() {
         let setup = r#"
@@ -325,4 +413,199 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn replaces_quoted_identifiers() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!(r#"select "na{}" from users;"#, CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let results = complete(params);
+
+        let item = results
+            .into_iter()
+            .find(|i| i.label == "name")
+            .expect("Should suggest the `name` column");
+
+        let completion_text = item.completion_text;
+
+        assert_eq!(completion_text.text, r#""name""#);
+        assert_eq!(
+            completion_text.range,
+            TextRange::new(TextSize::new(7), TextSize::new(11))
+        );
+    }
+
+    #[tokio::test]
+    async fn matches_quoted_identifiers_against_unquoted_names() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                email text,
+                name text
+            );
+        "#;
+
+        let query = format!(r#"select "na{}" from users;"#, CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let results = complete(params);
+
+        let best_match = results
+            .first()
+            .expect("Should return at least one completion item");
+
+        assert_eq!(best_match.label, "name");
+    }
+
+    #[tokio::test]
+    async fn suggests_shared_columns_in_using_clause() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+
+            create table posts (
+                post_id serial primary key,
+                id int,
+                title text
+            );
+        "#;
+
+        let query = format!("select * from users join posts using ({})", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let results = complete(params);
+
+        assert_eq!(
+            results
+                .iter()
+                .map(|item| (item.label.as_str(), &item.kind))
+                .collect::<Vec<_>>(),
+            vec![("id", &CompletionItemKind::Column)]
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_columns_inside_aggregate_filter() {
+        let setup = r#"
+            create table orders (
+                id serial primary key,
+                status text
+            );
+
+            create table customers (
+                id serial primary key,
+                email text
+            );
+        "#;
+
+        let query = format!("select count(*) filter (where {}) from orders", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let results = complete(params);
+
+        assert!(
+            results
+                .iter()
+                .any(|i| i.label == "status" && i.kind == CompletionItemKind::Column),
+            "Expected the columns of `orders`, got {:?}",
+            results.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+        assert!(
+            results.iter().all(|i| i.label != "email"),
+            "Columns of unrelated tables should not be suggested"
+        );
+        assert!(
+            results.iter().all(|i| i.kind != CompletionItemKind::Table),
+            "Tables should not be suggested inside a filter clause"
+        );
+    }
+
+    #[test]
+    fn suggests_defined_columns_inside_check() {
+        let query = format!("create table t (a int, b text, check ({}))", CURSOR_POS);
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        let tree = parser
+            .parse("create table t (a int, b text, check ())", None)
+            .unwrap();
+        let cache = SchemaCache::default();
+
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        let mut labels: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Column)
+            .map(|i| i.label.as_str())
+            .collect();
+        labels.sort();
+
+        assert_eq!(labels, vec!["a", "b"]);
+    }
+
+    #[tokio::test]
+    async fn suggests_columns_of_the_target_in_returning_clause() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+
+            create table audit_log (
+                entry_id serial primary key,
+                message text
+            );
+        "#;
+
+        let queries = [
+            format!(
+                "insert into users (name) values ('Alice') returning {}",
+                CURSOR_POS
+            ),
+            format!(
+                "update users set name = 'Bob' where id = 1 returning {}",
+                CURSOR_POS
+            ),
+        ];
+
+        for query in queries {
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            let columns: Vec<&str> = items
+                .iter()
+                .filter(|i| i.kind == CompletionItemKind::Column)
+                .take(2)
+                .map(|i| i.label.as_str())
+                .collect();
+            assert_eq!(columns, vec!["id", "name"], "{query}");
+
+            assert!(
+                items
+                    .iter()
+                    .any(|i| i.label == "*" && i.kind == CompletionItemKind::Keyword),
+                "{query}"
+            );
+            assert!(
+                !items.iter().any(|i| i.kind == CompletionItemKind::Table),
+                "{query}"
+            );
+        }
+    }
 }
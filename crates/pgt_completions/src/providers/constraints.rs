@@ -0,0 +1,129 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::get_completion_text_for_quoted_identifier;
+
+pub fn complete_constraints<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    if ctx.get_conflict_constraint_table().is_none() {
+        return;
+    }
+
+    for constraint in &ctx.schema_cache.constraints {
+        let relevance = CompletionRelevanceData::Constraint(constraint);
+
+        let item = PossibleCompletionItem {
+            label: constraint.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!(
+                "Constraint on {}.{} ({})",
+                constraint.schema,
+                constraint.table_name,
+                constraint.columns.join(", ")
+            ),
+            kind: CompletionItemKind::Constraint,
+            completion_text: get_completion_text_for_quoted_identifier(ctx, &constraint.name),
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_unique_constraints_of_the_insert_target() {
+        let setup = r#"
+            create table public.users (
+                id serial primary key,
+                email text,
+                constraint users_email_key unique (email),
+                constraint users_email_check check (email <> '')
+            );
+
+            create table public.posts (
+                id serial primary key,
+                title text unique
+            );
+        "#;
+
+        let query = format!(
+            "insert into public.users (email) values ('a') on conflict on constraint {}",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Constraint)
+            .map(|i| i.label.as_str())
+            .collect();
+
+        assert!(labels.contains(&"users_email_key"));
+        assert!(labels.contains(&"users_pkey"));
+        assert!(!labels.contains(&"users_email_check"));
+        assert!(!labels.contains(&"posts_pkey"));
+        assert!(
+            items
+                .iter()
+                .all(|i| i.kind == CompletionItemKind::Constraint),
+            "Only constraints should be suggested after `on constraint`"
+        );
+    }
+
+    #[tokio::test]
+    async fn only_suggests_constraints_of_the_insert_target_in_its_schema() {
+        let setup = r#"
+            create schema audit;
+
+            create table public.users (
+                id serial primary key
+            );
+
+            create table audit.users (
+                id serial,
+                constraint audit_users_id_key unique (id)
+            );
+        "#;
+
+        let cases = [
+            ("users", "users_pkey", "audit_users_id_key"),
+            ("public.users", "users_pkey", "audit_users_id_key"),
+            ("audit.users", "audit_users_id_key", "users_pkey"),
+        ];
+
+        for (table, expected, unexpected) in cases {
+            let query = format!(
+                "insert into {table} (id) values (1) on conflict on constraint {}",
+                CURSOR_POS
+            );
+
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+            assert!(
+                labels.contains(&expected),
+                "Expected `{expected}` for `{query}`, got {labels:?}"
+            );
+            assert!(
+                !labels.contains(&unexpected),
+                "Did not expect `{unexpected}` for `{query}`"
+            );
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    item::CompletionItemKind,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::get_completion_text_for_quoted_identifier;
+
+pub fn complete_ctes<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
+    for cte in &ctx.ctes {
+        let relevance = CompletionRelevanceData::Cte(cte);
+
+        let item = PossibleCompletionItem {
+            label: cte.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Common Table Expression".into(),
+            kind: CompletionItemKind::Table,
+            completion_text: get_completion_text_for_quoted_identifier(ctx, cte),
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_recursive_cte_within_its_own_body() {
+        let query = format!(
+            "with recursive numbers as (select 1 as n union all select n + 1 from num{} where n < 10) select * from numbers;",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items.iter().any(|i| i.label == "numbers"),
+            "Expected the recursive CTE to be suggested, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn does_not_suggest_cte_within_its_own_body() {
+        let query = format!(
+            "with numbers as (select * from num{}) select * from numbers;",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(items.iter().all(|i| i.label != "numbers"));
+    }
+}
@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::quote_ident;
+
+const INTEGER_TYPES: &[&str] = &[
+    "smallint", "integer", "int", "bigint", "int2", "int4", "int8",
+];
+
+const NUMERIC_TYPES: &[&str] = &[
+    "smallint", "integer", "int", "bigint", "int2", "int4", "int8", "numeric", "decimal", "real",
+    "double", "float4", "float8",
+];
+
+const TEXT_TYPES: &[&str] = &["text", "varchar", "character", "char"];
+
+/// Expressions that are commonly used as the default of a column, with the types they fit.
+const DEFAULT_EXPRESSIONS: &[(&str, CompletionItemKind, &[&str])] = &[
+    (
+        "now()",
+        CompletionItemKind::Function,
+        &["timestamp", "timestamptz", "date", "time", "timetz"],
+    ),
+    (
+        "current_timestamp",
+        CompletionItemKind::Keyword,
+        &["timestamp", "timestamptz"],
+    ),
+    ("current_date", CompletionItemKind::Keyword, &["date"]),
+    ("gen_random_uuid()", CompletionItemKind::Function, &["uuid"]),
+    ("0", CompletionItemKind::Keyword, NUMERIC_TYPES),
+    ("''", CompletionItemKind::Keyword, TEXT_TYPES),
+];
+
+pub fn complete_column_defaults<'a>(
+    ctx: &CompletionContext<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) {
+    let position = match ctx.get_column_default_position() {
+        Some(p) => p,
+        None => return,
+    };
+
+    for (expression, kind, types) in DEFAULT_EXPRESSIONS {
+        if !types.contains(&position.data_type.as_str()) {
+            continue;
+        }
+
+        add_default(builder, Cow::Borrowed(expression), *kind);
+    }
+
+    if !INTEGER_TYPES.contains(&position.data_type.as_str()) {
+        return;
+    }
+
+    // serial-like columns draw their values from a sequence
+    for sequence in &ctx.schema_cache.sequences {
+        let name = if sequence.schema == "public" {
+            quote_ident(&sequence.name).into_owned()
+        } else {
+            format!(
+                "{}.{}",
+                quote_ident(&sequence.schema),
+                quote_ident(&sequence.name)
+            )
+        };
+
+        // the name is parsed from a string literal
+        add_default(
+            builder,
+            Cow::Owned(format!("nextval('{}')", name.replace('\'', "''"))),
+            CompletionItemKind::Function,
+        );
+    }
+}
+
+fn add_default<'a>(
+    builder: &mut CompletionBuilder<'a>,
+    expression: Cow<'a, str>,
+    kind: CompletionItemKind,
+) {
+    let relevance = CompletionRelevanceData::DefaultExpression(expression.clone());
+
+    let item = PossibleCompletionItem {
+        label: expression.into_owned(),
+        score: CompletionScore::from(relevance.clone()),
+        filter: CompletionFilter::from(relevance),
+        description: "Default".into(),
+        kind,
+        completion_text: None,
+        documentation: None,
+    };
+
+    builder.add_item(item);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_now_for_timestamp_defaults() {
+        let query = format!(
+            "create table events (id int, created_at timestamp default {})",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert!(labels.contains(&"now()"), "Expected now(), got {labels:?}");
+        assert!(!labels.contains(&"gen_random_uuid()"));
+    }
+
+    #[tokio::test]
+    async fn suggests_sequences_for_integer_defaults() {
+        let setup = "create sequence event_ids;";
+
+        let query = format!("create table events (id bigint default {})", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(items.iter().any(|i| i.label == "nextval('event_ids')"));
+        assert!(items.iter().all(|i| i.label != "now()"));
+    }
+
+    #[tokio::test]
+    async fn quotes_the_names_of_sequences() {
+        let setup = r#"
+            create schema "Billing";
+            create sequence "Billing"."Invoice IDs";
+        "#;
+
+        let query = format!("create table invoices (id bigint default {})", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+        assert!(
+            labels.contains(&r#"nextval('"Billing"."Invoice IDs"')"#),
+            "got {labels:?}"
+        );
+    }
+}
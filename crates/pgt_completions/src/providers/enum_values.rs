@@ -0,0 +1,101 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::find_mentioned_column;
+
+/// Offers the labels of an enum column within the string literal it is compared to,
+/// e.g. `select * from orders where status = '|'`.
+pub fn complete_enum_values<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    let Some(compared) = ctx.compared_column.as_ref() else {
+        return;
+    };
+
+    let Some(column) = find_mentioned_column(ctx, compared.qualifier.as_ref(), &compared.column)
+    else {
+        return;
+    };
+
+    let Some(enum_type) = ctx.schema_cache.find_type_by_id(column.type_id) else {
+        return;
+    };
+
+    for label in enum_type.enum_labels() {
+        let relevance = CompletionRelevanceData::EnumValue(label);
+
+        let item = PossibleCompletionItem {
+            label: label.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("Value of {}.{}", enum_type.schema, enum_type.name),
+            kind: CompletionItemKind::EnumValue,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_enum_labels_in_comparisons() {
+        let setup = r#"
+            create type order_status as enum ('pending', 'shipped', 'delivered');
+
+            create table orders (
+                id serial primary key,
+                status order_status not null,
+                note text
+            );
+        "#;
+
+        let queries = [
+            format!("select * from orders where status = '{}'", CURSOR_POS),
+            format!(
+                "select * from orders o where o.status <> 'sh{}' and id > 1",
+                CURSOR_POS
+            ),
+        ];
+
+        for query in queries {
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+            assert!(labels.contains(&"pending"), "{query}: {labels:?}");
+            assert!(labels.contains(&"shipped"), "{query}: {labels:?}");
+            assert!(
+                items.iter().all(|i| i.kind == CompletionItemKind::EnumValue
+                    && i.description == "Value of public.order_status"),
+                "Only enum labels belong into the literal, got {labels:?}"
+            );
+        }
+
+        // text columns have no labels to offer
+        let query = format!("select * from orders where note = '{}'", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        assert!(complete(params).is_empty());
+
+        // the column of a table that is not part of the statement
+        let query = format!("select * from shipments where status = '{}'", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        assert!(
+            complete(params)
+                .iter()
+                .all(|i| i.kind != CompletionItemKind::EnumValue)
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::{CompletionContext, ExtensionPosition},
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::get_completion_text_for_case_sensitive_identifier;
+
+pub fn complete_extensions<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    if !matches!(
+        ctx.get_extension_position(),
+        Some(ExtensionPosition::Name { .. })
+    ) {
+        return;
+    }
+
+    for extension in &ctx.schema_cache.extensions {
+        let relevance = CompletionRelevanceData::Extension(extension);
+
+        let description = match extension.installed_version.as_ref() {
+            Some(version) => format!("Extension: {} (installed)", version),
+            None => format!(
+                "Extension: {}",
+                extension.default_version.as_deref().unwrap_or("")
+            ),
+        };
+
+        let item = PossibleCompletionItem {
+            label: extension.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description,
+            kind: CompletionItemKind::Extension,
+            // names like `uuid-ossp` must be quoted
+            completion_text: get_completion_text_for_case_sensitive_identifier(
+                ctx,
+                &extension.name,
+            ),
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_available_extensions() {
+        let query = format!("create extension {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let uuid_ossp = items
+            .iter()
+            .find(|i| i.label == "uuid-ossp")
+            .expect("Expected the uuid-ossp extension");
+
+        assert_eq!(uuid_ossp.kind, CompletionItemKind::Extension);
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "if not exists" && i.kind == CompletionItemKind::Keyword)
+        );
+        assert!(
+            items.iter().all(|i| matches!(
+                i.kind,
+                CompletionItemKind::Extension | CompletionItemKind::Keyword
+            )),
+            "Only extensions should be suggested after `create extension`"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_options_after_extension_name() {
+        let query = format!("create extension pgcrypto {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(items.iter().any(|i| i.label == "with schema"));
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Keyword),
+            "Only keywords should follow the name of the extension"
+        );
+    }
+}
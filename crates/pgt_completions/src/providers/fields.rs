@@ -0,0 +1,131 @@
+use pgt_schema_cache::PostgresType;
+
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::{CompletionContext, CompositeFieldAccess},
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::find_mentioned_column;
+
+pub fn complete_fields<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    let composite = match ctx
+        .composite_field_access
+        .as_ref()
+        .and_then(|access| resolve_composite_type(ctx, access))
+    {
+        Some(t) => t,
+        None => return,
+    };
+
+    for field in &composite.attributes.attrs {
+        let relevance = CompletionRelevanceData::Field(field);
+
+        let field_type = ctx
+            .schema_cache
+            .find_type_by_id(field.type_id)
+            .map(|t| t.format.as_str())
+            .unwrap_or("unknown");
+
+        let item = PossibleCompletionItem {
+            label: field.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("{}: {}", composite.name, field_type),
+            kind: CompletionItemKind::Field,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+/// Finds the composite type of the accessed column, preferring columns of the
+/// qualifying table and of the tables mentioned in the statement.
+fn resolve_composite_type<'a>(
+    ctx: &CompletionContext<'a>,
+    access: &CompositeFieldAccess,
+) -> Option<&'a PostgresType> {
+    // the fields are still worth offering before the table is mentioned, e.g. `select (home).|`
+    let column =
+        find_mentioned_column(ctx, access.qualifier.as_ref(), &access.column).or_else(|| {
+            ctx.schema_cache
+                .columns
+                .iter()
+                .find(|c| c.name == access.column)
+        })?;
+
+    let mut column_type = ctx.schema_cache.find_type_by_id(column.type_id)?;
+
+    if access.is_subscripted {
+        column_type = ctx
+            .schema_cache
+            .find_type_by_id(column_type.element_type_id?)?;
+    }
+
+    Some(column_type).filter(|t| !t.attributes.attrs.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    static SETUP: &str = r#"
+        create type address as (
+            street text,
+            city text
+        );
+
+        create table users (
+            id serial primary key,
+            home address,
+            addresses address[]
+        );
+    "#;
+
+    #[tokio::test]
+    async fn suggests_fields_of_composite_column() {
+        let query = format!("select (home).{} from users;", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(SETUP, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert_eq!(labels, vec!["city", "street"]);
+        assert!(items.iter().all(|i| i.kind == CompletionItemKind::Field));
+    }
+
+    #[tokio::test]
+    async fn suggests_fields_of_unmentioned_composite_column() {
+        let query = format!("select (home).{}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(SETUP, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert_eq!(labels, vec!["city", "street"]);
+    }
+
+    #[tokio::test]
+    async fn suggests_fields_of_composite_array_element() {
+        let query = format!("select addresses[1].{} from users;", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(SETUP, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert!(labels.contains(&"street"));
+        assert!(labels.contains(&"city"));
+    }
+}
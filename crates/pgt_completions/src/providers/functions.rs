@@ -5,7 +5,9 @@ use crate::{
     relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
 };
 
-use super::helper::get_completion_text_with_schema;
+use super::helper::{
+    get_completion_text_for_quoted_identifier, get_completion_text_with_schema, get_documentation,
+};
 
 pub fn complete_functions<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
     let available_functions = &ctx.schema_cache.functions;
@@ -19,7 +21,9 @@ pub fn complete_functions<'a>(ctx: &'a CompletionContext, builder: &mut Completi
             filter: CompletionFilter::from(relevance),
             description: format!("Schema: {}", func.schema),
             kind: CompletionItemKind::Function,
-            completion_text: get_completion_text_with_schema(ctx, &func.name, &func.schema),
+            completion_text: get_completion_text_for_quoted_identifier(ctx, &func.name)
+                .or_else(|| get_completion_text_with_schema(ctx, &func.name, &func.schema)),
+            documentation: get_documentation(func.comment.as_ref()),
         };
 
         builder.add_item(item);
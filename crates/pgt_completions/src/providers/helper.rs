@@ -1,7 +1,67 @@
+use std::borrow::Cow;
+
+use pgt_schema_cache::Column;
 use pgt_text_size::{TextRange, TextSize};
 
 use crate::{CompletionText, context::CompletionContext};
 
+/// If the user started typing a double-quoted identifier, e.g. `select "na|`,
+/// the completion replaces the whole identifier, including its quotes.
+pub(crate) fn get_completion_text_for_quoted_identifier(
+    ctx: &CompletionContext,
+    item_name: &str,
+) -> Option<CompletionText> {
+    ctx.quoted_identifier_range.map(|range| CompletionText {
+        text: format!("\"{}\"", item_name),
+        range,
+    })
+}
+
+/// Identifiers that are not all lowercase must be quoted, e.g. the collation `"C"`.
+pub(crate) fn get_completion_text_for_case_sensitive_identifier(
+    ctx: &CompletionContext,
+    item_name: &str,
+) -> Option<CompletionText> {
+    if ctx.quoted_identifier_range.is_some() {
+        return get_completion_text_for_quoted_identifier(ctx, item_name);
+    }
+
+    if item_name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+    {
+        return None;
+    }
+
+    let node = ctx.node_under_cursor?;
+
+    Some(CompletionText {
+        text: format!("\"{}\"", item_name),
+        range: TextRange::new(
+            TextSize::try_from(node.start_byte()).unwrap(),
+            TextSize::try_from(node.end_byte()).unwrap(),
+        ),
+    })
+}
+
+/// Quotes `name` if it is not a plain lowercase identifier, like `quote_ident` does,
+/// e.g. `"Event IDs"` for `Event IDs`.
+pub(crate) fn quote_ident(name: &str) -> Cow<'_, str> {
+    let is_plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '$');
+
+    if is_plain {
+        Cow::Borrowed(name)
+    } else {
+        Cow::Owned(format!("\"{}\"", name.replace('"', "\"\"")))
+    }
+}
+
 pub(crate) fn get_completion_text_with_schema(
     ctx: &CompletionContext,
     item_name: &str,
@@ -23,3 +83,35 @@ pub(crate) fn get_completion_text_with_schema(
         })
     }
 }
+
+/// The documentation of an object is its comment. A blank comment documents nothing.
+pub(crate) fn get_documentation(comment: Option<&String>) -> Option<String> {
+    comment.filter(|c| !c.trim().is_empty()).cloned()
+}
+
+/// Finds the column named `column` that belongs to the table `qualifier` or, as the qualifier
+/// may be an alias, to one of the tables mentioned in the statement.
+pub(crate) fn find_mentioned_column<'a>(
+    ctx: &CompletionContext<'a>,
+    qualifier: Option<&String>,
+    column: &str,
+) -> Option<&'a Column> {
+    let candidates: Vec<&Column> = ctx
+        .schema_cache
+        .columns
+        .iter()
+        .filter(|c| c.name == column)
+        .collect();
+
+    let is_mentioned = |c: &&&Column| {
+        ctx.mentioned_relations
+            .values()
+            .any(|tables| tables.contains(&c.table_name))
+    };
+
+    candidates
+        .iter()
+        .find(|c| qualifier == Some(&c.table_name))
+        .or_else(|| candidates.iter().find(is_mentioned))
+        .copied()
+}
@@ -0,0 +1,815 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::{
+        ClauseType, CompletionContext, CopyPosition, ExtensionPosition, FunctionDefinitionPosition,
+        GeneratedPosition, GrantPosition, SettingPosition, TriggerPosition, WrappingNode,
+        first_leaf,
+    },
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+/// Keywords that make up the frame clause of a window specification,
+/// e.g. `over (order by id rows between unbounded preceding and current row)`.
+const WINDOW_FRAME_KEYWORDS: &[&str] = &[
+    "rows",
+    "range",
+    "groups",
+    "between",
+    "unbounded",
+    "preceding",
+    "following",
+    "current",
+];
+
+/// Keywords that may trail the objects of a statement, keyed by the first token
+/// of the statement, e.g. `drop table users cascade`.
+const TRAILING_KEYWORDS: &[(&str, &[&str])] = &[
+    ("keyword_drop", &["cascade", "restrict"]),
+    ("keyword_truncate", &["cascade", "restrict"]),
+];
+
+const TABLE_PRIVILEGES: &[&str] = &[
+    "select",
+    "insert",
+    "update",
+    "delete",
+    "truncate",
+    "references",
+    "trigger",
+    "all",
+];
+
+const SEQUENCE_PRIVILEGES: &[&str] = &["usage", "select", "update", "all"];
+
+const ROUTINE_PRIVILEGES: &[&str] = &["execute", "all"];
+
+const SCHEMA_PRIVILEGES: &[&str] = &["usage", "create", "all"];
+
+const DATABASE_PRIVILEGES: &[&str] = &["create", "connect", "temporary", "all"];
+
+/// Offered if the kind of the object is not known (yet).
+const ALL_PRIVILEGES: &[&str] = &[
+    "select",
+    "insert",
+    "update",
+    "delete",
+    "truncate",
+    "references",
+    "trigger",
+    "usage",
+    "execute",
+    "create",
+    "connect",
+    "temporary",
+    "all",
+];
+
+const TRIGGER_TIMINGS: &[&str] = &["before", "after", "instead of"];
+
+const TRIGGER_EVENTS: &[&str] = &["insert", "update", "delete", "truncate"];
+
+/// The kinds of generated columns, e.g. `id int generated always as identity`
+/// or `total int generated always as (price * amount) stored`.
+///
+/// The expression of a generated column is left to the user, `stored` is offered after it.
+const GENERATED_KINDS: &[&str] = &["always", "by default", "always as ("];
+
+/// Keywords that combine the conditions of a `check` constraint on the columns being defined,
+/// e.g. `create table t (a int, b int, check (a is not null or b > 0))`.
+const CHECK_EXPRESSION_KEYWORDS: &[&str] = &["and", "or", "not", "is", "null"];
+
+/// Options that may follow the name of the extension in `create extension`.
+const EXTENSION_OPTIONS: &[&str] = &["with schema", "schema", "version", "cascade"];
+
+/// Options of the parenthesized option list of `copy`, e.g. `copy users to stdout with (format csv)`.
+const COPY_OPTIONS: &[&str] = &[
+    "format",
+    "freeze",
+    "delimiter",
+    "null",
+    "default",
+    "header",
+    "quote",
+    "escape",
+    "force_quote",
+    "force_not_null",
+    "force_null",
+    "on_error",
+    "encoding",
+];
+
+const COPY_FORMATS: &[&str] = &["text", "csv", "binary"];
+
+/// Sampling methods that are always available in a `TABLESAMPLE` clause.
+const BUILTIN_TABLESAMPLE_METHODS: &[&str] = &["bernoulli", "system"];
+
+/// Languages that are always available, even if they are not part of the schema cache.
+const BUILTIN_LANGUAGES: &[&str] = &["sql", "plpgsql", "c"];
+
+/// Attributes that may follow the signature of a function,
+/// e.g. `create function f() returns int language sql immutable as 'select 1'`.
+const FUNCTION_ATTRIBUTES: &[&str] = &[
+    "immutable",
+    "stable",
+    "volatile",
+    "strict",
+    "called on null input",
+    "returns null on null input",
+    "security definer",
+    "security invoker",
+    "leakproof",
+    "not leakproof",
+    "parallel safe",
+    "parallel restricted",
+    "parallel unsafe",
+    "language",
+    "cost",
+    "rows",
+];
+
+pub fn complete_keywords<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    for keyword in keywords_for_context(ctx) {
+        let relevance = CompletionRelevanceData::Keyword(keyword);
+
+        let item = PossibleCompletionItem {
+            label: keyword.to_string(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Keyword".into(),
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+fn keywords_for_context<'a>(ctx: &CompletionContext<'a>) -> Vec<&'a str> {
+    if let Some(WrappingNode::WindowSpecification) = ctx.wrapping_node_kind.as_ref() {
+        return WINDOW_FRAME_KEYWORDS.to_vec();
+    }
+
+    if ctx.get_grant_position() == Some(GrantPosition::Privileges) {
+        return privilege_keywords(ctx).to_vec();
+    }
+
+    // the sequence options and transaction modes are offered by their own providers
+    if ctx.is_identity_options_position() || ctx.get_transaction_position().is_some() {
+        return vec![];
+    }
+
+    if ctx.is_check_expression_position() {
+        return CHECK_EXPRESSION_KEYWORDS.to_vec();
+    }
+
+    if ctx.is_tablesample_method_position() {
+        return tablesample_method_keywords(ctx);
+    }
+
+    match ctx.get_extension_position() {
+        Some(ExtensionPosition::Name {
+            if_not_exists: false,
+        }) => return vec!["if not exists"],
+        Some(ExtensionPosition::Name {
+            if_not_exists: true,
+        }) => return vec![],
+        Some(ExtensionPosition::Options) => return EXTENSION_OPTIONS.to_vec(),
+        None => {}
+    }
+
+    match ctx.get_copy_position() {
+        Some(CopyPosition::Source) => return vec!["stdin", "program"],
+        Some(CopyPosition::Target) => return vec!["stdout", "program"],
+        Some(CopyPosition::AfterSource) => return vec!["with"],
+        Some(CopyPosition::Options) => return COPY_OPTIONS.to_vec(),
+        Some(CopyPosition::Format) => return COPY_FORMATS.to_vec(),
+        None => {}
+    }
+
+    // the star forms of a `returning` clause are offered along with its columns
+    if ctx.wrapping_clause_type == Some(ClauseType::Returning) {
+        return vec![];
+    }
+
+    match ctx.get_row_count_clause() {
+        Some(ClauseType::Limit) => return vec!["all"],
+        Some(_) => return vec![],
+        None => {}
+    }
+
+    match ctx.get_trigger_position() {
+        Some(TriggerPosition::Timing) => return TRIGGER_TIMINGS.to_vec(),
+        // views cannot be truncated
+        Some(TriggerPosition::Event { instead_of: true }) => return TRIGGER_EVENTS[..3].to_vec(),
+        Some(TriggerPosition::Event { instead_of: false }) => return TRIGGER_EVENTS.to_vec(),
+        Some(TriggerPosition::UpdateColumns { .. }) => return vec![],
+        None => {}
+    }
+
+    match ctx.get_generated_position() {
+        Some(GeneratedPosition::Kind) => return GENERATED_KINDS.to_vec(),
+        Some(GeneratedPosition::Always) => return vec!["as identity", "as ("],
+        Some(GeneratedPosition::ByDefault) => return vec!["as identity"],
+        Some(GeneratedPosition::AfterExpression) => return vec!["stored"],
+        None => {}
+    }
+
+    match ctx.get_setting_position() {
+        Some(SettingPosition::Assignment) => return vec!["to", "="],
+        Some(SettingPosition::Value(name)) => return setting_value_keywords(ctx, name),
+        _ => {}
+    }
+
+    // `only` excludes the tables inheriting from the named one
+    if ctx.is_table_reference_position() {
+        return match ctx.words_before_cursor.last() {
+            Some(w) if w == "only" => vec![],
+            _ => vec!["only"],
+        };
+    }
+
+    match ctx.get_function_definition_position() {
+        Some(FunctionDefinitionPosition::Language) => language_keywords(ctx),
+        Some(FunctionDefinitionPosition::Attributes) => FUNCTION_ATTRIBUTES.to_vec(),
+        None => trailing_keywords(ctx).to_vec(),
+    }
+}
+
+/// Every setting may be reset to its default, enums additionally offer their values.
+fn setting_value_keywords<'a>(ctx: &CompletionContext<'a>, name: &str) -> Vec<&'a str> {
+    let mut values = vec!["default"];
+
+    if let Some(enum_values) = ctx
+        .schema_cache
+        .settings
+        .iter()
+        .find(|s| s.name == name)
+        .and_then(|s| s.enum_values.as_ref())
+    {
+        values.extend(enum_values.iter().map(|v| v.as_str()));
+    }
+
+    values
+}
+
+/// The builtin sampling methods plus those installed in the database, e.g. by `tsm_system_rows`.
+/// Sampling methods are functions that return a `tsm_handler`.
+fn tablesample_method_keywords<'a>(ctx: &CompletionContext<'a>) -> Vec<&'a str> {
+    let mut methods = BUILTIN_TABLESAMPLE_METHODS.to_vec();
+
+    for function in &ctx.schema_cache.functions {
+        if function.return_type == "tsm_handler" && !methods.contains(&function.name.as_str()) {
+            methods.push(function.name.as_str());
+        }
+    }
+
+    methods
+}
+
+/// The builtin languages plus all languages installed in the database.
+fn language_keywords<'a>(ctx: &CompletionContext<'a>) -> Vec<&'a str> {
+    let mut languages = BUILTIN_LANGUAGES.to_vec();
+
+    for language in &ctx.schema_cache.languages {
+        if !languages.contains(&language.name.as_str()) {
+            languages.push(language.name.as_str());
+        }
+    }
+
+    languages
+}
+
+/// Privileges depend on the kind of the object they are granted on,
+/// e.g. `grant | on sequence user_ids to admin`.
+fn privilege_keywords(ctx: &CompletionContext) -> &'static [&'static str] {
+    let mut object_words = ctx
+        .words_after_cursor
+        .iter()
+        .skip_while(|w| *w != "on")
+        .skip(1)
+        // `on all tables in schema public`
+        .skip_while(|w| *w == "all");
+
+    match object_words.next().map(|w| w.as_str()) {
+        Some("sequence" | "sequences") => SEQUENCE_PRIVILEGES,
+        Some("function" | "functions" | "procedure" | "procedures" | "routine" | "routines") => {
+            ROUTINE_PRIVILEGES
+        }
+        Some("schema") => SCHEMA_PRIVILEGES,
+        Some("database") => DATABASE_PRIVILEGES,
+        // tables are the default object kind: `grant select on users to admin`
+        Some(_) => TABLE_PRIVILEGES,
+        None => ALL_PRIVILEGES,
+    }
+}
+
+fn trailing_keywords(ctx: &CompletionContext) -> &'static [&'static str] {
+    let statement = match ctx.get_statement_node() {
+        Some(s) => s,
+        None => return &[],
+    };
+
+    let leading_token = first_leaf(statement).kind();
+
+    let keywords = match TRAILING_KEYWORDS
+        .iter()
+        .find(|(token, _)| *token == leading_token)
+    {
+        Some((_, keywords)) => *keywords,
+        None => return &[],
+    };
+
+    // the user is still typing the name of an object
+    if ctx
+        .node_under_cursor
+        .is_some_and(|n| has_ancestor(n, "object_reference"))
+    {
+        return &[];
+    }
+
+    // trailing keywords can only follow the objects of the statement
+    if !has_object_reference_before(statement, ctx.position) {
+        return &[];
+    }
+
+    keywords
+}
+
+fn has_ancestor(node: tree_sitter::Node, kind: &str) -> bool {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if n.kind() == kind {
+            return true;
+        }
+        current = n.parent();
+    }
+    false
+}
+
+fn has_object_reference_before(node: tree_sitter::Node, position: usize) -> bool {
+    if node.kind() == "object_reference" {
+        return node.end_byte() < position;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if has_object_reference_before(child, position) {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_columns_in_window_partition_by() {
+        let setup = r#"
+            create table orders (
+                id serial primary key,
+                customer_id int,
+                amount int
+            );
+        "#;
+
+        let query = format!(
+            "select sum(amount) over (partition by cust{}) from orders;",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let best_match = items
+            .first()
+            .expect("Should return at least one completion item");
+
+        assert_eq!(best_match.label, "customer_id");
+        assert_eq!(best_match.kind, CompletionItemKind::Column);
+        assert!(
+            items.iter().all(|i| i.kind != CompletionItemKind::Table),
+            "Tables should not be suggested inside a window specification"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_columns_after_partition_by() {
+        let setup = r#"
+            create table orders (
+                id serial primary key,
+                customer_id int,
+                amount int
+            );
+        "#;
+
+        let query = format!(
+            "select sum(amount) over (partition by {}) from orders;",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let columns: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Column)
+            .map(|i| i.label.as_str())
+            .collect();
+
+        assert!(columns.contains(&"customer_id"));
+        assert!(columns.contains(&"amount"));
+    }
+
+    #[tokio::test]
+    async fn suggests_trailing_keywords_after_drop_table() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("drop table users {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let keywords: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Keyword)
+            .map(|i| i.label.as_str())
+            .collect();
+
+        assert!(keywords.contains(&"cascade"));
+        assert!(keywords.contains(&"restrict"));
+    }
+
+    #[tokio::test]
+    async fn suggests_privileges_after_grant() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("grant {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "select" && i.kind == CompletionItemKind::Keyword)
+        );
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Keyword),
+            "Only privileges should be suggested after `grant`"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_privileges_for_the_object_kind() {
+        let setup = r#"
+            create sequence user_ids;
+        "#;
+
+        let query = format!("revoke {} on sequence user_ids from public;", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert!(labels.contains(&"usage"));
+        assert!(!labels.contains(&"insert"));
+    }
+
+    #[tokio::test]
+    async fn suggests_objects_after_grant_on() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("grant select on {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "users" && i.kind == CompletionItemKind::Table)
+        );
+        assert!(
+            items
+                .iter()
+                .all(|i| i.kind != CompletionItemKind::Column
+                    && i.kind != CompletionItemKind::Keyword)
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_languages_after_language() {
+        let query = format!(
+            "create function answer() returns int language {}",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "plpgsql" && i.kind == CompletionItemKind::Keyword)
+        );
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Keyword),
+            "Only languages should be suggested after `language`"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_function_attributes_after_body() {
+        let query = format!(
+            "create function answer() returns int language sql as 'select 42' {}",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let keywords: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Keyword)
+            .map(|i| i.label.as_str())
+            .collect();
+
+        assert!(keywords.contains(&"immutable"));
+        assert!(keywords.contains(&"security definer"));
+    }
+
+    #[tokio::test]
+    async fn suggests_sampling_methods_after_tablesample() {
+        let setup = r#"
+            create table public.events (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * from events tablesample {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert!(labels.contains(&"bernoulli"));
+        assert!(labels.contains(&"system"));
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Keyword),
+            "Only sampling methods should be suggested after `tablesample`"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_copy_sources_and_options() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let cases = [
+            (format!("copy users from {}", CURSOR_POS), "stdin"),
+            (format!("copy users to {}", CURSOR_POS), "stdout"),
+            (format!("copy users from stdin {}", CURSOR_POS), "with"),
+            (
+                format!("copy users to stdout with ({}", CURSOR_POS),
+                "format",
+            ),
+            (
+                format!("copy users to stdout with (format {}", CURSOR_POS),
+                "csv",
+            ),
+        ];
+
+        for (query, expected) in cases {
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            assert!(
+                items.iter().any(|i| i.label == expected),
+                "Expected `{expected}` to be suggested for `{query}`"
+            );
+            assert!(
+                items.iter().all(|i| i.kind == CompletionItemKind::Keyword),
+                "Only keywords should be suggested for `{query}`"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn suggests_trigger_timing_and_events() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let labels = |items: Vec<crate::CompletionItem>| {
+            items.into_iter().map(|i| i.label).collect::<Vec<_>>()
+        };
+
+        let query = format!("create trigger t {} on users", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let timings = labels(complete(get_test_params(
+            &tree,
+            &cache,
+            query.as_str().into(),
+        )));
+
+        assert!(timings.contains(&"before".to_string()));
+        assert!(!timings.contains(&"insert".to_string()));
+
+        let query = format!("create trigger t before {} on users", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let events = labels(complete(get_test_params(
+            &tree,
+            &cache,
+            query.as_str().into(),
+        )));
+
+        assert!(events.contains(&"insert".to_string()));
+        assert!(!events.contains(&"before".to_string()));
+
+        let query = format!("create trigger t before update of {} on users", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let columns = labels(complete(get_test_params(
+            &tree,
+            &cache,
+            query.as_str().into(),
+        )));
+
+        assert!(columns.contains(&"name".to_string()));
+        assert!(!columns.contains(&"insert".to_string()));
+    }
+
+    #[tokio::test]
+    async fn suggests_no_objects_after_limit() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * from users limit {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Keyword),
+            "Only keywords should be suggested after `limit`, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+        assert!(items.iter().any(|i| i.label == "all"));
+    }
+
+    #[tokio::test]
+    async fn suggests_generated_column_kinds() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!(
+            "create table t (id int generated {} as identity)",
+            CURSOR_POS
+        );
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let labels: Vec<String> = complete(get_test_params(&tree, &cache, query.as_str().into()))
+            .into_iter()
+            .map(|i| i.label)
+            .collect();
+
+        assert!(labels.contains(&"always".to_string()));
+        assert!(labels.contains(&"by default".to_string()));
+        assert!(!labels.contains(&"users".to_string()));
+        assert!(labels.iter().all(|l| !l.contains("expr")));
+
+        let query = format!(
+            "create table t (price int, total int generated always as (price * 2) {})",
+            CURSOR_POS
+        );
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let labels: Vec<String> = complete(get_test_params(&tree, &cache, query.as_str().into()))
+            .into_iter()
+            .map(|i| i.label)
+            .collect();
+
+        assert_eq!(labels, vec!["stored".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn suggests_only_before_the_table_of_from() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * from {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "only" && i.kind == CompletionItemKind::Keyword),
+            "Expected `only` to be suggested before the table"
+        );
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "users" && i.kind == CompletionItemKind::Table),
+            "Tables should still be suggested"
+        );
+
+        let query = format!("select * from only {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items.iter().all(|i| i.label != "only"),
+            "`only` should not be suggested twice"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_operators_inside_check() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!(
+            "create table t (a int, b text, check (a is not null {}))",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let mut keywords: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Keyword)
+            .map(|i| i.label.as_str())
+            .collect();
+        keywords.sort();
+
+        assert_eq!(keywords, vec!["and", "is", "not", "null", "or"]);
+        assert!(
+            items.iter().all(|i| i.label != "users"),
+            "Tables should not be suggested inside a check expression"
+        );
+    }
+}
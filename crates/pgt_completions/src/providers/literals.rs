@@ -0,0 +1,73 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::{CompletionContext, ValuePosition},
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+const LITERALS: &[&str] = &["true", "false", "null"];
+
+pub fn complete_literals<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    let literals: &[&str] = match ctx.get_value_position() {
+        Some(ValuePosition::Comparison | ValuePosition::Default) => LITERALS,
+        // a column of an inserted row may explicitly take its default value
+        Some(ValuePosition::ValuesSlot) => &["true", "false", "null", "default"],
+        None => return,
+    };
+
+    for literal in literals {
+        let relevance = CompletionRelevanceData::Keyword(literal);
+
+        let item = PossibleCompletionItem {
+            label: literal.to_string(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Literal".into(),
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_literals_after_comparison() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                active boolean
+            );
+        "#;
+
+        let query = format!("select * from users where active = {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let literals: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Keyword)
+            .map(|i| i.label.as_str())
+            .collect();
+
+        assert!(literals.contains(&"true"));
+        assert!(literals.contains(&"false"));
+        assert!(literals.contains(&"null"));
+
+        let position = |label: &str| items.iter().position(|i| i.label == label).unwrap();
+        assert!(
+            position("active") < position("true"),
+            "Columns should be ranked above literals"
+        );
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+const META_COMMANDS: &[(&str, &str)] = &[
+    ("\\c", "Connect to a database"),
+    (
+        "\\conninfo",
+        "Display information about the current connection",
+    ),
+    ("\\d", "Describe a table, view, sequence or index"),
+    ("\\da", "List aggregates"),
+    ("\\db", "List tablespaces"),
+    ("\\dC", "List casts"),
+    ("\\dd", "Show object descriptions"),
+    ("\\dD", "List domains"),
+    ("\\des", "List foreign servers"),
+    ("\\det", "List foreign tables"),
+    ("\\df", "List functions"),
+    ("\\dF", "List text search configurations"),
+    ("\\dg", "List roles"),
+    ("\\di", "List indexes"),
+    ("\\dm", "List materialized views"),
+    ("\\dn", "List schemas"),
+    ("\\do", "List operators"),
+    ("\\dp", "List access privileges"),
+    ("\\ds", "List sequences"),
+    ("\\dt", "List tables"),
+    ("\\dT", "List data types"),
+    ("\\du", "List roles"),
+    ("\\dv", "List views"),
+    ("\\dx", "List extensions"),
+    ("\\dy", "List event triggers"),
+    ("\\echo", "Write a string to standard output"),
+    ("\\i", "Execute commands from a file"),
+    ("\\l", "List databases"),
+    ("\\set", "Set an internal variable"),
+    ("\\timing", "Toggle the timing of commands"),
+    ("\\x", "Toggle expanded output"),
+];
+
+/// Suggests psql meta-commands if the token under the cursor starts with a backslash, e.g. `\d|`.
+pub fn complete_meta_commands<'a>(
+    ctx: &CompletionContext<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) {
+    let Some(range) = ctx.meta_command_range else {
+        return;
+    };
+
+    // psql commands are case-sensitive, `\dt` lists tables while `\dT` lists types
+    let typed = &ctx.text[range];
+
+    for &(command, description) in META_COMMANDS
+        .iter()
+        .filter(|(command, _)| command.starts_with(typed))
+    {
+        let relevance = CompletionRelevanceData::MetaCommand(command);
+
+        let item = PossibleCompletionItem {
+            label: command.to_string(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: description.to_string(),
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_schema_cache::SchemaCache;
+    use pgt_text_size::{TextRange, TextSize};
+
+    use crate::{
+        complete,
+        test_helper::{CURSOR_POS, get_test_params},
+    };
+
+    #[test]
+    fn suggests_describe_commands() {
+        let query = format!("\\d{}", CURSOR_POS);
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        let tree = parser.parse("\\d", None).unwrap();
+
+        let cache = SchemaCache::default();
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let dt = items
+            .iter()
+            .find(|i| i.label == "\\dt")
+            .expect("Should suggest `\\dt`");
+
+        assert_eq!(dt.description, "List tables");
+        assert_eq!(
+            dt.completion_text.range,
+            TextRange::new(TextSize::new(0), TextSize::new(2))
+        );
+        assert!(
+            items.iter().all(|i| i.label.starts_with("\\d")),
+            "Only meta-commands should be suggested, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn does_not_suggest_meta_commands_in_strings() {
+        let query = format!("select E'first line \\n{}", CURSOR_POS);
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        let tree = parser.parse("select E'first line \\n", None).unwrap();
+
+        let cache = SchemaCache::default();
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items.iter().all(|i| !i.label.starts_with('\\')),
+            "An escape is not a meta-command, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+    }
+}
@@ -1,10 +1,52 @@
+mod aliases;
+mod collations;
 mod columns;
+mod constraints;
+mod ctes;
+mod defaults;
+mod enum_values;
+mod extensions;
+mod fields;
 mod functions;
 mod helper;
+mod keywords;
+mod literals;
+mod meta_commands;
+mod operator_classes;
+mod policies;
+mod returning;
 mod schemas;
+mod sequence_options;
+mod settings;
+mod storage_parameters;
 mod tables;
+mod time_zones;
+mod transactions;
+mod types;
+mod windows;
 
+pub use aliases::*;
+pub use collations::*;
 pub use columns::*;
+pub use constraints::*;
+pub use ctes::*;
+pub use defaults::*;
+pub use enum_values::*;
+pub use extensions::*;
+pub use fields::*;
 pub use functions::*;
+pub use keywords::*;
+pub use literals::*;
+pub use meta_commands::*;
+pub use operator_classes::*;
+pub use policies::*;
+pub use returning::*;
 pub use schemas::*;
+pub use sequence_options::*;
+pub use settings::*;
+pub use storage_parameters::*;
 pub use tables::*;
+pub use time_zones::*;
+pub use transactions::*;
+pub use types::*;
+pub use windows::*;
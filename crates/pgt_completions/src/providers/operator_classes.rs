@@ -0,0 +1,105 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+pub fn complete_operator_classes<'a>(
+    ctx: &CompletionContext<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) {
+    if ctx.get_operator_class_position().is_none() {
+        return;
+    }
+
+    for opclass in &ctx.schema_cache.operator_classes {
+        let relevance = CompletionRelevanceData::OperatorClass(opclass);
+
+        let item = PossibleCompletionItem {
+            label: opclass.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("Operator class for {}", opclass.input_type),
+            kind: CompletionItemKind::OperatorClass,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_operator_classes_for_gin_indexes() {
+        let setup = r#"
+            create extension if not exists pg_trgm;
+
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("create index on users using gin (name {})", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "gin_trgm_ops" && i.kind == CompletionItemKind::OperatorClass)
+        );
+        // btree operator classes do not apply to gin indexes
+        assert!(items.iter().all(|i| i.label != "text_pattern_ops"));
+    }
+
+    #[tokio::test]
+    async fn only_suggests_operator_classes_for_the_type_of_the_column() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name varchar(255)
+            );
+        "#;
+
+        // (indexed column, offered, not offered)
+        let cases = [
+            ("(id", "int4_ops", Some("text_pattern_ops")),
+            // varchar columns use the operator classes of text
+            ("(name", "text_pattern_ops", Some("int4_ops")),
+            // all operator classes of the access method might apply to an unknown column
+            ("using gin (email", "jsonb_ops", None),
+            ("using gin (email", "array_ops", None),
+        ];
+
+        for (column, offered, not_offered) in cases {
+            let query = format!("create index on users {column} {}", CURSOR_POS);
+
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+            let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+            assert!(
+                labels.contains(&offered),
+                "Expected `{offered}` for `{query}`, got {labels:?}"
+            );
+            if let Some(not_offered) = not_offered {
+                assert!(
+                    !labels.contains(&not_offered),
+                    "Did not expect `{not_offered}` for `{query}`"
+                );
+            }
+        }
+    }
+}
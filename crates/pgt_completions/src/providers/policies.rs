@@ -0,0 +1,77 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::get_completion_text_for_quoted_identifier;
+
+pub fn complete_policies<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    if ctx.get_policy_position().is_none() {
+        return;
+    }
+
+    for policy in &ctx.schema_cache.policies {
+        let relevance = CompletionRelevanceData::Policy(policy);
+
+        let item = PossibleCompletionItem {
+            label: policy.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("Policy on {}.{}", policy.schema, policy.table_name),
+            kind: CompletionItemKind::Policy,
+            completion_text: get_completion_text_for_quoted_identifier(ctx, &policy.name),
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_policies_of_the_altered_table() {
+        let setup = r#"
+            create table public.users (
+                id serial primary key,
+                name text
+            );
+
+            create table public.posts (
+                id serial primary key,
+                title text
+            );
+
+            alter table public.users enable row level security;
+            alter table public.posts enable row level security;
+
+            create policy users_select on public.users for select using (true);
+            create policy posts_select on public.posts for select using (true);
+        "#;
+
+        let query = format!("alter policy {} on users", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items
+            .iter()
+            .filter(|i| i.kind == CompletionItemKind::Policy)
+            .map(|i| i.label.as_str())
+            .collect();
+
+        assert_eq!(labels, vec!["users_select"]);
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Policy),
+            "Only policies should be suggested after `alter policy`"
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+/// Offers `*` and `table.*` for the target of the statement in a `RETURNING` clause,
+/// e.g. `update users set name = 'a' returning |`.
+pub fn complete_returning<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
+    let Some(target) = ctx.returning_table.as_ref() else {
+        return;
+    };
+
+    let table = ctx
+        .schema_cache
+        .tables
+        .iter()
+        .find(|t| target.is(&t.schema, &t.name));
+
+    let mut forms = vec![(
+        "*".to_string(),
+        "All columns".to_string(),
+        CompletionRelevanceData::AllColumns(None),
+    )];
+    if let Some(table) = table {
+        forms.push((
+            format!("{}.*", table.name),
+            format!("All columns of {}.{}", table.schema, table.name),
+            CompletionRelevanceData::AllColumns(Some(table)),
+        ));
+    }
+
+    for (label, description, relevance) in forms {
+        let item = PossibleCompletionItem {
+            label,
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description,
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_all_columns_in_returning_clause() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let queries = [
+            format!("delete from users where id = 1 returning {}", CURSOR_POS),
+            format!(
+                "update users set name = 'Bob' where id = 1 returning id, {}",
+                CURSOR_POS
+            ),
+        ];
+
+        for query in queries {
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+            // the star forms are ranked among the columns of the target
+            let top: Vec<&str> = labels.iter().take(4).copied().collect();
+            assert!(top.contains(&"*"), "{query}: {labels:?}");
+            assert!(top.contains(&"users.*"), "{query}: {labels:?}");
+            assert!(
+                items.iter().all(|i| i.kind != CompletionItemKind::Table),
+                "{query}: {labels:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn matches_the_schema_of_the_target() {
+        let setup = r#"
+            create schema auth;
+
+            create table public.users (
+                id serial primary key
+            );
+
+            create table auth.users (
+                id serial primary key,
+                email text
+            );
+        "#;
+
+        let query = format!("update auth.users set email = 'a' returning {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let star = items
+            .iter()
+            .find(|i| i.label == "users.*")
+            .expect("Expected the star form of the target");
+        assert_eq!(star.description, "All columns of auth.users");
+    }
+}
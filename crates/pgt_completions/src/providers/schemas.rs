@@ -4,7 +4,14 @@ use crate::{
     relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
 };
 
+use super::helper::get_completion_text_for_quoted_identifier;
+
 pub fn complete_schemas<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
+    // the user already qualified the object with a schema, e.g. `public.|`
+    if ctx.schema_name.is_some() {
+        return;
+    }
+
     let available_schemas = &ctx.schema_cache.schemas;
 
     for schema in available_schemas {
@@ -16,7 +23,8 @@ pub fn complete_schemas<'a>(ctx: &'a CompletionContext, builder: &mut Completion
             kind: crate::CompletionItemKind::Schema,
             score: CompletionScore::from(relevance.clone()),
             filter: CompletionFilter::from(relevance),
-            completion_text: None,
+            completion_text: get_completion_text_for_quoted_identifier(ctx, &schema.name),
+            documentation: None,
         };
 
         builder.add_item(item);
@@ -27,8 +35,11 @@ pub fn complete_schemas<'a>(ctx: &'a CompletionContext, builder: &mut Completion
 mod tests {
 
     use crate::{
-        CompletionItemKind,
-        test_helper::{CURSOR_POS, CompletionAssertion, assert_complete_results},
+        CompletionItemKind, complete,
+        test_helper::{
+            CURSOR_POS, CompletionAssertion, assert_complete_results, get_test_deps,
+            get_test_params,
+        },
     };
 
     #[tokio::test]
@@ -101,4 +112,91 @@ mod tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn does_not_suggest_schemas_after_schema_qualifier() {
+        let setup = r#"
+            create schema private;
+
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * from public.{}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .all(|item| item.kind != CompletionItemKind::Schema),
+            "Expected no schema completions after `public.`, got {:?}",
+            items
+                .iter()
+                .filter(|item| item.kind == CompletionItemKind::Schema)
+                .map(|item| &item.label)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_only_schemas_as_select_into_target() {
+        let setup = r#"
+            create schema private;
+
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * into {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "private" && i.kind == CompletionItemKind::Schema)
+        );
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Schema),
+            "Expected only schemas after `select * into`, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn excludes_system_schemas_if_disabled() {
+        let setup = r#"
+            create schema private;
+
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * from {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.include_system_schemas = false;
+        let items = complete(params);
+
+        assert!(items.iter().any(|item| item.label == "private"));
+        assert!(
+            items.iter().all(|item| item.label != "pg_catalog"
+                && !item.description.contains("pg_catalog")
+                && item.label != "information_schema"),
+            "Expected no system schema completions, got {:?}",
+            items.iter().map(|item| &item.label).collect::<Vec<_>>()
+        );
+    }
 }
@@ -0,0 +1,108 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::{CompletionContext, IdentityOptionsPosition},
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+/// The options of the sequence behind an identity column,
+/// e.g. `id int generated always as identity (start with 10)`.
+const SEQUENCE_OPTIONS: &[&str] = &[
+    "start with",
+    "increment by",
+    "minvalue",
+    "maxvalue",
+    "no minvalue",
+    "no maxvalue",
+    "cache",
+    "cycle",
+    "no cycle",
+    "as",
+    "sequence name",
+];
+
+/// The options that can be turned off, e.g. `(no cycle)`.
+const NEGATABLE_OPTIONS: &[&str] = &["minvalue", "maxvalue", "cycle"];
+
+pub fn complete_sequence_options<'a>(
+    ctx: &CompletionContext<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) {
+    let options = match ctx.get_identity_options_position() {
+        Some(IdentityOptionsPosition::Option) => SEQUENCE_OPTIONS,
+        Some(IdentityOptionsPosition::AfterNo) => NEGATABLE_OPTIONS,
+        None => return,
+    };
+
+    for option in options {
+        let relevance = CompletionRelevanceData::Keyword(option);
+
+        let item = PossibleCompletionItem {
+            label: option.to_string(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Sequence option".into(),
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_sequence_options_of_identity_columns() {
+        let queries = [
+            format!(
+                "create table users (id int generated always as identity ({}))",
+                CURSOR_POS
+            ),
+            format!(
+                "create table users (id int generated by default as identity (start with 10 {}))",
+                CURSOR_POS
+            ),
+        ];
+
+        for query in queries {
+            let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+            assert!(labels.contains(&"start with"), "{query}: {labels:?}");
+            assert!(labels.contains(&"increment by"), "{query}: {labels:?}");
+            assert!(
+                items.iter().all(|i| i.description == "Sequence option"),
+                "Only sequence options belong into the options, got {labels:?}"
+            );
+        }
+
+        // the value of an option is not an option
+        let query = format!(
+            "create table users (id int generated always as identity (start with {}))",
+            CURSOR_POS
+        );
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+        assert!(!items.iter().any(|i| i.label == "start with"));
+
+        // only some options can be turned off
+        let query = format!(
+            "create table users (id int generated always as identity (no {}))",
+            CURSOR_POS
+        );
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+        let mut labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+        labels.sort();
+        assert_eq!(labels, vec!["cycle", "maxvalue", "minvalue"]);
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+pub fn complete_settings<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    for setting in &ctx.schema_cache.settings {
+        let relevance = CompletionRelevanceData::Setting(setting);
+
+        let item = PossibleCompletionItem {
+            label: setting.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("Setting: {}", setting.description),
+            kind: CompletionItemKind::Setting,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_settings_after_set() {
+        let query = format!("set {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "search_path" && i.kind == CompletionItemKind::Setting)
+        );
+        assert!(
+            items.iter().all(|i| i.kind == CompletionItemKind::Setting),
+            "Only settings should be suggested after `set`"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_enum_values_of_a_setting() {
+        let query = format!("set client_min_messages to {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert!(labels.contains(&"warning"));
+        assert!(labels.contains(&"default"));
+    }
+
+    #[tokio::test]
+    async fn suggests_no_assignment_after_role_commands() {
+        for query in [
+            format!("set role {}", CURSOR_POS),
+            format!("set local role {}", CURSOR_POS),
+            format!("set session authorization {}", CURSOR_POS),
+        ] {
+            let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+            assert!(
+                !labels.contains(&"to") && !labels.contains(&"="),
+                "`{query}` takes a role, got {labels:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+/// The storage parameters of a table, e.g. `create table users (id int) with (fillfactor = 70)`.
+const STORAGE_PARAMETERS: &[&str] = &[
+    "fillfactor",
+    "toast_tuple_target",
+    "parallel_workers",
+    "autovacuum_enabled",
+    "vacuum_index_cleanup",
+    "vacuum_truncate",
+    "autovacuum_vacuum_threshold",
+    "autovacuum_vacuum_scale_factor",
+    "autovacuum_vacuum_insert_threshold",
+    "autovacuum_vacuum_insert_scale_factor",
+    "autovacuum_analyze_threshold",
+    "autovacuum_analyze_scale_factor",
+    "autovacuum_vacuum_cost_delay",
+    "autovacuum_vacuum_cost_limit",
+    "autovacuum_freeze_min_age",
+    "autovacuum_freeze_max_age",
+    "autovacuum_freeze_table_age",
+    "autovacuum_multixact_freeze_min_age",
+    "autovacuum_multixact_freeze_max_age",
+    "autovacuum_multixact_freeze_table_age",
+    "log_autovacuum_min_duration",
+    "user_catalog_table",
+];
+
+pub fn complete_storage_parameters<'a>(
+    ctx: &CompletionContext<'a>,
+    builder: &mut CompletionBuilder<'a>,
+) {
+    if !ctx.is_storage_parameter_position() {
+        return;
+    }
+
+    for parameter in STORAGE_PARAMETERS {
+        let relevance = CompletionRelevanceData::Keyword(parameter);
+
+        let item = PossibleCompletionItem {
+            label: parameter.to_string(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Storage parameter".into(),
+            kind: CompletionItemKind::Parameter,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_storage_parameters_in_with_options() {
+        let query = format!(
+            "create table users (id int, name text) with ({})",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "fillfactor" && i.kind == CompletionItemKind::Parameter)
+        );
+        assert!(
+            items
+                .iter()
+                .all(|i| i.kind == CompletionItemKind::Parameter),
+            "Only storage parameters belong into the options, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+    }
+}
@@ -5,7 +5,9 @@ use crate::{
     relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
 };
 
-use super::helper::get_completion_text_with_schema;
+use super::helper::{
+    get_completion_text_for_quoted_identifier, get_completion_text_with_schema, get_documentation,
+};
 
 pub fn complete_tables<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
     let available_tables = &ctx.schema_cache.tables;
@@ -19,7 +21,9 @@ pub fn complete_tables<'a>(ctx: &'a CompletionContext, builder: &mut CompletionB
             filter: CompletionFilter::from(relevance),
             description: format!("Schema: {}", table.schema),
             kind: CompletionItemKind::Table,
-            completion_text: get_completion_text_with_schema(ctx, &table.name, &table.schema),
+            completion_text: get_completion_text_for_quoted_identifier(ctx, &table.name)
+                .or_else(|| get_completion_text_with_schema(ctx, &table.name, &table.schema)),
+            documentation: get_documentation(table.comment.as_ref()),
         };
 
         builder.add_item(item);
@@ -29,8 +33,10 @@ pub fn complete_tables<'a>(ctx: &'a CompletionContext, builder: &mut CompletionB
 #[cfg(test)]
 mod tests {
 
+    use pgt_text_size::{TextRange, TextSize};
+
     use crate::{
-        CompletionItem, CompletionItemKind, complete,
+        CompletionItem, CompletionItemKind, HiddenObjects, complete,
         test_helper::{
             CURSOR_POS, CompletionAssertion, assert_complete_results, assert_no_complete_results,
             get_test_deps, get_test_params,
@@ -64,6 +70,33 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn replaces_the_typed_prefix() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * from us{}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let users = items
+            .into_iter()
+            .find(|i| i.label == "users")
+            .expect("Should suggest the `users` table");
+
+        assert_eq!(users.completion_text.text, "users");
+        assert_eq!(
+            users.completion_text.range,
+            TextRange::new(TextSize::new(14), TextSize::new(16))
+        );
+    }
+
     #[tokio::test]
     async fn autocompletes_table_alphanumerically() {
         let setup = r#"
@@ -273,4 +306,131 @@ mod tests {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn suggests_parent_tables_in_create_table() {
+        let setup = r#"
+            create table public.animals (
+                id serial primary key,
+                name text
+            );
+
+            create table public.measurements (
+                logged_at date not null,
+                value int
+            ) partition by range (logged_at);
+        "#;
+
+        let query = format!("create table dogs () inherits ({})", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "animals" && i.kind == CompletionItemKind::Table)
+        );
+        assert!(
+            items.iter().all(
+                |i| i.kind == CompletionItemKind::Table || i.kind == CompletionItemKind::Schema
+            ),
+            "Only tables should be suggested as parents"
+        );
+
+        let query = format!("create table measurements_2025 partition of {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        let best_match = items
+            .first()
+            .expect("Should return at least one completion item");
+
+        assert_eq!(best_match.label, "measurements");
+        assert_eq!(best_match.kind, CompletionItemKind::Table);
+    }
+
+    #[tokio::test]
+    async fn does_not_suggest_hidden_tables() {
+        let setup = r#"
+            create table users (id serial primary key);
+            create table _deprecated_users (id serial primary key);
+            create table accounts (id serial primary key);
+            comment on table accounts is '@internal';
+        "#;
+
+        let query = format!("select * from {}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+        assert!(items.iter().any(|i| i.label == "_deprecated_users"));
+
+        let hidden = HiddenObjects::new(["_deprecated*"], Some("@internal".into())).unwrap();
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.hidden_objects = Some(&hidden);
+        let items = complete(params);
+
+        assert!(items.iter().any(|i| i.label == "users"));
+        assert!(!items.iter().any(|i| i.label == "_deprecated_users"));
+        assert!(!items.iter().any(|i| i.label == "accounts"));
+    }
+
+    #[tokio::test]
+    async fn documents_objects_with_their_comments() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                email text
+            );
+            create table accounts (
+                id serial primary key
+            );
+            create function get_users() returns setof users language sql as 'select * from users';
+
+            comment on table users is 'All registered users';
+            comment on column users.email is 'Used for logging in';
+            comment on table accounts is '   ';
+            comment on function get_users() is 'Lists the users';
+        "#;
+
+        let documentation = |items: &[CompletionItem], label: &str| {
+            items
+                .iter()
+                .find(|i| i.label == label)
+                .map(|i| i.documentation.clone())
+        };
+
+        let query = format!("select * from {}", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        assert_eq!(
+            documentation(&items, "users"),
+            Some(Some("All registered users".into()))
+        );
+        // a blank comment documents nothing
+        assert_eq!(documentation(&items, "accounts"), Some(None));
+
+        let query = format!("select em{} from users", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        assert_eq!(
+            documentation(&items, "email"),
+            Some(Some("Used for logging in".into()))
+        );
+
+        let query = format!("select get_us{}", CURSOR_POS);
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        assert_eq!(
+            documentation(&items, "get_users"),
+            Some(Some("Lists the users".into()))
+        );
+    }
 }
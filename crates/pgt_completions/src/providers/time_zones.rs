@@ -0,0 +1,76 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+pub fn complete_time_zones<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    if ctx.time_zone_range.is_none() {
+        return;
+    }
+
+    for time_zone in &ctx.schema_cache.time_zones {
+        let relevance = CompletionRelevanceData::TimeZone(time_zone);
+
+        let item = PossibleCompletionItem {
+            label: time_zone.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!(
+                "Time zone {} (UTC {})",
+                time_zone.abbrev, time_zone.utc_offset
+            ),
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_time_zones_after_at_time_zone() {
+        let query = format!("select now() at time zone '{}'", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "UTC" && i.kind == CompletionItemKind::Keyword),
+            "Expected `UTC` to be suggested"
+        );
+        assert!(
+            items.iter().all(|i| i.description.starts_with("Time zone")),
+            "Only time zones should be suggested inside the literal"
+        );
+    }
+
+    #[tokio::test]
+    async fn suggests_time_zones_after_literals_with_escaped_quotes() {
+        let query = format!(
+            r"select 'it''s', E'don\'t', now() at time zone '{}'",
+            CURSOR_POS
+        );
+
+        let (tree, cache) = get_test_deps("", query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let items = complete(params);
+
+        assert!(
+            items.iter().any(|i| i.label == "UTC"),
+            "Expected `UTC` to be suggested"
+        );
+    }
+}
@@ -0,0 +1,165 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::{CompletionContext, TransactionPosition},
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+/// The modes of a transaction, e.g. `begin read only` or `set transaction isolation level serializable`.
+const TRANSACTION_MODES: &[&str] = &[
+    "isolation level",
+    "read write",
+    "read only",
+    "deferrable",
+    "not deferrable",
+];
+
+const ISOLATION_LEVELS: &[&str] = &[
+    "serializable",
+    "repeatable read",
+    "read committed",
+    "read uncommitted",
+];
+
+/// The options of `commit`, `end`, `abort` and `rollback`.
+const END_OPTIONS: &[&str] = &["and chain", "and no chain", "work", "transaction"];
+
+pub fn complete_transactions<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
+    let Some(position) = ctx.get_transaction_position() else {
+        return;
+    };
+
+    let keywords: Vec<&str> = match position {
+        TransactionPosition::Modes { after_begin } => {
+            let mut keywords = TRANSACTION_MODES.to_vec();
+            if after_begin {
+                keywords.extend(["work", "transaction"]);
+            }
+            keywords
+        }
+        TransactionPosition::IsolationLevel => ISOLATION_LEVELS.to_vec(),
+        TransactionPosition::End { is_rollback } => {
+            let mut keywords = END_OPTIONS.to_vec();
+            if is_rollback {
+                keywords.push("to savepoint");
+            }
+            keywords
+        }
+        TransactionPosition::Savepoint {
+            after_savepoint_keyword,
+        } => {
+            if after_savepoint_keyword {
+                vec![]
+            } else {
+                vec!["savepoint"]
+            }
+        }
+    };
+
+    for keyword in keywords {
+        let relevance = CompletionRelevanceData::Keyword(keyword);
+
+        builder.add_item(PossibleCompletionItem {
+            label: keyword.to_string(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Transaction option".into(),
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        });
+    }
+
+    if !matches!(position, TransactionPosition::Savepoint { .. }) {
+        return;
+    }
+
+    for savepoint in &ctx.savepoints {
+        let relevance = CompletionRelevanceData::Keyword(savepoint);
+
+        builder.add_item(PossibleCompletionItem {
+            label: savepoint.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Savepoint".into(),
+            kind: CompletionItemKind::Keyword,
+            completion_text: None,
+            documentation: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_schema_cache::SchemaCache;
+
+    use crate::{
+        complete,
+        test_helper::{CURSOR_POS, get_test_params},
+    };
+
+    fn parse(sql: &str) -> tree_sitter::Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        parser.parse(sql, None).unwrap()
+    }
+
+    #[test]
+    fn suggests_transaction_modes() {
+        let query = format!("set transaction {}", CURSOR_POS);
+        let tree = parse("set transaction");
+        let cache = SchemaCache::default();
+
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert!(labels.contains(&"isolation level"), "{labels:?}");
+        assert!(labels.contains(&"read only"), "{labels:?}");
+        assert!(
+            !labels.contains(&"to"),
+            "`set transaction` is not a setting, got {labels:?}"
+        );
+    }
+
+    #[test]
+    fn suggests_declared_savepoints() {
+        let query = format!("rollback to {}", CURSOR_POS);
+        let tree = parse("rollback to");
+        let cache = SchemaCache::default();
+
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.preceding_statements = &["begin;", "savepoint before_import;"];
+        let items = complete(params);
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert!(labels.contains(&"before_import"), "{labels:?}");
+        assert!(labels.contains(&"savepoint"), "{labels:?}");
+    }
+
+    #[test]
+    fn only_suggests_savepoints_of_the_current_transaction() {
+        let query = format!("rollback to savepoint {}", CURSOR_POS);
+        let tree = parse("rollback to savepoint");
+        let cache = SchemaCache::default();
+
+        let mut params = get_test_params(&tree, &cache, query.as_str().into());
+        params.preceding_statements = &[
+            "begin;",
+            "savepoint committed_before;",
+            "commit;",
+            "begin;",
+            "-- savepoint in_comment\nselect 'savepoint in_string';",
+            "savepoint first;",
+            "savepoint second;",
+            "savepoint third;",
+            "rollback to second;",
+            "release savepoint second;",
+        ];
+        let items = complete(params);
+        let labels: Vec<&str> = items.iter().map(|i| i.label.as_str()).collect();
+
+        assert_eq!(labels, vec!["first"]);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::get_completion_text_with_schema;
+
+/// Suggests the built-in and user-defined types, e.g. `int4` after `select id::|`.
+pub fn complete_types<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    if !ctx.is_type_position() {
+        return;
+    }
+
+    // array types are suggested with their `[]` notation instead
+    let types = ctx
+        .schema_cache
+        .types
+        .iter()
+        .filter(|t| !(t.name.starts_with('_') && t.element_type_id.is_some()));
+
+    for t in types {
+        let relevance = CompletionRelevanceData::Type(t);
+
+        let kind = if !t.enums.values.is_empty() {
+            "Enum"
+        } else if !t.attributes.attrs.is_empty() {
+            "Composite type"
+        } else {
+            "Type"
+        };
+
+        // the built-in types are found without a schema
+        let completion_text = if t.schema == "pg_catalog" {
+            None
+        } else {
+            get_completion_text_with_schema(ctx, &t.name, &t.schema)
+        };
+
+        let item = PossibleCompletionItem {
+            label: t.name.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("{kind}: {}.{}", t.schema, t.name),
+            kind: CompletionItemKind::Type,
+            completion_text,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+/// Suggests the array variants of the known types, e.g. `int4[]` after `select id::int4|`.
+pub fn complete_array_types<'a>(ctx: &CompletionContext<'a>, builder: &mut CompletionBuilder<'a>) {
+    if !ctx.is_type_position() {
+        return;
+    }
+
+    // the array types of postgres are named after their element type with a leading underscore
+    let element_types = ctx
+        .schema_cache
+        .types
+        .iter()
+        .filter(|t| t.name.starts_with('_'))
+        .filter_map(|t| ctx.schema_cache.find_type_by_id(t.element_type_id?));
+
+    for element_type in element_types {
+        let relevance = CompletionRelevanceData::ArrayType(element_type);
+
+        let item = PossibleCompletionItem {
+            label: format!("{}[]", element_type.name),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: format!("Array of {}.{}", element_type.schema, element_type.name),
+            kind: CompletionItemKind::Type,
+            completion_text: None,
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    #[tokio::test]
+    async fn suggests_array_types_after_a_base_type() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let queries = [
+            format!("select id::int4{} from users;", CURSOR_POS),
+            format!("create table orders (tags tex{});", CURSOR_POS),
+        ];
+
+        for (query, base_type) in queries.iter().zip(["int4", "text"]) {
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            // the base type is offered next to its array type
+            for expected in [base_type.to_string(), format!("{base_type}[]")] {
+                assert!(
+                    items
+                        .iter()
+                        .any(|i| i.label == expected && i.kind == CompletionItemKind::Type),
+                    "Expected `{expected}` to be suggested for `{query}`, got {:?}",
+                    items.iter().map(|i| &i.label).collect::<Vec<_>>()
+                );
+            }
+            assert!(
+                items.iter().all(|i| i.kind == CompletionItemKind::Type),
+                "Only types should be suggested for `{query}`"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn suggests_types_as_cast_targets() {
+        let setup = r#"
+            create schema app;
+
+            create type mood as enum ('happy', 'sad');
+            create type app.address as (street text, city text);
+
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let queries = [
+            (format!("select id::int{} from users;", CURSOR_POS), "int4"),
+            (
+                format!("select now()::timestamp{};", CURSOR_POS),
+                "timestamptz",
+            ),
+            (
+                format!("select cast(name as moo{}) from users;", CURSOR_POS),
+                "mood",
+            ),
+            (
+                format!("select cast(name as addr{}) from users;", CURSOR_POS),
+                "address",
+            ),
+        ];
+
+        for (query, expected) in queries {
+            let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+            let params = get_test_params(&tree, &cache, query.as_str().into());
+            let items = complete(params);
+
+            let item = items
+                .iter()
+                .find(|i| i.label == expected && i.kind == CompletionItemKind::Type)
+                .unwrap_or_else(|| panic!("Expected `{expected}` to be suggested for `{query}`"));
+
+            // user-defined types outside of `public` are qualified with their schema
+            let qualified = item.completion_text.as_ref().map(|c| c.text.as_str());
+            match expected {
+                "address" => assert_eq!(qualified, Some("app.address")),
+                _ => assert_eq!(qualified, None),
+            }
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{
+    CompletionItemKind,
+    builder::{CompletionBuilder, PossibleCompletionItem},
+    context::CompletionContext,
+    relevance::{CompletionRelevanceData, filtering::CompletionFilter, scoring::CompletionScore},
+};
+
+use super::helper::get_completion_text_for_quoted_identifier;
+
+pub fn complete_windows<'a>(ctx: &'a CompletionContext, builder: &mut CompletionBuilder<'a>) {
+    for window in &ctx.named_windows {
+        let relevance = CompletionRelevanceData::Window(window);
+
+        let item = PossibleCompletionItem {
+            label: window.clone(),
+            score: CompletionScore::from(relevance.clone()),
+            filter: CompletionFilter::from(relevance),
+            description: "Window".into(),
+            kind: CompletionItemKind::Window,
+            completion_text: get_completion_text_for_quoted_identifier(ctx, window),
+            documentation: None,
+        };
+
+        builder.add_item(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_schema_cache::SchemaCache;
+
+    use crate::{
+        CompletionItemKind, complete,
+        test_helper::{CURSOR_POS, get_test_params},
+    };
+
+    #[test]
+    fn suggests_named_window_after_over() {
+        let query = format!(
+            "select sum(x) over {} from t window w as (order by x)",
+            CURSOR_POS
+        );
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(tree_sitter_sql::language())
+            .expect("Error loading sql language");
+        let tree = parser
+            .parse("select sum(x) over  from t window w as (order by x)", None)
+            .unwrap();
+        let cache = SchemaCache::default();
+
+        let items = complete(get_test_params(&tree, &cache, query.as_str().into()));
+
+        assert!(
+            items
+                .iter()
+                .any(|i| i.label == "w" && i.kind == CompletionItemKind::Window),
+            "Expected the named window to be suggested, got {:?}",
+            items.iter().map(|i| &i.label).collect::<Vec<_>>()
+        );
+    }
+}
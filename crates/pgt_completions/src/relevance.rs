@@ -1,10 +1,44 @@
+use std::borrow::Cow;
+
 pub(crate) mod filtering;
 pub(crate) mod scoring;
 
+/// Schemas that hold the objects of postgres itself rather than those of the user.
+pub(crate) const SYSTEM_SCHEMAS: &[&str] = &["pg_catalog", "information_schema", "pg_toast"];
+
 #[derive(Debug, Clone)]
 pub(crate) enum CompletionRelevanceData<'a> {
     Table(&'a pgt_schema_cache::Table),
     Function(&'a pgt_schema_cache::Function),
     Column(&'a pgt_schema_cache::Column),
     Schema(&'a pgt_schema_cache::Schema),
+    Keyword(&'a str),
+    Setting(&'a pgt_schema_cache::Setting),
+    /// The name of a common table expression of the current statement.
+    Cte(&'a str),
+    /// A field of a composite type.
+    Field(&'a pgt_schema_cache::PostgresTypeAttribute),
+    Collation(&'a pgt_schema_cache::Collation),
+    Extension(&'a pgt_schema_cache::Extension),
+    /// An alias for the table or column before the cursor.
+    Alias(&'a str),
+    /// An expression for the default of a column, e.g. `now()`.
+    DefaultExpression(Cow<'a, str>),
+    OperatorClass(&'a pgt_schema_cache::OperatorClass),
+    /// A psql meta-command, e.g. `\dt`.
+    MetaCommand(&'a str),
+    /// A window defined in the `WINDOW` clause of the current statement.
+    Window(&'a str),
+    /// A column defined by the `CREATE TABLE` statement the cursor is in.
+    DefinedColumn(&'a str),
+    Constraint(&'a pgt_schema_cache::Constraint),
+    Policy(&'a pgt_schema_cache::Policy),
+    TimeZone(&'a pgt_schema_cache::TimeZone),
+    /// An array of the given element type, e.g. `int4[]`.
+    ArrayType(&'a pgt_schema_cache::PostgresType),
+    Type(&'a pgt_schema_cache::PostgresType),
+    /// `*`, or `table.*` if a table is given, e.g. in a `RETURNING` clause.
+    AllColumns(Option<&'a pgt_schema_cache::Table>),
+    /// A label of the enum type of the column compared to a string literal.
+    EnumValue(&'a str),
 }
@@ -1,6 +1,11 @@
-use crate::context::{ClauseType, CompletionContext};
+use crate::context::{
+    ClauseType, CompletionContext, ExtensionPosition, FunctionDefinitionPosition, GrantPosition,
+    SettingPosition, TriggerPosition,
+};
 
-use super::CompletionRelevanceData;
+use pgt_schema_cache::{Column, OperatorClass};
+
+use super::{CompletionRelevanceData, SYSTEM_SCHEMAS};
 
 #[derive(Debug)]
 pub(crate) struct CompletionFilter<'a> {
@@ -15,10 +20,218 @@ impl<'a> From<CompletionRelevanceData<'a>> for CompletionFilter<'a> {
 
 impl CompletionFilter<'_> {
     pub fn is_relevant(&self, ctx: &CompletionContext) -> Option<()> {
+        self.check_system_schema(ctx)?;
+        self.check_hidden_object(ctx)?;
+
+        // only fields can follow the access of a composite value, e.g. `(address).|`
+        if ctx.composite_field_access.is_some() {
+            return matches!(self.data, CompletionRelevanceData::Field(_)).then_some(());
+        }
+
+        // aliases, defaults and meta-commands are only derived where they are grammatically expected
+        if matches!(
+            self.data,
+            CompletionRelevanceData::Alias(_)
+                | CompletionRelevanceData::DefaultExpression(_)
+                | CompletionRelevanceData::MetaCommand(_)
+        ) {
+            return Some(());
+        }
+
+        // the string literal of `at time zone '|'` holds the name of a time zone, and nothing else
+        let is_time_zone = matches!(self.data, CompletionRelevanceData::TimeZone(_));
+        if ctx.time_zone_range.is_some() || is_time_zone {
+            return (ctx.time_zone_range.is_some() && is_time_zone).then_some(());
+        }
+
+        // the string literal compared to a column holds a value of the column, e.g. an enum label
+        let is_enum_value = matches!(self.data, CompletionRelevanceData::EnumValue(_));
+        if ctx.compared_column.is_some() || is_enum_value {
+            return (ctx.compared_column.is_some() && is_enum_value).then_some(());
+        }
+
+        // only collations can follow `collate`, and they cannot appear anywhere else
+        let is_collation = matches!(self.data, CompletionRelevanceData::Collation(_));
+        let is_collation_position = ctx.is_collation_position();
+        if is_collation_position || is_collation {
+            return (is_collation_position && is_collation).then_some(());
+        }
+
+        // a `check` expression within `create table` references the columns being defined,
+        // combined by operators like `and` or `is not null`
+        let is_defined_column = matches!(self.data, CompletionRelevanceData::DefinedColumn(_));
+        if ctx.is_check_expression_position() {
+            return matches!(
+                self.data,
+                CompletionRelevanceData::DefinedColumn(_)
+                    | CompletionRelevanceData::Function(_)
+                    | CompletionRelevanceData::Keyword(_)
+            )
+            .then_some(());
+        } else if is_defined_column {
+            return None;
+        }
+
+        // `over |` references a window of the `window` clause, and only there
+        let is_window = matches!(self.data, CompletionRelevanceData::Window(_));
+        let is_window_position = ctx.is_window_reference_position();
+        if is_window_position || is_window {
+            return (is_window_position && is_window).then_some(());
+        }
+
+        // only available extensions can follow `create extension`, and only there
+        let is_extension = matches!(self.data, CompletionRelevanceData::Extension(_));
+        match ctx.get_extension_position() {
+            Some(ExtensionPosition::Name { .. }) => {
+                let is_keyword = matches!(self.data, CompletionRelevanceData::Keyword(_));
+                return (is_extension || is_keyword).then_some(());
+            }
+            Some(ExtensionPosition::Options) => {
+                return matches!(self.data, CompletionRelevanceData::Keyword(_)).then_some(());
+            }
+            None if is_extension => return None,
+            None => {}
+        }
+
+        // only operator classes can follow the column of an index, and they cannot appear anywhere else
+        let is_operator_class = matches!(self.data, CompletionRelevanceData::OperatorClass(_));
+        if let Some(position) = ctx.get_operator_class_position() {
+            return match self.data {
+                // any operator class of the access method might apply to an unknown column
+                CompletionRelevanceData::OperatorClass(opclass) => (opclass.access_method
+                    == position.access_method
+                    && position
+                        .column_type_id
+                        .is_none_or(|type_id| indexes_type(ctx, opclass, type_id)))
+                .then_some(()),
+                _ => None,
+            };
+        } else if is_operator_class {
+            return None;
+        }
+
+        // only types can be cast to or declare a column, and they cannot appear anywhere else
+        let is_type = matches!(
+            self.data,
+            CompletionRelevanceData::ArrayType(_) | CompletionRelevanceData::Type(_)
+        );
+        let is_type_position = ctx.is_type_position();
+        if is_type_position || is_type {
+            return (is_type_position && is_type).then_some(());
+        }
+
+        // `on conflict on constraint |` names a unique constraint of the insert target
+        let is_constraint = matches!(self.data, CompletionRelevanceData::Constraint(_));
+        if let Some(table) = ctx.get_conflict_constraint_table() {
+            return match self.data {
+                CompletionRelevanceData::Constraint(constraint) => (constraint.is_unique()
+                    && table.is(&constraint.schema, &constraint.table_name))
+                .then_some(()),
+                _ => None,
+            };
+        } else if is_constraint {
+            return None;
+        }
+
+        // `alter policy |` and `drop policy |` name an existing policy, of the given table if any
+        let is_policy = matches!(self.data, CompletionRelevanceData::Policy(_));
+        if let Some(position) = ctx.get_policy_position() {
+            return match self.data {
+                CompletionRelevanceData::Policy(policy) => position
+                    .table
+                    .as_ref()
+                    .is_none_or(|table| *table == policy.table_name)
+                    .then_some(()),
+                _ => None,
+            };
+        } else if is_policy {
+            return None;
+        }
+
+        // `inherits (|)` and `partition of |` name existing tables
+        if ctx.get_parent_table_position().is_some() {
+            return match self.data {
+                CompletionRelevanceData::Table(table) => ctx
+                    .schema_name
+                    .as_ref()
+                    .is_none_or(|schema| schema == &table.schema)
+                    .then_some(()),
+                CompletionRelevanceData::Schema(_) => ctx.schema_name.is_none().then_some(()),
+                _ => None,
+            };
+        }
+
+        // the source, target and options of `copy` are all keywords,
+        // just like the storage parameters of a table, the sequence options of an identity
+        // and the modes and savepoints of a transaction
+        if ctx.get_copy_position().is_some()
+            || ctx.is_storage_parameter_position()
+            || ctx.is_identity_options_position()
+            || ctx.get_transaction_position().is_some()
+        {
+            return matches!(self.data, CompletionRelevanceData::Keyword(_)).then_some(());
+        }
+
+        // the timing and events of a trigger are keywords, `update of |` lists the columns of its table
+        match ctx.get_trigger_position() {
+            Some(TriggerPosition::UpdateColumns { table }) => {
+                return match self.data {
+                    CompletionRelevanceData::Column(col) => table
+                        .as_ref()
+                        .is_none_or(|table| *table == col.table_name)
+                        .then_some(()),
+                    _ => None,
+                };
+            }
+            Some(_) => {
+                return matches!(self.data, CompletionRelevanceData::Keyword(_)).then_some(());
+            }
+            None => {}
+        }
+
+        // the row count of `limit` and `offset` is a number, `limit all` is the only keyword,
+        // and the kind of a generated column is spelled out in keywords
+        if ctx.get_row_count_clause().is_some() || ctx.get_generated_position().is_some() {
+            return matches!(self.data, CompletionRelevanceData::Keyword(_)).then_some(());
+        }
+
+        // `select * into |` names a new table, so only schemas to qualify it make sense
+        if ctx.is_select_into_target() {
+            let is_schema = matches!(self.data, CompletionRelevanceData::Schema(_));
+            return (is_schema && ctx.schema_name.is_none()).then_some(());
+        }
+
+        // `join b using (|)` only accepts the columns both joined relations have
+        if let Some((left, right)) = ctx.get_using_join_relations() {
+            return match self.data {
+                CompletionRelevanceData::Column(col) => (is_column_of(col, left)
+                    && ctx
+                        .schema_cache
+                        .columns
+                        .iter()
+                        .any(|c| c.name == col.name && is_column_of(c, right)))
+                .then_some(()),
+                _ => None,
+            };
+        }
+
+        // `count(*) filter (where |)` holds a predicate over the relations of the statement
+        if ctx.is_filter_where_position() {
+            return match self.data {
+                CompletionRelevanceData::Column(col) => is_mentioned(ctx, col).then_some(()),
+                CompletionRelevanceData::Function(_) => Some(()),
+                _ => None,
+            };
+        }
+
         self.completable_context(ctx)?;
         self.check_clause(ctx)?;
         self.check_invocation(ctx)?;
         self.check_mentioned_schema(ctx)?;
+        self.check_grant_position(ctx)?;
+        self.check_function_definition_position(ctx)?;
+        self.check_setting_position(ctx)?;
+        self.check_tablesample_position(ctx)?;
 
         Some(())
     }
@@ -36,12 +249,17 @@ impl CompletionFilter<'_> {
         }
 
         // No autocompletions if there are two identifiers without a separator.
-        if ctx.node_under_cursor.is_some_and(|n| {
-            n.prev_sibling().is_some_and(|p| {
-                (p.kind() == "identifier" || p.kind() == "object_reference")
-                    && n.kind() == "identifier"
+        // Keywords are the exception, since they may trail an identifier,
+        // e.g. `drop table users cascade`.
+        let is_keyword = matches!(self.data, CompletionRelevanceData::Keyword(_));
+        if !is_keyword
+            && ctx.node_under_cursor.is_some_and(|n| {
+                n.prev_sibling().is_some_and(|p| {
+                    (p.kind() == "identifier" || p.kind() == "object_reference")
+                        && n.kind() == "identifier"
+                })
             })
-        }) {
+        {
             return None;
         }
 
@@ -52,11 +270,14 @@ impl CompletionFilter<'_> {
         let clause = ctx.wrapping_clause_type.as_ref();
 
         match self.data {
-            CompletionRelevanceData::Table(_) => {
+            CompletionRelevanceData::Table(_) | CompletionRelevanceData::Cte(_) => {
                 let in_select_clause = clause.is_some_and(|c| c == &ClauseType::Select);
                 let in_where_clause = clause.is_some_and(|c| c == &ClauseType::Where);
+                let in_window_clause = clause
+                    .is_some_and(|c| c == &ClauseType::PartitionBy || c == &ClauseType::OrderBy);
+                let in_returning_clause = clause.is_some_and(|c| c == &ClauseType::Returning);
 
-                if in_select_clause || in_where_clause {
+                if in_select_clause || in_where_clause || in_window_clause || in_returning_clause {
                     return None;
                 };
             }
@@ -79,13 +300,106 @@ impl CompletionFilter<'_> {
         }
 
         match self.data {
-            CompletionRelevanceData::Table(_) | CompletionRelevanceData::Column(_) => return None,
+            CompletionRelevanceData::Table(_)
+            | CompletionRelevanceData::Cte(_)
+            | CompletionRelevanceData::Column(_)
+            | CompletionRelevanceData::Keyword(_) => return None,
             _ => {}
         }
 
         Some(())
     }
 
+    fn check_grant_position(&self, ctx: &CompletionContext) -> Option<()> {
+        let relevant = match ctx.get_grant_position() {
+            None => true,
+            Some(GrantPosition::Privileges) => {
+                matches!(self.data, CompletionRelevanceData::Keyword(_))
+            }
+            Some(GrantPosition::Object) => !matches!(
+                self.data,
+                CompletionRelevanceData::Column(_) | CompletionRelevanceData::Keyword(_)
+            ),
+        };
+
+        relevant.then_some(())
+    }
+
+    fn check_function_definition_position(&self, ctx: &CompletionContext) -> Option<()> {
+        match ctx.get_function_definition_position() {
+            // only keywords are valid in these positions
+            Some(FunctionDefinitionPosition::Language | FunctionDefinitionPosition::Attributes) => {
+                matches!(self.data, CompletionRelevanceData::Keyword(_)).then_some(())
+            }
+            None => Some(()),
+        }
+    }
+
+    fn check_setting_position(&self, ctx: &CompletionContext) -> Option<()> {
+        let relevant = match ctx.get_setting_position() {
+            None => !matches!(self.data, CompletionRelevanceData::Setting(_)),
+            Some(SettingPosition::Name) => {
+                matches!(self.data, CompletionRelevanceData::Setting(_))
+            }
+            Some(SettingPosition::Assignment | SettingPosition::Value(_)) => {
+                matches!(self.data, CompletionRelevanceData::Keyword(_))
+            }
+        };
+
+        relevant.then_some(())
+    }
+
+    fn check_tablesample_position(&self, ctx: &CompletionContext) -> Option<()> {
+        if !ctx.is_tablesample_method_position() {
+            return Some(());
+        }
+
+        // only sampling methods are valid after `tablesample`
+        matches!(self.data, CompletionRelevanceData::Keyword(_)).then_some(())
+    }
+
+    /// Objects of postgres itself are only offered if the user opted into them.
+    fn check_system_schema(&self, ctx: &CompletionContext) -> Option<()> {
+        if ctx.include_system_schemas {
+            return Some(());
+        }
+
+        let schema = match self.data {
+            CompletionRelevanceData::Table(table) => &table.schema,
+            CompletionRelevanceData::Function(f) => &f.schema,
+            CompletionRelevanceData::Column(col) => &col.schema_name,
+            CompletionRelevanceData::Schema(schema) => &schema.name,
+            CompletionRelevanceData::Collation(c) => &c.schema,
+            CompletionRelevanceData::OperatorClass(o) => &o.schema,
+            CompletionRelevanceData::Constraint(c) => &c.schema,
+            CompletionRelevanceData::Policy(p) => &p.schema,
+            _ => return Some(()),
+        };
+
+        (!SYSTEM_SCHEMAS.contains(&schema.as_str())).then_some(())
+    }
+
+    /// Objects the user configured as hidden, e.g. deprecated ones, are never offered.
+    fn check_hidden_object(&self, ctx: &CompletionContext) -> Option<()> {
+        let Some(hidden) = ctx.hidden_objects else {
+            return Some(());
+        };
+
+        let (name, comment) = match self.data {
+            CompletionRelevanceData::Table(table) => (&table.name, table.comment.as_deref()),
+            CompletionRelevanceData::Column(col) => (&col.name, col.comment.as_deref()),
+            CompletionRelevanceData::Function(f) => (&f.name, None),
+            CompletionRelevanceData::Schema(schema) => (&schema.name, None),
+            CompletionRelevanceData::Collation(c) => (&c.name, None),
+            CompletionRelevanceData::OperatorClass(o) => (&o.name, None),
+            CompletionRelevanceData::Constraint(c) => (&c.name, None),
+            CompletionRelevanceData::Policy(p) => (&p.name, None),
+            _ => return Some(()),
+        };
+
+        (!hidden.hides(name, comment)).then_some(())
+    }
+
     fn check_mentioned_schema(&self, ctx: &CompletionContext) -> Option<()> {
         if ctx.schema_name.is_none() {
             return Some(());
@@ -104,6 +418,29 @@ impl CompletionFilter<'_> {
                 // we should never allow schema suggestions if there already was one.
                 true
             }
+            CompletionRelevanceData::Keyword(_) | CompletionRelevanceData::Setting(_) => true,
+            // ctes belong to the statement, not to a schema
+            CompletionRelevanceData::Cte(_) => true,
+            CompletionRelevanceData::Field(_) => true,
+            CompletionRelevanceData::Collation(c) => &c.schema != name,
+            CompletionRelevanceData::Extension(_) => true,
+            CompletionRelevanceData::Alias(_) => true,
+            CompletionRelevanceData::DefaultExpression(_) => true,
+            CompletionRelevanceData::MetaCommand(_) => true,
+            CompletionRelevanceData::Window(_) => true,
+            CompletionRelevanceData::DefinedColumn(_) => true,
+            // constraints are named without their schema
+            CompletionRelevanceData::Constraint(_) => true,
+            // policies are named without their schema, too
+            CompletionRelevanceData::Policy(_) => true,
+            CompletionRelevanceData::TimeZone(_) => true,
+            CompletionRelevanceData::EnumValue(_) => true,
+            CompletionRelevanceData::OperatorClass(o) => &o.schema != name,
+            CompletionRelevanceData::ArrayType(t) | CompletionRelevanceData::Type(t) => {
+                &t.schema != name
+            }
+            // `schema.table.*` is not offered
+            CompletionRelevanceData::AllColumns(_) => true,
         };
 
         if does_not_match {
@@ -113,3 +450,58 @@ impl CompletionFilter<'_> {
         Some(())
     }
 }
+
+/// Returns true if the table of `col` is mentioned in the statement,
+/// or if the relations of the statement are not known.
+fn is_mentioned(ctx: &CompletionContext, col: &Column) -> bool {
+    if !ctx.mentioned_relations.is_empty() {
+        return [Some(col.schema_name.clone()), None].iter().any(|schema| {
+            ctx.mentioned_relations
+                .get(schema)
+                .is_some_and(|tables| tables.contains(&col.table_name))
+        });
+    }
+
+    // tree-sitter might not make sense of the statement, so fall back to its words
+    let words: Vec<&String> = ctx
+        .words_before_cursor
+        .iter()
+        .chain(&ctx.words_after_cursor)
+        .collect();
+
+    let mut relations = words
+        .windows(2)
+        .filter(|w| w[0] == "from" || w[0] == "join")
+        .map(|w| w[1].replace('"', ""))
+        .peekable();
+
+    relations.peek().is_none() || relations.any(|r| is_column_of(col, &r))
+}
+
+/// Returns true if `opclass` can index values of the type `type_id`,
+/// e.g. `text_ops` a `varchar` column or `array_ops` an `int4[]` column.
+fn indexes_type(ctx: &CompletionContext, opclass: &OperatorClass, type_id: i64) -> bool {
+    // varchar has no operator classes of its own, it is binary coercible to text
+    const TEXT: i64 = 25;
+    const VARCHAR: i64 = 1043;
+
+    if opclass.input_type_id == type_id || (type_id == VARCHAR && opclass.input_type_id == TEXT) {
+        return true;
+    }
+
+    let column_type = ctx.schema_cache.find_type_by_id(type_id);
+    match opclass.input_type.as_str() {
+        "anyarray" => column_type.is_some_and(|t| t.element_type_id.is_some()),
+        "anyenum" => column_type.is_some_and(|t| !t.enum_labels().is_empty()),
+        // e.g. `range_ops`, which can't be told apart without the type category
+        input_type => input_type.starts_with("any"),
+    }
+}
+
+/// Returns true if `col` belongs to `relation`, which may be schema-qualified.
+fn is_column_of(col: &Column, relation: &str) -> bool {
+    match relation.split_once('.') {
+        Some((schema, table)) => col.schema_name == schema && col.table_name == table,
+        None => col.table_name == relation,
+    }
+}
@@ -1,11 +1,14 @@
-use crate::context::{ClauseType, CompletionContext, WrappingNode};
+use crate::context::{ClauseType, CompletionContext, ParentTablePosition, WrappingNode};
 
-use super::CompletionRelevanceData;
+use super::{CompletionRelevanceData, SYSTEM_SCHEMAS};
 
 #[derive(Debug)]
 pub(crate) struct CompletionScore<'a> {
     score: i32,
     data: CompletionRelevanceData<'a>,
+
+    /// The individual contributions to the score, keyed by the check that made them.
+    breakdown: Vec<(&'static str, i32)>,
 }
 
 impl<'a> From<CompletionRelevanceData<'a>> for CompletionScore<'a> {
@@ -13,6 +16,7 @@ impl<'a> From<CompletionRelevanceData<'a>> for CompletionScore<'a> {
         Self {
             score: 0,
             data: value,
+            breakdown: vec![],
         }
     }
 }
@@ -22,6 +26,20 @@ impl CompletionScore<'_> {
         self.score
     }
 
+    /// Explains how the score came about, e.g. `[("matches_query_input", 30), ("is_keyword", -5)]`.
+    pub fn get_breakdown(&self) -> &[(&'static str, i32)] {
+        &self.breakdown
+    }
+
+    fn add(&mut self, check: &'static str, score: i32) {
+        if score == 0 {
+            return;
+        }
+
+        self.score += score;
+        self.breakdown.push((check, score));
+    }
+
     pub fn calc_score(&mut self, ctx: &CompletionContext) {
         self.check_is_user_defined();
         self.check_matches_schema(ctx);
@@ -30,10 +48,64 @@ impl CompletionScore<'_> {
         self.check_matching_clause_type(ctx);
         self.check_matching_wrapping_node(ctx);
         self.check_relations_in_stmt(ctx);
+        self.check_using_join_column(ctx);
+        self.check_filter_where_position(ctx);
+        self.check_partition_parent(ctx);
+        self.check_operator_class_type(ctx);
+        self.check_is_keyword();
+    }
+
+    /// Only partitioned tables can have partitions.
+    fn check_partition_parent(&mut self, ctx: &CompletionContext) {
+        if let CompletionRelevanceData::Table(table) = self.data {
+            if table.is_partitioned
+                && ctx.get_parent_table_position() == Some(ParentTablePosition::PartitionOf)
+            {
+                self.add("partition_parent", 30);
+            }
+        }
+    }
+
+    /// Operator classes for the type of the indexed column are what the user is looking for,
+    /// the default one of the type is usually implied.
+    fn check_operator_class_type(&mut self, ctx: &CompletionContext) {
+        let CompletionRelevanceData::OperatorClass(opclass) = self.data else {
+            return;
+        };
+
+        let Some(position) = ctx.get_operator_class_position() else {
+            return;
+        };
+
+        if position.column_type_id == Some(opclass.input_type_id) {
+            self.add("operator_class_type", 20);
+        }
+
+        if opclass.is_default {
+            self.add("operator_class_type", -5);
+        }
+    }
+
+    /// The predicate of a `FILTER` clause is mostly about columns, like a `WHERE` clause.
+    fn check_filter_where_position(&mut self, ctx: &CompletionContext) {
+        if matches!(self.data, CompletionRelevanceData::Column(_)) && ctx.is_filter_where_position()
+        {
+            self.add("filter_where_column", 10);
+        }
+    }
+
+    /// The only columns that remain in a `USING` list are the shared ones,
+    /// and those are exactly what the user is looking for.
+    fn check_using_join_column(&mut self, ctx: &CompletionContext) {
+        if matches!(self.data, CompletionRelevanceData::Column(_))
+            && ctx.get_using_join_relations().is_some()
+        {
+            self.add("using_join_column", 30);
+        }
     }
 
     fn check_matches_query_input(&mut self, ctx: &CompletionContext) {
-        let content = match ctx.get_node_under_cursor_content() {
+        let content = match ctx.get_node_under_cursor_identifier() {
             Some(c) => c,
             None => return,
         };
@@ -43,6 +115,26 @@ impl CompletionScore<'_> {
             CompletionRelevanceData::Table(t) => t.name.as_str(),
             CompletionRelevanceData::Column(c) => c.name.as_str(),
             CompletionRelevanceData::Schema(s) => s.name.as_str(),
+            CompletionRelevanceData::Keyword(k) => k,
+            CompletionRelevanceData::Setting(s) => s.name.as_str(),
+            CompletionRelevanceData::Cte(name) => name,
+            CompletionRelevanceData::Field(f) => f.name.as_str(),
+            CompletionRelevanceData::Collation(c) => c.name.as_str(),
+            CompletionRelevanceData::Extension(e) => e.name.as_str(),
+            CompletionRelevanceData::Alias(a) => a,
+            CompletionRelevanceData::DefaultExpression(ref e) => &**e,
+            CompletionRelevanceData::OperatorClass(o) => o.name.as_str(),
+            CompletionRelevanceData::MetaCommand(m) => m,
+            CompletionRelevanceData::Window(w) => w,
+            CompletionRelevanceData::DefinedColumn(c) => c,
+            CompletionRelevanceData::Constraint(c) => c.name.as_str(),
+            CompletionRelevanceData::Policy(p) => p.name.as_str(),
+            CompletionRelevanceData::TimeZone(z) => z.name.as_str(),
+            CompletionRelevanceData::EnumValue(v) => v,
+            CompletionRelevanceData::ArrayType(t) | CompletionRelevanceData::Type(t) => {
+                t.name.as_str()
+            }
+            CompletionRelevanceData::AllColumns(table) => table.map_or("*", |t| t.name.as_str()),
         };
 
         if name.starts_with(content.as_str()) {
@@ -51,7 +143,7 @@ impl CompletionScore<'_> {
                 .try_into()
                 .expect("The length of the input exceeds i32 capacity");
 
-            self.score += len * 10;
+            self.add("matches_query_input", len * 10);
         };
     }
 
@@ -64,32 +156,72 @@ impl CompletionScore<'_> {
         let has_mentioned_tables = !ctx.mentioned_relations.is_empty();
         let has_mentioned_schema = ctx.schema_name.is_some();
 
-        self.score += match self.data {
-            CompletionRelevanceData::Table(_) => match clause_type {
-                ClauseType::From => 5,
-                ClauseType::Update => 10,
-                ClauseType::Delete => 10,
-                _ => -50,
-            },
+        let score = match self.data {
+            CompletionRelevanceData::Table(_) | CompletionRelevanceData::Cte(_) => {
+                match clause_type {
+                    ClauseType::From => 5,
+                    ClauseType::Update => 10,
+                    ClauseType::Delete => 10,
+                    _ => -50,
+                }
+            }
             CompletionRelevanceData::Function(_) => match clause_type {
                 ClauseType::Select if !has_mentioned_tables => 15,
                 ClauseType::Select if has_mentioned_tables => 0,
                 ClauseType::From => 0,
                 _ => -50,
             },
-            CompletionRelevanceData::Column(_) => match clause_type {
+            CompletionRelevanceData::Column(col) => match clause_type {
                 ClauseType::Select if has_mentioned_tables => 10,
                 ClauseType::Select if !has_mentioned_tables => 0,
                 ClauseType::Where => 10,
+                ClauseType::PartitionBy | ClauseType::OrderBy => 10,
+                // only the rows of the target table can be returned
+                ClauseType::Returning
+                    if ctx
+                        .returning_table
+                        .as_ref()
+                        .is_some_and(|table| table.is(&col.schema_name, &col.table_name)) =>
+                {
+                    20
+                }
                 _ => -15,
             },
+            // the whole rows are returned as often as single columns
+            CompletionRelevanceData::AllColumns(_) => match clause_type {
+                ClauseType::Returning => 20,
+                _ => 0,
+            },
             CompletionRelevanceData::Schema(_) => match clause_type {
                 ClauseType::From if !has_mentioned_schema => 15,
                 ClauseType::Update if !has_mentioned_schema => 15,
                 ClauseType::Delete if !has_mentioned_schema => 15,
                 _ => -50,
             },
-        }
+            CompletionRelevanceData::Keyword(_) => match clause_type {
+                // a `partition by` list only ever holds expressions
+                ClauseType::PartitionBy => -15,
+                _ => 0,
+            },
+            CompletionRelevanceData::Setting(_)
+            | CompletionRelevanceData::Field(_)
+            | CompletionRelevanceData::Collation(_)
+            | CompletionRelevanceData::Extension(_)
+            | CompletionRelevanceData::Alias(_)
+            | CompletionRelevanceData::DefaultExpression(_)
+            | CompletionRelevanceData::OperatorClass(_)
+            | CompletionRelevanceData::MetaCommand(_)
+            | CompletionRelevanceData::Window(_)
+            | CompletionRelevanceData::DefinedColumn(_)
+            | CompletionRelevanceData::Constraint(_)
+            | CompletionRelevanceData::Policy(_)
+            | CompletionRelevanceData::TimeZone(_)
+            | CompletionRelevanceData::EnumValue(_)
+            | CompletionRelevanceData::ArrayType(_)
+            | CompletionRelevanceData::Type(_) => 0,
+        };
+
+        self.add("matching_clause_type", score);
     }
 
     fn check_matching_wrapping_node(&mut self, ctx: &CompletionContext) {
@@ -101,13 +233,15 @@ impl CompletionScore<'_> {
         let has_mentioned_schema = ctx.schema_name.is_some();
         let has_node_text = ctx.get_node_under_cursor_content().is_some();
 
-        self.score += match self.data {
-            CompletionRelevanceData::Table(_) => match wrapping_node {
-                WrappingNode::Relation if has_mentioned_schema => 15,
-                WrappingNode::Relation if !has_mentioned_schema => 10,
-                WrappingNode::BinaryExpression => 5,
-                _ => -50,
-            },
+        let score = match self.data {
+            CompletionRelevanceData::Table(_) | CompletionRelevanceData::Cte(_) => {
+                match wrapping_node {
+                    WrappingNode::Relation if has_mentioned_schema => 15,
+                    WrappingNode::Relation if !has_mentioned_schema => 10,
+                    WrappingNode::BinaryExpression => 5,
+                    _ => -50,
+                }
+            }
             CompletionRelevanceData::Function(_) => match wrapping_node {
                 WrappingNode::Relation => 10,
                 _ => -50,
@@ -122,16 +256,41 @@ impl CompletionScore<'_> {
                 WrappingNode::Relation if !has_mentioned_schema && has_node_text => 0,
                 _ => -50,
             },
-        }
+            CompletionRelevanceData::Keyword(_) => match wrapping_node {
+                WrappingNode::WindowSpecification => 5,
+                _ => -50,
+            },
+            CompletionRelevanceData::Setting(_)
+            | CompletionRelevanceData::Field(_)
+            | CompletionRelevanceData::Collation(_)
+            | CompletionRelevanceData::Extension(_)
+            | CompletionRelevanceData::Alias(_)
+            | CompletionRelevanceData::DefaultExpression(_)
+            | CompletionRelevanceData::OperatorClass(_)
+            | CompletionRelevanceData::MetaCommand(_)
+            | CompletionRelevanceData::Window(_)
+            | CompletionRelevanceData::DefinedColumn(_)
+            | CompletionRelevanceData::Constraint(_)
+            | CompletionRelevanceData::Policy(_)
+            | CompletionRelevanceData::TimeZone(_)
+            | CompletionRelevanceData::EnumValue(_)
+            | CompletionRelevanceData::ArrayType(_)
+            | CompletionRelevanceData::Type(_)
+            | CompletionRelevanceData::AllColumns(_) => 0,
+        };
+
+        self.add("matching_wrapping_node", score);
     }
 
     fn check_is_invocation(&mut self, ctx: &CompletionContext) {
-        self.score += match self.data {
+        let score = match self.data {
             CompletionRelevanceData::Function(_) if ctx.is_invocation => 30,
             CompletionRelevanceData::Function(_) if !ctx.is_invocation => -10,
             _ if ctx.is_invocation => -10,
             _ => 0,
         };
+
+        self.add("is_invocation", score);
     }
 
     fn check_matches_schema(&mut self, ctx: &CompletionContext) {
@@ -143,9 +302,9 @@ impl CompletionScore<'_> {
         let data_schema = self.get_schema_name();
 
         if schema_name == data_schema {
-            self.score += 25;
+            self.add("matches_schema", 25);
         } else {
-            self.score -= 10;
+            self.add("matches_schema", -10);
         }
     }
 
@@ -155,6 +314,27 @@ impl CompletionScore<'_> {
             CompletionRelevanceData::Table(t) => t.schema.as_str(),
             CompletionRelevanceData::Column(c) => c.schema_name.as_str(),
             CompletionRelevanceData::Schema(s) => s.name.as_str(),
+            CompletionRelevanceData::Collation(c) => c.schema.as_str(),
+            CompletionRelevanceData::OperatorClass(o) => o.schema.as_str(),
+            CompletionRelevanceData::Constraint(c) => c.schema.as_str(),
+            CompletionRelevanceData::Policy(p) => p.schema.as_str(),
+            CompletionRelevanceData::ArrayType(t) | CompletionRelevanceData::Type(t) => {
+                t.schema.as_str()
+            }
+            CompletionRelevanceData::AllColumns(table) => table.map_or("", |t| t.schema.as_str()),
+            // keywords do not live in any schema
+            CompletionRelevanceData::Keyword(_) | CompletionRelevanceData::Setting(_) => "",
+            CompletionRelevanceData::Cte(_)
+            | CompletionRelevanceData::Field(_)
+            | CompletionRelevanceData::Alias(_)
+            | CompletionRelevanceData::DefaultExpression(_)
+            | CompletionRelevanceData::MetaCommand(_)
+            | CompletionRelevanceData::Window(_)
+            | CompletionRelevanceData::DefinedColumn(_)
+            | CompletionRelevanceData::TimeZone(_)
+            | CompletionRelevanceData::EnumValue(_) => "",
+            // extensions are installed into a schema, but not available from one before
+            CompletionRelevanceData::Extension(_) => "",
         }
     }
 
@@ -162,13 +342,16 @@ impl CompletionScore<'_> {
         match self.data {
             CompletionRelevanceData::Column(c) => Some(c.table_name.as_str()),
             CompletionRelevanceData::Table(t) => Some(t.name.as_str()),
+            CompletionRelevanceData::AllColumns(table) => table.map(|t| t.name.as_str()),
             _ => None,
         }
     }
 
     fn check_relations_in_stmt(&mut self, ctx: &CompletionContext) {
         match self.data {
-            CompletionRelevanceData::Table(_) | CompletionRelevanceData::Function(_) => return,
+            CompletionRelevanceData::Table(_)
+            | CompletionRelevanceData::Function(_)
+            | CompletionRelevanceData::Cte(_) => return,
             _ => {}
         }
 
@@ -183,29 +366,78 @@ impl CompletionScore<'_> {
             .get(&Some(schema.to_string()))
             .is_some_and(|tables| tables.contains(table_name))
         {
-            self.score += 45;
+            self.add("relations_in_stmt", 45);
         } else if ctx
             .mentioned_relations
             .get(&None)
             .is_some_and(|tables| tables.contains(table_name))
         {
-            self.score += 30;
+            self.add("relations_in_stmt", 30);
+        }
+    }
+
+    /// Keywords should never outrank the objects of the schema.
+    fn check_is_keyword(&mut self) {
+        if let CompletionRelevanceData::Keyword(_) = self.data {
+            self.add("is_keyword", -5);
         }
     }
 
     fn check_is_user_defined(&mut self) {
         let schema = self.get_schema_name().to_string();
 
-        let system_schemas = ["pg_catalog", "information_schema", "pg_toast"];
-
-        if system_schemas.contains(&schema.as_str()) {
-            self.score -= 10;
+        if SYSTEM_SCHEMAS.contains(&schema.as_str()) {
+            self.add("is_user_defined", -10);
         }
 
         // "public" is the default postgres schema where users
         // create objects. Prefer it by a slight bit.
         if schema.as_str() == "public" {
-            self.score += 2;
+            self.add("is_user_defined", 2);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        context::CompletionContext,
+        relevance::CompletionRelevanceData,
+        sanitization::SanitizedCompletionParams,
+        test_helper::{CURSOR_POS, get_test_deps, get_test_params},
+    };
+
+    use super::CompletionScore;
+
+    #[tokio::test]
+    async fn breakdown_adds_up_to_the_score() {
+        let setup = r#"
+            create table users (
+                id serial primary key,
+                name text
+            );
+        "#;
+
+        let query = format!("select * from us{}", CURSOR_POS);
+
+        let (tree, cache) = get_test_deps(setup, query.as_str().into()).await;
+        let params = get_test_params(&tree, &cache, query.as_str().into());
+        let sanitized = SanitizedCompletionParams::from(params);
+        let ctx = CompletionContext::new(&sanitized);
+
+        let table = cache
+            .tables
+            .iter()
+            .find(|t| t.name == "users")
+            .expect("Should have loaded the users table");
+
+        let mut score = CompletionScore::from(CompletionRelevanceData::Table(table));
+        score.calc_score(&ctx);
+
+        assert!(!score.get_breakdown().is_empty());
+        assert_eq!(
+            score.get_breakdown().iter().map(|(_, s)| s).sum::<i32>(),
+            score.get_score()
+        );
+    }
+}
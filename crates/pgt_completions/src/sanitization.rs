@@ -2,13 +2,16 @@ use std::{borrow::Cow, cmp::max};
 
 use pgt_text_size::TextSize;
 
-use crate::CompletionParams;
+use crate::{CompletionParams, HiddenObjects};
 
 pub(crate) struct SanitizedCompletionParams<'a> {
     pub position: TextSize,
     pub text: String,
     pub schema: &'a pgt_schema_cache::SchemaCache,
     pub tree: Cow<'a, tree_sitter::Tree>,
+    pub include_system_schemas: bool,
+    pub hidden_objects: Option<&'a HiddenObjects>,
+    pub preceding_statements: &'a [&'a str],
 }
 
 pub fn benchmark_sanitization(params: CompletionParams) -> String {
@@ -33,7 +36,7 @@ where
     }
 }
 
-static SANITIZED_TOKEN: &str = "REPLACED_TOKEN";
+pub(crate) static SANITIZED_TOKEN: &str = "REPLACED_TOKEN";
 
 impl<'larger, 'smaller> SanitizedCompletionParams<'smaller>
 where
@@ -79,6 +82,9 @@ where
             text: sql,
             schema: params.schema,
             tree: Cow::Owned(tree),
+            include_system_schemas: params.include_system_schemas,
+            hidden_objects: params.hidden_objects,
+            preceding_statements: params.preceding_statements,
         }
     }
     fn unadjusted(params: CompletionParams<'larger>) -> Self {
@@ -87,6 +93,9 @@ where
             text: params.text.clone(),
             schema: params.schema,
             tree: Cow::Borrowed(params.tree),
+            include_system_schemas: params.include_system_schemas,
+            hidden_objects: params.hidden_objects,
+            preceding_statements: params.preceding_statements,
         }
     }
 
@@ -99,6 +99,12 @@ pub(crate) fn get_test_params<'a>(
         schema: schema_cache,
         tree,
         text,
+        min_chars: 0,
+        only_kinds: None,
+        include_system_schemas: true,
+        hidden_objects: None,
+        limit: None,
+        preceding_statements: &[],
     }
 }
 
@@ -25,11 +25,13 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub enum RuleGroup {
     Safety,
+    Style,
 }
 impl RuleGroup {
     pub const fn as_str(self) -> &'static str {
         match self {
             Self::Safety => Safety::GROUP_NAME,
+            Self::Style => Style::GROUP_NAME,
         }
     }
 }
@@ -38,6 +40,7 @@ impl std::str::FromStr for RuleGroup {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             Safety::GROUP_NAME => Ok(Self::Safety),
+            Style::GROUP_NAME => Ok(Self::Style),
             _ => Err("This rule group doesn't exist."),
         }
     }
@@ -54,6 +57,8 @@ pub struct Rules {
     pub all: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub safety: Option<Safety>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub style: Option<Style>,
 }
 impl Rules {
     #[doc = r" Checks if the code coming from [pgt_diagnostics::Diagnostic] corresponds to a rule."]
@@ -61,6 +66,7 @@ impl Rules {
     pub fn has_rule(group: RuleGroup, rule_name: &str) -> Option<&'static str> {
         match group {
             RuleGroup::Safety => Safety::has_rule(rule_name),
+            RuleGroup::Style => Style::has_rule(rule_name),
         }
     }
     #[doc = r" Given a category coming from [Diagnostic](pgt_diagnostics::Diagnostic), this function returns"]
@@ -92,6 +98,21 @@ impl Rules {
                     },
                     |(level, _)| level.into(),
                 ),
+            RuleGroup::Style => self
+                .style
+                .as_ref()
+                .and_then(|group| group.get_rule_configuration(rule_name))
+                .filter(|(level, _)| !matches!(level, RulePlainConfiguration::Off))
+                .map_or_else(
+                    || {
+                        if Style::is_recommended_rule(rule_name) {
+                            Severity::Error
+                        } else {
+                            Severity::Warning
+                        }
+                    },
+                    |(level, _)| level.into(),
+                ),
         };
         Some(severity)
     }
@@ -103,6 +124,9 @@ impl Rules {
         if let Some(group) = &mut self.safety {
             group.recommended = None;
         }
+        if let Some(group) = &mut self.style {
+            group.recommended = None;
+        }
     }
     pub(crate) const fn is_recommended_false(&self) -> bool {
         matches!(self.recommended, Some(false))
@@ -129,6 +153,19 @@ impl Rules {
         } else if !self.is_recommended_false() {
             enabled_rules.extend(Safety::recommended_rules_as_filters());
         }
+        if let Some(group) = self.style.as_ref() {
+            group.collect_preset_rules(
+                self.is_all_true(),
+                !self.is_recommended_false(),
+                &mut enabled_rules,
+            );
+            enabled_rules.extend(&group.get_enabled_rules());
+            disabled_rules.extend(&group.get_disabled_rules());
+        } else if self.is_all_true() {
+            enabled_rules.extend(Style::all_rules_as_filters());
+        } else if !self.is_recommended_false() {
+            enabled_rules.extend(Style::recommended_rules_as_filters());
+        }
         enabled_rules.difference(&disabled_rules).copied().collect()
     }
 }
@@ -293,9 +330,107 @@ impl Safety {
         }
     }
 }
+#[derive(Clone, Debug, Default, Deserialize, Eq, Merge, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", default, deny_unknown_fields)]
+#[doc = r" A list of rules that belong to this group"]
+pub struct Style {
+    #[doc = r" It enables the recommended rules for this group"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recommended: Option<bool>,
+    #[doc = r" It enables ALL rules for this group."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub all: Option<bool>,
+    #[doc = "Object names should not be reserved words."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reserved_identifier: Option<RuleConfiguration<pgt_analyser::options::ReservedIdentifier>>,
+}
+impl Style {
+    const GROUP_NAME: &'static str = "style";
+    pub(crate) const GROUP_RULES: &'static [&'static str] = &["reservedIdentifier"];
+    const RECOMMENDED_RULES: &'static [&'static str] = &[];
+    const RECOMMENDED_RULES_AS_FILTERS: &'static [RuleFilter<'static>] = &[];
+    const ALL_RULES_AS_FILTERS: &'static [RuleFilter<'static>] =
+        &[RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[0])];
+    #[doc = r" Retrieves the recommended rules"]
+    pub(crate) fn is_recommended_true(&self) -> bool {
+        matches!(self.recommended, Some(true))
+    }
+    pub(crate) fn is_recommended_unset(&self) -> bool {
+        self.recommended.is_none()
+    }
+    pub(crate) fn is_all_true(&self) -> bool {
+        matches!(self.all, Some(true))
+    }
+    pub(crate) fn is_all_unset(&self) -> bool {
+        self.all.is_none()
+    }
+    pub(crate) fn get_enabled_rules(&self) -> FxHashSet<RuleFilter<'static>> {
+        let mut index_set = FxHashSet::default();
+        if let Some(rule) = self.reserved_identifier.as_ref() {
+            if rule.is_enabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[0]));
+            }
+        }
+        index_set
+    }
+    pub(crate) fn get_disabled_rules(&self) -> FxHashSet<RuleFilter<'static>> {
+        let mut index_set = FxHashSet::default();
+        if let Some(rule) = self.reserved_identifier.as_ref() {
+            if rule.is_disabled() {
+                index_set.insert(RuleFilter::Rule(Self::GROUP_NAME, Self::GROUP_RULES[0]));
+            }
+        }
+        index_set
+    }
+    #[doc = r" Checks if, given a rule name, matches one of the rules contained in this category"]
+    pub(crate) fn has_rule(rule_name: &str) -> Option<&'static str> {
+        Some(Self::GROUP_RULES[Self::GROUP_RULES.binary_search(&rule_name).ok()?])
+    }
+    #[doc = r" Checks if, given a rule name, it is marked as recommended"]
+    pub(crate) fn is_recommended_rule(rule_name: &str) -> bool {
+        Self::RECOMMENDED_RULES.contains(&rule_name)
+    }
+    pub(crate) fn recommended_rules_as_filters() -> &'static [RuleFilter<'static>] {
+        Self::RECOMMENDED_RULES_AS_FILTERS
+    }
+    pub(crate) fn all_rules_as_filters() -> &'static [RuleFilter<'static>] {
+        Self::ALL_RULES_AS_FILTERS
+    }
+    #[doc = r" Select preset rules"]
+    pub(crate) fn collect_preset_rules(
+        &self,
+        parent_is_all: bool,
+        parent_is_recommended: bool,
+        enabled_rules: &mut FxHashSet<RuleFilter<'static>>,
+    ) {
+        if self.is_all_true() || self.is_all_unset() && parent_is_all {
+            enabled_rules.extend(Self::all_rules_as_filters());
+        } else if self.is_recommended_true()
+            || self.is_recommended_unset() && self.is_all_unset() && parent_is_recommended
+        {
+            enabled_rules.extend(Self::recommended_rules_as_filters());
+        }
+    }
+    pub(crate) fn get_rule_configuration(
+        &self,
+        rule_name: &str,
+    ) -> Option<(RulePlainConfiguration, Option<RuleOptions>)> {
+        match rule_name {
+            "reservedIdentifier" => self
+                .reserved_identifier
+                .as_ref()
+                .map(|conf| (conf.level(), conf.get_options())),
+            _ => None,
+        }
+    }
+}
 #[test]
 fn test_order() {
     for items in Safety::GROUP_RULES.windows(2) {
         assert!(items[0] < items[1], "{} < {}", items[0], items[1]);
     }
+    for items in Style::GROUP_RULES.windows(2) {
+        assert!(items[0] < items[1], "{} < {}", items[0], items[1]);
+    }
 }
@@ -0,0 +1,59 @@
+use biome_deserialize::StringSet;
+use biome_deserialize_macros::{Merge, Partial};
+use bpaf::Bpaf;
+use serde::{Deserialize, Serialize};
+
+/// The configuration of the completions
+#[derive(Clone, Debug, Deserialize, Eq, Partial, PartialEq, Serialize)]
+#[partial(derive(Bpaf, Clone, Eq, PartialEq, Merge))]
+#[partial(serde(rename_all = "camelCase", default, deny_unknown_fields))]
+#[partial(cfg_attr(feature = "schema", derive(schemars::JsonSchema)))]
+pub struct CompletionsConfiguration {
+    /// The number of characters that have to be typed before completions are offered.
+    /// Useful for large schemas. Defaults to 0
+    #[partial(bpaf(long("completions-min-chars"), argument("NUMBER")))]
+    pub min_chars: u16,
+
+    /// The maximum number of completion items that are offered. Defaults to 50
+    #[partial(bpaf(long("completions-limit"), argument("NUMBER")))]
+    pub limit: u16,
+
+    /// Whether objects in system schemas like `pg_catalog` and `information_schema`
+    /// are suggested. Defaults to true
+    #[partial(bpaf(long("completions-include-system-schemas"), argument("true|false")))]
+    pub include_system_schemas: bool,
+
+    /// A list of Unix shell style patterns. Objects whose name matches one of these
+    /// patterns, e.g. `_deprecated*`, are not suggested.
+    #[partial(bpaf(hide))]
+    pub hidden_objects: StringSet,
+
+    /// Tables and columns whose comment contains this marker, e.g. `@deprecated`,
+    /// are not suggested.
+    #[partial(bpaf(long("completions-hidden-objects-comment-marker"), argument("MARKER")))]
+    pub hidden_objects_comment_marker: String,
+
+    /// Whether the objects matched by `hiddenObjects` and `hiddenObjectsCommentMarker`
+    /// are suggested anyway. Defaults to false
+    #[partial(bpaf(long("completions-include-hidden-objects"), argument("true|false")))]
+    pub include_hidden_objects: bool,
+
+    /// Whether every accepted completion and its rank among the shown items are emitted
+    /// as structured tracing events, to evaluate the ranking. Defaults to false
+    #[partial(bpaf(long("completions-telemetry"), argument("true|false")))]
+    pub telemetry: bool,
+}
+
+impl Default for CompletionsConfiguration {
+    fn default() -> Self {
+        Self {
+            min_chars: 0,
+            limit: 50,
+            include_system_schemas: true,
+            hidden_objects: Default::default(),
+            hidden_objects_comment_marker: String::new(),
+            include_hidden_objects: false,
+            telemetry: false,
+        }
+    }
+}
@@ -31,6 +31,11 @@ pub struct DatabaseConfiguration {
     #[partial(bpaf(long("database")))]
     pub database: String,
 
+    /// The other databases on the same server that a file may select with a
+    /// `-- pgt:connection=<name>` directive in its leading comments.
+    #[partial(bpaf(long("connections")))]
+    pub connections: StringSet,
+
     #[partial(bpaf(long("allow_statement_executions_against")))]
     pub allow_statement_executions_against: StringSet,
 
@@ -38,6 +43,11 @@ pub struct DatabaseConfiguration {
     #[partial(bpaf(long("conn_timeout_secs"), fallback(Some(10)), debug_fallback))]
     pub conn_timeout_secs: u16,
 
+    /// The time in milliseconds after which an idle connection is closed.
+    /// A new connection is opened on the next database access.
+    #[partial(bpaf(long("idle_timeout_ms"), fallback(Some(600000)), debug_fallback))]
+    pub idle_timeout_ms: u64,
+
     /// Actively disable all database-related features.
     #[partial(bpaf(long("disable-db"), switch, fallback(Some(false))))]
     #[partial(cfg_attr(feature = "schema", schemars(skip)))]
@@ -53,8 +63,10 @@ impl Default for DatabaseConfiguration {
             username: "postgres".to_string(),
             password: "postgres".to_string(),
             database: "postgres".to_string(),
+            connections: Default::default(),
             allow_statement_executions_against: Default::default(),
             conn_timeout_secs: 10,
+            idle_timeout_ms: 600_000,
         }
     }
 }
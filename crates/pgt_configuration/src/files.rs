@@ -10,6 +10,9 @@ pub const DEFAULT_FILE_SIZE_LIMIT: NonZeroU64 =
     // SAFETY: This constant is initialized with a non-zero value
     NonZeroU64::new(1024 * 1024).unwrap();
 
+/// The extensions of the files that are handled by default
+pub const DEFAULT_SQL_EXTENSIONS: [&str; 2] = ["sql", "pg"];
+
 /// The configuration of the filesystem
 #[derive(Clone, Debug, Deserialize, Eq, Partial, PartialEq, Serialize)]
 #[partial(derive(Bpaf, Clone, Eq, PartialEq, Merge))]
@@ -30,6 +33,16 @@ pub struct FilesConfiguration {
     /// match these patterns.
     #[partial(bpaf(hide))]
     pub include: StringSet,
+
+    /// Whether a single newline terminates a statement. Useful for files that
+    /// do not use semicolons. By default, only blank lines and semicolons end a
+    /// statement.
+    #[partial(bpaf(long("files-newline-terminates-statements"), argument("true|false")))]
+    pub newline_terminates_statements: bool,
+
+    /// The file extensions of SQL files, without the leading dot. Defaults to `sql` and `pg`.
+    #[partial(bpaf(hide))]
+    pub sql_extensions: StringSet,
 }
 
 impl Default for FilesConfiguration {
@@ -38,6 +51,8 @@ impl Default for FilesConfiguration {
             max_size: DEFAULT_FILE_SIZE_LIMIT,
             ignore: Default::default(),
             include: Default::default(),
+            newline_terminates_statements: false,
+            sql_extensions: StringSet::from_iter(DEFAULT_SQL_EXTENSIONS.map(String::from)),
         }
     }
 }
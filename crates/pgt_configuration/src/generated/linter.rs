@@ -16,4 +16,13 @@ pub fn push_to_analyser_rules(
             }
         }
     }
+    if let Some(rules) = rules.style.as_ref() {
+        for rule_name in Style::GROUP_RULES {
+            if let Some((_, Some(rule_options))) = rules.get_rule_configuration(rule_name) {
+                if let Some(rule_key) = metadata.find_rule("style", rule_name) {
+                    analyser_rules.push_rule(rule_key, rule_options);
+                }
+            }
+        }
+    }
 }
@@ -4,6 +4,7 @@
 //! by language. The language might further options divided by tool.
 
 pub mod analyser;
+pub mod completions;
 pub mod database;
 pub mod diagnostics;
 pub mod files;
@@ -24,6 +25,9 @@ pub use analyser::{
 };
 use biome_deserialize_macros::{Merge, Partial};
 use bpaf::Bpaf;
+use completions::{
+    CompletionsConfiguration, PartialCompletionsConfiguration, partial_completions_configuration,
+};
 use database::{
     DatabaseConfiguration, PartialDatabaseConfiguration, partial_database_configuration,
 };
@@ -78,6 +82,13 @@ pub struct Configuration {
         bpaf(external(partial_database_configuration), optional, hide_usage)
     )]
     pub db: DatabaseConfiguration,
+
+    /// The configuration of the completions
+    #[partial(
+        type,
+        bpaf(external(partial_completions_configuration), optional, hide_usage)
+    )]
+    pub completions: CompletionsConfiguration,
 }
 
 impl PartialConfiguration {
@@ -110,10 +121,13 @@ impl PartialConfiguration {
                 username: Some("postgres".to_string()),
                 password: Some("postgres".to_string()),
                 database: Some("postgres".to_string()),
+                connections: Default::default(),
                 allow_statement_executions_against: Default::default(),
                 conn_timeout_secs: Some(10),
+                idle_timeout_ms: Some(600_000),
                 disable_connection: Some(false),
             }),
+            completions: None,
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::{borrow::Cow, fmt, io, time::Duration};
 
-pub use crate::write::{HTML, Termcolor, Write};
+pub use crate::write::{ColorTheme, HTML, Termcolor, Write};
 use crate::{Markup, MarkupElement, markup};
 
 /// A stack-allocated linked-list of [MarkupElement] slices
@@ -38,6 +38,31 @@ impl<'a> MarkupElements<'a> {
     }
 }
 
+/// How a single level of indentation is rendered
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndentStyle {
+    /// A tab character per level
+    Tab,
+    /// The given number of spaces per level
+    Spaces(u8),
+}
+
+impl Default for IndentStyle {
+    fn default() -> Self {
+        Self::Spaces(2)
+    }
+}
+
+impl IndentStyle {
+    /// Returns the text of `levels` levels of indentation
+    pub fn render(&self, levels: usize) -> String {
+        match self {
+            IndentStyle::Tab => "\t".repeat(levels),
+            IndentStyle::Spaces(count) => " ".repeat(usize::from(*count) * levels),
+        }
+    }
+}
+
 /// The [Formatter] is the `pgt_console` equivalent to [std::fmt::Formatter]:
 /// it's never constructed directly by consumers, and can only be used through
 /// the mutable reference passed to implementations of the [Display] trait).
@@ -49,6 +74,8 @@ pub struct Formatter<'fmt> {
     state: MarkupElements<'fmt>,
     /// Inner IO writer this [Formatter] will print text into
     writer: &'fmt mut dyn Write,
+    /// How nested blocks printed through this [Formatter] are indented
+    indent_style: IndentStyle,
 }
 
 impl<'fmt> Formatter<'fmt> {
@@ -57,9 +84,20 @@ impl<'fmt> Formatter<'fmt> {
         Self {
             state: MarkupElements::Root,
             writer,
+            indent_style: IndentStyle::default(),
         }
     }
 
+    /// Sets how nested blocks printed through this [Formatter] are indented
+    pub fn with_indent_style(mut self, indent_style: IndentStyle) -> Self {
+        self.indent_style = indent_style;
+        self
+    }
+
+    pub fn indent_style(&self) -> IndentStyle {
+        self.indent_style
+    }
+
     pub fn wrap_writer<'b: 'c, 'c>(
         &'b mut self,
         wrap: impl FnOnce(&'b mut dyn Write) -> &'c mut dyn Write,
@@ -67,6 +105,7 @@ impl<'fmt> Formatter<'fmt> {
         Formatter {
             state: self.state,
             writer: wrap(self.writer),
+            indent_style: self.indent_style,
         }
     }
 
@@ -75,6 +114,7 @@ impl<'fmt> Formatter<'fmt> {
         Formatter {
             state: MarkupElements::Node(&self.state, elements),
             writer: self.writer,
+            indent_style: self.indent_style,
         }
     }
 
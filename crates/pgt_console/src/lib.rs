@@ -1,18 +1,20 @@
 //! # pgt_console
 
 use std::io;
-use std::io::{IsTerminal, Read, Write};
+use std::io::{IsTerminal, Read};
 use std::panic::RefUnwindSafe;
-use termcolor::{ColorChoice, StandardStream};
-use write::Termcolor;
+use termcolor::{BufferWriter, ColorChoice, WriteColor};
+use write::{ColorTheme, Termcolor};
 
 pub mod fmt;
 mod markup;
+mod prefix;
 mod utils;
 mod write;
 
 pub use self::markup::{Markup, MarkupBuf, MarkupElement, MarkupNode};
 pub use pgt_markup::markup;
+pub use prefix::PrefixConsole;
 pub use utils::*;
 
 /// Determines the "output stream" a message should get printed to
@@ -74,11 +76,16 @@ impl<T: Console + ?Sized> ConsoleExt for T {
 /// Implementation of [Console] printing messages to the standard output and standard error
 pub struct EnvConsole {
     /// Channel to print messages
-    out: StandardStream,
+    out: BufferWriter,
     /// Channel to print errors
-    err: StandardStream,
+    err: BufferWriter,
     /// Channel to read arbitrary input
     r#in: io::Stdin,
+    /// Set once the reader of a channel went away, e.g. when piping into `head`
+    out_closed: bool,
+    err_closed: bool,
+    /// The colors of the markup
+    theme: ColorTheme,
 }
 
 #[derive(Debug, Clone)]
@@ -120,16 +127,23 @@ impl EnvConsole {
         let (out_mode, err_mode) = Self::compute_color(colors);
 
         Self {
-            out: StandardStream::stdout(out_mode),
-            err: StandardStream::stderr(err_mode),
+            out: BufferWriter::stdout(out_mode),
+            err: BufferWriter::stderr(err_mode),
             r#in: io::stdin(),
+            out_closed: false,
+            err_closed: false,
+            theme: ColorTheme::default(),
         }
     }
 
     pub fn set_color(&mut self, colors: ColorMode) {
         let (out_mode, err_mode) = Self::compute_color(colors);
-        self.out = StandardStream::stdout(out_mode);
-        self.err = StandardStream::stderr(err_mode);
+        self.out = BufferWriter::stdout(out_mode);
+        self.err = BufferWriter::stderr(err_mode);
+    }
+
+    pub fn set_theme(&mut self, theme: ColorTheme) {
+        self.theme = theme;
     }
 }
 
@@ -139,31 +153,58 @@ impl Default for EnvConsole {
     }
 }
 
-impl Console for EnvConsole {
-    fn println(&mut self, level: LogLevel, args: Markup) {
-        let mut out = match level {
-            LogLevel::Error => self.err.lock(),
-            LogLevel::Log => self.out.lock(),
+impl EnvConsole {
+    fn write(&mut self, level: LogLevel, args: Markup, newline: bool) {
+        let theme = self.theme;
+        let (stream, closed) = match level {
+            LogLevel::Error => (&self.err, &mut self.err_closed),
+            LogLevel::Log => (&self.out, &mut self.out_closed),
         };
 
-        fmt::Formatter::new(&mut Termcolor(&mut out))
-            .write_markup(args)
-            .unwrap();
+        if *closed {
+            return;
+        }
 
-        writeln!(out).unwrap();
+        // the message is written and flushed at once, instead of with every styled element
+        let mut buffer = stream.buffer();
+        let result =
+            write_message(&mut buffer, args, newline, theme).and_then(|()| stream.print(&buffer));
+        *closed = handle_write_result(result);
     }
+}
 
-    fn print(&mut self, level: LogLevel, args: Markup) {
-        let mut out = match level {
-            LogLevel::Error => self.err.lock(),
-            LogLevel::Log => self.out.lock(),
-        };
+/// Writes `args` to `out` using the colors of `theme`.
+fn write_message<W: WriteColor>(
+    out: &mut W,
+    args: Markup,
+    newline: bool,
+    theme: ColorTheme,
+) -> io::Result<()> {
+    fmt::Formatter::new(&mut Termcolor::with_theme(&mut *out, theme)).write_markup(args)?;
+
+    if newline {
+        writeln!(out)?;
+    }
 
-        fmt::Formatter::new(&mut Termcolor(&mut out))
-            .write_markup(args)
-            .unwrap();
+    Ok(())
+}
+
+/// Returns true if the stream can no longer be written to, so further output is discarded.
+///
+/// A broken pipe is expected when the output is piped into a command like `head`,
+/// which exits after reading what it needs. Any other error means the stream is unusable
+/// as well, and a failing console must not bring down the command that prints to it.
+fn handle_write_result(result: io::Result<()>) -> bool {
+    result.is_err()
+}
 
-        write!(out, "").unwrap();
+impl Console for EnvConsole {
+    fn println(&mut self, level: LogLevel, args: Markup) {
+        self.write(level, args, true);
+    }
+
+    fn print(&mut self, level: LogLevel, args: Markup) {
+        self.write(level, args, false);
     }
 
     fn read(&mut self) -> Option<String> {
@@ -195,6 +236,54 @@ impl BufferConsole {
         self.print_json = true;
         self
     }
+
+    /// Serializes all printed messages into a deterministic JSON array of
+    /// `{ "level", "content" }` objects, with the markup flattened to plain text.
+    ///
+    /// Useful for snapshot testing the output of a command.
+    pub fn to_snapshot(&self) -> String {
+        if self.out_buffer.is_empty() {
+            return String::from("[]");
+        }
+
+        let entries: Vec<String> = self
+            .out_buffer
+            .iter()
+            .map(|message| {
+                let level = match message.level {
+                    LogLevel::Error => "error",
+                    LogLevel::Log => "log",
+                };
+                let content = StdDisplay(&message.content).to_string();
+
+                format!(
+                    "  {{ \"level\": \"{level}\", \"content\": {} }}",
+                    json_string(&content)
+                )
+            })
+            .collect();
+
+        format!("[\n{}\n]", entries.join(",\n"))
+    }
+}
+
+/// Quotes and escapes `value` as a JSON string literal
+fn json_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
 }
 
 /// Individual message entry printed to a [BufferConsole]
@@ -228,3 +317,84 @@ impl Console for BufferConsole {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate as pgt_console;
+    use crate::fmt::ColorTheme;
+    use crate::{BufferConsole, ConsoleExt, handle_write_result, markup, write_message};
+
+    #[test]
+    fn snapshots_printed_messages() {
+        let mut console = BufferConsole::default();
+
+        console.log(markup! { "Checked "<Emphasis>"2"</Emphasis>" files." });
+        console.error(markup! { <Error>"Something \"went\" wrong"</Error> });
+        console.log(markup! { "first line\nsecond line" });
+
+        assert_eq!(
+            console.to_snapshot(),
+            r#"[
+  { "level": "log", "content": "Checked 2 files." },
+  { "level": "error", "content": "Something \"went\" wrong" },
+  { "level": "log", "content": "first line\nsecond line" }
+]"#
+        );
+    }
+
+    #[test]
+    fn snapshots_empty_console() {
+        assert_eq!(BufferConsole::default().to_snapshot(), "[]");
+    }
+
+    /// A writer whose reader went away
+    struct BrokenPipe;
+
+    impl std::io::Write for BrokenPipe {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+    }
+
+    #[test]
+    fn tolerates_broken_pipes() {
+        let mut out = termcolor::NoColor::new(BrokenPipe);
+
+        let result = write_message(
+            &mut out,
+            markup! { "Checked 1 file." },
+            true,
+            ColorTheme::default(),
+        );
+
+        assert_eq!(
+            result.as_ref().map_err(|err| err.kind()),
+            Err(std::io::ErrorKind::BrokenPipe)
+        );
+        assert!(handle_write_result(result));
+    }
+
+    #[test]
+    fn writes_with_the_given_theme() {
+        let render = |theme| {
+            let mut out = termcolor::Ansi::new(Vec::new());
+            write_message(&mut out, markup! { <Error>"failed"</Error> }, false, theme).unwrap();
+            String::from_utf8(out.into_inner()).unwrap()
+        };
+
+        assert_ne!(render(ColorTheme::DARK), render(ColorTheme::LIGHT));
+        assert_eq!(render(ColorTheme::default()), render(ColorTheme::DARK));
+    }
+
+    #[test]
+    fn discards_output_after_other_write_errors() {
+        assert!(handle_write_result(Err(
+            std::io::ErrorKind::PermissionDenied.into()
+        )));
+        assert!(!handle_write_result(Ok(())));
+    }
+}
@@ -2,12 +2,14 @@ use std::{
     borrow::Cow,
     fmt::{self, Debug},
     io,
+    ops::{Add, AddAssign},
 };
 
 use pgt_text_size::TextSize;
-use termcolor::{Color, ColorSpec};
+use termcolor::ColorSpec;
+use unicode_segmentation::UnicodeSegmentation;
 
-use crate::fmt::{Display, Formatter, MarkupElements, Write};
+use crate::fmt::{ColorTheme, Display, Formatter, MarkupElements, Write};
 
 /// Enumeration of all the supported markup elements
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -44,8 +46,8 @@ impl fmt::Display for MarkupElement<'_> {
 
 impl MarkupElement<'_> {
     /// Mutate a [ColorSpec] object in place to apply this element's associated
-    /// style to it
-    pub(crate) fn update_color(&self, color: &mut ColorSpec) {
+    /// style to it, using the colors of `theme`
+    pub(crate) fn update_color(&self, color: &mut ColorSpec, theme: &ColorTheme) {
         match self {
             // Text Styles
             MarkupElement::Emphasis => {
@@ -62,26 +64,13 @@ impl MarkupElement<'_> {
             }
 
             // Text Colors
-            MarkupElement::Error => {
-                color.set_fg(Some(Color::Red));
-            }
-            MarkupElement::Success => {
-                color.set_fg(Some(Color::Green));
-            }
-            MarkupElement::Warn => {
-                color.set_fg(Some(Color::Yellow));
-            }
-            MarkupElement::Trace => {
-                color.set_fg(Some(Color::Magenta));
-            }
-            MarkupElement::Info | MarkupElement::Debug => {
-                // Blue is really difficult to see on the standard windows command line
-                #[cfg(windows)]
-                const BLUE: Color = Color::Cyan;
-                #[cfg(not(windows))]
-                const BLUE: Color = Color::Blue;
-
-                color.set_fg(Some(BLUE));
+            MarkupElement::Error
+            | MarkupElement::Success
+            | MarkupElement::Warn
+            | MarkupElement::Trace
+            | MarkupElement::Info
+            | MarkupElement::Debug => {
+                color.set_fg(theme.color(self));
             }
 
             MarkupElement::Inverse | MarkupElement::Hyperlink { .. } => {}
@@ -195,6 +184,106 @@ impl MarkupBuf {
             .iter()
             .fold(0, |acc, string| acc + string.content.len())
     }
+
+    /// Appends a single node at the end of this buffer.
+    ///
+    /// If the last node has the same elements, the content is merged into it.
+    pub fn push_node(&mut self, node: MarkupNodeBuf) {
+        if let Some(last) = self.0.last_mut() {
+            if last.elements == node.elements {
+                last.content.push_str(&node.content);
+                return;
+            }
+        }
+
+        self.0.push(node);
+    }
+
+    /// Appends a piece of text without any markup elements applied to it.
+    pub fn push_str(&mut self, content: &str) {
+        self.push_node(MarkupNodeBuf {
+            elements: Vec::new(),
+            content: content.into(),
+        });
+    }
+
+    /// Appends a piece of [Markup], usually created with the `markup!` macro.
+    pub fn push_markup(&mut self, markup: Markup) {
+        // SAFETY: The implementation of Write for MarkupBuf always returns Ok
+        Formatter::new(self).write_markup(markup).unwrap();
+    }
+
+    /// Moves all nodes of `other` to the end of this buffer.
+    pub fn append(&mut self, other: MarkupBuf) {
+        self.extend(other.0);
+    }
+
+    /// Returns the content of all nodes without any markup elements.
+    pub fn to_text(&self) -> String {
+        self.0.iter().map(|node| node.content.as_str()).collect()
+    }
+
+    /// Shortens the content to at most `max` grapheme clusters and appends `ellipsis`
+    /// if anything was cut off.
+    ///
+    /// Grapheme clusters are never split, so multi-byte characters stay intact.
+    pub fn truncate_graphemes(&mut self, max: usize, ellipsis: &str) {
+        let mut remaining = max;
+
+        let Some(idx) = self.0.iter().position(|node| {
+            let count = node.content.graphemes(true).count();
+            if count > remaining {
+                return true;
+            }
+            remaining -= count;
+            false
+        }) else {
+            return;
+        };
+
+        let node = &mut self.0[idx];
+        let end = node
+            .content
+            .grapheme_indices(true)
+            .nth(remaining)
+            .map_or(node.content.len(), |(offset, _)| offset);
+        node.content.truncate(end);
+
+        self.0.truncate(idx + 1);
+        self.0.retain(|node| !node.content.is_empty());
+        self.push_str(ellipsis);
+    }
+}
+
+impl Extend<MarkupNodeBuf> for MarkupBuf {
+    fn extend<T: IntoIterator<Item = MarkupNodeBuf>>(&mut self, iter: T) {
+        for node in iter {
+            self.push_node(node);
+        }
+    }
+}
+
+impl<'fmt> Extend<MarkupNode<'fmt>> for MarkupBuf {
+    fn extend<T: IntoIterator<Item = MarkupNode<'fmt>>>(&mut self, iter: T) {
+        for node in iter {
+            self.push_markup(Markup(&[node]));
+        }
+    }
+}
+
+impl Add for MarkupBuf {
+    type Output = MarkupBuf;
+
+    fn add(mut self, rhs: MarkupBuf) -> Self::Output {
+        self.append(rhs);
+        self
+    }
+}
+
+impl AddAssign for MarkupBuf {
+    fn add_assign(&mut self, rhs: MarkupBuf) {
+        self.append(rhs);
+    }
 }
 
 impl Write for MarkupBuf {
@@ -265,3 +354,118 @@ impl Debug for MarkupBuf {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate as pgt_console;
+    use crate::{MarkupBuf, MarkupElement, MarkupNode};
+    use pgt_markup::markup;
+
+    use super::MarkupNodeBuf;
+
+    fn node(elements: Vec<MarkupElement<'static>>, content: &str) -> MarkupNodeBuf {
+        MarkupNodeBuf {
+            elements,
+            content: content.into(),
+        }
+    }
+
+    #[test]
+    fn concatenates_buffers_in_order() {
+        let first = markup! { <Error>"error"</Error>" in " }.to_owned();
+        let second = markup! { <Emphasis>"file.sql"</Emphasis> }.to_owned();
+
+        let result = first + second;
+
+        assert_eq!(
+            result.0,
+            vec![
+                node(vec![MarkupElement::Error], "error"),
+                node(vec![], " in "),
+                node(vec![MarkupElement::Emphasis], "file.sql"),
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_adjacent_nodes_with_same_elements() {
+        let mut result = markup! { <Warn>"one"</Warn> }.to_owned();
+
+        result += markup! { <Warn>" two"</Warn> }.to_owned();
+        result.push_str(" three");
+        result.push_str(" four");
+
+        assert_eq!(
+            result.0,
+            vec![
+                node(vec![MarkupElement::Warn], "one two"),
+                node(vec![], " three four"),
+            ]
+        );
+    }
+
+    #[test]
+    fn extends_with_markup_nodes() {
+        let mut result = MarkupBuf::default();
+        result.push_str("prefix ");
+
+        result.extend([
+            MarkupNode {
+                elements: &[MarkupElement::Info],
+                content: &"info",
+            },
+            MarkupNode {
+                elements: &[],
+                content: &42,
+            },
+        ]);
+
+        result.push_markup(markup! { <Dim>" suffix"</Dim> });
+
+        assert_eq!(
+            result.0,
+            vec![
+                node(vec![], "prefix "),
+                node(vec![MarkupElement::Info], "info"),
+                node(vec![], "42"),
+                node(vec![MarkupElement::Dim], " suffix"),
+            ]
+        );
+    }
+
+    #[test]
+    fn truncates_at_grapheme_boundaries() {
+        let mut result = MarkupBuf::default();
+        // the family emoji is a single grapheme made up of several code points
+        result.push_str("ab👨‍👩‍👧e\u{301}d");
+
+        result.truncate_graphemes(3, "…");
+
+        assert_eq!(result.to_text(), "ab👨‍👩‍👧…");
+    }
+
+    #[test]
+    fn truncates_across_nodes() {
+        let mut result = markup! { <Error>"héllo"</Error>" wörld" }.to_owned();
+
+        result.truncate_graphemes(7, "…");
+
+        assert_eq!(
+            result.0,
+            vec![
+                node(vec![MarkupElement::Error], "héllo"),
+                node(vec![], " w…"),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_content_that_fits() {
+        let mut result = MarkupBuf::default();
+        result.push_str("e\u{301}té");
+
+        result.truncate_graphemes(3, "…");
+
+        assert_eq!(result.to_text(), "e\u{301}té");
+    }
+}
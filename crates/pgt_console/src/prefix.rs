@@ -0,0 +1,136 @@
+use crate::markup::MarkupNodeBuf;
+use crate::{Console, LogLevel, Markup, MarkupBuf, markup};
+
+/// Implementation of [Console] that prefixes every printed line with a tag, e.g. `[pgt] `,
+/// before passing it on to another console.
+///
+/// Useful when the output is embedded into a larger log, like the one of a CI job.
+pub struct PrefixConsole<C: Console> {
+    inner: C,
+    prefix: String,
+    /// Whether the next message starts a new line on the respective channel
+    out_at_line_start: bool,
+    err_at_line_start: bool,
+}
+
+impl<C: Console> PrefixConsole<C> {
+    pub fn new(inner: C, prefix: impl Into<String>) -> Self {
+        Self {
+            inner,
+            prefix: prefix.into(),
+            out_at_line_start: true,
+            err_at_line_start: true,
+        }
+    }
+
+    /// Returns the wrapped console
+    pub fn into_inner(self) -> C {
+        self.inner
+    }
+
+    /// Inserts the prefix at the start of every line of `args`.
+    ///
+    /// The prefix never carries the markup elements of the surrounding text, so styles
+    /// spanning multiple lines are interrupted for it and resumed afterwards.
+    fn prefix_lines(&mut self, level: LogLevel, args: Markup, newline: bool) -> MarkupBuf {
+        let at_line_start = match level {
+            LogLevel::Error => &mut self.err_at_line_start,
+            LogLevel::Log => &mut self.out_at_line_start,
+        };
+
+        let mut result = MarkupBuf::default();
+
+        for node in args.to_owned().0 {
+            for line in node.content.split_inclusive('\n') {
+                if *at_line_start {
+                    result.push_str(&self.prefix);
+                }
+
+                result.push_node(MarkupNodeBuf {
+                    elements: node.elements.clone(),
+                    content: line.into(),
+                });
+                *at_line_start = line.ends_with('\n');
+            }
+        }
+
+        if newline {
+            // empty lines carry the prefix as well
+            if *at_line_start {
+                result.push_str(&self.prefix);
+            }
+            *at_line_start = true;
+        }
+
+        result
+    }
+}
+
+impl<C: Console> Console for PrefixConsole<C> {
+    fn println(&mut self, level: LogLevel, args: Markup) {
+        use crate as pgt_console;
+
+        let content = self.prefix_lines(level, args, true);
+        self.inner.println(level, markup! {{content}});
+    }
+
+    fn print(&mut self, level: LogLevel, args: Markup) {
+        use crate as pgt_console;
+
+        let content = self.prefix_lines(level, args, false);
+        self.inner.print(level, markup! {{content}});
+    }
+
+    fn read(&mut self) -> Option<String> {
+        self.inner.read()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as pgt_console;
+    use crate::{BufferConsole, ConsoleExt, MarkupElement, PrefixConsole, StdDisplay, markup};
+
+    #[test]
+    fn prefixes_every_line() {
+        let mut console = PrefixConsole::new(BufferConsole::default(), "[pgt] ");
+
+        console.log(markup! { "Checked "<Emphasis>"2"</Emphasis>" files." });
+        console.error(markup! { <Error>"first line\nsecond line"</Error> });
+        console.append(markup! { "start of a line, " });
+        console.log(markup! { "end of it\n" });
+
+        let console = console.into_inner();
+        let contents: Vec<String> = console
+            .out_buffer
+            .iter()
+            .map(|message| StdDisplay(&message.content).to_string())
+            .collect();
+
+        assert_eq!(
+            contents,
+            vec![
+                "[pgt] Checked 2 files.",
+                "[pgt] first line\n[pgt] second line",
+                "[pgt] start of a line, ",
+                "end of it\n[pgt] ",
+            ]
+        );
+
+        // all messages but the third one are printed with a new line at the end
+        let rendered = format!(
+            "{}\n{}\n{}{}\n",
+            contents[0], contents[1], contents[2], contents[3]
+        );
+        assert!(rendered.lines().all(|line| line.starts_with("[pgt] ")));
+
+        // the style spanning both lines is resumed after the prefix
+        let error = &console.out_buffer[1].content.0;
+        assert_eq!(error[0].elements, vec![]);
+        assert_eq!(error[1].elements, vec![MarkupElement::Error]);
+        assert_eq!(error[2].content, "[pgt] ");
+        assert_eq!(error[2].elements, vec![]);
+        assert_eq!(error[3].content, "second line");
+        assert_eq!(error[3].elements, vec![MarkupElement::Error]);
+    }
+}
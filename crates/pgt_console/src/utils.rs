@@ -14,7 +14,7 @@ where
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let mut buffer: Vec<u8> = Vec::new();
-        let mut termcolor = Termcolor(NoColor::new(&mut buffer));
+        let mut termcolor = Termcolor::new(NoColor::new(&mut buffer));
         let mut formatter = Formatter::new(&mut termcolor);
 
         self.0.fmt(&mut formatter).map_err(|_| std::fmt::Error)?;
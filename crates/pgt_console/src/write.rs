@@ -1,11 +1,12 @@
 mod html;
 mod termcolor;
+mod theme;
 
 use std::{fmt, io};
 
 use crate::fmt::MarkupElements;
 
-pub use self::{html::HTML, termcolor::Termcolor};
+pub use self::{html::HTML, termcolor::Termcolor, theme::ColorTheme};
 
 pub trait Write {
     fn write_str(&mut self, elements: &MarkupElements, content: &str) -> io::Result<()>;
@@ -175,6 +175,24 @@ mod test {
         assert_eq!(String::from_utf8(buf).unwrap(), "Hello<br />World");
     }
 
+    #[test]
+    fn test_hyperlinks() {
+        let mut buf = Vec::new();
+        let mut writer = super::HTML(&mut buf, false);
+        let mut formatter = Formatter::new(&mut writer);
+
+        formatter
+            .write_markup(markup! {
+                <Hyperlink href="https://pgtools.dev">"docs"</Hyperlink>
+            })
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "<a href=\"https://pgtools.dev\">docs</a>"
+        );
+    }
+
     #[test]
     fn test_escapes() {
         let mut buf = Vec::new();
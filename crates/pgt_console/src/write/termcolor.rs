@@ -9,17 +9,36 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::{MarkupElement, fmt::MarkupElements};
 
-use super::Write;
+use super::{ColorTheme, Write};
 
 /// Adapter struct implementing [Write] over types implementing [WriteColor]
-pub struct Termcolor<W>(pub W);
+pub struct Termcolor<W> {
+    writer: W,
+    theme: ColorTheme,
+}
+
+impl<W> Termcolor<W> {
+    /// Creates a writer using the default [ColorTheme]
+    pub fn new(writer: W) -> Self {
+        Self::with_theme(writer, ColorTheme::default())
+    }
+
+    pub fn with_theme(writer: W, theme: ColorTheme) -> Self {
+        Self { writer, theme }
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
 
 impl<W> Write for Termcolor<W>
 where
     W: WriteColor,
 {
     fn write_str(&mut self, elements: &MarkupElements, content: &str) -> io::Result<()> {
-        with_format(&mut self.0, elements, |writer| {
+        with_format(&mut self.writer, &self.theme, elements, |writer| {
             let mut adapter = SanitizeAdapter {
                 writer,
                 error: Ok(()),
@@ -41,7 +60,7 @@ where
     }
 
     fn write_fmt(&mut self, elements: &MarkupElements, content: fmt::Arguments) -> io::Result<()> {
-        with_format(&mut self.0, elements, |writer| {
+        with_format(&mut self.writer, &self.theme, elements, |writer| {
             let mut adapter = SanitizeAdapter {
                 writer,
                 error: Ok(()),
@@ -61,10 +80,11 @@ where
     }
 }
 
-/// Applies the current format in `state` to `writer`, calls `func` to
-/// print a piece of text, then reset the printing format
+/// Applies the current format in `state` to `writer` using the colors of
+/// `theme`, calls `func` to print a piece of text, then reset the printing format
 fn with_format<W>(
     writer: &mut W,
+    theme: &ColorTheme,
     state: &MarkupElements,
     func: impl FnOnce(&mut W) -> io::Result<()>,
 ) -> io::Result<()>
@@ -85,7 +105,7 @@ where
                     link = Some(href);
                 }
                 _ => {
-                    element.update_color(&mut color);
+                    element.update_color(&mut color, theme);
                 }
             }
         }
@@ -214,7 +234,64 @@ fn unicode_to_ascii(c: char) -> char {
 mod tests {
     use std::{fmt::Write, str::from_utf8};
 
-    use super::SanitizeAdapter;
+    use super::{ColorTheme, SanitizeAdapter, Termcolor};
+    use crate as pgt_console;
+    use crate::{fmt::Formatter, markup};
+
+    fn render_with_theme(theme: ColorTheme) -> String {
+        let mut writer = Termcolor::with_theme(termcolor::Ansi::new(Vec::new()), theme);
+
+        Formatter::new(&mut writer)
+            .write_markup(markup! { <Error>"failed"</Error>" and "<Warn>"warned"</Warn> })
+            .unwrap();
+
+        String::from_utf8(writer.into_inner().into_inner()).unwrap()
+    }
+
+    #[test]
+    fn themes_produce_different_colors() {
+        let dark = render_with_theme(ColorTheme::DARK);
+        let light = render_with_theme(ColorTheme::LIGHT);
+
+        assert_ne!(dark, light);
+
+        // red and yellow in the dark theme, 256-color shades in the light theme
+        assert!(dark.contains("\x1b[0m\x1b[31m"));
+        assert!(dark.contains("\x1b[0m\x1b[33m"));
+        assert!(light.contains("\x1b[0m\x1b[38;5;124m"));
+        assert!(light.contains("\x1b[0m\x1b[38;5;130m"));
+    }
+
+    #[test]
+    fn hyperlinks_emit_osc_8() {
+        let mut writer = Termcolor::new(termcolor::Ansi::new(Vec::new()));
+
+        Formatter::new(&mut writer)
+            .write_markup(markup! { <Hyperlink href="https://pgtools.dev">"docs"</Hyperlink> })
+            .unwrap();
+
+        let output = String::from_utf8(writer.into_inner().into_inner()).unwrap();
+
+        assert!(output.contains("\x1b]8;;https://pgtools.dev\x1b\\docs\x1b]8;;\x1b\\"));
+    }
+
+    #[test]
+    fn hyperlinks_degrade_to_plain_text_without_colors() {
+        let mut writer = Termcolor::new(termcolor::NoColor::new(Vec::new()));
+
+        Formatter::new(&mut writer)
+            .write_markup(markup! { <Hyperlink href="https://pgtools.dev">"docs"</Hyperlink> })
+            .unwrap();
+
+        let output = String::from_utf8(writer.into_inner().into_inner()).unwrap();
+
+        assert_eq!(output, "docs");
+    }
+
+    #[test]
+    fn default_theme_is_dark() {
+        assert_eq!(ColorTheme::default(), ColorTheme::DARK);
+    }
 
     #[test]
     fn test_printing_complex_emojis() {
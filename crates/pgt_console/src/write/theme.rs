@@ -0,0 +1,68 @@
+use termcolor::Color;
+
+use crate::MarkupElement;
+
+/// Maps the colored [MarkupElement] kinds to concrete terminal colors
+///
+/// The default is [ColorTheme::DARK], which suits terminals with a dark
+/// background. [ColorTheme::LIGHT] uses darker shades that stay readable on a
+/// light background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorTheme {
+    pub error: Color,
+    pub success: Color,
+    pub warn: Color,
+    pub info: Color,
+    pub debug: Color,
+    pub trace: Color,
+}
+
+// Blue is really difficult to see on the standard windows command line
+#[cfg(windows)]
+const BLUE: Color = Color::Cyan;
+#[cfg(not(windows))]
+const BLUE: Color = Color::Blue;
+
+impl ColorTheme {
+    pub const DARK: ColorTheme = ColorTheme {
+        error: Color::Red,
+        success: Color::Green,
+        warn: Color::Yellow,
+        info: BLUE,
+        debug: BLUE,
+        trace: Color::Magenta,
+    };
+
+    pub const LIGHT: ColorTheme = ColorTheme {
+        error: Color::Ansi256(124),
+        success: Color::Ansi256(28),
+        warn: Color::Ansi256(130),
+        info: Color::Ansi256(25),
+        debug: Color::Ansi256(25),
+        trace: Color::Ansi256(90),
+    };
+
+    /// Returns the foreground color of `element`, if it is a colored element
+    pub fn color(&self, element: &MarkupElement) -> Option<Color> {
+        match element {
+            MarkupElement::Error => Some(self.error),
+            MarkupElement::Success => Some(self.success),
+            MarkupElement::Warn => Some(self.warn),
+            MarkupElement::Info => Some(self.info),
+            MarkupElement::Debug => Some(self.debug),
+            MarkupElement::Trace => Some(self.trace),
+            MarkupElement::Emphasis
+            | MarkupElement::Dim
+            | MarkupElement::Italic
+            | MarkupElement::Underline
+            | MarkupElement::Inverse
+            | MarkupElement::Hyperlink { .. } => None,
+        }
+    }
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
@@ -3,6 +3,7 @@ use pgt_console::{
     HorizontalLine, Markup, MarkupBuf, MarkupElement, MarkupNode, Padding, fmt, markup,
 };
 use pgt_text_edit::TextEdit;
+use std::borrow::Cow;
 use std::path::Path;
 use std::{env, io, iter};
 use unicode_width::UnicodeWidthStr;
@@ -78,7 +79,7 @@ impl<D: AsDiagnostic + ?Sized> fmt::Display for PrintDiagnostic<'_, D> {
         })?;
         // Wrap the formatter with an indentation level and print the advices
         let mut slot = None;
-        let mut fmt = IndentWriter::wrap(fmt, &mut slot, true, "  ");
+        let mut fmt = IndentWriter::wrap_level(fmt, &mut slot, true);
 
         if self.search {
             let mut visitor = PrintSearch(&mut fmt);
@@ -356,7 +357,7 @@ impl<D: Diagnostic + ?Sized> fmt::Display for PrintCauseChain<'_, D> {
             fmt.write_str("\n\nCaused by:\n")?;
 
             let mut slot = None;
-            let mut fmt = IndentWriter::wrap(fmt, &mut slot, true, "  ");
+            let mut fmt = IndentWriter::wrap_level(fmt, &mut slot, true);
             diagnostic.message(&mut fmt)?;
         }
 
@@ -390,7 +391,7 @@ impl PrintAdvices<'_, '_> {
         self.0.write_str(" ")?;
 
         let mut slot = None;
-        let mut fmt = IndentWriter::wrap(self.0, &mut slot, false, "  ");
+        let mut fmt = IndentWriter::wrap_level(self.0, &mut slot, false);
         fmt.write_markup(Markup(&[MarkupNode {
             elements: &[kind],
             content: text,
@@ -413,7 +414,7 @@ impl Visit for PrintAdvices<'_, '_> {
     fn record_list(&mut self, list: &[&dyn fmt::Display]) -> io::Result<()> {
         for item in list {
             let mut slot = None;
-            let mut fmt = IndentWriter::wrap(self.0, &mut slot, false, "  ");
+            let mut fmt = IndentWriter::wrap_level(self.0, &mut slot, false);
             fmt.write_markup(markup! {
                 "- "{*item}"\n"
             })?;
@@ -463,7 +464,7 @@ impl Visit for PrintAdvices<'_, '_> {
         })?;
 
         let mut slot = None;
-        let mut fmt = IndentWriter::wrap(self.0, &mut slot, true, "  ");
+        let mut fmt = IndentWriter::wrap_level(self.0, &mut slot, true);
         let mut visitor = PrintAdvices(&mut fmt);
         advice.record(&mut visitor)
     }
@@ -601,7 +602,7 @@ impl<D: Diagnostic + ?Sized> Advices for PrintVerboseAdvices<'_, D> {
 struct IndentWriter<'a, W: ?Sized> {
     writer: &'a mut W,
     pending_indent: bool,
-    ident_text: &'static str,
+    ident_text: Cow<'static, str>,
 }
 
 impl<'write> IndentWriter<'write, dyn fmt::Write + 'write> {
@@ -609,8 +610,9 @@ impl<'write> IndentWriter<'write, dyn fmt::Write + 'write> {
         fmt: &'fmt mut fmt::Formatter<'_>,
         slot: &'slot mut Option<Self>,
         pending_indent: bool,
-        ident_text: &'static str,
+        ident_text: impl Into<Cow<'static, str>>,
     ) -> fmt::Formatter<'slot> {
+        let ident_text = ident_text.into();
         fmt.wrap_writer(|writer| {
             slot.get_or_insert(Self {
                 writer,
@@ -619,6 +621,16 @@ impl<'write> IndentWriter<'write, dyn fmt::Write + 'write> {
             })
         })
     }
+
+    /// Indents the content by one level, rendered in the indent style of `fmt`
+    fn wrap_level<'slot, 'fmt: 'write + 'slot>(
+        fmt: &'fmt mut fmt::Formatter<'_>,
+        slot: &'slot mut Option<Self>,
+        pending_indent: bool,
+    ) -> fmt::Formatter<'slot> {
+        let ident_text = fmt.indent_style().render(1);
+        Self::wrap(fmt, slot, pending_indent, ident_text)
+    }
 }
 
 impl<W: fmt::Write + ?Sized> fmt::Write for IndentWriter<'_, W> {
@@ -630,7 +642,7 @@ impl<W: fmt::Write + ?Sized> fmt::Write for IndentWriter<'_, W> {
         while !content.is_empty() {
             if self.pending_indent {
                 self.writer
-                    .write_str(&MarkupElements::Root, self.ident_text)?;
+                    .write_str(&MarkupElements::Root, &self.ident_text)?;
                 self.pending_indent = false;
             }
 
@@ -665,7 +677,7 @@ impl<W: fmt::Write + ?Sized> fmt::Write for IndentWriter<'_, W> {
 mod tests {
     use std::io;
 
-    use pgt_console::{fmt, markup};
+    use pgt_console::{fmt, fmt::IndentStyle, markup};
     use pgt_diagnostics::{DiagnosticTags, Severity};
     use pgt_diagnostics_categories::{Category, category};
     use pgt_text_edit::TextEdit;
@@ -1078,4 +1090,41 @@ mod tests {
             "\nactual:\n{diag:#?}\nexpected:\n{expected:#?}"
         );
     }
+
+    /// Prints a diagnostic with a group advice, whose logs are nested two levels deep, and
+    /// returns everything after the header.
+    fn print_nested_block(indent_style: IndentStyle) -> String {
+        let diag = TestDiagnostic {
+            advice: Some(GroupAdvice),
+            ..TestDiagnostic::empty()
+        };
+
+        let mut buffer = Vec::new();
+        {
+            let mut writer = fmt::Termcolor::new(termcolor::NoColor::new(&mut buffer));
+            fmt::Formatter::new(&mut writer)
+                .with_indent_style(indent_style)
+                .write_markup(markup!({ PrintDiagnostic::verbose(&diag) }))
+                .unwrap();
+        }
+
+        let output = String::from_utf8(buffer).unwrap();
+        output.split_once('\n').unwrap().1.to_string()
+    }
+
+    #[test]
+    fn test_indent_with_tabs() {
+        assert_eq!(
+            print_nested_block(IndentStyle::Tab),
+            "\n\t✖ diagnostic message\n\t\n\tGroup Title\n\t\n\t\t✖ error\n\t\t\n\t\t⚠ warn\n\t\t\n\t\tℹ info\n\t\t\n\t\tnone\n\t\t\n"
+        );
+    }
+
+    #[test]
+    fn test_indent_with_spaces() {
+        assert_eq!(
+            print_nested_block(IndentStyle::Spaces(2)),
+            "\n  ✖ diagnostic message\n  \n  Group Title\n  \n    ✖ error\n    \n    ⚠ warn\n    \n    ℹ info\n    \n    none\n    \n"
+        );
+    }
 }
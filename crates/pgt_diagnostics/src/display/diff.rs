@@ -672,7 +672,7 @@ mod tests {
     fn assert_eq_markup(actual: &MarkupBuf, expected: &MarkupBuf) {
         if actual != expected {
             let mut buffer = Buffer::ansi();
-            let mut writer = fmt::Termcolor(&mut buffer);
+            let mut writer = fmt::Termcolor::new(&mut buffer);
             let mut output = fmt::Formatter::new(&mut writer);
 
             output
@@ -78,7 +78,7 @@ impl pgt_console::fmt::Display for MessageAndDescription {
 /// Utility function to transform a [MarkupBuf] into a [String]
 pub fn markup_to_string(markup: &MarkupBuf) -> String {
     let mut buffer = Vec::new();
-    let mut write = Termcolor(NoColor::new(&mut buffer));
+    let mut write = Termcolor::new(NoColor::new(&mut buffer));
     let mut fmt = Formatter::new(&mut write);
     fmt.write_markup(markup! { {markup} })
         .expect("to have written in the buffer");
@@ -122,7 +122,7 @@ fn escape_property<S: AsRef<str>>(value: S) -> String {
 
 fn markup_to_string(markup: &MarkupBuf) -> Option<String> {
     let mut buffer = Vec::new();
-    let mut write = fmt::Termcolor(termcolor::NoColor::new(&mut buffer));
+    let mut write = fmt::Termcolor::new(termcolor::NoColor::new(&mut buffer));
     let mut fmt = fmt::Formatter::new(&mut write);
     fmt.write_markup(markup! { {markup} }).ok()?;
     String::from_utf8(buffer).ok()
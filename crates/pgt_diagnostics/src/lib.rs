@@ -38,7 +38,7 @@ pub use crate::display_github::PrintGitHubDiagnostic;
 pub use crate::error::{Error, Result};
 pub use crate::location::{LineIndex, LineIndexBuf, Location, Resource, SourceCode};
 use pgt_console::fmt::{Formatter, Termcolor};
-use pgt_console::markup;
+use pgt_console::{ColorMode, markup};
 use std::fmt::Write;
 
 pub mod prelude {
@@ -73,7 +73,7 @@ impl DiagnosticTag {
 pub fn print_diagnostic_to_string(diagnostic: &Error) -> String {
     let mut buffer = termcolor::Buffer::no_color();
 
-    Formatter::new(&mut Termcolor(&mut buffer))
+    Formatter::new(&mut Termcolor::new(&mut buffer))
         .write_markup(markup! {
             {PrintDiagnostic::verbose(diagnostic)}
         })
@@ -89,3 +89,77 @@ pub fn print_diagnostic_to_string(diagnostic: &Error) -> String {
 
     content
 }
+
+/// Renders an [Error] to a string, the way the CLI prints it.
+///
+/// [ColorMode::Enabled] emits ANSI escape sequences, while [ColorMode::Disabled] renders
+/// plain text. There is no terminal to detect when rendering to a string, so
+/// [ColorMode::Auto] renders plain text as well.
+pub fn render_diagnostic(diagnostic: &Error, color_mode: ColorMode) -> String {
+    let buffer = match color_mode {
+        ColorMode::Enabled => render_to(termcolor::Ansi::new(Vec::new()), diagnostic).into_inner(),
+        ColorMode::Disabled | ColorMode::Auto => {
+            render_to(termcolor::NoColor::new(Vec::new()), diagnostic).into_inner()
+        }
+    };
+
+    String::from_utf8(buffer).expect("non utf8 in error buffer")
+}
+
+fn render_to<W: termcolor::WriteColor>(writer: W, diagnostic: &Error) -> W {
+    let mut writer = Termcolor::new(writer);
+
+    Formatter::new(&mut writer)
+        .write_markup(markup! {
+            {PrintDiagnostic::simple(diagnostic)}
+        })
+        .expect("failed to emit diagnostic");
+
+    writer.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use pgt_console::{ColorMode, fmt};
+
+    use crate::{Category, Diagnostic, Error, Severity, category, render_diagnostic};
+
+    #[derive(Debug)]
+    struct SimpleDiagnostic;
+
+    impl Diagnostic for SimpleDiagnostic {
+        fn category(&self) -> Option<&'static Category> {
+            Some(category!("internalError/io"))
+        }
+
+        fn severity(&self) -> Severity {
+            Severity::Error
+        }
+
+        fn message(&self, fmt: &mut fmt::Formatter<'_>) -> io::Result<()> {
+            fmt.write_str("diagnostic message")
+        }
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        let rendered = render_diagnostic(&Error::from(SimpleDiagnostic), ColorMode::Disabled);
+
+        assert!(rendered.starts_with("internalError/io ━━"), "{rendered}");
+        assert!(
+            rendered.contains("\n  ✖ diagnostic message\n"),
+            "{rendered}"
+        );
+        assert!(!rendered.contains('\x1b'), "{rendered}");
+    }
+
+    #[test]
+    fn renders_colors() {
+        let rendered = render_diagnostic(&Error::from(SimpleDiagnostic), ColorMode::Enabled);
+
+        assert!(rendered.contains('\x1b'), "{rendered}");
+        assert!(rendered.contains("diagnostic message"), "{rendered}");
+    }
+}
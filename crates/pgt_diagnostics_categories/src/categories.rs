@@ -17,11 +17,13 @@ define_categories! {
     "lint/safety/banDropColumn": "https://pglt.dev/linter/rules/ban-drop-column",
     "lint/safety/banDropNotNull": "https://pglt.dev/linter/rules/ban-drop-not-null",
     "lint/safety/banDropTable": "https://pglt.dev/linter/rules/ban-drop-table",
+    "lint/style/reservedIdentifier": "https://pglt.dev/linter/rules/reserved-identifier",
     // end lint rules
     ;
     // General categories
     "stdin",
     "check",
+    "complete",
     "configuration",
     "database/connection",
     "internalError/io",
@@ -38,5 +40,6 @@ define_categories! {
     "lint",
     "lint/performance",
     "lint/safety",
+    "lint/style",
     // Lint groups end
 }
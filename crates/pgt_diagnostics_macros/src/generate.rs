@@ -138,7 +138,7 @@ fn generate_description(input: &DeriveStructInput) -> TokenStream {
             let mut buffer = Vec::new();
 
             let write = pgt_diagnostics::termcolor::NoColor::new(&mut buffer);
-            let mut write = pgt_diagnostics::console::fmt::Termcolor(write);
+            let mut write = pgt_diagnostics::console::fmt::Termcolor::new(write);
             let mut write = pgt_diagnostics::console::fmt::Formatter::new(&mut write);
 
             use pgt_diagnostics::console as pgt_console;
@@ -8,7 +8,7 @@ use crate::{
 use pgt_diagnostics::{DiagnosticExt, Error, Severity, adapters::IoError};
 use rayon::{Scope, scope};
 use std::fs::{DirEntry, FileType};
-use std::process::Command;
+use std::process::{Command, Output};
 use std::{
     env, fs,
     io::{self, ErrorKind as IoErrorKind, Read, Seek, Write},
@@ -91,10 +91,7 @@ impl FileSystem for OsFileSystem {
             .arg(format!("{base}...HEAD"))
             .output()?;
 
-        Ok(String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .map(|l| l.to_string())
-            .collect())
+        git_output_lines(output)
     }
 
     fn get_staged_files(&self) -> io::Result<Vec<String>> {
@@ -111,13 +108,27 @@ impl FileSystem for OsFileSystem {
             .arg("--diff-filter=ACMR")
             .output()?;
 
-        Ok(String::from_utf8_lossy(&output.stdout)
-            .lines()
-            .map(|l| l.to_string())
-            .collect())
+        git_output_lines(output)
     }
 }
 
+/// Returns the lines printed by a git command, or an error if git failed,
+/// e.g. because the base to compare against doesn't exist
+fn git_output_lines(output: Output) -> io::Result<Vec<String>> {
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git exited with {}: {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|l| l.to_string())
+        .collect())
+}
+
 struct OsFile {
     inner: fs::File,
     version: i32,
@@ -174,10 +174,13 @@ impl PgTPath {
 
     /// The priority of the file.
     /// - `postgrestools.jsonc` has the highest priority
+    /// - `.gitignore` and `.ignore` files are ignore files
     /// - Other files are considered as files to handle
     fn priority(file_name: &OsStr) -> FileKinds {
         if file_name == ConfigName::pgt_jsonc() {
             FileKind::Config.into()
+        } else if file_name == ".gitignore" || file_name == ".ignore" {
+            FileKind::Ignore.into()
         } else {
             FileKind::Handleable.into()
         }
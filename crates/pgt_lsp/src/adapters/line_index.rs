@@ -106,6 +106,11 @@ impl LineIndex {
             for c in wide_chars {
                 if c.end <= col {
                     res -= usize::from(c.len()) - c.wide_len(enc);
+                } else if c.start < col {
+                    // The offset points into the middle of the character, e.g. because a span
+                    // was computed on bytes. Snap it to the start of the character.
+                    res -= usize::from(col - c.start);
+                    break;
                 } else {
                     // From here on, all utf16 characters come *after* the character we are mapping,
                     // so we don't need to take them into account
@@ -2,12 +2,15 @@ use crate::adapters::{PositionEncoding, WideEncoding, negotiated_encoding};
 use pgt_workspace::features::code_actions::CommandActionCategory;
 use strum::IntoEnumIterator;
 use tower_lsp::lsp_types::{
-    ClientCapabilities, CompletionOptions, ExecuteCommandOptions, PositionEncodingKind,
+    ClientCapabilities, CompletionOptions, ExecuteCommandOptions, OneOf, PositionEncodingKind,
     SaveOptions, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind,
     TextDocumentSyncOptions, TextDocumentSyncSaveOptions, WorkDoneProgressOptions,
 };
 
-use crate::handlers::code_actions::command_id;
+use crate::handlers::{
+    code_actions::{CLOSE_ALL_COMMAND, command_id},
+    completions::COMPLETION_ACCEPTED_COMMAND,
+};
 
 /// The capabilities to send from server as part of [`InitializeResult`]
 ///
@@ -53,6 +56,10 @@ pub(crate) fn server_capabilities(capabilities: &ClientCapabilities) -> ServerCa
         execute_command_provider: Some(ExecuteCommandOptions {
             commands: CommandActionCategory::iter()
                 .map(|c| command_id(&c))
+                .chain([
+                    CLOSE_ALL_COMMAND.to_string(),
+                    COMPLETION_ACCEPTED_COMMAND.to_string(),
+                ])
                 .collect::<Vec<String>>(),
 
             ..Default::default()
@@ -63,7 +70,7 @@ pub(crate) fn server_capabilities(capabilities: &ClientCapabilities) -> ServerCa
         code_action_provider: Some(tower_lsp::lsp_types::CodeActionProviderCapability::Simple(
             true,
         )),
-        rename_provider: None,
+        rename_provider: Some(OneOf::Left(true)),
         ..Default::default()
     }
 }
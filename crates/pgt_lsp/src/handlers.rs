@@ -1,3 +1,4 @@
 pub(crate) mod code_actions;
 pub(crate) mod completions;
+pub(crate) mod rename;
 pub(crate) mod text_document;
@@ -1,12 +1,24 @@
-use crate::{adapters::get_cursor_position, session::Session};
+use std::collections::HashMap;
+
+use crate::{
+    adapters::{self, get_cursor_position},
+    handlers::completions::COMPLETION_ACCEPTED_COMMAND,
+    session::Session,
+};
 use anyhow::{Result, anyhow};
 use tower_lsp::lsp_types::{
     self, CodeAction, CodeActionDisabled, CodeActionOrCommand, Command, ExecuteCommandParams,
-    MessageType,
+    MessageType, TextEdit, WorkspaceEdit,
 };
 
-use pgt_workspace::features::code_actions::{
-    CodeActionKind, CodeActionsParams, CommandActionCategory, ExecuteStatementParams,
+use pgt_workspace::{
+    features::{
+        code_actions::{
+            CodeActionKind, CodeActionsParams, CommandActionCategory, ExecuteStatementParams,
+        },
+        completions::RecordCompletionOutcomeParams,
+    },
+    workspace::{CloseAllParams, GetFileContentParams, OpenFileParams},
 };
 
 #[tracing::instrument(level = "debug", skip(session), err)]
@@ -19,6 +31,11 @@ pub fn get_actions(
 
     let cursor_position = get_cursor_position(session, &url, params.range.start)?;
 
+    let doc = session
+        .document(&url)
+        .map_err(|_| anyhow!("Document not found."))?;
+    let encoding = session.position_encoding();
+
     let workspace_actions = session.workspace.pull_code_actions(CodeActionsParams {
         path,
         cursor_position,
@@ -56,6 +73,36 @@ pub fn get_actions(
                 }
             }
 
+            CodeActionKind::Edit(edit) => {
+                let edits = edit
+                    .edits
+                    .into_iter()
+                    .map(|e| {
+                        adapters::to_lsp::range(&doc.line_index, e.range, encoding).map(|range| {
+                            TextEdit {
+                                range,
+                                new_text: e.new_text,
+                            }
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .ok()?;
+
+                Some(CodeAction {
+                    title: action.title,
+                    kind: Some(lsp_types::CodeActionKind::QUICKFIX),
+                    is_preferred: Some(edit.is_safe),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(url.clone(), edits)])),
+                        ..Default::default()
+                    }),
+                    disabled: action
+                        .disabled_reason
+                        .map(|reason| CodeActionDisabled { reason }),
+                    ..Default::default()
+                })
+            }
+
             _ => todo!(),
         })
         .collect();
@@ -66,6 +113,10 @@ pub fn get_actions(
         .collect())
 }
 
+/// Resets the state of all documents, e.g. after switching branches, by closing them and
+/// opening them again with their current content.
+pub const CLOSE_ALL_COMMAND: &str = "pgt.closeAll";
+
 pub fn command_id(command: &CommandActionCategory) -> String {
     match command {
         CommandActionCategory::ExecuteStatement(_) => "pgt.executeStatement".into(),
@@ -106,6 +157,47 @@ pub async fn execute_command(
             Ok(None)
         }
 
+        CLOSE_ALL_COMMAND => {
+            // the documents are still open in the client, so they are opened again
+            let mut documents = Vec::new();
+            for (url, doc) in session.documents() {
+                let path = session.file_path(&url)?;
+                let content = session
+                    .workspace
+                    .get_file_content(GetFileContentParams { path: path.clone() })?;
+
+                documents.push(OpenFileParams {
+                    path,
+                    version: doc.version,
+                    content,
+                });
+            }
+
+            session.workspace.close_all(CloseAllParams {})?;
+
+            for params in documents {
+                session.workspace.open_file(params)?;
+            }
+
+            session.update_all_diagnostics().await;
+
+            Ok(None)
+        }
+
+        COMPLETION_ACCEPTED_COMMAND => {
+            let accepted_label: String = serde_json::from_value(params.arguments[0].clone())?;
+            let shown_order: usize = serde_json::from_value(params.arguments[1].clone())?;
+
+            session
+                .workspace
+                .record_completion_outcome(RecordCompletionOutcomeParams {
+                    accepted_label,
+                    shown_order,
+                })?;
+
+            Ok(None)
+        }
+
         any => Err(anyhow!(format!("Unknown command: {}", any))),
     }
 }
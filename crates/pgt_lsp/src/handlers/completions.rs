@@ -5,7 +5,12 @@ use crate::{
 };
 use anyhow::Result;
 use pgt_workspace::{WorkspaceError, features::completions::GetCompletionsParams};
-use tower_lsp::lsp_types::{self, CompletionItem, CompletionItemLabelDetails, TextEdit};
+use serde_json::json;
+use tower_lsp::lsp_types::{self, Command, CompletionItem, CompletionItemLabelDetails, TextEdit};
+
+/// Executed by the client when a completion item is accepted, to record the outcome of the
+/// completion if telemetry is enabled
+pub const COMPLETION_ACCEPTED_COMMAND: &str = "pgt.completionAccepted";
 
 #[tracing::instrument(level = "debug", skip(session), err)]
 pub fn get_completions(
@@ -16,11 +21,12 @@ pub fn get_completions(
     let path = session.file_path(&url)?;
 
     let doc = session.document(&url)?;
-    let encoding = adapters::negotiated_encoding(session.client_capabilities().unwrap());
+    let encoding = session.position_encoding();
 
     let completion_result = match session.workspace.get_completions(GetCompletionsParams {
         path,
         position: get_cursor_position(session, &url, params.text_document_position.position)?,
+        limit: None,
     }) {
         Ok(result) => result,
         Err(e) => match e {
@@ -33,9 +39,18 @@ pub fn get_completions(
         },
     };
 
+    // the outcome is only reported back if it is recorded at all
+    let telemetry = completion_result.telemetry;
+
     let items: Vec<CompletionItem> = completion_result
         .into_iter()
-        .map(|i| CompletionItem {
+        .enumerate()
+        .map(|(shown_order, i)| CompletionItem {
+            command: telemetry.then(|| Command {
+                title: String::new(),
+                command: COMPLETION_ACCEPTED_COMMAND.to_string(),
+                arguments: Some(vec![json!(i.label), json!(shown_order)]),
+            }),
             label: i.label,
             label_details: Some(CompletionItemLabelDetails {
                 description: Some(i.description),
@@ -43,13 +58,13 @@ pub fn get_completions(
             }),
             preselect: Some(i.preselected),
             sort_text: Some(i.sort_text),
-            text_edit: i.completion_text.map(|c| {
-                lsp_types::CompletionTextEdit::Edit(TextEdit {
-                    new_text: c.text,
-                    range: adapters::to_lsp::range(&doc.line_index, c.range, encoding).unwrap(),
-                })
-            }),
+            text_edit: Some(lsp_types::CompletionTextEdit::Edit(TextEdit {
+                range: adapters::to_lsp::range(&doc.line_index, i.completion_text.range, encoding)
+                    .unwrap(),
+                new_text: i.completion_text.text,
+            })),
             kind: Some(to_lsp_types_completion_item_kind(i.kind)),
+            documentation: i.documentation.map(lsp_types::Documentation::String),
             ..CompletionItem::default()
         })
         .collect();
@@ -65,5 +80,22 @@ fn to_lsp_types_completion_item_kind(
         pgt_completions::CompletionItemKind::Table => lsp_types::CompletionItemKind::CLASS,
         pgt_completions::CompletionItemKind::Column => lsp_types::CompletionItemKind::FIELD,
         pgt_completions::CompletionItemKind::Schema => lsp_types::CompletionItemKind::CLASS,
+        pgt_completions::CompletionItemKind::Keyword => lsp_types::CompletionItemKind::KEYWORD,
+        pgt_completions::CompletionItemKind::Setting => lsp_types::CompletionItemKind::VARIABLE,
+        pgt_completions::CompletionItemKind::Collation => lsp_types::CompletionItemKind::CONSTANT,
+        pgt_completions::CompletionItemKind::Extension => lsp_types::CompletionItemKind::MODULE,
+        pgt_completions::CompletionItemKind::Alias => lsp_types::CompletionItemKind::REFERENCE,
+        pgt_completions::CompletionItemKind::Field => lsp_types::CompletionItemKind::FIELD,
+        pgt_completions::CompletionItemKind::Parameter => lsp_types::CompletionItemKind::PROPERTY,
+        pgt_completions::CompletionItemKind::OperatorClass => {
+            lsp_types::CompletionItemKind::TYPE_PARAMETER
+        }
+        pgt_completions::CompletionItemKind::Window => lsp_types::CompletionItemKind::REFERENCE,
+        pgt_completions::CompletionItemKind::Constraint => lsp_types::CompletionItemKind::REFERENCE,
+        pgt_completions::CompletionItemKind::Type => lsp_types::CompletionItemKind::STRUCT,
+        pgt_completions::CompletionItemKind::Policy => lsp_types::CompletionItemKind::REFERENCE,
+        pgt_completions::CompletionItemKind::EnumValue => {
+            lsp_types::CompletionItemKind::ENUM_MEMBER
+        }
     }
 }
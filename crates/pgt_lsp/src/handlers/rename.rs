@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use crate::{
+    adapters::{self, get_cursor_position},
+    session::Session,
+};
+use anyhow::{Result, anyhow};
+use pgt_workspace::features::rename::RenameSymbolParams;
+use tower_lsp::lsp_types::{self, TextEdit, WorkspaceEdit};
+
+#[tracing::instrument(level = "debug", skip(session), err)]
+pub fn rename(session: &Session, params: lsp_types::RenameParams) -> Result<Option<WorkspaceEdit>> {
+    let url = params.text_document_position.text_document.uri;
+    let path = session.file_path(&url)?;
+
+    let doc = session
+        .document(&url)
+        .map_err(|_| anyhow!("Document not found."))?;
+    let encoding = session.position_encoding();
+
+    let result = session.workspace.rename_symbol(RenameSymbolParams {
+        path,
+        position: get_cursor_position(session, &url, params.text_document_position.position)?,
+        new_name: params.new_name,
+    })?;
+
+    if result.edits.is_empty() {
+        return Ok(None);
+    }
+
+    let edits = result
+        .edits
+        .into_iter()
+        .map(|e| {
+            adapters::to_lsp::range(&doc.line_index, e.range, encoding).map(|range| TextEdit {
+                range,
+                new_text: e.new_text,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(WorkspaceEdit {
+        changes: Some(HashMap::from([(url, edits)])),
+        ..Default::default()
+    }))
+}
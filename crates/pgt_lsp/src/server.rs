@@ -5,7 +5,7 @@ use crate::utils::{into_lsp_error, panic_to_lsp_error};
 use futures::FutureExt;
 use futures::future::ready;
 use pgt_fs::{ConfigName, FileSystem, OsFileSystem};
-use pgt_workspace::{DynRef, Workspace, workspace};
+use pgt_workspace::{DynRef, Workspace, features::completions::CompletionTelemetrySink, workspace};
 use rustc_hash::FxHashMap;
 use serde_json::json;
 use std::panic::RefUnwindSafe;
@@ -234,6 +234,14 @@ impl LanguageServer for LSPServer {
         }
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    async fn rename(&self, params: RenameParams) -> LspResult<Option<WorkspaceEdit>> {
+        match handlers::rename::rename(&self.session, params) {
+            Ok(result) => LspResult::Ok(result),
+            Err(e) => LspResult::Err(into_lsp_error(e)),
+        }
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     async fn execute_command(
         &self,
@@ -326,6 +334,10 @@ pub struct ServerFactory {
     /// This shared flag is set to true once at least one sessions has been
     /// initialized on this server instance
     is_initialized: Arc<AtomicBool>,
+
+    /// Receives the outcomes of completions of the workspaces created by this
+    /// factory, if telemetry is enabled
+    completion_telemetry: Option<Arc<dyn CompletionTelemetrySink>>,
 }
 
 impl ServerFactory {
@@ -337,9 +349,16 @@ impl ServerFactory {
             next_session_key: AtomicU64::new(0),
             stop_on_disconnect,
             is_initialized: Arc::default(),
+            completion_telemetry: None,
         }
     }
 
+    /// Records the outcomes of completions with `sink` instead of emitting them as tracing events
+    pub fn with_completion_telemetry(mut self, sink: Arc<dyn CompletionTelemetrySink>) -> Self {
+        self.completion_telemetry = Some(sink);
+        self
+    }
+
     pub fn create(&self, config_path: Option<PathBuf>) -> ServerConnection {
         self.create_with_fs(config_path, DynRef::Owned(Box::<OsFileSystem>::default()))
     }
@@ -350,10 +369,13 @@ impl ServerFactory {
         config_path: Option<PathBuf>,
         fs: DynRef<'static, dyn FileSystem>,
     ) -> ServerConnection {
-        let workspace = self
-            .workspace
-            .clone()
-            .unwrap_or_else(workspace::server_sync);
+        let workspace =
+            self.workspace
+                .clone()
+                .unwrap_or_else(|| match self.completion_telemetry.clone() {
+                    Some(sink) => workspace::server_sync_with_completion_telemetry(sink),
+                    None => workspace::server_sync(),
+                });
 
         let session_key = SessionKey(self.next_session_key.fetch_add(1, Ordering::Relaxed));
 
@@ -390,12 +412,16 @@ impl ServerFactory {
 
         workspace_method!(builder, is_path_ignored);
         workspace_method!(builder, update_settings);
+        workspace_method!(builder, validate_settings);
         workspace_method!(builder, get_file_content);
         workspace_method!(builder, open_file);
         workspace_method!(builder, change_file);
         workspace_method!(builder, close_file);
+        workspace_method!(builder, close_all);
         workspace_method!(builder, pull_diagnostics);
         workspace_method!(builder, get_completions);
+        workspace_method!(builder, record_completion_outcome);
+        workspace_method!(builder, rename_symbol);
 
         let (service, socket) = builder.finish();
         ServerConnection { socket, service }
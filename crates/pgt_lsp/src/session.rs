@@ -259,6 +259,7 @@ impl Session {
                         categories: categories.build(),
                         only: Vec::new(),
                         skip: Vec::new(),
+                        changed_range: None,
                     })?;
 
             result
@@ -330,6 +331,16 @@ impl Session {
         self.documents.write().unwrap().remove(url);
     }
 
+    /// Returns all [`Document`]s with their [`lsp_types::Url`]s
+    pub(crate) fn documents(&self) -> Vec<(lsp_types::Url, Document)> {
+        self.documents
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(url, doc)| (url.clone(), doc.clone()))
+            .collect()
+    }
+
     pub(crate) fn file_path(&self, url: &lsp_types::Url) -> Result<PgTPath> {
         let path_to_file = match url.to_file_path() {
             Err(_) => {
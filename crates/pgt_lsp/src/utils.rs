@@ -142,13 +142,13 @@ impl Visit for RelatedInformationVisitor<'_> {
 /// Convert a piece of markup into a String
 #[allow(unused)]
 fn print_markup(markup: &MarkupBuf) -> String {
-    let mut message = Termcolor(NoColor::new(Vec::new()));
+    let mut message = Termcolor::new(NoColor::new(Vec::new()));
     fmt::Display::fmt(markup, &mut Formatter::new(&mut message))
         // SAFETY: Writing to a memory buffer should never fail
         .unwrap();
 
     // SAFETY: Printing uncolored markup never generates non UTF-8 byte sequences
-    String::from_utf8(message.0.into_inner()).unwrap()
+    String::from_utf8(message.into_inner().into_inner()).unwrap()
 }
 
 /// Helper to create a [tower_lsp::jsonrpc::Error] from a message
@@ -237,12 +237,22 @@ pub(crate) fn apply_document_changes(
 #[cfg(test)]
 mod tests {
     use crate::adapters::line_index::LineIndex;
-    use crate::adapters::{PositionEncoding, to_lsp};
+    use crate::adapters::{PositionEncoding, WideEncoding, to_lsp};
     use anyhow::Result;
+    use pgt_diagnostics::Diagnostic;
     use pgt_text_edit::{CompressedOp, DiffOp, TextEdit};
-    use pgt_text_size::TextSize;
+    use pgt_text_size::{TextRange, TextSize};
     use tower_lsp::lsp_types as lsp;
 
+    use super::diagnostic_to_lsp;
+
+    #[derive(Debug, Diagnostic)]
+    #[diagnostic(category = "syntax", severity = Error, message = "Invalid statement")]
+    struct TestDiagnostic {
+        #[location(span)]
+        span: TextRange,
+    }
+
     fn text_edit(
         line_index: &LineIndex,
         diff: TextEdit,
@@ -438,4 +448,50 @@ line 7 new";
     //
     //     assert_eq!(output, expected);
     // }
+
+    #[test]
+    fn diagnostic_spans_use_the_negotiated_encoding() {
+        const TEXT: &str = "select 1;\nselect 'héllo🐘', x from t;";
+
+        let url = lsp::Url::parse("file:///test.sql").unwrap();
+        let line_index = LineIndex::new(TEXT);
+
+        let start = TEXT.find(", x").unwrap() + 2;
+        let span = TextRange::at(TextSize::try_from(start).unwrap(), TextSize::from(1));
+
+        let range_in = |encoding| {
+            diagnostic_to_lsp(TestDiagnostic { span }, &url, &line_index, encoding, None)
+                .unwrap()
+                .range
+        };
+
+        // `é` is a single UTF-16 code unit and `🐘` is a surrogate pair
+        assert_eq!(
+            range_in(PositionEncoding::Wide(WideEncoding::Utf16)),
+            lsp::Range::new(lsp::Position::new(1, 18), lsp::Position::new(1, 19))
+        );
+        assert_eq!(
+            range_in(PositionEncoding::Wide(WideEncoding::Utf32)),
+            lsp::Range::new(lsp::Position::new(1, 17), lsp::Position::new(1, 18))
+        );
+        assert_eq!(
+            range_in(PositionEncoding::Utf8),
+            lsp::Range::new(lsp::Position::new(1, 21), lsp::Position::new(1, 22))
+        );
+    }
+
+    #[test]
+    fn offsets_inside_a_character_snap_to_its_start() {
+        const TEXT: &str = "select '🐘'";
+
+        let line_index = LineIndex::new(TEXT);
+        let encoding = PositionEncoding::Wide(WideEncoding::Utf16);
+
+        let inside = TextSize::try_from(TEXT.find('🐘').unwrap() + 2).unwrap();
+
+        assert_eq!(
+            to_lsp::position(&line_index, inside, encoding).unwrap(),
+            lsp::Position::new(0, 8)
+        );
+    }
 }
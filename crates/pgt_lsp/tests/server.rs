@@ -616,7 +616,7 @@ async fn test_issue_271() -> Result<()> {
 
     server
         .change_document(
-            3,
+            1,
             vec![TextDocumentContentChangeEvent {
                 range: Some(Range {
                     start: Position {
@@ -636,7 +636,7 @@ async fn test_issue_271() -> Result<()> {
 
     server
         .change_document(
-            1,
+            2,
             vec![TextDocumentContentChangeEvent {
                 range: Some(Range {
                     start: Position {
@@ -656,7 +656,7 @@ async fn test_issue_271() -> Result<()> {
 
     server
         .change_document(
-            2,
+            3,
             vec![TextDocumentContentChangeEvent {
                 range: Some(Range {
                     start: Position {
@@ -676,7 +676,7 @@ async fn test_issue_271() -> Result<()> {
 
     server
         .change_document(
-            3,
+            4,
             vec![TextDocumentContentChangeEvent {
                 range: Some(Range {
                     start: Position {
@@ -696,7 +696,7 @@ async fn test_issue_271() -> Result<()> {
 
     server
         .change_document(
-            4,
+            5,
             vec![TextDocumentContentChangeEvent {
                 range: Some(Range {
                     start: Position {
@@ -716,7 +716,7 @@ async fn test_issue_271() -> Result<()> {
 
     server
         .change_document(
-            5,
+            6,
             vec![TextDocumentContentChangeEvent {
                 range: Some(Range {
                     start: Position {
@@ -1113,3 +1113,100 @@ async fn test_issue_303() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_rename() -> Result<()> {
+    let factory = ServerFactory::default();
+    let mut fs = MemoryFileSystem::default();
+    let test_db = get_new_test_db().await;
+
+    let setup = r#"
+            create table public.users (
+                id serial primary key,
+                name varchar(255) not null
+            );
+            create table public.orders (
+                id serial primary key,
+                user_id int not null
+            );
+        "#;
+
+    test_db
+        .execute(setup)
+        .await
+        .expect("Failed to setup test database");
+
+    let mut conf = PartialConfiguration::init();
+    conf.merge_with(PartialConfiguration {
+        db: Some(PartialDatabaseConfiguration {
+            database: Some(
+                test_db
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string(),
+            ),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    fs.insert(
+        url!("postgrestools.jsonc").to_file_path().unwrap(),
+        serde_json::to_string_pretty(&conf).unwrap(),
+    );
+
+    let (service, client) = factory
+        .create_with_fs(None, DynRef::Owned(Box::new(fs)))
+        .into_inner();
+
+    let (stream, sink) = client.split();
+    let mut server = Server::new(service);
+
+    let (sender, _) = channel(CHANNEL_BUFFER_SIZE);
+    let reader = tokio::spawn(client_handler(stream, sink, sender));
+
+    server.initialize().await?;
+    server.initialized().await?;
+
+    server.load_configuration().await?;
+
+    server
+        .open_document("select u.id, o.id from users u join orders o on o.user_id = u.id;\n")
+        .await?;
+
+    let res: Option<lsp::WorkspaceEdit> = server
+        .request(
+            "textDocument/rename",
+            "rename",
+            lsp::RenameParams {
+                text_document_position: TextDocumentPositionParams {
+                    text_document: TextDocumentIdentifier {
+                        uri: url!("document.sql"),
+                    },
+                    position: Position {
+                        line: 0,
+                        character: 9,
+                    },
+                },
+                new_name: "user_id".to_string(),
+                work_done_progress_params: WorkDoneProgressParams::default(),
+            },
+        )
+        .await?;
+
+    let edits = res
+        .and_then(|edit| edit.changes)
+        .and_then(|mut changes| changes.remove(&url!("document.sql")))
+        .expect("expected edits for the document");
+
+    let mut starts: Vec<u32> = edits.iter().map(|e| e.range.start.character).collect();
+    starts.sort();
+
+    assert_eq!(starts, vec![9, 62]);
+    assert!(edits.iter().all(|e| e.new_text == "user_id"));
+
+    server.shutdown().await?;
+    reader.abort();
+
+    Ok(())
+}
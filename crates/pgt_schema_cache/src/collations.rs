@@ -0,0 +1,23 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+/// A collation, as listed in `pg_collation`.
+#[derive(Debug, Clone, Default)]
+pub struct Collation {
+    pub name: String,
+    pub schema: String,
+
+    /// The provider of the collation, `libc`, `icu` or `builtin`. `default` for the database default.
+    pub provider: String,
+}
+
+impl SchemaCacheItem for Collation {
+    type Item = Collation;
+
+    async fn load(pool: &PgPool) -> Result<Vec<Collation>, sqlx::Error> {
+        sqlx::query_file_as!(Collation, "src/queries/collations.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
@@ -0,0 +1,69 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ConstraintKind {
+    Check,
+    ForeignKey,
+    PrimaryKey,
+    Unique,
+    Exclusion,
+    #[default]
+    Other,
+}
+
+impl From<&str> for ConstraintKind {
+    fn from(value: &str) -> Self {
+        match value {
+            "c" => ConstraintKind::Check,
+            "f" => ConstraintKind::ForeignKey,
+            "p" => ConstraintKind::PrimaryKey,
+            "u" => ConstraintKind::Unique,
+            "x" => ConstraintKind::Exclusion,
+            // e.g. constraint triggers
+            _ => ConstraintKind::Other,
+        }
+    }
+}
+
+impl From<String> for ConstraintKind {
+    fn from(value: String) -> Self {
+        ConstraintKind::from(value.as_str())
+    }
+}
+
+/// A table constraint, as listed in `pg_constraint`.
+#[derive(Debug, Clone, Default)]
+pub struct Constraint {
+    pub name: String,
+    pub schema: String,
+
+    pub table_name: String,
+    pub table_oid: i64,
+
+    pub kind: ConstraintKind,
+
+    /// The constrained columns, in the order of their declaration in the constraint.
+    pub columns: Vec<String>,
+}
+
+impl Constraint {
+    /// Whether the constraint makes its columns unique, i.e. can be the target of `on conflict`.
+    pub fn is_unique(&self) -> bool {
+        matches!(
+            self.kind,
+            ConstraintKind::PrimaryKey | ConstraintKind::Unique
+        )
+    }
+}
+
+impl SchemaCacheItem for Constraint {
+    type Item = Constraint;
+
+    async fn load(pool: &PgPool) -> Result<Vec<Constraint>, sqlx::Error> {
+        sqlx::query_file_as!(Constraint, "src/queries/constraints.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::SchemaCache;
+
+/// The objects that were added or removed between two loads of the [SchemaCache].
+///
+/// Objects are identified by their qualified names, e.g. `public.users` for a table,
+/// `public.users.email` for a column and `public.add(integer, integer)` for a function.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaCacheDiff {
+    pub added_tables: Vec<String>,
+    pub removed_tables: Vec<String>,
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    pub added_functions: Vec<String>,
+    pub removed_functions: Vec<String>,
+}
+
+impl SchemaCacheDiff {
+    /// Returns true if both caches contain the same tables, columns and functions
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty()
+            && self.removed_tables.is_empty()
+            && self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.added_functions.is_empty()
+            && self.removed_functions.is_empty()
+    }
+}
+
+impl fmt::Display for SchemaCacheDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tables +{}/-{}, columns +{}/-{}, functions +{}/-{}",
+            self.added_tables.len(),
+            self.removed_tables.len(),
+            self.added_columns.len(),
+            self.removed_columns.len(),
+            self.added_functions.len(),
+            self.removed_functions.len()
+        )
+    }
+}
+
+impl SchemaCache {
+    /// Compares this cache with a previously loaded one
+    pub fn diff(&self, old: &SchemaCache) -> SchemaCacheDiff {
+        let (added_tables, removed_tables) = added_and_removed(table_names(self), table_names(old));
+        let (added_columns, removed_columns) =
+            added_and_removed(column_names(self), column_names(old));
+        let (added_functions, removed_functions) =
+            added_and_removed(function_names(self), function_names(old));
+
+        SchemaCacheDiff {
+            added_tables,
+            removed_tables,
+            added_columns,
+            removed_columns,
+            added_functions,
+            removed_functions,
+        }
+    }
+}
+
+fn table_names(cache: &SchemaCache) -> BTreeSet<String> {
+    cache
+        .tables
+        .iter()
+        .map(|t| format!("{}.{}", t.schema, t.name))
+        .collect()
+}
+
+fn column_names(cache: &SchemaCache) -> BTreeSet<String> {
+    cache
+        .columns
+        .iter()
+        .map(|c| format!("{}.{}.{}", c.schema_name, c.table_name, c.name))
+        .collect()
+}
+
+fn function_names(cache: &SchemaCache) -> BTreeSet<String> {
+    // overloads only differ in their arguments
+    cache
+        .functions
+        .iter()
+        .map(|f| {
+            format!(
+                "{}.{}({})",
+                f.schema,
+                f.name,
+                f.identity_argument_types.as_deref().unwrap_or_default()
+            )
+        })
+        .collect()
+}
+
+fn added_and_removed(new: BTreeSet<String>, old: BTreeSet<String>) -> (Vec<String>, Vec<String>) {
+    let added = new.difference(&old).cloned().collect();
+    let removed = old.difference(&new).cloned().collect();
+
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{SchemaCache, Table};
+
+    fn table(schema: &str, name: &str) -> Table {
+        Table {
+            schema: schema.into(),
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn reports_added_and_removed_tables() {
+        let old = SchemaCache {
+            tables: vec![table("public", "users"), table("public", "orders")],
+            ..Default::default()
+        };
+        let new = SchemaCache {
+            tables: vec![table("public", "users"), table("public", "invoices")],
+            ..Default::default()
+        };
+
+        let diff = new.diff(&old);
+
+        assert_eq!(diff.added_tables, vec!["public.invoices".to_string()]);
+        assert_eq!(diff.removed_tables, vec!["public.orders".to_string()]);
+        assert!(diff.added_columns.is_empty() && diff.removed_columns.is_empty());
+        assert!(!diff.is_empty());
+
+        assert!(new.diff(&new).is_empty());
+    }
+}
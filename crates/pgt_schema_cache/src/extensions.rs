@@ -0,0 +1,24 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+/// An extension that can be installed, as listed in `pg_available_extensions`.
+#[derive(Debug, Clone, Default)]
+pub struct Extension {
+    pub name: String,
+    pub default_version: Option<String>,
+
+    /// The installed version, if the extension is installed in the database.
+    pub installed_version: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl SchemaCacheItem for Extension {
+    type Item = Extension;
+
+    async fn load(pool: &PgPool) -> Result<Vec<Extension>, sqlx::Error> {
+        sqlx::query_file_as!(Extension, "src/queries/extensions.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
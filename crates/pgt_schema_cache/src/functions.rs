@@ -104,6 +104,9 @@ pub struct Function {
 
     /// Is the function's security set to `Definer` (true) or `Invoker` (false)?
     pub security_definer: bool,
+
+    /// Comment inserted via `COMMENT ON FUNCTION my_function() IS '...'`, if present.
+    pub comment: Option<String>,
 }
 
 impl SchemaCacheItem for Function {
@@ -0,0 +1,21 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+#[derive(Debug, Clone, Default)]
+pub struct Language {
+    pub name: String,
+
+    /// Whether unprivileged users may create functions in this language.
+    pub trusted: bool,
+}
+
+impl SchemaCacheItem for Language {
+    type Item = Language;
+
+    async fn load(pool: &PgPool) -> Result<Vec<Language>, sqlx::Error> {
+        sqlx::query_file_as!(Language, "src/queries/languages.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
@@ -2,16 +2,37 @@
 
 #![allow(dead_code)]
 
+mod collations;
 mod columns;
+mod constraints;
+mod diff;
+mod extensions;
 mod functions;
+mod languages;
+mod operator_classes;
+mod policies;
 mod schema_cache;
 mod schemas;
+mod sequences;
+mod settings;
 mod tables;
+mod time_zones;
 mod types;
 mod versions;
 
+pub use collations::Collation;
 pub use columns::*;
+pub use constraints::{Constraint, ConstraintKind};
+pub use diff::SchemaCacheDiff;
+pub use extensions::Extension;
 pub use functions::{Behavior, Function, FunctionArg, FunctionArgs};
+pub use languages::Language;
+pub use operator_classes::OperatorClass;
+pub use policies::{Policy, PolicyCommand};
 pub use schema_cache::SchemaCache;
 pub use schemas::Schema;
+pub use sequences::Sequence;
+pub use settings::Setting;
 pub use tables::{ReplicaIdentity, Table};
+pub use time_zones::TimeZone;
+pub use types::{PostgresType, PostgresTypeAttribute};
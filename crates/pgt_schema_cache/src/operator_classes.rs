@@ -0,0 +1,30 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+/// An operator class, as listed in `pg_opclass`, e.g. `gin_trgm_ops`.
+#[derive(Debug, Clone, Default)]
+pub struct OperatorClass {
+    pub name: String,
+    pub schema: String,
+
+    /// The index access method the operator class belongs to, e.g. `gin`.
+    pub access_method: String,
+
+    /// The type of the indexed values.
+    pub input_type_id: i64,
+    pub input_type: String,
+
+    /// Whether the operator class is used for its input type if none is specified.
+    pub is_default: bool,
+}
+
+impl SchemaCacheItem for OperatorClass {
+    type Item = OperatorClass;
+
+    async fn load(pool: &PgPool) -> Result<Vec<OperatorClass>, sqlx::Error> {
+        sqlx::query_file_as!(OperatorClass, "src/queries/operator_classes.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
@@ -0,0 +1,57 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PolicyCommand {
+    #[default]
+    All,
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl From<&str> for PolicyCommand {
+    fn from(value: &str) -> Self {
+        match value {
+            "r" => PolicyCommand::Select,
+            "a" => PolicyCommand::Insert,
+            "w" => PolicyCommand::Update,
+            "d" => PolicyCommand::Delete,
+            _ => PolicyCommand::All,
+        }
+    }
+}
+
+impl From<String> for PolicyCommand {
+    fn from(value: String) -> Self {
+        PolicyCommand::from(value.as_str())
+    }
+}
+
+/// A row-level security policy, as listed in `pg_policy`.
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    pub name: String,
+    pub schema: String,
+
+    pub table_name: String,
+    pub table_oid: i64,
+
+    /// The command the policy applies to.
+    pub command: PolicyCommand,
+
+    /// Whether the policy is combined with the others using `or` rather than `and`.
+    pub is_permissive: bool,
+}
+
+impl SchemaCacheItem for Policy {
+    type Item = Policy;
+
+    async fn load(pool: &PgPool) -> Result<Vec<Policy>, sqlx::Error> {
+        sqlx::query_file_as!(Policy, "src/queries/policies.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
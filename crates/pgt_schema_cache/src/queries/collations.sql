@@ -0,0 +1,15 @@
+select
+  c.collname as "name!",
+  n.nspname as "schema!",
+  case c.collprovider
+    when 'c' then 'libc'
+    when 'i' then 'icu'
+    when 'b' then 'builtin'
+    else 'default'
+  end as "provider!"
+from
+  pg_collation c
+  join pg_namespace n on n.oid = c.collnamespace
+where
+  -- collations that do not match the database encoding cannot be used
+  c.collencoding in (-1, pg_char_to_encoding(getdatabaseencoding()));
\ No newline at end of file
@@ -0,0 +1,20 @@
+select
+  c.conname as "name!",
+  n.nspname as "schema!",
+  t.relname as "table_name!",
+  c.conrelid :: int8 as "table_oid!",
+  c.contype :: text as "kind!",
+  array(
+    select
+      a.attname :: text
+    from
+      unnest (c.conkey) with ordinality as k (attnum, ord)
+      join pg_attribute a on a.attrelid = c.conrelid
+      and a.attnum = k.attnum
+    order by
+      k.ord
+  ) as "columns!"
+from
+  pg_constraint c
+  join pg_class t on t.oid = c.conrelid
+  join pg_namespace n on n.oid = c.connamespace;
\ No newline at end of file
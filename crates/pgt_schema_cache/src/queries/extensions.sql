@@ -0,0 +1,7 @@
+select
+  e.name as "name!",
+  e.default_version,
+  e.installed_version,
+  e.comment
+from
+  pg_available_extensions e;
\ No newline at end of file
@@ -10,6 +10,7 @@ with functions as (
     prolang,
     pronamespace,
     proconfig,
+    prokind,
     -- proargmodes is null when all arg modes are IN
     coalesce(
       p.proargmodes,
@@ -35,7 +36,8 @@ with functions as (
   from
     pg_proc as p
   where
-    p.prokind = 'f'
+    -- aggregates and window functions are included, e.g. to check the argument count of calls
+    p.prokind in ('f', 'a', 'w')
 )
 select
   f.oid :: int8 as "id!",
@@ -48,6 +50,8 @@ select
   end as body,
   case
     when l.lanname = 'internal' then null
+    -- aggregates have no function definition
+    when f.prokind = 'a' then null
     else pg_get_functiondef(f.oid)
   end as definition,
   coalesce(f_args.args, '[]') as args,
@@ -62,7 +66,8 @@ select
     when f.provolatile = 's' then 'STABLE'
     when f.provolatile = 'v' then 'VOLATILE'
   end as behavior,
-  f.prosecdef as security_definer
+  f.prosecdef as security_definer,
+  obj_description(f.oid, 'pg_proc') as comment
 from
   functions f
   left join pg_namespace n on f.pronamespace = n.oid
@@ -0,0 +1,7 @@
+select
+  l.lanname as name,
+  l.lanpltrusted as trusted
+from
+  pg_language l
+where
+  l.lanname <> 'internal';
\ No newline at end of file
@@ -0,0 +1,11 @@
+select
+  o.opcname as "name!",
+  n.nspname as "schema!",
+  am.amname as "access_method!",
+  o.opcintype :: int8 as "input_type_id!",
+  format_type(o.opcintype, null) as "input_type!",
+  o.opcdefault as "is_default!"
+from
+  pg_opclass o
+  join pg_am am on am.oid = o.opcmethod
+  join pg_namespace n on n.oid = o.opcnamespace;
\ No newline at end of file
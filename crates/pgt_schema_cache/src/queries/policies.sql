@@ -0,0 +1,11 @@
+select
+  p.polname as "name!",
+  n.nspname as "schema!",
+  c.relname as "table_name!",
+  p.polrelid :: int8 as "table_oid!",
+  p.polcmd :: text as "command!",
+  p.polpermissive as "is_permissive!"
+from
+  pg_policy p
+  join pg_class c on c.oid = p.polrelid
+  join pg_namespace n on n.oid = c.relnamespace;
\ No newline at end of file
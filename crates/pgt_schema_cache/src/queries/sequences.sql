@@ -0,0 +1,11 @@
+select
+  c.relname as "name!",
+  n.nspname as "schema!",
+  format_type(s.seqtypid, null) as "data_type!"
+from
+  pg_sequence s
+  join pg_class c on c.oid = s.seqrelid
+  join pg_namespace n on n.oid = c.relnamespace
+where
+  n.nspname not in ('pg_catalog', 'information_schema')
+  and n.nspname not like 'pg_toast%';
\ No newline at end of file
@@ -0,0 +1,8 @@
+select
+  s.name as "name!",
+  s.setting as "value!",
+  s.short_desc as "description!",
+  s.vartype as "kind!",
+  s.enumvals as enum_values
+from
+  pg_settings s;
\ No newline at end of file
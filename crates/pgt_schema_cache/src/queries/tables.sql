@@ -16,6 +16,7 @@ select
   ) as "size!",
   pg_stat_get_live_tuples(c.oid) as "live_rows_estimate!",
   pg_stat_get_dead_tuples(c.oid) as "dead_rows_estimate!",
+  c.relkind = 'p' as "is_partitioned!",
   obj_description(c.oid) as comment
 from
   pg_namespace nc
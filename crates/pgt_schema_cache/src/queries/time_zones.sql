@@ -0,0 +1,7 @@
+select
+  z.name as "name!",
+  z.abbrev as "abbrev!",
+  z.utc_offset :: text as "utc_offset!",
+  z.is_dst as "is_dst!"
+from
+  pg_timezone_names z;
\ No newline at end of file
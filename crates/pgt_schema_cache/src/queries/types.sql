@@ -5,6 +5,7 @@ select
   format_type (t.oid, null) as "format!",
   coalesce(t_enums.enums, '[]') as enums,
   coalesce(t_attributes.attributes, '[]') as attributes,
+  nullif(t.typelem, 0) :: int8 as element_type_id,
   obj_description (t.oid, 'pg_type') as comment
 from
   pg_type t
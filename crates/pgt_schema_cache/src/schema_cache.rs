@@ -1,9 +1,18 @@
 use sqlx::postgres::PgPool;
 
+use crate::collations::Collation;
 use crate::columns::Column;
+use crate::constraints::Constraint;
+use crate::extensions::Extension;
 use crate::functions::Function;
+use crate::languages::Language;
+use crate::operator_classes::OperatorClass;
+use crate::policies::Policy;
 use crate::schemas::Schema;
+use crate::sequences::Sequence;
+use crate::settings::Setting;
 use crate::tables::Table;
+use crate::time_zones::TimeZone;
 use crate::types::PostgresType;
 use crate::versions::Version;
 
@@ -15,17 +24,51 @@ pub struct SchemaCache {
     pub types: Vec<PostgresType>,
     pub versions: Vec<Version>,
     pub columns: Vec<Column>,
+    pub languages: Vec<Language>,
+    pub settings: Vec<Setting>,
+    pub collations: Vec<Collation>,
+    pub extensions: Vec<Extension>,
+    pub sequences: Vec<Sequence>,
+    pub operator_classes: Vec<OperatorClass>,
+    pub constraints: Vec<Constraint>,
+    pub policies: Vec<Policy>,
+    pub time_zones: Vec<TimeZone>,
 }
 
 impl SchemaCache {
     pub async fn load(pool: &PgPool) -> Result<SchemaCache, sqlx::Error> {
-        let (schemas, tables, functions, types, versions, columns) = futures_util::try_join!(
+        let (
+            schemas,
+            tables,
+            functions,
+            types,
+            versions,
+            columns,
+            languages,
+            settings,
+            collations,
+            extensions,
+            sequences,
+            operator_classes,
+            constraints,
+            policies,
+            time_zones,
+        ) = futures_util::try_join!(
             Schema::load(pool),
             Table::load(pool),
             Function::load(pool),
             PostgresType::load(pool),
             Version::load(pool),
-            Column::load(pool)
+            Column::load(pool),
+            Language::load(pool),
+            Setting::load(pool),
+            Collation::load(pool),
+            Extension::load(pool),
+            Sequence::load(pool),
+            OperatorClass::load(pool),
+            Constraint::load(pool),
+            Policy::load(pool),
+            TimeZone::load(pool)
         )?;
 
         Ok(SchemaCache {
@@ -35,9 +78,37 @@ impl SchemaCache {
             types,
             versions,
             columns,
+            languages,
+            settings,
+            collations,
+            extensions,
+            sequences,
+            operator_classes,
+            constraints,
+            policies,
+            time_zones,
         })
     }
 
+    /// Returns true if nothing was loaded, e.g. because there is no database connection
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+            && self.tables.is_empty()
+            && self.functions.is_empty()
+            && self.types.is_empty()
+            && self.versions.is_empty()
+            && self.columns.is_empty()
+            && self.languages.is_empty()
+            && self.settings.is_empty()
+            && self.collations.is_empty()
+            && self.extensions.is_empty()
+            && self.sequences.is_empty()
+            && self.operator_classes.is_empty()
+            && self.constraints.is_empty()
+            && self.policies.is_empty()
+            && self.time_zones.is_empty()
+    }
+
     /// Applies an AST node to the repository
     ///
     /// For example, alter table add column will add the column to the table if it does not exist
@@ -58,6 +129,10 @@ impl SchemaCache {
             .find(|t| t.name == name && schema.is_none() || Some(t.schema.as_str()) == schema)
     }
 
+    pub fn find_type_by_id(&self, id: i64) -> Option<&PostgresType> {
+        self.types.iter().find(|t| t.id == id)
+    }
+
     pub fn find_col(&self, name: &str, table: &str, schema: Option<&str>) -> Option<&Column> {
         self.columns.iter().find(|c| {
             c.name.as_str() == name
@@ -0,0 +1,23 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+/// A sequence, as listed in `pg_sequence`.
+#[derive(Debug, Clone, Default)]
+pub struct Sequence {
+    pub name: String,
+    pub schema: String,
+
+    /// The type of the values of the sequence, e.g. `bigint`.
+    pub data_type: String,
+}
+
+impl SchemaCacheItem for Sequence {
+    type Item = Sequence;
+
+    async fn load(pool: &PgPool) -> Result<Vec<Sequence>, sqlx::Error> {
+        sqlx::query_file_as!(Sequence, "src/queries/sequences.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
@@ -0,0 +1,27 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+/// A run-time parameter, as listed in `pg_settings`.
+#[derive(Debug, Clone, Default)]
+pub struct Setting {
+    pub name: String,
+    pub value: String,
+    pub description: String,
+
+    /// One of `bool`, `enum`, `integer`, `real` or `string`.
+    pub kind: String,
+
+    /// The allowed values, if this is an `enum` setting.
+    pub enum_values: Option<Vec<String>>,
+}
+
+impl SchemaCacheItem for Setting {
+    type Item = Setting;
+
+    async fn load(pool: &PgPool) -> Result<Vec<Setting>, sqlx::Error> {
+        sqlx::query_file_as!(Setting, "src/queries/settings.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
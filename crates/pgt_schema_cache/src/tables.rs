@@ -35,6 +35,7 @@ pub struct Table {
     pub size: String,
     pub live_rows_estimate: i64,
     pub dead_rows_estimate: i64,
+    pub is_partitioned: bool,
     pub comment: Option<String>,
 }
 
@@ -0,0 +1,26 @@
+use sqlx::PgPool;
+
+use crate::schema_cache::SchemaCacheItem;
+
+/// A time zone, as listed in `pg_timezone_names`.
+#[derive(Debug, Clone, Default)]
+pub struct TimeZone {
+    pub name: String,
+    pub abbrev: String,
+
+    /// The current offset from UTC, e.g. `-05:00:00`.
+    pub utc_offset: String,
+
+    /// Whether the zone currently observes daylight saving time.
+    pub is_dst: bool,
+}
+
+impl SchemaCacheItem for TimeZone {
+    type Item = TimeZone;
+
+    async fn load(pool: &PgPool) -> Result<Vec<TimeZone>, sqlx::Error> {
+        sqlx::query_file_as!(TimeZone, "src/queries/time_zones.sql")
+            .fetch_all(pool)
+            .await
+    }
+}
@@ -6,13 +6,13 @@ use crate::schema_cache::SchemaCacheItem;
 
 #[derive(Debug, Clone, Default)]
 pub struct TypeAttributes {
-    attrs: Vec<PostgresTypeAttribute>,
+    pub attrs: Vec<PostgresTypeAttribute>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize)]
 pub struct PostgresTypeAttribute {
-    name: String,
-    type_id: i64,
+    pub name: String,
+    pub type_id: i64,
 }
 
 impl From<Option<JsonValue>> for TypeAttributes {
@@ -44,9 +44,18 @@ pub struct PostgresType {
     pub format: String,
     pub enums: Enums,
     pub attributes: TypeAttributes,
+    /// The type of the elements, if this is an array type.
+    pub element_type_id: Option<i64>,
     pub comment: Option<String>,
 }
 
+impl PostgresType {
+    /// The labels of an enum type in their sort order, empty for all other types.
+    pub fn enum_labels(&self) -> &[String] {
+        &self.enums.values
+    }
+}
+
 impl SchemaCacheItem for PostgresType {
     type Item = PostgresType;
 
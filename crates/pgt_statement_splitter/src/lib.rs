@@ -7,10 +7,24 @@ mod parser;
 use parser::{Parser, ParserResult, source};
 use pgt_lexer::diagnostics::ScanError;
 
+/// Options that control how statements are split
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SplitOptions {
+    /// Treat every newline as the end of a statement, instead of only blank lines and semicolons
+    pub newline_terminates_statements: bool,
+}
+
 pub fn split(sql: &str) -> Result<ParserResult, Vec<ScanError>> {
+    split_with_options(sql, SplitOptions::default())
+}
+
+pub fn split_with_options(
+    sql: &str,
+    options: SplitOptions,
+) -> Result<ParserResult, Vec<ScanError>> {
     let tokens = pgt_lexer::lex(sql)?;
 
-    let mut parser = Parser::new(tokens);
+    let mut parser = Parser::with_options(tokens, options);
 
     source(&mut parser);
 
@@ -112,6 +126,44 @@ mod tests {
             .expect_statements(vec!["select 1 from contact", "select 1"]);
     }
 
+    #[test]
+    fn newline_terminates_statements() {
+        let input = "set search_path to app\nselect id from users\ndrop table old_users\ninsert into tbl (id) values (1)";
+        let options = SplitOptions {
+            newline_terminates_statements: true,
+        };
+
+        let parse = split_with_options(input, options).expect("Failed to split");
+
+        assert_eq!(
+            parse.ranges.iter().map(|r| &input[*r]).collect::<Vec<_>>(),
+            vec![
+                "set search_path to app",
+                "select id from users",
+                "drop table old_users",
+                "insert into tbl (id) values (1)"
+            ]
+        );
+    }
+
+    #[test]
+    fn newline_terminates_statements_keeps_parenthesis_together() {
+        let input = "create table users (\n  id int,\n  name text\n)\nselect 1";
+        let options = SplitOptions {
+            newline_terminates_statements: true,
+        };
+
+        let parse = split_with_options(input, options).expect("Failed to split");
+
+        assert_eq!(
+            parse.ranges.iter().map(|r| &input[*r]).collect::<Vec<_>>(),
+            vec![
+                "create table users (\n  id int,\n  name text\n)",
+                "select 1"
+            ]
+        );
+    }
+
     #[test]
     fn grant() {
         Tester::from("GRANT SELECT ON TABLE \"public\".\"my_table\" TO \"my_role\";")
@@ -8,7 +8,7 @@ pub use common::source;
 use pgt_lexer::{SyntaxKind, Token, WHITESPACE_TOKENS};
 use pgt_text_size::{TextRange, TextSize};
 
-use crate::diagnostics::SplitDiagnostic;
+use crate::{SplitOptions, diagnostics::SplitDiagnostic};
 
 /// Main parser that exposes the `cstree` api, and collects errors and statements
 /// It is modelled after a Pratt Parser. For a gentle introduction to Pratt Parsing, see https://matklad.github.io/2020/04/13/simple-but-powerful-pratt-parsing.html
@@ -26,6 +26,9 @@ pub struct Parser {
     eof_token: Token,
 
     current_pos: usize,
+
+    /// Whether single newlines are relevant and end statements
+    newline_terminates_statements: bool,
 }
 
 #[derive(Debug)]
@@ -38,6 +41,12 @@ pub struct ParserResult {
 
 impl Parser {
     pub fn new(tokens: Vec<Token>) -> Self {
+        Self::with_options(tokens, SplitOptions::default())
+    }
+
+    pub fn with_options(tokens: Vec<Token>, options: SplitOptions) -> Self {
+        let newline_terminates_statements = options.newline_terminates_statements;
+
         let eof_token = Token::eof(usize::from(
             tokens
                 .last()
@@ -47,7 +56,10 @@ impl Parser {
 
         // Place `current_pos` on the first relevant token
         let mut current_pos = 0;
-        while is_irrelevant_token(tokens.get(current_pos).unwrap_or(&eof_token)) {
+        while is_irrelevant_token(
+            tokens.get(current_pos).unwrap_or(&eof_token),
+            newline_terminates_statements,
+        ) {
             current_pos += 1;
         }
 
@@ -58,6 +70,7 @@ impl Parser {
             current_stmt_start: None,
             tokens,
             current_pos,
+            newline_terminates_statements,
         }
     }
 
@@ -118,12 +131,13 @@ impl Parser {
     /// NOTE: This will skip irrelevant tokens.
     fn advance(&mut self) -> &Token {
         // can't reuse any `find_next_relevant` logic because of Mr. Borrow Checker
+        let newline_terminates_statements = self.newline_terminates_statements;
         let (pos, token) = self
             .tokens
             .iter()
             .enumerate()
             .skip(self.current_pos + 1)
-            .find(|(_, t)| is_relevant(t))
+            .find(|(_, t)| is_relevant(t, newline_terminates_statements))
             .unwrap_or((self.tokens.len(), &self.eof_token));
 
         self.current_pos = pos;
@@ -134,7 +148,7 @@ impl Parser {
         self.tokens
             .iter()
             .skip(self.current_pos + 1)
-            .find(|t| is_relevant(t))
+            .find(|t| is_relevant(t, self.newline_terminates_statements))
     }
 
     /// Returns `None` if there are no previous relevant tokens
@@ -160,7 +174,7 @@ impl Parser {
             .iter()
             .enumerate()
             .take(self.current_pos)
-            .rfind(|(_, t)| is_relevant(t))
+            .rfind(|(_, t)| is_relevant(t, self.newline_terminates_statements))
     }
 }
 
@@ -168,22 +182,25 @@ impl Parser {
 /// Returns true if the token is relevant for the parsing process
 ///
 /// On windows, a newline is represented by `\r\n` which is two characters.
-fn is_irrelevant_token(t: &Token) -> bool {
+fn is_irrelevant_token(t: &Token, newline_terminates_statements: bool) -> bool {
     WHITESPACE_TOKENS.contains(&t.kind)
-        // double new lines are relevant, single ones are not
-        && (t.kind != SyntaxKind::Newline || t.text == "\r\n" || t.text.chars().count() == 1)
+        // double new lines are relevant, single ones are not unless configured otherwise
+        && (t.kind != SyntaxKind::Newline
+            || (!newline_terminates_statements
+                && (t.text == "\r\n" || t.text.chars().count() == 1)))
 }
 
 #[cfg(not(windows))]
 /// Returns true if the token is relevant for the parsing process
-fn is_irrelevant_token(t: &Token) -> bool {
+fn is_irrelevant_token(t: &Token, newline_terminates_statements: bool) -> bool {
     WHITESPACE_TOKENS.contains(&t.kind)
-        // double new lines are relevant, single ones are not
-        && (t.kind != SyntaxKind::Newline || t.text.chars().count() == 1)
+        // double new lines are relevant, single ones are not unless configured otherwise
+        && (t.kind != SyntaxKind::Newline
+            || (!newline_terminates_statements && t.text.chars().count() == 1))
 }
 
-fn is_relevant(t: &Token) -> bool {
-    !is_irrelevant_token(t)
+fn is_relevant(t: &Token, newline_terminates_statements: bool) -> bool {
+    !is_irrelevant_token(t, newline_terminates_statements)
 }
 
 #[cfg(test)]
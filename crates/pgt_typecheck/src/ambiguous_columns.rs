@@ -0,0 +1,318 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use pgt_query_ext::{ChildrenIterator, NodeEnum, protobuf::SelectStmt};
+use pgt_schema_cache::SchemaCache;
+
+use crate::TypecheckDiagnostic;
+use crate::diagnostics::create_ambiguous_column_error;
+use crate::unbound_qualifiers::identifier_chain;
+
+/// Checks the unqualified column references of every `SELECT` against the columns of the tables
+/// in its `FROM` clause and flags those that more than one of them provides, e.g. `name` in
+/// `select name from users join teams on users.team_id = teams.id`.
+///
+/// Only the target list, the `WHERE` clause and the `ON` conditions of joins are checked, since
+/// `ORDER BY` and `GROUP BY` may reference output columns. An `ON` condition is checked against the
+/// tables of its own join only. Unqualified table names are resolved against the default search
+/// path. Columns merged by `USING` are exempt, and selects with a `NATURAL` join are skipped
+/// entirely. Spans are relative to the statement.
+pub fn check_ambiguous_columns(
+    ast: &NodeEnum,
+    sql: &str,
+    schema_cache: &SchemaCache,
+) -> Vec<TypecheckDiagnostic> {
+    // ctes shadow tables of the same name
+    let ctes: HashSet<String> = ChildrenIterator::new(ast.clone())
+        .filter_map(|node| match node {
+            NodeEnum::CommonTableExpr(n) => Some(n.ctename),
+            _ => None,
+        })
+        .collect();
+
+    ChildrenIterator::new(ast.clone())
+        .filter_map(|node| match node {
+            NodeEnum::SelectStmt(n) => Some(n),
+            _ => None,
+        })
+        .flat_map(|select| check_select(&select, sql, schema_cache, &ctes))
+        .collect()
+}
+
+/// The relations of the `FROM` clause of a single select.
+#[derive(Default)]
+struct Scope<'a> {
+    /// The column names of every table, once per occurrence
+    relations: Vec<HashSet<&'a str>>,
+    /// Columns merged by `join .. using (..)`
+    merged: HashSet<String>,
+    /// The `on` conditions of joins, with the indices of the relations they can reference
+    join_quals: Vec<(NodeEnum, Range<usize>)>,
+    has_natural_join: bool,
+}
+
+/// The schemas that unqualified relation names are looked up in, in order
+const SEARCH_PATH: &[&str] = &["pg_catalog", "public"];
+
+impl<'a> Scope<'a> {
+    fn add_from_item(
+        &mut self,
+        item: Option<&NodeEnum>,
+        schema_cache: &'a SchemaCache,
+        ctes: &HashSet<String>,
+    ) {
+        match item {
+            Some(NodeEnum::RangeVar(n)) => {
+                // the columns of a relation with column aliases are renamed
+                if n.alias.as_ref().is_some_and(|a| !a.colnames.is_empty())
+                    || (n.schemaname.is_empty() && ctes.contains(&n.relname))
+                {
+                    return;
+                }
+
+                let schema = if n.schemaname.is_empty() {
+                    SEARCH_PATH.iter().copied().find(|schema| {
+                        schema_cache
+                            .columns
+                            .iter()
+                            .any(|c| c.table_name == n.relname && c.schema_name == *schema)
+                    })
+                } else {
+                    Some(n.schemaname.as_str())
+                };
+
+                let Some(schema) = schema else {
+                    return;
+                };
+
+                let columns: HashSet<&str> = schema_cache
+                    .columns
+                    .iter()
+                    .filter(|c| c.table_name == n.relname && c.schema_name == schema)
+                    .map(|c| c.name.as_str())
+                    .collect();
+
+                if !columns.is_empty() {
+                    self.relations.push(columns);
+                }
+            }
+            Some(NodeEnum::JoinExpr(n)) => {
+                self.has_natural_join |= n.is_natural;
+                self.merged
+                    .extend(n.using_clause.iter().filter_map(|name| match &name.node {
+                        Some(NodeEnum::String(s)) => Some(s.sval.clone()),
+                        _ => None,
+                    }));
+
+                let first_relation = self.relations.len();
+
+                self.add_from_item(
+                    n.larg.as_ref().and_then(|n| n.node.as_ref()),
+                    schema_cache,
+                    ctes,
+                );
+                self.add_from_item(
+                    n.rarg.as_ref().and_then(|n| n.node.as_ref()),
+                    schema_cache,
+                    ctes,
+                );
+
+                if let Some(qual) = n.quals.as_ref().and_then(|n| n.node.clone()) {
+                    self.join_quals
+                        .push((qual, first_relation..self.relations.len()));
+                }
+            }
+            // the columns of subqueries and functions are not known
+            _ => {}
+        }
+    }
+}
+
+fn check_select(
+    select: &SelectStmt,
+    sql: &str,
+    schema_cache: &SchemaCache,
+    ctes: &HashSet<String>,
+) -> Vec<TypecheckDiagnostic> {
+    let mut scope = Scope::default();
+    for item in &select.from_clause {
+        scope.add_from_item(item.node.as_ref(), schema_cache, ctes);
+    }
+
+    if scope.relations.len() < 2 || scope.has_natural_join {
+        return vec![];
+    }
+
+    let all_relations = 0..scope.relations.len();
+    let exprs = select
+        .target_list
+        .iter()
+        .chain(select.where_clause.as_deref())
+        .filter_map(|n| n.node.clone())
+        .map(|expr| (expr, all_relations.clone()))
+        .chain(std::mem::take(&mut scope.join_quals));
+
+    let mut diagnostics = vec![];
+
+    for (expr, relations) in exprs {
+        let nodes: Vec<NodeEnum> = ChildrenIterator::new(expr).collect();
+
+        // references within subqueries are resolved against the relations of the subquery first
+        let nested: HashSet<i32> = nodes
+            .iter()
+            .filter_map(|node| match node {
+                NodeEnum::SelectStmt(n) => Some(n),
+                _ => None,
+            })
+            .flat_map(|n| ChildrenIterator::new(NodeEnum::SelectStmt(n.clone())))
+            .filter_map(|node| match node {
+                NodeEnum::ColumnRef(n) => Some(n.location),
+                _ => None,
+            })
+            .collect();
+
+        for node in nodes {
+            let NodeEnum::ColumnRef(column_ref) = node else {
+                continue;
+            };
+
+            let [field] = column_ref.fields.as_slice() else {
+                continue;
+            };
+
+            let Some(NodeEnum::String(name)) = &field.node else {
+                continue;
+            };
+
+            if nested.contains(&column_ref.location) || scope.merged.contains(&name.sval) {
+                continue;
+            }
+
+            let providers = scope.relations[relations.clone()]
+                .iter()
+                .filter(|columns| columns.contains(name.sval.as_str()))
+                .count();
+
+            if providers > 1 {
+                let span = usize::try_from(column_ref.location)
+                    .ok()
+                    .and_then(|location| identifier_chain(sql, location).first().copied());
+
+                diagnostics.push(create_ambiguous_column_error(&name.sval, span));
+            }
+        }
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_diagnostics::{Diagnostic, Severity};
+    use pgt_schema_cache::{Column, ColumnClassKind, SchemaCache};
+
+    use super::check_ambiguous_columns;
+
+    fn column(table: &str, name: &str) -> Column {
+        Column {
+            name: name.into(),
+            table_name: table.into(),
+            table_oid: 0,
+            class_kind: ColumnClassKind::OrdinaryTable,
+            schema_name: "public".into(),
+            type_id: 25,
+            is_nullable: true,
+            is_primary_key: false,
+            is_unique: false,
+            default_expr: None,
+            varchar_length: None,
+            comment: None,
+        }
+    }
+
+    fn cache() -> SchemaCache {
+        SchemaCache {
+            columns: vec![
+                column("users", "id"),
+                column("users", "name"),
+                column("users", "team_id"),
+                column("teams", "id"),
+                column("teams", "name"),
+                Column {
+                    schema_name: "audit".into(),
+                    ..column("teams", "team_id")
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn ambiguous_columns(sql: &str) -> Vec<(String, Severity)> {
+        let ast = pgt_query_ext::parse(sql).unwrap();
+
+        check_ambiguous_columns(&ast, sql, &cache())
+            .into_iter()
+            .map(|d| {
+                (
+                    sql[d.location().span.expect("Expected a span")].to_string(),
+                    d.severity(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flags_columns_of_several_joined_tables() {
+        assert_eq!(
+            ambiguous_columns(
+                "select name, team_id from users join teams on users.team_id = teams.id"
+            ),
+            vec![("name".to_string(), Severity::Error)]
+        );
+    }
+
+    #[test]
+    fn accepts_qualified_merged_and_shadowed_columns() {
+        assert!(
+            ambiguous_columns("select u.name from users u join teams t on u.team_id = t.id")
+                .is_empty()
+        );
+        assert!(
+            ambiguous_columns("select id, name from users join teams using (id, name)").is_empty()
+        );
+        assert!(
+            ambiguous_columns(
+                "select name from users where exists (select 1 from teams where name = 'a')"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn flags_columns_of_join_conditions() {
+        assert_eq!(
+            ambiguous_columns("select users.id from users join teams on name = 'admins'"),
+            vec![("name".to_string(), Severity::Error)]
+        );
+        assert!(
+            ambiguous_columns(
+                "select users.id from users join teams on users.team_id = teams.id and teams.name = 'admins'"
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn resolves_tables_within_their_schema() {
+        assert!(
+            ambiguous_columns("select team_id from users join teams on users.team_id = teams.id")
+                .is_empty()
+        );
+        assert_eq!(
+            ambiguous_columns(
+                "select team_id from users join audit.teams on users.team_id = teams.id"
+            ),
+            vec![("team_id".to_string(), Severity::Error)]
+        );
+    }
+}
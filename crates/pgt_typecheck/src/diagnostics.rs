@@ -94,6 +94,95 @@ impl Advices for TypecheckAdvices {
     }
 }
 
+/// Creates the diagnostic Postgres would report for a qualifier that names no relation in scope.
+pub(crate) fn create_missing_from_error(
+    table: &str,
+    span: Option<TextRange>,
+) -> TypecheckDiagnostic {
+    TypecheckDiagnostic {
+        message: format!("missing FROM-clause entry for table \"{table}\"").into(),
+        severity: Severity::Error,
+        span,
+        advices: TypecheckAdvices {
+            code: "42P01".to_string(),
+            schema: None,
+            table: None,
+            column: None,
+            data_type: None,
+            constraint: None,
+            detail: None,
+            where_: None,
+            hint: Some(format!(
+                "Add \"{table}\" to the FROM clause or use the name or alias of a relation in scope."
+            )),
+            line: None,
+            file: None,
+            routine: None,
+        },
+    }
+}
+
+/// Creates the diagnostic Postgres would report for a column that several relations in scope provide.
+pub(crate) fn create_ambiguous_column_error(
+    column: &str,
+    span: Option<TextRange>,
+) -> TypecheckDiagnostic {
+    TypecheckDiagnostic {
+        message: format!("column reference \"{column}\" is ambiguous").into(),
+        severity: Severity::Error,
+        span,
+        advices: TypecheckAdvices {
+            code: "42702".to_string(),
+            schema: None,
+            table: None,
+            column: Some(column.to_string()),
+            data_type: None,
+            constraint: None,
+            detail: None,
+            where_: None,
+            hint: Some("Qualify the column with the name or alias of its relation.".to_string()),
+            line: None,
+            file: None,
+            routine: None,
+        },
+    }
+}
+
+/// Creates the warning for a function call whose argument count matches no known overload.
+pub(crate) fn create_argument_count_warning(
+    function: &str,
+    arg_count: usize,
+    span: Option<TextRange>,
+) -> TypecheckDiagnostic {
+    let arguments = if arg_count == 1 {
+        "argument"
+    } else {
+        "arguments"
+    };
+
+    TypecheckDiagnostic {
+        message: format!("no function \"{function}\" accepts {arg_count} {arguments}").into(),
+        severity: Severity::Warning,
+        span,
+        advices: TypecheckAdvices {
+            code: "42883".to_string(),
+            schema: None,
+            table: None,
+            column: None,
+            data_type: None,
+            constraint: None,
+            detail: None,
+            where_: None,
+            hint: Some(
+                "Check the number of arguments against the signature of the function.".to_string(),
+            ),
+            line: None,
+            file: None,
+            routine: None,
+        },
+    }
+}
+
 pub(crate) fn create_type_error(
     pg_err: &PgDatabaseError,
     ts: &tree_sitter::Tree,
@@ -0,0 +1,185 @@
+use pgt_query_ext::{ChildrenIterator, NodeEnum, protobuf::CoercionForm};
+use pgt_schema_cache::{Function, SchemaCache};
+use pgt_text_size::TextRange;
+
+use crate::TypecheckDiagnostic;
+use crate::diagnostics::create_argument_count_warning;
+use crate::unbound_qualifiers::identifier_chain;
+
+/// Checks the function calls of a statement against the functions of the schema cache and
+/// flags calls whose argument count matches no overload of a function with the same name.
+///
+/// The check is conservative: calls to unknown functions, aggregate calls with `*`, an order or
+/// a filter, window calls, variadic calls and calls using named arguments are skipped, and an
+/// overload with a variadic parameter accepts any number of arguments. Overloads of all schemas
+/// are considered for unqualified calls, e.g. a user defined `sum(a, b)` does not hide the
+/// built-in aggregate `sum(x)`. Spans are relative to the statement.
+pub fn check_function_arity(
+    ast: &NodeEnum,
+    sql: &str,
+    schema_cache: &SchemaCache,
+) -> Vec<TypecheckDiagnostic> {
+    // procedures are not part of the schema cache
+    if matches!(ast, NodeEnum::CallStmt(_)) {
+        return vec![];
+    }
+
+    ChildrenIterator::new(ast.clone())
+        .filter_map(|node| match node {
+            NodeEnum::FuncCall(n) => Some(n),
+            _ => None,
+        })
+        .filter(|call| {
+            !call.agg_star
+                && !call.func_variadic
+                && call.agg_order.is_empty()
+                && call.agg_filter.is_none()
+                && call.over.is_none()
+                // sql syntax like `extract(.. from ..)` is rewritten to internal functions
+                && call.funcformat != CoercionForm::CoerceSqlSyntax as i32
+                && !call
+                    .args
+                    .iter()
+                    .any(|arg| matches!(arg.node, Some(NodeEnum::NamedArgExpr(_))))
+        })
+        .filter_map(|call| {
+            let names: Vec<&str> = call
+                .funcname
+                .iter()
+                .filter_map(|n| match &n.node {
+                    Some(NodeEnum::String(s)) => Some(s.sval.as_str()),
+                    _ => None,
+                })
+                .collect();
+
+            let (schema, name) = match names.as_slice() {
+                [name] => (None, *name),
+                [.., schema, name] => (Some(*schema), *name),
+                [] => return None,
+            };
+
+            let mut overloads = schema_cache
+                .functions
+                .iter()
+                .filter(|f| f.name == name && schema.is_none_or(|s| f.schema == s))
+                .peekable();
+
+            // we know nothing about functions that are not in the cache
+            overloads.peek()?;
+
+            let arg_count = call.args.len();
+            if overloads.any(|f| accepts(f, arg_count)) {
+                return None;
+            }
+
+            let span = usize::try_from(call.location).ok().and_then(|location| {
+                let chain = identifier_chain(sql, location);
+                Some(TextRange::new(chain.first()?.start(), chain.last()?.end()))
+            });
+
+            Some(create_argument_count_warning(name, arg_count, span))
+        })
+        .collect()
+}
+
+/// Whether `function` can be called with `arg_count` positional arguments.
+fn accepts(function: &Function, arg_count: usize) -> bool {
+    let input_args: Vec<_> = function
+        .args
+        .args
+        .iter()
+        .filter(|arg| matches!(arg.mode.as_str(), "in" | "inout" | "variadic"))
+        .collect();
+
+    if input_args.iter().any(|arg| arg.mode == "variadic") {
+        return true;
+    }
+
+    let defaults = input_args
+        .iter()
+        .filter(|arg| arg.has_default.unwrap_or(false))
+        .count();
+
+    (input_args.len() - defaults..=input_args.len()).contains(&arg_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_diagnostics::{Diagnostic, Severity};
+    use pgt_schema_cache::{Function, FunctionArg, FunctionArgs, SchemaCache};
+
+    use super::check_function_arity;
+
+    fn function(name: &str, args: &[(&str, bool)]) -> Function {
+        Function {
+            schema: "public".into(),
+            name: name.into(),
+            args: FunctionArgs {
+                args: args
+                    .iter()
+                    .map(|(mode, has_default)| FunctionArg {
+                        mode: mode.to_string(),
+                        name: String::new(),
+                        type_id: 25,
+                        has_default: Some(*has_default),
+                    })
+                    .collect(),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn cache() -> SchemaCache {
+        SchemaCache {
+            functions: vec![
+                function("greet", &[("in", false)]),
+                function("pad", &[("in", false), ("in", true)]),
+                function("concat_all", &[("variadic", false)]),
+                function("sum", &[("in", false), ("in", false)]),
+                Function {
+                    schema: "pg_catalog".into(),
+                    ..function("sum", &[("in", false)])
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn arity_warnings(sql: &str) -> Vec<(String, Severity)> {
+        let ast = pgt_query_ext::parse(sql).unwrap();
+
+        check_function_arity(&ast, sql, &cache())
+            .into_iter()
+            .map(|d| {
+                (
+                    sql[d.location().span.expect("Expected a span")].to_string(),
+                    d.severity(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn flags_calls_without_a_matching_overload() {
+        assert_eq!(
+            arity_warnings("select public.greet('a', 'b', 'c')"),
+            vec![("public.greet".to_string(), Severity::Warning)]
+        );
+    }
+
+    #[test]
+    fn accepts_defaults_variadics_and_unknown_functions() {
+        assert!(arity_warnings("select greet('a'), pad('a'), pad('a', 'b')").is_empty());
+        assert!(arity_warnings("select concat_all('a', 'b', 'c')").is_empty());
+        assert!(arity_warnings("select unknown_fn(1, 2, 3), other.greet(1, 2)").is_empty());
+    }
+
+    #[test]
+    fn accepts_calls_matching_an_overload_of_another_schema() {
+        assert!(arity_warnings("select sum(x), sum(x, y) from t").is_empty());
+        assert_eq!(
+            arity_warnings("select public.sum(x) from t"),
+            vec![("public.sum".to_string(), Severity::Warning)]
+        );
+    }
+}
@@ -1,11 +1,17 @@
+mod ambiguous_columns;
 mod diagnostics;
+mod function_arity;
+mod unbound_qualifiers;
 
+pub use ambiguous_columns::check_ambiguous_columns;
 pub use diagnostics::TypecheckDiagnostic;
 use diagnostics::create_type_error;
+pub use function_arity::check_function_arity;
 use pgt_text_size::TextRange;
 use sqlx::postgres::PgDatabaseError;
 pub use sqlx::postgres::PgSeverity;
 use sqlx::{Executor, PgPool};
+pub use unbound_qualifiers::check_unbound_qualifiers;
 
 #[derive(Debug)]
 pub struct TypecheckParams<'a> {
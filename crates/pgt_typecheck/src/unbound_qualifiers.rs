@@ -0,0 +1,199 @@
+use std::collections::HashSet;
+
+use pgt_query_ext::{ChildrenIterator, NodeEnum};
+use pgt_text_size::{TextRange, TextSize};
+
+use crate::TypecheckDiagnostic;
+use crate::diagnostics::create_missing_from_error;
+
+/// Statically checks a statement for column references whose qualifier does not match any
+/// relation in scope, e.g. `z` in `select z.x from users u`.
+///
+/// Postgres would reject these statements with a "missing FROM-clause entry" error, so this
+/// catches them before the database is consulted. All relations of the statement are considered
+/// to be in scope, which keeps correlated subqueries and `lateral` joins free of false positives.
+/// Spans are relative to the statement.
+pub fn check_unbound_qualifiers(ast: &NodeEnum, sql: &str) -> Vec<TypecheckDiagnostic> {
+    if !matches!(
+        ast,
+        NodeEnum::SelectStmt(_)
+            | NodeEnum::InsertStmt(_)
+            | NodeEnum::UpdateStmt(_)
+            | NodeEnum::DeleteStmt(_)
+    ) {
+        return vec![];
+    }
+
+    // the pseudo relation of the proposed row in `on conflict do update`
+    let mut in_scope: HashSet<String> = HashSet::from(["excluded".to_string()]);
+    let mut qualified_refs = Vec::new();
+
+    for node in ChildrenIterator::new(ast.clone()) {
+        match node {
+            NodeEnum::RangeVar(n) => {
+                in_scope.insert(n.relname);
+                if let Some(alias) = n.alias {
+                    in_scope.insert(alias.aliasname);
+                }
+            }
+            NodeEnum::RangeSubselect(n) => {
+                if let Some(alias) = n.alias {
+                    in_scope.insert(alias.aliasname);
+                }
+            }
+            NodeEnum::RangeFunction(n) => match &n.alias {
+                Some(alias) => {
+                    in_scope.insert(alias.aliasname.clone());
+                }
+                // without an alias, the function name is used as the relation name
+                None => in_scope.extend(
+                    ChildrenIterator::new(NodeEnum::RangeFunction(n.clone())).filter_map(|node| {
+                        match node {
+                            NodeEnum::FuncCall(f) => last_name(&f.funcname),
+                            _ => None,
+                        }
+                    }),
+                ),
+            },
+            NodeEnum::RangeTableFunc(n) => {
+                if let Some(alias) = n.alias {
+                    in_scope.insert(alias.aliasname);
+                }
+            }
+            NodeEnum::JoinExpr(n) => {
+                if let Some(alias) = n.alias {
+                    in_scope.insert(alias.aliasname);
+                }
+            }
+            NodeEnum::CommonTableExpr(n) => {
+                in_scope.insert(n.ctename);
+            }
+            NodeEnum::ColumnRef(n) => {
+                let Some(qualifier_idx) = n.fields.len().checked_sub(2) else {
+                    continue;
+                };
+
+                if let Some(NodeEnum::String(qualifier)) = &n.fields[qualifier_idx].node {
+                    qualified_refs.push((qualifier.sval.clone(), qualifier_idx, n.location));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    qualified_refs
+        .into_iter()
+        .filter(|(qualifier, _, _)| !in_scope.contains(qualifier))
+        .map(|(qualifier, idx, location)| {
+            let span = usize::try_from(location)
+                .ok()
+                .and_then(|location| identifier_chain(sql, location).get(idx).copied());
+
+            create_missing_from_error(&qualifier, span)
+        })
+        .collect()
+}
+
+fn last_name(names: &[pgt_query_ext::protobuf::Node]) -> Option<String> {
+    match names.last().and_then(|n| n.node.as_ref()) {
+        Some(NodeEnum::String(s)) => Some(s.sval.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the ranges of the identifiers in a dotted chain like `schema.table.column` that
+/// starts at byte offset `start`.
+pub(crate) fn identifier_chain(sql: &str, start: usize) -> Vec<TextRange> {
+    let bytes = sql.as_bytes();
+    let mut chain = Vec::new();
+    let mut pos = start;
+
+    loop {
+        let ident_start = pos;
+
+        if bytes.get(pos) == Some(&b'"') {
+            pos += 1;
+            loop {
+                match bytes.get(pos) {
+                    // a doubled quote is an escaped quote within the identifier
+                    Some(b'"') if bytes.get(pos + 1) == Some(&b'"') => pos += 2,
+                    Some(b'"') => {
+                        pos += 1;
+                        break;
+                    }
+                    Some(_) => pos += 1,
+                    None => return chain,
+                }
+            }
+        } else {
+            pos += sql[pos..]
+                .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .unwrap_or(sql.len() - pos);
+        }
+
+        if pos == ident_start {
+            break;
+        }
+
+        chain.push(TextRange::new(
+            TextSize::try_from(ident_start).unwrap(),
+            TextSize::try_from(pos).unwrap(),
+        ));
+
+        let after_whitespace = |pos: usize| {
+            pos + sql[pos..]
+                .find(|c: char| !c.is_whitespace())
+                .unwrap_or(sql.len() - pos)
+        };
+
+        pos = after_whitespace(pos);
+        if bytes.get(pos) != Some(&b'.') {
+            break;
+        }
+        pos = after_whitespace(pos + 1);
+    }
+
+    chain
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_diagnostics::Diagnostic;
+
+    use super::check_unbound_qualifiers;
+
+    fn unbound_qualifiers(sql: &str) -> Vec<String> {
+        let ast = pgt_query_ext::parse(sql).unwrap();
+
+        check_unbound_qualifiers(&ast, sql)
+            .into_iter()
+            .map(|d| sql[d.location().span.expect("Expected a span")].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn flags_qualifiers_without_a_relation() {
+        assert_eq!(unbound_qualifiers("select z.x from users u"), vec!["z"]);
+        assert_eq!(
+            unbound_qualifiers("select public.\"Z\".x from users u"),
+            vec!["\"Z\""]
+        );
+    }
+
+    #[test]
+    fn accepts_qualifiers_in_scope() {
+        assert!(unbound_qualifiers("select u.x, users.y from public.users u").is_empty());
+        assert!(
+            unbound_qualifiers(
+                "with c as (select 1 as x) select c.x, s.x from c, (select 1 as x) s where exists (select 1 from t where t.x = c.x)"
+            )
+            .is_empty()
+        );
+        assert!(
+            unbound_qualifiers(
+                "insert into t (x) values (1) on conflict (x) do update set x = excluded.x"
+            )
+            .is_empty()
+        );
+    }
+}
@@ -39,6 +39,8 @@ pub enum WorkspaceError {
     Vcs(VcsDiagnostic),
     /// Error in the async runtime
     RuntimeError(RuntimeError),
+    /// Emitted when a change is older than the document it should be applied to
+    StaleChange(StaleChange),
 }
 
 impl WorkspaceError {
@@ -61,6 +63,14 @@ impl WorkspaceError {
         Self::Vcs(VcsDiagnostic::DisabledVcs(DisabledVcs {}))
     }
 
+    pub fn stale_change(path: impl Into<String>, version: i32, current_version: i32) -> Self {
+        Self::StaleChange(StaleChange {
+            path: path.into(),
+            version,
+            current_version,
+        })
+    }
+
     pub fn runtime(msg: &str) -> Self {
         Self::RuntimeError(RuntimeError {
             message: msg.into(),
@@ -190,6 +200,22 @@ pub struct RuntimeError {
     message: String,
 }
 
+#[derive(Debug, Diagnostic, Serialize, Deserialize)]
+#[diagnostic(
+    category = "internalError/fs",
+    severity = Error,
+    message(
+        description = "Rejected change with version {version} for {path}, the document is already at version {current_version}.",
+        message("Rejected change with version "{self.version}" for "<Emphasis>{self.path}</Emphasis>", the document is already at version "{self.current_version}"."),
+    )
+)]
+pub struct StaleChange {
+    #[location(resource)]
+    pub path: String,
+    pub version: i32,
+    pub current_version: i32,
+}
+
 impl From<JoinError> for WorkspaceError {
     fn from(err: JoinError) -> Self {
         Self::RuntimeError(RuntimeError {
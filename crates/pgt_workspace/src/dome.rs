@@ -2,6 +2,9 @@ use pgt_fs::PgTPath;
 use std::collections::BTreeSet;
 use std::collections::btree_set::Iter;
 use std::iter::{FusedIterator, Peekable};
+use std::path::Path;
+
+use crate::workspace::server::migration::get_migration;
 
 /// A type that holds the evaluated paths, and provides an iterator to extract
 /// specific paths like configuration files, manifests and more.
@@ -29,6 +32,39 @@ impl Dome {
     pub fn to_paths(self) -> BTreeSet<PgTPath> {
         self.paths
     }
+
+    /// Groups the paths by the kind of file in a single pass.
+    ///
+    /// Files within `migrations_dir` that follow one of the supported naming patterns are
+    /// migrations, all other files to handle are plain sql files.
+    pub fn partition(&self, migrations_dir: Option<&Path>) -> DomePartition {
+        let mut partition = DomePartition::default();
+
+        for path in &self.paths {
+            let group = if path.is_config() {
+                &mut partition.config
+            } else if path.is_ignore() {
+                &mut partition.ignore
+            } else if migrations_dir.is_some_and(|dir| get_migration(path, dir).is_some()) {
+                &mut partition.migrations
+            } else {
+                &mut partition.sql
+            };
+
+            group.insert(path.clone());
+        }
+
+        partition
+    }
+}
+
+/// The paths of a [Dome], grouped by the kind of file.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DomePartition {
+    pub migrations: BTreeSet<PgTPath>,
+    pub sql: BTreeSet<PgTPath>,
+    pub config: BTreeSet<PgTPath>,
+    pub ignore: BTreeSet<PgTPath>,
 }
 
 pub struct DomeIterator<'a> {
@@ -70,3 +106,48 @@ impl<'a> Iterator for DomeIterator<'a> {
 }
 
 impl FusedIterator for DomeIterator<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::fs;
+
+    use pgt_fs::PgTPath;
+    use tempfile::TempDir;
+
+    use super::Dome;
+
+    #[test]
+    fn partitions_paths_by_file_kind() {
+        let tmp = TempDir::new().unwrap();
+        let migrations_dir = tmp.path().join("migrations");
+        fs::create_dir(&migrations_dir).unwrap();
+
+        let migration = migrations_dir.join("20250101000000_create_users.sql");
+        let not_a_migration = migrations_dir.join("seed.sql");
+        let query = tmp.path().join("queries.sql");
+        for file in [&migration, &not_a_migration, &query] {
+            fs::write(file, "select 1;").unwrap();
+        }
+
+        let config = tmp.path().join("postgrestools.jsonc");
+        let ignore = tmp.path().join(".gitignore");
+
+        let dome = [&migration, &not_a_migration, &query, &config, &ignore]
+            .into_iter()
+            .fold(Dome::default(), |dome, path| {
+                dome.with_path(PgTPath::new(path))
+            });
+
+        let partition = dome.partition(Some(&migrations_dir));
+
+        let set = |paths: &[&std::path::PathBuf]| -> BTreeSet<PgTPath> {
+            paths.iter().map(|p| PgTPath::new(*p)).collect()
+        };
+
+        assert_eq!(partition.migrations, set(&[&migration]));
+        assert_eq!(partition.sql, set(&[&not_a_migration, &query]));
+        assert_eq!(partition.config, set(&[&config]));
+        assert_eq!(partition.ignore, set(&[&ignore]));
+    }
+}
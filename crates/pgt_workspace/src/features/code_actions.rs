@@ -1,7 +1,7 @@
 use crate::workspace::StatementId;
 use pgt_configuration::RuleSelector;
 use pgt_fs::PgTPath;
-use pgt_text_size::TextSize;
+use pgt_text_size::{TextRange, TextSize};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -36,7 +36,18 @@ pub enum CodeActionKind {
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
-pub struct EditAction {}
+pub struct EditAction {
+    pub edits: Vec<TextEdit>,
+    /// Whether the edits can be applied without review, e.g. by `check --write`
+    pub is_safe: bool,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TextEdit {
+    pub range: TextRange,
+    pub new_text: String,
+}
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -4,7 +4,9 @@ use pgt_completions::CompletionItem;
 use pgt_fs::PgTPath;
 use pgt_text_size::{TextRange, TextSize};
 
-use crate::workspace::{GetCompletionsFilter, GetCompletionsMapper, ParsedDocument, StatementId};
+use crate::workspace::{
+    GetCompletionsFilter, ParsedDocumentSnapshot, StatementFilter, StatementId,
+};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -13,12 +15,50 @@ pub struct GetCompletionsParams {
     pub path: PgTPath,
     /// The Cursor position in the file for which a completion is requested.
     pub position: TextSize,
+    /// The maximum number of completion items, `completions.limit` if not set.
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CompletionsResult {
     pub(crate) items: Vec<CompletionItem>,
+    /// Whether `completions.telemetry` is enabled, i.e. whether the outcome of the completion
+    /// should be recorded.
+    #[serde(default)]
+    pub telemetry: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RecordCompletionOutcomeParams {
+    /// The label of the completion item that was accepted.
+    pub accepted_label: String,
+    /// The position of the accepted item among the items that were shown, starting at 0.
+    pub shown_order: usize,
+}
+
+/// Receives the outcomes of completions, e.g. to evaluate how well they are ranked.
+///
+/// Outcomes are only recorded if `completions.telemetry` is enabled.
+pub trait CompletionTelemetrySink: Send + Sync {
+    fn record(&self, outcome: &RecordCompletionOutcomeParams);
+}
+
+/// Emits every outcome as a structured tracing event with the target `completion_telemetry`
+#[derive(Debug, Default)]
+pub struct TracingCompletionTelemetry;
+
+impl CompletionTelemetrySink for TracingCompletionTelemetry {
+    fn record(&self, outcome: &RecordCompletionOutcomeParams) {
+        tracing::info!(
+            target: "completion_telemetry",
+            accepted_label = outcome.accepted_label.as_str(),
+            shown_order = outcome.shown_order,
+            "Completion accepted"
+        );
+    }
 }
 
 impl IntoIterator for CompletionsResult {
@@ -30,21 +70,26 @@ impl IntoIterator for CompletionsResult {
 }
 
 pub(crate) fn get_statement_for_completions(
-    doc: &ParsedDocument,
+    doc: &ParsedDocumentSnapshot,
     position: TextSize,
 ) -> Option<(StatementId, TextRange, String, Arc<tree_sitter::Tree>)> {
-    let count = doc.count();
+    let count = doc.statements().len();
     // no arms no cookies
     if count == 0 {
         return None;
     }
 
-    let mut eligible_statements = doc.iter_with_filter(
-        GetCompletionsMapper,
-        GetCompletionsFilter {
-            cursor_position: position,
-        },
-    );
+    let filter = GetCompletionsFilter {
+        cursor_position: position,
+        newline_terminates_statements: doc.newline_terminates_statements(),
+        document_content: doc.get_document_content(),
+    };
+
+    let mut eligible_statements = doc
+        .statements()
+        .iter()
+        .filter(|s| filter.predicate(&s.id, &s.range, &s.content))
+        .map(|s| (s.id.clone(), s.range, s.content.clone(), s.cst.clone()));
 
     if count == 1 {
         eligible_statements.next()
@@ -71,9 +116,10 @@ pub(crate) fn get_statement_for_completions(
 #[cfg(test)]
 mod tests {
     use pgt_fs::PgTPath;
+    use pgt_statement_splitter::SplitOptions;
     use pgt_text_size::TextSize;
 
-    use crate::workspace::ParsedDocument;
+    use crate::workspace::{DefaultMapper, ParsedDocument};
 
     use super::get_statement_for_completions;
 
@@ -112,7 +158,7 @@ mod tests {
         let (doc, position) = get_doc_and_pos(sql.as_str());
 
         let (_, _, text, _) =
-            get_statement_for_completions(&doc, position).expect("Expected Statement");
+            get_statement_for_completions(&doc.snapshot(), position).expect("Expected Statement");
 
         assert_eq!(text, "update users set email = 'myemail@com';")
     }
@@ -123,7 +169,7 @@ mod tests {
 
         let (doc, position) = get_doc_and_pos(sql.as_str());
 
-        assert!(get_statement_for_completions(&doc, position).is_none());
+        assert!(get_statement_for_completions(&doc.snapshot(), position).is_none());
     }
 
     #[test]
@@ -135,7 +181,7 @@ mod tests {
         // make sure these are parsed as two
         assert_eq!(doc.count(), 2);
 
-        assert!(get_statement_for_completions(&doc, position).is_none());
+        assert!(get_statement_for_completions(&doc.snapshot(), position).is_none());
     }
 
     #[test]
@@ -145,7 +191,7 @@ mod tests {
         let (doc, position) = get_doc_and_pos(sql.as_str());
 
         let (_, _, text, _) =
-            get_statement_for_completions(&doc, position).expect("Expected Statement");
+            get_statement_for_completions(&doc.snapshot(), position).expect("Expected Statement");
 
         assert_eq!(text, "select * from          ;")
     }
@@ -157,7 +203,7 @@ mod tests {
         let (doc, position) = get_doc_and_pos(sql.as_str());
 
         let (_, _, text, _) =
-            get_statement_for_completions(&doc, position).expect("Expected Statement");
+            get_statement_for_completions(&doc.snapshot(), position).expect("Expected Statement");
 
         assert_eq!(text, "select * from")
     }
@@ -168,7 +214,7 @@ mod tests {
 
         let (doc, position) = get_doc_and_pos(sql.as_str());
 
-        assert!(get_statement_for_completions(&doc, position).is_none());
+        assert!(get_statement_for_completions(&doc.snapshot(), position).is_none());
     }
 
     #[test]
@@ -177,6 +223,41 @@ mod tests {
 
         let (doc, position) = get_doc_and_pos(sql.as_str());
 
-        assert!(get_statement_for_completions(&doc, position).is_none());
+        assert!(get_statement_for_completions(&doc.snapshot(), position).is_none());
+    }
+
+    #[test]
+    fn targets_newline_terminated_statements() {
+        let sql = format!(
+            "drop table old_users\nselect * from {}\nselect 2\n",
+            CURSOR_POSITION
+        );
+
+        let pos: u32 = sql.find(CURSOR_POSITION).unwrap().try_into().unwrap();
+        let doc = ParsedDocument::with_split_options(
+            PgTPath::new("test.sql"),
+            sql.replace(CURSOR_POSITION, ""),
+            5,
+            SplitOptions {
+                newline_terminates_statements: true,
+            },
+        );
+
+        let statements = doc
+            .iter(DefaultMapper)
+            .map(|(_, _, text)| text)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statements,
+            vec!["drop table old_users", "select * from", "select 2"]
+        );
+
+        let (_, _, text, _) = get_statement_for_completions(&doc.snapshot(), TextSize::new(pos))
+            .expect("Expected Statement");
+        assert_eq!(text, "select * from");
+
+        // the line after a statement belongs to no statement
+        let after_last_line = TextSize::new(doc.get_document_content().len().try_into().unwrap());
+        assert!(get_statement_for_completions(&doc.snapshot(), after_last_line).is_none());
     }
 }
@@ -1,6 +1,9 @@
 use pgt_analyse::RuleCategories;
 use pgt_configuration::RuleSelector;
 use pgt_fs::PgTPath;
+use pgt_text_size::TextRange;
+
+use crate::features::code_actions::CodeAction;
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
@@ -10,12 +13,22 @@ pub struct PullDiagnosticsParams {
     pub max_diagnostics: u64,
     pub only: Vec<RuleSelector>,
     pub skip: Vec<RuleSelector>,
+    /// The range of the document that changed since the last pull.
+    ///
+    /// If set, only the statements overlapping it are analysed again and the diagnostics of all
+    /// other statements are taken from the last pull, unless it used other filters or the
+    /// settings or the schema changed in the meantime.
+    #[serde(default)]
+    pub changed_range: Option<TextRange>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct PullDiagnosticsResult {
     pub diagnostics: Vec<pgt_diagnostics::serde::Diagnostic>,
+    /// The fixes of the reported diagnostics, as edits of the document
+    #[serde(default)]
+    pub fixes: Vec<CodeAction>,
     pub errors: usize,
     pub skipped_diagnostics: u64,
 }
@@ -1,3 +1,4 @@
 pub mod code_actions;
 pub mod completions;
 pub mod diagnostics;
+pub mod rename;
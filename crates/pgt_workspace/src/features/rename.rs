@@ -0,0 +1,452 @@
+use std::collections::HashSet;
+
+use pgt_fs::PgTPath;
+use pgt_lexer::{SyntaxKind, Token, TokenType, WHITESPACE_TOKENS};
+use pgt_query_ext::{ChildrenIterator, NodeEnum, protobuf::RangeVar};
+use pgt_schema_cache::SchemaCache;
+use pgt_text_size::{TextRange, TextSize};
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RenameSymbolParams {
+    pub path: PgTPath,
+    /// The position of the table or column name to rename
+    pub position: TextSize,
+    pub new_name: String,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, Default)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RenameSymbolResult {
+    pub edits: Vec<RenameEdit>,
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct RenameEdit {
+    pub range: TextRange,
+    pub new_text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SymbolKind {
+    Table,
+    Column,
+}
+
+/// A table, identified by its schema and name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TableName {
+    pub schema: String,
+    pub name: String,
+}
+
+/// A name within a statement that refers to a table or a column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SymbolReference {
+    pub range: TextRange,
+    pub kind: SymbolKind,
+    /// The name as resolved by the parser, i.e. with unquoted identifiers folded to lower case
+    pub name: String,
+    /// The referenced table, or the table of the referenced column.
+    ///
+    /// `None` if the statement does not tell which table a column belongs to.
+    pub table: Option<TableName>,
+}
+
+impl SymbolReference {
+    /// Whether both reference the same table, or the same column of the same table
+    pub fn refers_to_same_symbol(&self, other: &SymbolReference) -> bool {
+        self.kind == other.kind
+            && self.name == other.name
+            && self.table.is_some()
+            && self.table == other.table
+    }
+}
+
+/// A table the statement reads or writes, e.g. `public.users u` in a `FROM` clause.
+struct Relation {
+    table: TableName,
+    alias: Option<String>,
+}
+
+/// Collects all table and column references of a statement.
+///
+/// Qualifiers are resolved against the tables and aliases of the statement, unqualified tables
+/// against the schema cache. The AST only knows where a reference starts, so the name itself is
+/// located by walking the dotted identifier chain in the tokens of the statement. Ranges are
+/// relative to the statement.
+pub(crate) fn symbol_references(
+    ast: &NodeEnum,
+    content: &str,
+    schema_cache: &SchemaCache,
+) -> Vec<SymbolReference> {
+    let tokens: Vec<Token> = match pgt_lexer::lex(content) {
+        Ok(tokens) => tokens
+            .into_iter()
+            .filter(|t| !WHITESPACE_TOKENS.contains(&t.kind))
+            .collect(),
+        Err(_) => return vec![],
+    };
+
+    let nodes: Vec<NodeEnum> = ChildrenIterator::new(ast.clone()).collect();
+
+    // ctes shadow tables of the same name
+    let ctes: HashSet<&str> = nodes
+        .iter()
+        .filter_map(|node| match node {
+            NodeEnum::CommonTableExpr(n) => Some(n.ctename.as_str()),
+            _ => None,
+        })
+        .collect();
+
+    let is_cte = |n: &RangeVar| n.schemaname.is_empty() && ctes.contains(n.relname.as_str());
+
+    let relations: Vec<Relation> = nodes
+        .iter()
+        .filter_map(|node| match node {
+            NodeEnum::RangeVar(n) if !is_cte(n) => Some(Relation {
+                table: table_name(&n.schemaname, &n.relname, schema_cache),
+                alias: n.alias.as_ref().map(|a| a.aliasname.clone()),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    // the table whose columns are defined or assigned by the statement
+    let (target, assigned_columns) = match ast {
+        NodeEnum::CreateStmt(n) => (n.relation.as_ref(), vec![]),
+        NodeEnum::AlterTableStmt(n) => (n.relation.as_ref(), vec![]),
+        NodeEnum::InsertStmt(n) => (n.relation.as_ref(), n.cols.iter().collect()),
+        NodeEnum::UpdateStmt(n) => (n.relation.as_ref(), n.target_list.iter().collect()),
+        _ => (None, vec![]),
+    };
+    let target = target.map(|n| table_name(&n.schemaname, &n.relname, schema_cache));
+
+    let mut references = Vec::new();
+
+    for node in assigned_columns.into_iter().filter_map(|n| n.node.as_ref()) {
+        let NodeEnum::ResTarget(n) = node else {
+            continue;
+        };
+
+        if let Some(range) =
+            identifier_chain(&tokens, n.location).and_then(|chain| chain.first().copied())
+        {
+            references.push(SymbolReference {
+                range,
+                kind: SymbolKind::Column,
+                name: n.name.clone(),
+                table: target.clone(),
+            });
+        }
+    }
+
+    for node in &nodes {
+        match node {
+            NodeEnum::RangeVar(n) if !is_cte(n) => {
+                if let Some(range) =
+                    identifier_chain(&tokens, n.location).and_then(|chain| chain.last().copied())
+                {
+                    references.push(SymbolReference {
+                        range,
+                        kind: SymbolKind::Table,
+                        name: n.relname.clone(),
+                        table: Some(table_name(&n.schemaname, &n.relname, schema_cache)),
+                    });
+                }
+            }
+            NodeEnum::ColumnRef(n) => {
+                let names: Vec<Option<&String>> = n
+                    .fields
+                    .iter()
+                    .map(|f| match &f.node {
+                        Some(NodeEnum::String(s)) => Some(&s.sval),
+                        _ => None,
+                    })
+                    .collect();
+
+                // the `*` of `users.*` is not part of the identifier chain
+                let (column, qualifiers) = match names.split_last() {
+                    Some((column, qualifiers)) => (*column, qualifiers),
+                    None => continue,
+                };
+
+                let Some(qualifiers) = qualifiers.iter().copied().collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+
+                let chain = match identifier_chain(&tokens, n.location) {
+                    Some(chain)
+                        if chain.len() >= qualifiers.len() + usize::from(column.is_some()) =>
+                    {
+                        chain
+                    }
+                    _ => continue,
+                };
+
+                let table = match qualifiers.as_slice() {
+                    [] => unqualified_column_table(column, &relations, schema_cache),
+                    [.., schema, table] => {
+                        let table = TableName {
+                            schema: (*schema).clone(),
+                            name: (*table).clone(),
+                        };
+                        references.push(SymbolReference {
+                            range: chain[qualifiers.len() - 1],
+                            kind: SymbolKind::Table,
+                            name: table.name.clone(),
+                            table: Some(table.clone()),
+                        });
+                        Some(table)
+                    }
+                    [qualifier] => {
+                        if let Some(relation) = relations
+                            .iter()
+                            .find(|r| r.alias.as_ref() == Some(*qualifier))
+                        {
+                            Some(relation.table.clone())
+                        } else if ctes.contains(qualifier.as_str()) {
+                            // the columns of a cte are not the columns of a table
+                            continue;
+                        } else {
+                            let table = relations
+                                .iter()
+                                .find(|r| r.alias.is_none() && r.table.name == **qualifier)
+                                .map(|r| r.table.clone())
+                                .unwrap_or_else(|| table_name("", qualifier, schema_cache));
+
+                            references.push(SymbolReference {
+                                range: chain[0],
+                                kind: SymbolKind::Table,
+                                name: table.name.clone(),
+                                table: Some(table.clone()),
+                            });
+                            Some(table)
+                        }
+                    }
+                };
+
+                if let Some(column) = column {
+                    references.push(SymbolReference {
+                        range: chain[qualifiers.len()],
+                        kind: SymbolKind::Column,
+                        name: column.clone(),
+                        table,
+                    });
+                }
+            }
+            NodeEnum::ColumnDef(n) => {
+                if let Some(range) =
+                    identifier_chain(&tokens, n.location).and_then(|chain| chain.first().copied())
+                {
+                    references.push(SymbolReference {
+                        range,
+                        kind: SymbolKind::Column,
+                        name: n.colname.clone(),
+                        table: target.clone(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    references
+}
+
+/// Resolves a table name as written in a statement.
+///
+/// Unqualified tables are looked up in the schema cache and assumed to be in `public`, the
+/// first schema of the default search path, if the name is not unique.
+fn table_name(schema: &str, name: &str, schema_cache: &SchemaCache) -> TableName {
+    let schema = if schema.is_empty() {
+        let mut schemas = schema_cache
+            .tables
+            .iter()
+            .filter(|t| t.name == name)
+            .map(|t| t.schema.as_str());
+
+        match (schemas.next(), schemas.next()) {
+            (Some(schema), None) => schema,
+            _ => "public",
+        }
+    } else {
+        schema
+    };
+
+    TableName {
+        schema: schema.to_string(),
+        name: name.to_string(),
+    }
+}
+
+/// Returns the table of an unqualified column: the only table of the statement, or the only one
+/// with a column of that name.
+fn unqualified_column_table(
+    column: Option<&String>,
+    relations: &[Relation],
+    schema_cache: &SchemaCache,
+) -> Option<TableName> {
+    let column = column?;
+
+    let mut tables: Vec<&TableName> = vec![];
+    for relation in relations {
+        if !tables.contains(&&relation.table) {
+            tables.push(&relation.table);
+        }
+    }
+
+    if let [table] = tables.as_slice() {
+        return Some((*table).clone());
+    }
+
+    let mut providers = tables.into_iter().filter(|table| {
+        schema_cache.columns.iter().any(|c| {
+            c.schema_name == table.schema && c.table_name == table.name && c.name == *column
+        })
+    });
+
+    match (providers.next(), providers.next()) {
+        (Some(table), None) => Some(table.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the ranges of the identifiers in a dotted chain like `schema.table` that starts at `location`.
+fn identifier_chain(tokens: &[Token], location: i32) -> Option<Vec<TextRange>> {
+    let location = TextSize::try_from(usize::try_from(location).ok()?).ok()?;
+
+    let start = tokens.iter().position(|t| t.span.start() >= location)?;
+
+    let mut chain = Vec::new();
+    let mut iter = tokens[start..].iter();
+
+    loop {
+        match iter.next() {
+            Some(t) if is_identifier(t) => chain.push(t.span),
+            _ => break,
+        }
+
+        match iter.next() {
+            Some(t) if t.kind == SyntaxKind::Ascii46 => {}
+            _ => break,
+        }
+    }
+
+    (!chain.is_empty()).then_some(chain)
+}
+
+fn is_identifier(token: &Token) -> bool {
+    token.kind == SyntaxKind::Ident
+        || matches!(
+            token.token_type,
+            TokenType::UnreservedKeyword
+                | TokenType::ColNameKeyword
+                | TokenType::TypeFuncNameKeyword
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use pgt_schema_cache::{Column, ColumnClassKind, SchemaCache};
+
+    use super::{SymbolKind, symbol_references};
+
+    fn references_with_cache(
+        sql: &str,
+        schema_cache: &SchemaCache,
+    ) -> Vec<(SymbolKind, Option<String>, String)> {
+        let ast = pgt_query_ext::parse(sql).unwrap();
+
+        let mut refs: Vec<_> = symbol_references(&ast, sql, schema_cache)
+            .into_iter()
+            .map(|r| {
+                let table = r.table.map(|t| format!("{}.{}", t.schema, t.name));
+                (r.kind, table, sql[r.range].to_string())
+            })
+            .collect();
+        refs.sort_by_key(|(_, _, text)| text.clone());
+        refs
+    }
+
+    fn references(sql: &str) -> Vec<(SymbolKind, Option<String>, String)> {
+        references_with_cache(sql, &SchemaCache::default())
+    }
+
+    fn table(name: &str) -> Option<String> {
+        Some(name.to_string())
+    }
+
+    #[test]
+    fn finds_qualified_references() {
+        assert_eq!(
+            references("select users.id, \"Email\" from public.users"),
+            vec![
+                (
+                    SymbolKind::Column,
+                    table("public.users"),
+                    "\"Email\"".into()
+                ),
+                (SymbolKind::Column, table("public.users"), "id".into()),
+                (SymbolKind::Table, table("public.users"), "users".into()),
+                (SymbolKind::Table, table("public.users"), "users".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_aliases_stars_and_schemas() {
+        assert_eq!(
+            references("select u.id, o.* from auth.users u join orders o on o.user_id = u.id"),
+            vec![
+                (SymbolKind::Column, table("auth.users"), "id".into()),
+                (SymbolKind::Column, table("auth.users"), "id".into()),
+                (SymbolKind::Table, table("public.orders"), "orders".into()),
+                (SymbolKind::Column, table("public.orders"), "user_id".into()),
+                (SymbolKind::Table, table("auth.users"), "users".into()),
+            ]
+        );
+
+        assert_eq!(
+            references("select users.* from users"),
+            vec![
+                (SymbolKind::Table, table("public.users"), "users".into()),
+                (SymbolKind::Table, table("public.users"), "users".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_unqualified_columns_with_the_schema_cache() {
+        let sql = "select title from users join posts on posts.author_id = users.id";
+
+        // without the schema cache, the table of `title` is unknown
+        assert!(references(sql).contains(&(SymbolKind::Column, None, "title".into())));
+
+        let schema_cache = SchemaCache {
+            columns: vec![Column {
+                name: "title".into(),
+                table_name: "posts".into(),
+                table_oid: 0,
+                class_kind: ColumnClassKind::OrdinaryTable,
+                schema_name: "public".into(),
+                type_id: 25,
+                is_nullable: true,
+                is_primary_key: false,
+                is_unique: false,
+                default_expr: None,
+                varchar_length: None,
+                comment: None,
+            }],
+            ..Default::default()
+        };
+
+        assert!(references_with_cache(sql, &schema_cache).contains(&(
+            SymbolKind::Column,
+            table("public.posts"),
+            "title".into()
+        )));
+    }
+}
@@ -4,7 +4,7 @@ use pgt_diagnostics::Category;
 use std::{
     borrow::Cow,
     num::NonZeroU64,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
     sync::{RwLock, RwLockReadGuard, RwLockWriteGuard},
     time::Duration,
 };
@@ -12,6 +12,7 @@ use std::{
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use pgt_configuration::{
     ConfigurationDiagnostic, LinterConfiguration, PartialConfiguration,
+    completions::PartialCompletionsConfiguration,
     database::PartialDatabaseConfiguration,
     diagnostics::InvalidIgnorePattern,
     files::FilesConfiguration,
@@ -35,6 +36,12 @@ pub struct Settings {
 
     /// Migrations settings
     pub migrations: Option<MigrationSettings>,
+
+    /// Completions settings
+    pub completions: CompletionsSettings,
+
+    /// The directory of the workspace, used to resolve relative paths
+    pub working_directory: Option<PathBuf>,
 }
 
 #[derive(Debug)]
@@ -76,6 +83,14 @@ impl AsMut<Settings> for SettingsHandleMut<'_> {
     }
 }
 
+/// The parts of a [PartialConfiguration] that had to be validated, ready to be applied
+#[derive(Debug)]
+pub(crate) struct ValidatedConfiguration {
+    files: Option<FilesSettings>,
+    linter: Option<LinterSettings>,
+    completions: Option<CompletionsSettings>,
+}
+
 impl Settings {
     /// The [PartialConfiguration] is merged into the workspace
     #[tracing::instrument(level = "trace", skip(self), err)]
@@ -86,13 +101,21 @@ impl Settings {
         vcs_path: Option<PathBuf>,
         gitignore_matches: &[String],
     ) -> Result<(), WorkspaceError> {
-        // Filesystem settings
-        if let Some(files) = to_file_settings(
+        // everything that can fail is resolved up front,
+        // so an invalid configuration leaves the settings untouched
+        let validated = Self::validate_configuration(
+            &configuration,
             working_directory.clone(),
-            configuration.files.map(FilesConfiguration::from),
             vcs_path,
             gitignore_matches,
-        )? {
+        )?;
+
+        if working_directory.is_some() {
+            self.working_directory = working_directory.clone();
+        }
+
+        // Filesystem settings
+        if let Some(files) = validated.files {
             self.files = files;
         }
 
@@ -101,10 +124,14 @@ impl Settings {
             self.db = db.into()
         }
 
+        // completions settings
+        if let Some(completions) = validated.completions {
+            self.completions = completions;
+        }
+
         // linter part
-        if let Some(linter) = configuration.linter {
-            self.linter =
-                to_linter_settings(working_directory.clone(), LinterConfiguration::from(linter))?;
+        if let Some(linter) = validated.linter {
+            self.linter = linter;
         }
 
         // Migrations settings
@@ -118,6 +145,69 @@ impl Settings {
         Ok(())
     }
 
+    /// Resolves the parts of the [PartialConfiguration] that can be invalid, like glob patterns,
+    /// without applying them
+    pub(crate) fn validate_configuration(
+        configuration: &PartialConfiguration,
+        working_directory: Option<PathBuf>,
+        vcs_path: Option<PathBuf>,
+        gitignore_matches: &[String],
+    ) -> Result<ValidatedConfiguration, WorkspaceError> {
+        Ok(ValidatedConfiguration {
+            files: to_file_settings(
+                working_directory.clone(),
+                configuration.files.clone().map(FilesConfiguration::from),
+                vcs_path,
+                gitignore_matches,
+            )?,
+            linter: configuration
+                .linter
+                .clone()
+                .map(|linter| {
+                    to_linter_settings(working_directory, LinterConfiguration::from(linter))
+                })
+                .transpose()?,
+            completions: configuration
+                .completions
+                .clone()
+                .map(CompletionsSettings::try_from)
+                .transpose()?,
+        })
+    }
+
+    /// Resolves `path` against the workspace directory and removes `.` and `..` components,
+    /// so that a file is treated the same regardless of whether it's referenced by an
+    /// absolute or a relative path.
+    ///
+    /// This is purely lexical: the file system is not accessed and symlinks are not resolved.
+    pub fn normalize_path(&self, path: &Path) -> PathBuf {
+        let path = match &self.working_directory {
+            Some(working_directory) if path.is_relative() => working_directory.join(path),
+            _ => path.to_path_buf(),
+        };
+
+        let mut normalized = PathBuf::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if !normalized.pop() {
+                        normalized.push(component);
+                    }
+                }
+                _ => normalized.push(component),
+            }
+        }
+        normalized
+    }
+
+    /// Returns the path relative to the workspace directory, if it is located inside of it.
+    /// Expects a path returned by [Settings::normalize_path].
+    pub fn relative_to_workspace<'a>(&self, normalized_path: &'a Path) -> Option<&'a Path> {
+        let working_directory = self.working_directory.as_ref()?;
+        normalized_path.strip_prefix(working_directory).ok()
+    }
+
     /// Retrieves the settings of the linter
     pub fn linter(&self) -> &LinterSettings {
         &self.linter
@@ -185,6 +275,7 @@ fn to_file_settings(
             git_ignore,
             ignored_files: to_matcher(working_directory.clone(), Some(&config.ignore))?,
             included_files: to_matcher(working_directory, Some(&config.include))?,
+            newline_terminates_statements: config.newline_terminates_statements,
         }),
         _ => None,
     })
@@ -274,7 +365,10 @@ pub struct DatabaseSettings {
     pub username: String,
     pub password: String,
     pub database: String,
+    /// The other databases a document may select with a `-- pgt:connection=<name>` directive
+    pub connections: Vec<String>,
     pub conn_timeout_secs: Duration,
+    pub idle_timeout: Duration,
     pub allow_statement_executions: bool,
 }
 
@@ -287,7 +381,9 @@ impl Default for DatabaseSettings {
             username: "postgres".to_string(),
             password: "postgres".to_string(),
             database: "postgres".to_string(),
+            connections: vec![],
             conn_timeout_secs: Duration::from_secs(10),
+            idle_timeout: Duration::from_millis(600_000),
             allow_statement_executions: true,
         }
     }
@@ -329,11 +425,21 @@ impl From<PartialDatabaseConfiguration> for DatabaseSettings {
             database,
             host,
 
+            connections: value
+                .connections
+                .map(|names| names.into_iter().collect())
+                .unwrap_or(d.connections),
+
             conn_timeout_secs: value
                 .conn_timeout_secs
                 .map(|s| Duration::from_secs(s.into()))
                 .unwrap_or(d.conn_timeout_secs),
 
+            idle_timeout: value
+                .idle_timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(d.idle_timeout),
+
             allow_statement_executions,
         }
     }
@@ -353,6 +459,83 @@ pub struct FilesSettings {
 
     /// gitignore file patterns
     pub git_ignore: Option<Gitignore>,
+
+    /// Whether a single newline terminates a statement
+    pub newline_terminates_statements: bool,
+}
+
+/// Completions settings for the entire workspace
+#[derive(Debug)]
+pub struct CompletionsSettings {
+    /// The number of characters that have to be typed before completions are offered
+    pub min_chars: usize,
+    /// The maximum number of completion items
+    pub limit: usize,
+    /// Whether objects in system schemas are suggested
+    pub include_system_schemas: bool,
+    /// The objects that are not suggested, if any were configured and they are not included
+    pub hidden_objects: Option<pgt_completions::HiddenObjects>,
+    /// Whether the outcomes of completions are recorded
+    pub telemetry: bool,
+}
+
+impl Default for CompletionsSettings {
+    fn default() -> Self {
+        Self {
+            min_chars: 0,
+            limit: 50,
+            include_system_schemas: true,
+            hidden_objects: None,
+            telemetry: false,
+        }
+    }
+}
+
+impl TryFrom<PartialCompletionsConfiguration> for CompletionsSettings {
+    type Error = WorkspaceError;
+
+    fn try_from(value: PartialCompletionsConfiguration) -> Result<Self, Self::Error> {
+        Ok(Self {
+            min_chars: value.min_chars.map(usize::from).unwrap_or_default(),
+            limit: value.limit.map(usize::from).unwrap_or(50),
+            include_system_schemas: value.include_system_schemas.unwrap_or(true),
+            hidden_objects: to_hidden_objects(
+                value.hidden_objects.as_ref(),
+                value.hidden_objects_comment_marker,
+                value.include_hidden_objects.unwrap_or_default(),
+            )?,
+            telemetry: value.telemetry.unwrap_or_default(),
+        })
+    }
+}
+
+/// Creates the [pgt_completions::HiddenObjects] from the configured patterns and marker.
+///
+/// Returns `None` if the hidden objects should be included or nothing is hidden.
+///
+/// ## Errors
+///
+/// It can raise an error if the patterns aren't valid
+fn to_hidden_objects(
+    patterns: Option<&StringSet>,
+    comment_marker: Option<String>,
+    include_hidden_objects: bool,
+) -> Result<Option<pgt_completions::HiddenObjects>, WorkspaceError> {
+    if include_hidden_objects {
+        return Ok(None);
+    }
+
+    let patterns = patterns
+        .into_iter()
+        .flat_map(|set| set.iter().map(String::as_str));
+    let hidden = pgt_completions::HiddenObjects::new(patterns, comment_marker).map_err(|err| {
+        ConfigurationDiagnostic::new_invalid_ignore_pattern(
+            err.glob().unwrap_or_default(),
+            err.kind().to_string(),
+        )
+    })?;
+
+    Ok((!hidden.is_empty()).then_some(hidden))
 }
 
 /// Migration settings
@@ -393,6 +576,7 @@ impl Default for FilesSettings {
             ignored_files: Matcher::empty(),
             included_files: Matcher::empty(),
             git_ignore: None,
+            newline_terminates_statements: false,
         }
     }
 }
@@ -453,9 +637,13 @@ impl PartialConfigurationExt for PartialConfiguration {
 #[cfg(test)]
 mod tests {
     use biome_deserialize::StringSet;
-    use pgt_configuration::database::PartialDatabaseConfiguration;
+    use pgt_configuration::{
+        completions::PartialCompletionsConfiguration, database::PartialDatabaseConfiguration,
+    };
+
+    use std::time::Duration;
 
-    use super::DatabaseSettings;
+    use super::{CompletionsSettings, DatabaseSettings};
 
     #[test]
     fn should_identify_allowed_statement_executions() {
@@ -488,4 +676,41 @@ mod tests {
 
         assert!(!config.allow_statement_executions)
     }
+
+    #[test]
+    fn should_read_idle_timeout_in_milliseconds() {
+        let partial_config = PartialDatabaseConfiguration {
+            host: Some("localhost".into()),
+            idle_timeout_ms: Some(1500),
+            ..Default::default()
+        };
+
+        let config = DatabaseSettings::from(partial_config);
+
+        assert_eq!(config.idle_timeout, Duration::from_millis(1500))
+    }
+
+    #[test]
+    fn should_read_completions_limit() {
+        let config =
+            CompletionsSettings::try_from(PartialCompletionsConfiguration::default()).unwrap();
+        assert_eq!(config.limit, 50);
+
+        let config = CompletionsSettings::try_from(PartialCompletionsConfiguration {
+            limit: Some(10),
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(config.limit, 10);
+    }
+
+    #[test]
+    fn should_reject_invalid_hidden_objects_patterns() {
+        let partial_config = PartialCompletionsConfiguration {
+            hidden_objects: Some(StringSet::from_iter(vec![String::from("tmp_[")])),
+            ..Default::default()
+        };
+
+        assert!(CompletionsSettings::try_from(partial_config).is_err());
+    }
 }
@@ -4,7 +4,7 @@ pub use self::client::{TransportRequest, WorkspaceClient, WorkspaceTransport};
 use pgt_analyse::RuleCategories;
 use pgt_configuration::{PartialConfiguration, RuleSelector};
 use pgt_fs::PgTPath;
-use pgt_text_size::TextRange;
+use pgt_text_size::{TextRange, TextSize};
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -13,8 +13,12 @@ use crate::{
         code_actions::{
             CodeActionsParams, CodeActionsResult, ExecuteStatementParams, ExecuteStatementResult,
         },
-        completions::{CompletionsResult, GetCompletionsParams},
+        completions::{
+            CompletionTelemetrySink, CompletionsResult, GetCompletionsParams,
+            RecordCompletionOutcomeParams,
+        },
         diagnostics::{PullDiagnosticsParams, PullDiagnosticsResult},
+        rename::{RenameSymbolParams, RenameSymbolResult},
     },
 };
 
@@ -38,6 +42,10 @@ pub struct CloseFileParams {
     pub path: PgTPath,
 }
 
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CloseAllParams {}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct ChangeFileParams {
@@ -110,15 +118,34 @@ pub trait Workspace: Send + Sync + RefUnwindSafe {
         params: GetCompletionsParams,
     ) -> Result<CompletionsResult, WorkspaceError>;
 
+    /// Records which completion item was accepted and where it was ranked among the shown items
+    fn record_completion_outcome(
+        &self,
+        params: RecordCompletionOutcomeParams,
+    ) -> Result<(), WorkspaceError>;
+
+    /// Produces the edits that rename the table or column at a position within the same document
+    fn rename_symbol(
+        &self,
+        params: RenameSymbolParams,
+    ) -> Result<RenameSymbolResult, WorkspaceError>;
+
     /// Update the global settings for this workspace
     fn update_settings(&self, params: UpdateSettingsParams) -> Result<(), WorkspaceError>;
 
+    /// Checks that the settings can be applied with [Workspace::update_settings],
+    /// without applying them
+    fn validate_settings(&self, params: UpdateSettingsParams) -> Result<(), WorkspaceError>;
+
     /// Add a new file to the workspace
     fn open_file(&self, params: OpenFileParams) -> Result<(), WorkspaceError>;
 
     /// Remove a file from the workspace
     fn close_file(&self, params: CloseFileParams) -> Result<(), WorkspaceError>;
 
+    /// Remove all files from the workspace, e.g. when switching branches
+    fn close_all(&self, params: CloseAllParams) -> Result<(), WorkspaceError>;
+
     /// Change the content of an open file
     fn change_file(&self, params: ChangeFileParams) -> Result<(), WorkspaceError>;
 
@@ -152,6 +179,14 @@ pub fn server_sync() -> Arc<dyn Workspace> {
     Arc::new(server::WorkspaceServer::new())
 }
 
+/// Constructs a server instance of [Workspace] like [server_sync], which records the outcomes
+/// of completions with `sink` if telemetry is enabled
+pub fn server_sync_with_completion_telemetry(
+    sink: Arc<dyn CompletionTelemetrySink>,
+) -> Arc<dyn Workspace> {
+    Arc::new(server::WorkspaceServer::new().with_completion_telemetry(sink))
+}
+
 // Convenience function for constructing a client instance of [Workspace]
 pub fn client<T>(transport: T) -> Result<Box<dyn Workspace>, WorkspaceError>
 where
@@ -206,6 +241,29 @@ impl<'app, W: Workspace + ?Sized> FileGuard<'app, W> {
             max_diagnostics: max_diagnostics.into(),
             only,
             skip,
+            changed_range: None,
+        })
+    }
+
+    pub fn pull_code_actions(
+        &self,
+        cursor_position: TextSize,
+        only: Vec<RuleSelector>,
+        skip: Vec<RuleSelector>,
+    ) -> Result<CodeActionsResult, WorkspaceError> {
+        self.workspace.pull_code_actions(CodeActionsParams {
+            path: self.path.clone(),
+            cursor_position,
+            only,
+            skip,
+        })
+    }
+
+    pub fn get_completions(&self, position: TextSize) -> Result<CompletionsResult, WorkspaceError> {
+        self.workspace.get_completions(GetCompletionsParams {
+            path: self.path.clone(),
+            position,
+            limit: None,
         })
     }
 }
@@ -7,7 +7,9 @@ use std::{
     sync::atomic::{AtomicU64, Ordering},
 };
 
-use super::{CloseFileParams, GetFileContentParams, IsPathIgnoredParams, OpenFileParams};
+use super::{
+    CloseAllParams, CloseFileParams, GetFileContentParams, IsPathIgnoredParams, OpenFileParams,
+};
 
 pub struct WorkspaceClient<T> {
     transport: T,
@@ -103,6 +105,13 @@ where
         self.request("pgt/execute_statement", params)
     }
 
+    fn rename_symbol(
+        &self,
+        params: crate::features::rename::RenameSymbolParams,
+    ) -> Result<crate::features::rename::RenameSymbolResult, WorkspaceError> {
+        self.request("pgt/rename_symbol", params)
+    }
+
     fn open_file(&self, params: OpenFileParams) -> Result<(), WorkspaceError> {
         self.request("pgt/open_file", params)
     }
@@ -111,6 +120,10 @@ where
         self.request("pgt/close_file", params)
     }
 
+    fn close_all(&self, params: CloseAllParams) -> Result<(), WorkspaceError> {
+        self.request("pgt/close_all", params)
+    }
+
     fn change_file(&self, params: super::ChangeFileParams) -> Result<(), WorkspaceError> {
         self.request("pgt/change_file", params)
     }
@@ -119,6 +132,10 @@ where
         self.request("pgt/update_settings", params)
     }
 
+    fn validate_settings(&self, params: super::UpdateSettingsParams) -> Result<(), WorkspaceError> {
+        self.request("pgt/validate_settings", params)
+    }
+
     fn is_path_ignored(&self, params: IsPathIgnoredParams) -> Result<bool, WorkspaceError> {
         self.request("pgt/is_path_ignored", params)
     }
@@ -144,4 +161,11 @@ where
     ) -> Result<crate::features::completions::CompletionsResult, WorkspaceError> {
         self.request("pgt/get_completions", params)
     }
+
+    fn record_completion_outcome(
+        &self,
+        params: crate::features::completions::RecordCompletionOutcomeParams,
+    ) -> Result<(), WorkspaceError> {
+        self.request("pgt/record_completion_outcome", params)
+    }
 }
@@ -1,24 +1,39 @@
-use std::{fs, panic::RefUnwindSafe, path::Path, sync::RwLock};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    panic::RefUnwindSafe,
+    path::Path,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
 
 use analyser::AnalyserVisitorBuilder;
 use async_helper::run_async;
 use dashmap::DashMap;
-use db_connection::DbConnection;
-use document::Document;
+use db_connection::{DbConnection, RetryPolicy, acquire_with_retry};
+use document::{Document, SplitCache};
 use futures::{StreamExt, stream};
 use parsed_document::{
     AsyncDiagnosticsMapper, CursorPositionFilter, DefaultMapper, ExecuteStatementMapper,
-    ParsedDocument, SyncDiagnosticsMapper,
+    IdSetFilter, ParsedDocument, RangeFilter, StatementFilter, SyncDiagnosticsMapper,
 };
-use pgt_analyse::{AnalyserOptions, AnalysisFilter};
+use pgt_analyse::{AnalyserOptions, AnalysisFilter, FixSafety, RuleCategories, RuleFix};
 use pgt_analyser::{Analyser, AnalyserConfig, AnalyserContext};
+use pgt_console::MarkupBuf;
 use pgt_diagnostics::{
     Diagnostic, DiagnosticExt, Error, Severity, serde::Diagnostic as SDiagnostic,
 };
 use pgt_fs::{ConfigName, PgTPath};
+use pgt_schema_cache::SchemaCache;
+use pgt_statement_splitter::SplitOptions;
+use pgt_text_size::TextRange;
 use pgt_typecheck::TypecheckParams;
 use schema_cache_manager::SchemaCacheManager;
-use sqlx::Executor;
+use sqlx::PgPool;
+use statement_diagnostics::{AnalysisKey, StatementAnalysis};
+use statement_timing::{TimingHook, timed, timed_analysis};
 use tracing::info;
 
 use crate::{
@@ -27,17 +42,24 @@ use crate::{
     features::{
         code_actions::{
             self, CodeAction, CodeActionKind, CodeActionsResult, CommandAction,
-            CommandActionCategory, ExecuteStatementParams, ExecuteStatementResult,
+            CommandActionCategory, EditAction, ExecuteStatementParams, ExecuteStatementResult,
+            TextEdit,
+        },
+        completions::{
+            CompletionTelemetrySink, CompletionsResult, GetCompletionsParams,
+            RecordCompletionOutcomeParams, TracingCompletionTelemetry,
+            get_statement_for_completions,
         },
-        completions::{CompletionsResult, GetCompletionsParams, get_statement_for_completions},
         diagnostics::{PullDiagnosticsParams, PullDiagnosticsResult},
+        rename::{RenameEdit, RenameSymbolParams, RenameSymbolResult, symbol_references},
     },
+    matcher::Matcher,
     settings::{Settings, SettingsHandle, SettingsHandleMut},
 };
 
 use super::{
-    GetFileContentParams, IsPathIgnoredParams, OpenFileParams, ServerInfo, UpdateSettingsParams,
-    Workspace,
+    CloseAllParams, GetFileContentParams, IsPathIgnoredParams, OpenFileParams, ServerInfo,
+    UpdateSettingsParams, Workspace,
 };
 
 pub use statement_identifier::StatementId;
@@ -48,12 +70,14 @@ mod async_helper;
 mod change;
 mod db_connection;
 pub(crate) mod document;
-mod migration;
+pub(crate) mod migration;
 pub(crate) mod parsed_document;
 mod pg_query;
 mod schema_cache_manager;
 mod sql_function;
+mod statement_diagnostics;
 mod statement_identifier;
+mod statement_timing;
 mod tree_sitter;
 
 pub(super) struct WorkspaceServer {
@@ -65,7 +89,22 @@ pub(super) struct WorkspaceServer {
 
     parsed_documents: DashMap<PgTPath, ParsedDocument>,
 
+    /// The recent splits of all documents, kept when a document is closed so that reopening it
+    /// does not split it again
+    split_cache: Arc<SplitCache>,
+
     connection: RwLock<DbConnection>,
+
+    /// Incremented whenever the settings are updated, so that cached analyses are not reused
+    settings_generation: AtomicU64,
+
+    /// Receives how long the typecheck and the analysis of every statement in
+    /// `pull_diagnostics` took, in addition to the tracing spans. Only installed by tests.
+    timing_hook: Option<TimingHook>,
+
+    /// Receives the outcomes of completions if telemetry is enabled,
+    /// instead of [TracingCompletionTelemetry]
+    completion_telemetry: Option<Arc<dyn CompletionTelemetrySink>>,
 }
 
 /// The `Workspace` object is long-lived, so we want it to be able to cross
@@ -86,11 +125,31 @@ impl WorkspaceServer {
         Self {
             settings: RwLock::default(),
             parsed_documents: DashMap::default(),
+            split_cache: Arc::default(),
             schema_cache: SchemaCacheManager::default(),
             connection: RwLock::default(),
+            settings_generation: AtomicU64::new(0),
+            timing_hook: None,
+            completion_telemetry: None,
         }
     }
 
+    /// Installs a hook that is called with the typecheck and analysis durations of every statement
+    #[cfg(test)]
+    pub(crate) fn with_timing_hook(mut self, hook: TimingHook) -> Self {
+        self.timing_hook = Some(hook);
+        self
+    }
+
+    /// Installs the sink that receives the outcomes of completions if telemetry is enabled
+    pub(crate) fn with_completion_telemetry(
+        mut self,
+        sink: Arc<dyn CompletionTelemetrySink>,
+    ) -> Self {
+        self.completion_telemetry = Some(sink);
+        self
+    }
+
     /// Provides a reference to the current settings
     fn settings(&self) -> SettingsHandle {
         SettingsHandle::new(&self.settings)
@@ -100,6 +159,28 @@ impl WorkspaceServer {
         SettingsHandleMut::new(&self.settings)
     }
 
+    /// Returns the pool for the database named by a document's connection directive, or the
+    /// default one
+    fn get_pool(&self, connection_directive: Option<&str>) -> Option<PgPool> {
+        let conn = self.connection.read().unwrap();
+
+        match connection_directive {
+            Some(database) => conn.get_pool_for_database(database),
+            None => conn.get_pool(),
+        }
+    }
+
+    /// Options used to split newly opened documents into statements
+    fn split_options(&self) -> SplitOptions {
+        SplitOptions {
+            newline_terminates_statements: self
+                .settings()
+                .as_ref()
+                .files
+                .newline_terminates_statements,
+        }
+    }
+
     fn is_ignored_by_migration_config(&self, path: &Path) -> bool {
         let set = self.settings();
         set.as_ref()
@@ -118,21 +199,33 @@ impl WorkspaceServer {
     /// Check whether a file is ignored in the top-level config `files.ignore`/`files.include`
     fn is_ignored(&self, path: &Path) -> bool {
         let file_name = path.file_name().and_then(|s| s.to_str());
+
+        // The same file may be referenced by an absolute or a relative path,
+        // so we normalize it before matching.
+        let path = self.settings().as_ref().normalize_path(path);
+
         // Never ignore Postgres Tools's config file regardless `include`/`ignore`
         (file_name != Some(ConfigName::pgt_jsonc())) &&
             // Apply top-level `include`/`ignore
-            (self.is_ignored_by_top_level_config(path) || self.is_ignored_by_migration_config(path))
+            (self.is_ignored_by_top_level_config(&path) || self.is_ignored_by_migration_config(&path))
     }
 
     /// Check whether a file is ignored in the top-level config `files.ignore`/`files.include`
     fn is_ignored_by_top_level_config(&self, path: &Path) -> bool {
         let set = self.settings();
         let settings = set.as_ref();
+
+        // patterns may be written relative to the workspace directory or as absolute paths
+        let relative_path = settings.relative_to_workspace(path);
+        let matches = |matcher: &Matcher| {
+            matcher.matches_path(path) || relative_path.is_some_and(|p| matcher.matches_path(p))
+        };
+
         let is_included = settings.files.included_files.is_empty()
             || is_dir(path)
-            || settings.files.included_files.matches_path(path);
+            || matches(&settings.files.included_files);
         !is_included
-            || settings.files.ignored_files.matches_path(path)
+            || matches(&settings.files.ignored_files)
             || settings.files.git_ignore.as_ref().is_some_and(|ignore| {
                 // `matched_path_or_any_parents` panics if `source` is not under the gitignore root.
                 // This checks excludes absolute paths that are not a prefix of the base root.
@@ -175,16 +268,44 @@ impl Workspace for WorkspaceServer {
 
         tracing::info!("Updated Db connection settings");
 
+        // the schema might have changed as well, e.g. if the settings changed after a migration
+        if let Some(pool) = self.get_pool(None) {
+            self.schema_cache.refresh(pool);
+        }
+
+        let split_options = self.split_options();
+        for mut parser in self.parsed_documents.iter_mut() {
+            parser.set_split_options(split_options);
+        }
+
+        self.settings_generation.fetch_add(1, Ordering::Relaxed);
+
         Ok(())
     }
 
+    fn validate_settings(&self, params: UpdateSettingsParams) -> Result<(), WorkspaceError> {
+        Settings::validate_configuration(
+            &params.configuration,
+            params.workspace_directory,
+            params.vcs_base_path,
+            params.gitignore_matches.as_slice(),
+        )
+        .map(|_| ())
+    }
+
     /// Add a new file to the workspace
     #[tracing::instrument(level = "info", skip_all, fields(path = params.path.as_path().as_os_str().to_str()), err)]
     fn open_file(&self, params: OpenFileParams) -> Result<(), WorkspaceError> {
         self.parsed_documents
             .entry(params.path.clone())
             .or_insert_with(|| {
-                ParsedDocument::new(params.path.clone(), params.content, params.version)
+                ParsedDocument::with_split_cache(
+                    params.path.clone(),
+                    params.content,
+                    params.version,
+                    self.split_options(),
+                    self.split_cache.clone(),
+                )
             });
 
         Ok(())
@@ -199,24 +320,31 @@ impl Workspace for WorkspaceServer {
         Ok(())
     }
 
+    /// Remove all files from the workspace
+    fn close_all(&self, _params: CloseAllParams) -> Result<(), WorkspaceError> {
+        self.parsed_documents.clear();
+
+        Ok(())
+    }
+
     /// Change the content of an open file
     #[tracing::instrument(level = "debug", skip_all, fields(
         path = params.path.as_os_str().to_str(),
         version = params.version
     ), err)]
     fn change_file(&self, params: super::ChangeFileParams) -> Result<(), WorkspaceError> {
-        let mut parser =
-            self.parsed_documents
-                .entry(params.path.clone())
-                .or_insert(ParsedDocument::new(
-                    params.path.clone(),
-                    "".to_string(),
-                    params.version,
-                ));
-
-        parser.apply_change(params);
+        let mut parser = self.parsed_documents.entry(params.path.clone()).or_insert(
+            ParsedDocument::with_split_cache(
+                params.path.clone(),
+                "".to_string(),
+                // the change has to be newer than the empty document it is applied to
+                params.version - 1,
+                self.split_options(),
+                self.split_cache.clone(),
+            ),
+        );
 
-        Ok(())
+        parser.apply_change(params)
     }
 
     fn server_info(&self) -> Option<&ServerInfo> {
@@ -255,26 +383,54 @@ impl Workspace for WorkspaceServer {
             Some("Statement execution not allowed against database.".into())
         };
 
-        let actions = parser
-            .iter_with_filter(
-                DefaultMapper,
-                CursorPositionFilter::new(params.cursor_position),
-            )
-            .map(|(stmt, _, txt)| {
-                let title = format!(
-                    "Execute Statement: {}...",
-                    txt.chars().take(50).collect::<String>()
+        // fixes are offered for the diagnostics of the same rules `pull_diagnostics` reports
+        let (enabled_rules, disabled_rules) = AnalyserVisitorBuilder::new(&settings)
+            .with_linter_rules(&params.only, &params.skip)
+            .finish();
+        let options = AnalyserOptions {
+            rules: to_analyser_rules(&settings),
+        };
+        let analyser = Analyser::new(AnalyserConfig {
+            options: &options,
+            filter: AnalysisFilter {
+                categories: RuleCategories::all(),
+                enabled_rules: Some(enabled_rules.as_slice()),
+                disabled_rules: &disabled_rules,
+            },
+        });
+
+        let mut actions = Vec::new();
+
+        for (stmt, range, txt, ast) in parser.iter_with_filter(
+            ExecuteStatementMapper,
+            CursorPositionFilter::new(params.cursor_position),
+        ) {
+            if let Some(ast) = ast {
+                actions.extend(
+                    analyser
+                        .run(AnalyserContext {
+                            root: &ast,
+                            text: &txt,
+                        })
+                        .iter()
+                        .filter_map(|d| d.get_fix())
+                        .map(|fix| fix_action(fix, range)),
                 );
+            }
 
-                CodeAction {
-                    title,
-                    kind: CodeActionKind::Command(CommandAction {
-                        category: CommandActionCategory::ExecuteStatement(stmt),
-                    }),
-                    disabled_reason: disabled_reason.clone(),
-                }
-            })
-            .collect();
+            let mut preview = MarkupBuf::default();
+            preview.push_str(&txt);
+            preview.truncate_graphemes(50, "…");
+            let title = format!("Execute Statement: {}", preview.to_text());
+
+            actions.push(CodeAction {
+                title,
+                kind: CodeActionKind::Command(CommandAction {
+                    category: CommandActionCategory::ExecuteStatement(stmt),
+                }),
+                disabled_reason: disabled_reason.clone(),
+            });
+        }
 
         Ok(CodeActionsResult { actions })
     }
@@ -304,8 +460,7 @@ impl Workspace for WorkspaceServer {
             });
         };
 
-        let conn = self.connection.read().unwrap();
-        let pool = match conn.get_pool() {
+        let pool = match self.get_pool(parser.connection_directive()) {
             Some(p) => p,
             None => {
                 return Ok(ExecuteStatementResult {
@@ -314,7 +469,10 @@ impl Workspace for WorkspaceServer {
             }
         };
 
-        let result = run_async(async move { pool.execute(sqlx::query(&content)).await })??;
+        let result = run_async(async move {
+            let mut conn = acquire_with_retry(&pool).await?;
+            sqlx::query(&content).execute(&mut *conn).await
+        })??;
 
         Ok(ExecuteStatementResult {
             message: format!(
@@ -358,34 +516,87 @@ impl Workspace for WorkspaceServer {
 
         let mut diagnostics: Vec<SDiagnostic> = parser.document_diagnostics().to_vec();
 
-        if let Some(pool) = self
-            .connection
-            .read()
-            .expect("DbConnection RwLock panicked")
-            .get_pool()
-        {
+        let pool = self.get_pool(parser.connection_directive());
+
+        // the analysis of the last pull is only reused if nothing else it depends on changed
+        let key = AnalysisKey {
+            categories: params.categories,
+            only: params.only.clone(),
+            skip: params.skip.clone(),
+            database: parser.connection_directive().map(str::to_string),
+            settings_generation: self.settings_generation.load(Ordering::Relaxed),
+            schema_cache_generation: pool
+                .as_ref()
+                .map_or(0, |pool| self.schema_cache.generation(pool)),
+        };
+
+        // with a changed range, only the statements overlapping it and those that were not
+        // analysed yet are analysed again, all others keep the analysis of the last pull
+        let outdated: HashSet<StatementId> = parser
+            .iter(DefaultMapper)
+            .filter(|(id, range, content)| {
+                params
+                    .changed_range
+                    .is_none_or(|changed| RangeFilter::new(changed).predicate(id, range, content))
+                    || parser.cached_analysis(id, &key).is_none()
+            })
+            .map(|(id, _, _)| id)
+            .collect();
+
+        // the analysis of every outdated statement, with spans relative to the statement
+        let mut analysed: HashMap<StatementId, StatementAnalysis> = outdated
+            .iter()
+            .map(|id| (id.clone(), StatementAnalysis::default()))
+            .collect();
+
+        // with a database connection, the typechecker already reports unbound qualifiers
+        let check_unbound_qualifiers = pool.is_none();
+        // statements the typechecker rejected, which the schema cache checks skip
+        let mut rejected_statements = HashSet::new();
+
+        if let Some(pool) = pool.clone() {
             let path_clone = params.path.clone();
-            let input = parser.iter(AsyncDiagnosticsMapper).collect::<Vec<_>>();
+            let input = parser
+                .iter_with_filter(AsyncDiagnosticsMapper, IdSetFilter::new(&outdated))
+                .collect::<Vec<_>>();
+            let timing_hook = self.timing_hook.clone();
             let async_results = run_async(async move {
-                stream::iter(input)
-                    .map(|(_id, range, content, ast, cst)| {
+                // fail once if the database is unavailable, rather than for every statement
+                acquire_with_retry(&pool).await?;
+
+                let results = stream::iter(input)
+                    .map(|(id, range, content, ast, cst)| {
                         let pool = pool.clone();
                         let path = path_clone.clone();
+                        let timing_hook = timing_hook.clone();
                         async move {
                             if let Some(ast) = ast {
-                                pgt_typecheck::check_sql(TypecheckParams {
-                                    conn: &pool,
-                                    sql: &content,
-                                    ast: &ast,
-                                    tree: &cst,
-                                })
+                                // the typecheck acquires its own connection from the pool
+                                timed(
+                                    id.clone(),
+                                    timing_hook,
+                                    RetryPolicy::default().run(|| {
+                                        pgt_typecheck::check_sql(TypecheckParams {
+                                            conn: &pool,
+                                            sql: &content,
+                                            ast: &ast,
+                                            tree: &cst,
+                                        })
+                                    }),
+                                )
                                 .await
                                 .map(|d| {
                                     d.map(|d| {
-                                        let r = d.location().span.map(|span| span + range.start());
-
-                                        d.with_file_path(path.as_path().display().to_string())
-                                            .with_file_span(r.unwrap_or(range))
+                                        let span = d
+                                            .location()
+                                            .span
+                                            .unwrap_or(TextRange::up_to(range.len()));
+
+                                        (
+                                            id,
+                                            d.with_file_path(path.as_path().display().to_string())
+                                                .with_file_span(span),
+                                        )
                                     })
                                 })
                             } else {
@@ -395,60 +606,128 @@ impl Workspace for WorkspaceServer {
                     })
                     .buffer_unordered(10)
                     .collect::<Vec<_>>()
-                    .await
-            })?;
+                    .await;
+
+                Ok::<_, sqlx::Error>(results)
+            })??;
 
             for result in async_results.into_iter() {
                 let result = result?;
-                if let Some(diag) = result {
-                    diagnostics.push(SDiagnostic::new(diag));
+                if let Some((id, diag)) = result {
+                    rejected_statements.insert(id.clone());
+                    analysed
+                        .entry(id)
+                        .or_default()
+                        .diagnostics
+                        .push(SDiagnostic::new(diag));
                 }
             }
         }
 
-        diagnostics.extend(parser.iter(SyncDiagnosticsMapper).flat_map(
-            |(_id, range, ast, diag)| {
-                let mut errors: Vec<Error> = vec![];
+        // loaded after the typecheck, so that a background refresh is not blocked by this handle.
+        // without a schema cache, only the checks that depend on it are skipped
+        let schema_cache = pool.and_then(|pool| {
+            self.schema_cache
+                .load(pool)
+                .inspect_err(|err| {
+                    tracing::warn!("Skipping schema cache based checks: {err}");
+                })
+                .ok()
+        });
 
-                if let Some(diag) = diag {
-                    errors.push(diag.into());
-                }
+        for (id, range, content, ast, diag) in
+            parser.iter_with_filter(SyncDiagnosticsMapper, IdSetFilter::new(&outdated))
+        {
+            let mut errors: Vec<Error> = vec![];
+            let mut fixes: Vec<RuleFix> = vec![];
 
-                if let Some(ast) = ast {
-                    errors.extend(
-                        analyser
-                            .run(AnalyserContext { root: &ast })
+            if let Some(diag) = diag {
+                errors.push(diag.into());
+            }
+
+            if let Some(ast) = ast {
+                timed_analysis(&id, self.timing_hook.as_ref(), || {
+                    // the bodies of sql functions may reference their parameters by function name
+                    if check_unbound_qualifiers && matches!(id, StatementId::Root(_)) {
+                        errors.extend(
+                            pgt_typecheck::check_unbound_qualifiers(&ast, &content)
+                                .into_iter()
+                                .map(Error::from),
+                        );
+                    }
+
+                    if let Some(schema_cache) = schema_cache
+                        .as_ref()
+                        .filter(|_| !rejected_statements.contains(&id))
+                    {
+                        errors.extend(
+                            pgt_typecheck::check_function_arity(
+                                &ast,
+                                &content,
+                                schema_cache.as_ref(),
+                            )
                             .into_iter()
-                            .map(Error::from)
-                            .collect::<Vec<pgt_diagnostics::Error>>(),
+                            .chain(pgt_typecheck::check_ambiguous_columns(
+                                &ast,
+                                &content,
+                                schema_cache.as_ref(),
+                            ))
+                            .map(Error::from),
+                        );
+                    }
+
+                    let rule_diagnostics = analyser.run(AnalyserContext {
+                        root: &ast,
+                        text: &content,
+                    });
+                    fixes.extend(rule_diagnostics.iter().filter_map(|d| d.get_fix()).cloned());
+                    errors.extend(rule_diagnostics.into_iter().map(Error::from));
+                });
+            }
+
+            let analysis = analysed.entry(id).or_default();
+            analysis.fixes.extend(fixes);
+            analysis.diagnostics.extend(errors.into_iter().map(|d| {
+                let severity = d
+                    .category()
+                    .filter(|category| category.name().starts_with("lint/"))
+                    .map_or_else(
+                        || d.severity(),
+                        |category| {
+                            settings
+                                .as_ref()
+                                .get_severity_from_rule_code(category)
+                                .unwrap_or(Severity::Warning)
+                        },
                     );
-                }
 
-                errors
+                let span = d.location().span.unwrap_or(TextRange::up_to(range.len()));
+
+                SDiagnostic::new(
+                    d.with_file_path(params.path.as_path().display().to_string())
+                        .with_file_span(span)
+                        .with_severity(severity),
+                )
+            }));
+        }
+
+        for (id, analysis) in analysed {
+            parser.cache_analysis(id, &key, analysis);
+        }
+
+        // spans are relative to the statement, which might be the body of a sql function,
+        // so we shift them into the document
+        let mut fixes = Vec::new();
+        for (id, range, content) in parser.iter(DefaultMapper) {
+            let analysis = parser.cached_analysis(&id, &key).unwrap_or_default();
+            diagnostics.extend(
+                analysis
+                    .diagnostics
                     .into_iter()
-                    .map(|d| {
-                        let severity = d
-                            .category()
-                            .filter(|category| category.name().starts_with("lint/"))
-                            .map_or_else(
-                                || d.severity(),
-                                |category| {
-                                    settings
-                                        .as_ref()
-                                        .get_severity_from_rule_code(category)
-                                        .unwrap_or(Severity::Warning)
-                                },
-                            );
-
-                        SDiagnostic::new(
-                            d.with_file_path(params.path.as_path().display().to_string())
-                                .with_file_span(range)
-                                .with_severity(severity),
-                        )
-                    })
-                    .collect::<Vec<_>>()
-            },
-        ));
+                    .map(|d| d.with_offset(range.start())),
+            );
+            fixes.extend(analysis.fixes.iter().map(|fix| fix_action(fix, range)));
+        }
 
         let errors = diagnostics
             .iter()
@@ -458,6 +737,7 @@ impl Workspace for WorkspaceServer {
         info!("Pulled {:?} diagnostic(s)", diagnostics.len());
         Ok(PullDiagnosticsResult {
             diagnostics,
+            fixes,
             errors,
             skipped_diagnostics: 0,
         })
@@ -471,37 +751,161 @@ impl Workspace for WorkspaceServer {
         &self,
         params: GetCompletionsParams,
     ) -> Result<CompletionsResult, WorkspaceError> {
+        // the lock on the document is released before the slow work, so that changes to the
+        // document are not blocked by it
         let parsed_doc = self
             .parsed_documents
             .get(&params.path)
-            .ok_or(WorkspaceError::not_found())?;
-
-        let pool = match self.connection.read().unwrap().get_pool() {
-            Some(pool) => pool,
+            .ok_or(WorkspaceError::not_found())?
+            .snapshot();
+
+        let loaded_cache;
+        let offline_cache;
+        let schema_cache: &SchemaCache = match self.get_pool(parsed_doc.connection_directive()) {
+            Some(pool) => {
+                loaded_cache = self.schema_cache.load(pool)?;
+                loaded_cache.as_ref()
+            }
             None => {
-                tracing::debug!("No connection to database. Skipping completions.");
-                return Ok(CompletionsResult::default());
+                tracing::debug!("No connection to database. Skipping database completions.");
+                offline_cache = SchemaCache::default();
+                &offline_cache
             }
         };
 
-        let schema_cache = self.schema_cache.load(pool)?;
-
         match get_statement_for_completions(&parsed_doc, params.position) {
             None => Ok(CompletionsResult::default()),
             Some((_id, range, content, cst)) => {
                 let position = params.position - range.start();
-
-                let items = pgt_completions::complete(pgt_completions::CompletionParams {
+                let settings = self.settings();
+                let settings = settings.as_ref();
+
+                // e.g. to find the savepoints declared before the statement
+                let preceding_statements: Vec<&str> = parsed_doc
+                    .statements()
+                    .iter()
+                    .take_while(|s| s.range.end() <= range.start())
+                    .filter(|s| matches!(s.id, StatementId::Root(_)))
+                    .map(|s| s.content.as_str())
+                    .collect();
+
+                let mut items = pgt_completions::complete(pgt_completions::CompletionParams {
                     position,
-                    schema: schema_cache.as_ref(),
+                    schema: schema_cache,
                     tree: &cst,
                     text: content,
+                    min_chars: settings.completions.min_chars,
+                    include_system_schemas: settings.completions.include_system_schemas,
+                    hidden_objects: settings.completions.hidden_objects.as_ref(),
+                    only_kinds: None,
+                    limit: Some(params.limit.unwrap_or(settings.completions.limit)),
+                    preceding_statements: &preceding_statements,
                 });
 
-                Ok(CompletionsResult { items })
+                // the completion ranges are relative to the statement
+                for item in items.iter_mut() {
+                    item.completion_text.range += range.start();
+                }
+
+                Ok(CompletionsResult {
+                    items,
+                    telemetry: settings.completions.telemetry,
+                })
             }
         }
     }
+
+    fn record_completion_outcome(
+        &self,
+        params: RecordCompletionOutcomeParams,
+    ) -> Result<(), WorkspaceError> {
+        // telemetry is opt-in
+        if !self.settings().as_ref().completions.telemetry {
+            return Ok(());
+        }
+
+        match &self.completion_telemetry {
+            Some(sink) => sink.record(&params),
+            None => TracingCompletionTelemetry.record(&params),
+        }
+
+        Ok(())
+    }
+
+    fn rename_symbol(
+        &self,
+        params: RenameSymbolParams,
+    ) -> Result<RenameSymbolResult, WorkspaceError> {
+        let parser = self
+            .parsed_documents
+            .get(&params.path)
+            .ok_or(WorkspaceError::not_found())?;
+
+        // unqualified names are resolved against the schema cache, if it is available
+        let loaded_cache = self
+            .get_pool(parser.connection_directive())
+            .and_then(|pool| self.schema_cache.load(pool).ok());
+        let offline_cache = SchemaCache::default();
+        let schema_cache = loaded_cache
+            .as_ref()
+            .map_or(&offline_cache, |cache| cache.as_ref());
+
+        let references: Vec<_> = parser
+            .iter(ExecuteStatementMapper)
+            .filter_map(|(_id, range, content, ast)| {
+                let ast = ast?;
+                Some(
+                    symbol_references(&ast, &content, schema_cache)
+                        .into_iter()
+                        .map(move |mut r| {
+                            r.range += range.start();
+                            r
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .flatten()
+            .collect();
+
+        let Some(target) = references
+            .iter()
+            .find(|r| r.range.contains_inclusive(params.position))
+        else {
+            return Ok(RenameSymbolResult::default());
+        };
+
+        // a column whose table is not known is only renamed where it was requested
+        let edits = references
+            .iter()
+            .filter(|r| r == &target || r.refers_to_same_symbol(target))
+            .map(|r| RenameEdit {
+                range: r.range,
+                new_text: params.new_name.clone(),
+            })
+            .collect();
+
+        Ok(RenameSymbolResult { edits })
+    }
+}
+
+/// Returns the code action that applies `fix` to the statement at `range`
+fn fix_action(fix: &RuleFix, range: TextRange) -> CodeAction {
+    CodeAction {
+        title: fix.title.clone(),
+        kind: CodeActionKind::Edit(EditAction {
+            // the edits are relative to the statement
+            edits: fix
+                .edits
+                .iter()
+                .map(|edit| TextEdit {
+                    range: edit.range + range.start(),
+                    new_text: edit.new_text.clone(),
+                })
+                .collect(),
+            is_safe: fix.safety == FixSafety::Safe,
+        }),
+        disabled_reason: None,
+    }
 }
 
 /// Returns `true` if `path` is a directory or
@@ -509,3 +913,615 @@ impl Workspace for WorkspaceServer {
 fn is_dir(path: &Path) -> bool {
     path.is_dir() || (path.is_symlink() && fs::read_link(path).is_ok_and(|path| path.is_dir()))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::PathBuf,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use biome_deserialize::StringSet;
+    use pgt_configuration::{
+        PartialConfiguration, RuleSelector, completions::PartialCompletionsConfiguration,
+        files::PartialFilesConfiguration,
+    };
+    use pgt_fs::PgTPath;
+
+    use pgt_analyse::RuleCategories;
+    use pgt_diagnostics::{Diagnostic, DiagnosticTags};
+    use pgt_text_size::{TextRange, TextSize};
+
+    use super::{StatementId, WorkspaceServer, statement_timing::Stage};
+    use crate::{
+        Workspace, WorkspaceError,
+        features::{
+            code_actions::{CodeActionKind, CodeActionsParams},
+            completions::{CompletionTelemetrySink, RecordCompletionOutcomeParams},
+            diagnostics::PullDiagnosticsParams,
+            rename::RenameSymbolParams,
+        },
+        workspace::{
+            ChangeFileParams, ChangeParams, CloseAllParams, CloseFileParams, GetFileContentParams,
+            IsPathIgnoredParams, OpenFileParams, UpdateSettingsParams,
+        },
+    };
+
+    fn is_path_ignored(workspace: &WorkspaceServer, path: &str) -> bool {
+        workspace
+            .is_path_ignored(IsPathIgnoredParams {
+                pgt_path: PgTPath::new(path),
+            })
+            .unwrap()
+    }
+
+    fn ignore_settings(pattern: &str, workspace_directory: &str) -> UpdateSettingsParams {
+        UpdateSettingsParams {
+            configuration: PartialConfiguration {
+                files: Some(PartialFilesConfiguration {
+                    ignore: Some(StringSet::from_iter([pattern.to_string()])),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            vcs_base_path: None,
+            gitignore_matches: vec![],
+            workspace_directory: Some(PathBuf::from(workspace_directory)),
+        }
+    }
+
+    #[test]
+    fn validates_settings_without_applying_them() {
+        let workspace = WorkspaceServer::new();
+
+        workspace
+            .update_settings(ignore_settings("ignored/*.sql", "/workspace"))
+            .unwrap();
+
+        assert!(
+            workspace
+                .validate_settings(ignore_settings("other/*.sql", "/other"))
+                .is_ok()
+        );
+        assert!(
+            workspace
+                .validate_settings(ignore_settings("[invalid", "/other"))
+                .is_err()
+        );
+        // an invalid configuration is not applied partially either
+        assert!(
+            workspace
+                .update_settings(ignore_settings("[invalid", "/other"))
+                .is_err()
+        );
+
+        assert!(is_path_ignored(&workspace, "/workspace/ignored/a.sql"));
+        assert!(is_path_ignored(&workspace, "ignored/a.sql"));
+        assert!(!is_path_ignored(&workspace, "other/a.sql"));
+    }
+
+    #[test]
+    fn ignores_relative_and_absolute_paths_alike() {
+        let workspace = WorkspaceServer::new();
+
+        workspace
+            .update_settings(UpdateSettingsParams {
+                configuration: PartialConfiguration {
+                    files: Some(PartialFilesConfiguration {
+                        ignore: Some(StringSet::from_iter(["ignored/*.sql".to_string()])),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                vcs_base_path: None,
+                gitignore_matches: vec![],
+                workspace_directory: Some(PathBuf::from("/workspace")),
+            })
+            .unwrap();
+
+        let ignored = [
+            "ignored/a.sql",
+            "./ignored/a.sql",
+            "/workspace/ignored/a.sql",
+            "/workspace/src/../ignored/a.sql",
+        ];
+        for path in ignored {
+            assert!(
+                is_path_ignored(&workspace, path),
+                "{path} should be ignored"
+            );
+        }
+
+        let not_ignored = ["src/a.sql", "./src/a.sql", "/workspace/src/a.sql"];
+        for path in not_ignored {
+            assert!(
+                !is_path_ignored(&workspace, path),
+                "{path} should not be ignored"
+            );
+        }
+    }
+
+    #[test]
+    fn sql_function_body_diagnostics_point_into_the_body() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+
+        let content =
+            "select 1;\ncreate function f() returns int language sql as 'select from where';";
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: content.into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let result = workspace
+            .pull_diagnostics(PullDiagnosticsParams {
+                path,
+                categories: RuleCategories::all(),
+                max_diagnostics: 100,
+                only: vec![],
+                skip: vec![],
+                changed_range: None,
+            })
+            .unwrap();
+
+        let body_start = content.find("select from where").unwrap();
+        let body = TextRange::new(
+            body_start.try_into().unwrap(),
+            (body_start + "select from where".len()).try_into().unwrap(),
+        );
+
+        let syntax_diagnostic = result
+            .diagnostics
+            .iter()
+            .find(|d| d.category().is_some_and(|c| c.name() == "syntax"))
+            .expect("Expected a syntax diagnostic for the function body");
+
+        let span = syntax_diagnostic
+            .location()
+            .span
+            .expect("Expected the diagnostic to have a span");
+
+        assert!(
+            body.contains_range(span),
+            "{span:?} should be within the function body {body:?}"
+        );
+    }
+
+    #[test]
+    fn splits_open_files_again_when_the_split_settings_change() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: "drop table a\ndrop table b".into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let statement_count = || workspace.parsed_documents.get(&path).unwrap().count();
+        assert_eq!(statement_count(), 1);
+
+        workspace
+            .update_settings(UpdateSettingsParams {
+                configuration: PartialConfiguration {
+                    files: Some(PartialFilesConfiguration {
+                        newline_terminates_statements: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                vcs_base_path: None,
+                gitignore_matches: vec![],
+                workspace_directory: None,
+            })
+            .unwrap();
+
+        assert_eq!(statement_count(), 2);
+    }
+
+    #[test]
+    fn reuses_the_split_of_a_reopened_file() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+        let content = "select 1;\nselect 2;";
+
+        let open = || {
+            workspace
+                .open_file(OpenFileParams {
+                    path: path.clone(),
+                    content: content.into(),
+                    version: 1,
+                })
+                .unwrap()
+        };
+
+        open();
+        let split = workspace
+            .split_cache
+            .get_or_split(content, None, workspace.split_options());
+
+        workspace
+            .close_file(CloseFileParams { path: path.clone() })
+            .unwrap();
+        open();
+
+        // reopening the file is served from the cache instead of adding another split
+        assert_eq!(workspace.split_cache.len(), 1);
+        assert!(Arc::ptr_eq(
+            &split,
+            &workspace
+                .split_cache
+                .get_or_split(content, None, workspace.split_options())
+        ));
+    }
+
+    #[test]
+    fn close_all_removes_every_file() {
+        let workspace = WorkspaceServer::new();
+
+        let paths = ["a.sql", "b.sql", "c.sql"].map(PgTPath::new);
+
+        for path in &paths {
+            workspace
+                .open_file(OpenFileParams {
+                    path: path.clone(),
+                    content: "select 1;".into(),
+                    version: 1,
+                })
+                .unwrap();
+        }
+
+        workspace.close_all(CloseAllParams {}).unwrap();
+
+        for path in paths {
+            let result = workspace.get_file_content(GetFileContentParams { path });
+            assert!(matches!(result, Err(WorkspaceError::NotFound(_))));
+        }
+    }
+
+    #[test]
+    fn renames_table_across_statements() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+
+        let content = "select * from users;\nselect id from public.users;";
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: content.into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let result = workspace
+            .rename_symbol(RenameSymbolParams {
+                path,
+                position: TextSize::new(16),
+                new_name: "accounts".into(),
+            })
+            .unwrap();
+
+        let first = content.find("users").unwrap();
+        let second = content.rfind("users").unwrap();
+
+        let ranges: Vec<TextRange> = result.edits.iter().map(|e| e.range).collect();
+        assert_eq!(
+            ranges,
+            vec![
+                TextRange::at(first.try_into().unwrap(), 5.into()),
+                TextRange::at(second.try_into().unwrap(), 5.into()),
+            ]
+        );
+        assert!(result.edits.iter().all(|e| e.new_text == "accounts"));
+    }
+
+    #[test]
+    fn renames_only_the_column_of_the_same_table() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+
+        let content = "select users.id from public.users;\n\
+            select o.id, u.id from orders o join users u on o.user_id = u.id;\n\
+            select id from auth.users;";
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: content.into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let result = workspace
+            .rename_symbol(RenameSymbolParams {
+                path,
+                position: TextSize::try_from(content.find("id").unwrap()).unwrap(),
+                new_name: "user_id".into(),
+            })
+            .unwrap();
+
+        let mut renamed: Vec<usize> = result
+            .edits
+            .iter()
+            .map(|e| e.range.start().into())
+            .collect();
+        renamed.sort();
+
+        assert_eq!(
+            renamed,
+            vec![
+                content.find("users.id").unwrap() + "users.".len(),
+                content.find("u.id").unwrap() + "u.".len(),
+                content.find("= u.id").unwrap() + "= u.".len(),
+            ]
+        );
+        assert!(result.edits.iter().all(|e| e.range.len() == 2.into()));
+    }
+
+    #[test]
+    fn fixable_diagnostics_offer_a_code_action() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+
+        let content = "alter table Users add column age int NOT  NULL;";
+        let only: Vec<RuleSelector> = vec!["lint/safety/addingRequiredField".parse().unwrap()];
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: content.into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let result = workspace
+            .pull_diagnostics(PullDiagnosticsParams {
+                path: path.clone(),
+                categories: RuleCategories::all(),
+                max_diagnostics: 100,
+                only: only.clone(),
+                skip: vec![],
+                changed_range: None,
+            })
+            .unwrap();
+
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(
+            result.diagnostics[0]
+                .tags()
+                .contains(DiagnosticTags::FIXABLE)
+        );
+        assert_eq!(result.fixes.len(), 1);
+
+        let actions = workspace
+            .pull_code_actions(CodeActionsParams {
+                path,
+                cursor_position: TextSize::new(5),
+                only,
+                skip: vec![],
+            })
+            .unwrap()
+            .actions;
+
+        let edit = actions
+            .iter()
+            .find_map(|a| match &a.kind {
+                CodeActionKind::Edit(edit) => Some(edit),
+                _ => None,
+            })
+            .expect("Expected a fix for the diagnostic");
+
+        // only the constraint is removed, the rest of the statement is kept as written
+        assert_eq!(edit.edits.len(), 1);
+        let range = edit.edits[0].range;
+        let mut fixed = content.to_string();
+        fixed.replace_range(
+            usize::from(range.start())..usize::from(range.end()),
+            &edit.edits[0].new_text,
+        );
+        assert_eq!(fixed, "alter table Users add column age int;");
+        assert!(!edit.is_safe, "Dropping the constraint changes the column");
+    }
+
+    #[test]
+    fn reports_unbound_qualifiers_without_a_database() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+
+        let content = "select 1;\nselect z.x from users u;";
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: content.into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let diagnostics = workspace
+            .pull_diagnostics(PullDiagnosticsParams {
+                path,
+                categories: RuleCategories::all(),
+                max_diagnostics: 100,
+                only: vec![],
+                skip: vec![],
+                changed_range: None,
+            })
+            .unwrap()
+            .diagnostics;
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].category().map(|c| c.name()),
+            Some("typecheck")
+        );
+
+        let z = TextSize::try_from(content.find("z.").unwrap()).unwrap();
+        assert_eq!(
+            diagnostics[0].location().span,
+            Some(TextRange::new(z, z + TextSize::new(1)))
+        );
+    }
+
+    #[test]
+    fn reanalyses_only_statements_overlapping_the_changed_range() {
+        let analysed = Arc::new(Mutex::new(Vec::new()));
+        let workspace = WorkspaceServer::new().with_timing_hook({
+            let analysed = analysed.clone();
+            Arc::new(move |id: &StatementId, stage: Stage, _: Duration| {
+                if stage == Stage::Analysis {
+                    analysed.lock().unwrap().push(id.clone());
+                }
+            })
+        });
+        let path = PgTPath::new("test.sql");
+
+        let content = "alter table a drop column x;\nalter table b drop column y;";
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: content.into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let pull = |skip: Vec<RuleSelector>, changed_range: Option<TextRange>| {
+            workspace
+                .pull_diagnostics(PullDiagnosticsParams {
+                    path: path.clone(),
+                    categories: RuleCategories::all(),
+                    max_diagnostics: 100,
+                    only: vec![],
+                    skip,
+                    changed_range,
+                })
+                .unwrap()
+                .diagnostics
+        };
+        let analysed_statements = || analysed.lock().unwrap().drain(..).count();
+
+        assert_eq!(pull(vec![], None).len(), 2);
+        assert_eq!(analysed_statements(), 2);
+
+        let edit = TextRange::at(content.find('x').unwrap().try_into().unwrap(), 1.into());
+
+        workspace
+            .change_file(ChangeFileParams {
+                path: path.clone(),
+                version: 2,
+                changes: vec![ChangeParams {
+                    range: Some(edit),
+                    text: "z".into(),
+                }],
+            })
+            .unwrap();
+
+        assert_eq!(pull(vec![], Some(edit)).len(), 2);
+        assert_eq!(analysed_statements(), 1);
+
+        // the analyses of the last pull are not reused with other filters
+        let skip = vec!["lint/safety/banDropColumn".parse().unwrap()];
+        assert!(pull(skip, Some(edit)).is_empty());
+        assert_eq!(analysed_statements(), 2);
+    }
+
+    #[test]
+    fn changes_files_while_a_snapshot_is_in_use() {
+        let workspace = WorkspaceServer::new();
+        let path = PgTPath::new("test.sql");
+
+        workspace
+            .open_file(OpenFileParams {
+                path: path.clone(),
+                content: "select 1;".into(),
+                version: 1,
+            })
+            .unwrap();
+
+        let snapshot = workspace.parsed_documents.get(&path).unwrap().snapshot();
+
+        // the change would deadlock if the snapshot still held a lock on the document
+        let (sender, receiver) = std::sync::mpsc::channel();
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                let result = workspace.change_file(ChangeFileParams {
+                    path: path.clone(),
+                    version: 2,
+                    changes: vec![ChangeParams {
+                        range: None,
+                        text: "select 2;".into(),
+                    }],
+                });
+                sender.send(result).unwrap();
+            });
+
+            receiver
+                .recv_timeout(std::time::Duration::from_secs(5))
+                .expect("The change should not be blocked by the snapshot")
+                .unwrap();
+        });
+
+        assert_eq!(snapshot.get_document_content(), "select 1;");
+        assert_eq!(
+            workspace
+                .get_file_content(GetFileContentParams { path })
+                .unwrap(),
+            "select 2;"
+        );
+    }
+
+    #[derive(Default)]
+    struct RecordingSink(Mutex<Vec<RecordCompletionOutcomeParams>>);
+
+    impl CompletionTelemetrySink for RecordingSink {
+        fn record(&self, outcome: &RecordCompletionOutcomeParams) {
+            self.0.lock().unwrap().push(outcome.clone());
+        }
+    }
+
+    #[test]
+    fn records_completion_outcomes_if_telemetry_is_enabled() {
+        let sink = Arc::new(RecordingSink::default());
+        let workspace = WorkspaceServer::new().with_completion_telemetry(sink.clone());
+
+        let outcome = RecordCompletionOutcomeParams {
+            accepted_label: "users".into(),
+            shown_order: 2,
+        };
+
+        // telemetry is opt-in
+        workspace
+            .record_completion_outcome(outcome.clone())
+            .unwrap();
+        assert!(sink.0.lock().unwrap().is_empty());
+
+        workspace
+            .update_settings(UpdateSettingsParams {
+                configuration: PartialConfiguration {
+                    completions: Some(PartialCompletionsConfiguration {
+                        telemetry: Some(true),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                vcs_base_path: None,
+                gitignore_matches: vec![],
+                workspace_directory: None,
+            })
+            .unwrap();
+
+        workspace
+            .record_completion_outcome(outcome.clone())
+            .unwrap();
+        assert_eq!(*sink.0.lock().unwrap(), vec![outcome]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::{future::Future, sync::LazyLock};
 
-use tokio::runtime::Runtime;
+use tokio::{runtime::Runtime, task::JoinHandle};
 
 use crate::WorkspaceError;
 
@@ -19,3 +19,13 @@ where
 {
     futures::executor::block_on(async { RUNTIME.spawn(future).await.map_err(|e| e.into()) })
 }
+
+/// Use this function to run async functions in the background, without waiting for them to
+/// finish.
+pub fn spawn_async<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    RUNTIME.spawn(future)
+}
@@ -3,7 +3,7 @@ use std::ops::{Add, Sub};
 
 use crate::workspace::{ChangeFileParams, ChangeParams};
 
-use super::{Document, document, statement_identifier::StatementId};
+use super::{Document, statement_identifier::StatementId};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum StatementChange {
@@ -86,42 +86,62 @@ impl Document {
     ///
     /// Will always assume its a full change and reparse the whole document
     fn apply_full_change(&mut self, change: &ChangeParams) -> Vec<StatementChange> {
-        let mut changes = Vec::new();
-
-        changes.extend(self.drain_positions());
+        let mut removed: Vec<(StatementId, String)> = self
+            .positions
+            .drain(..)
+            .map(|(id, range)| (id, self.content[range].to_string()))
+            .collect();
 
         self.content = change.apply_to_text(&self.content);
 
-        let (ranges, diagnostics) = document::split_with_diagnostics(&self.content, None);
+        let (ranges, diagnostics) = self
+            .split_cache
+            .split(&self.content, None, self.split_options);
 
         self.diagnostics = diagnostics;
 
         // Do not add any statements if there is a fatal error
         if self.has_fatal_error() {
-            return changes;
+            return removed
+                .into_iter()
+                .map(|(id, _)| StatementChange::Deleted(id))
+                .collect();
         }
 
-        changes.extend(ranges.into_iter().map(|range| {
-            let id = self.id_generator.next();
-            let text = self.content[range].to_string();
-            self.positions.push((id.clone(), range));
+        let mut added = Vec::new();
 
-            StatementChange::Added(AddedStatement { stmt: id, text })
-        }));
+        for range in ranges {
+            let text = &self.content[range];
 
-        changes
+            let id = match take_identical(&mut removed, text) {
+                Some(id) => id,
+                None => {
+                    let id = self.id_generator.next();
+                    added.push(StatementChange::Added(AddedStatement {
+                        stmt: id.clone(),
+                        text: text.to_string(),
+                    }));
+                    id
+                }
+            };
+
+            self.positions.push((id, range));
+        }
+
+        removed
+            .into_iter()
+            .map(|(id, _)| StatementChange::Deleted(id))
+            .chain(added)
+            .collect()
     }
 
-    fn insert_statement(&mut self, range: TextRange) -> StatementId {
+    fn insert_statement(&mut self, id: StatementId, range: TextRange) {
         let pos = self
             .positions
             .binary_search_by(|(_, r)| r.start().cmp(&range.start()))
             .unwrap_err();
 
-        let new_id = self.id_generator.next();
-        self.positions.insert(pos, (new_id.clone(), range));
-
-        new_id
+        self.positions.insert(pos, (id, range));
     }
 
     /// Returns all relevant details about the change and its effects on the current state of the document.
@@ -255,8 +275,11 @@ impl Document {
         if affected_indices.len() == 1 {
             let changed_content = get_affected(&new_content, affected_range);
 
-            let (new_ranges, diags) =
-                document::split_with_diagnostics(changed_content, Some(affected_range.start()));
+            let (new_ranges, diags) = self.split_cache.split(
+                changed_content,
+                Some(affected_range.start()),
+                self.split_options,
+            );
 
             self.diagnostics = diags;
 
@@ -302,8 +325,11 @@ impl Document {
         // in any other case, parse the full affected range
         let changed_content = get_affected(&new_content, full_affected_range);
 
-        let (new_ranges, diags) =
-            document::split_with_diagnostics(changed_content, Some(full_affected_range.start()));
+        let (new_ranges, diags) = self.split_cache.split(
+            changed_content,
+            Some(full_affected_range.start()),
+            self.split_options,
+        );
 
         self.diagnostics = diags;
 
@@ -315,32 +341,45 @@ impl Document {
             return changed;
         }
 
-        // delete and add new ones
-        if let Some(next_index) = next_index {
-            changed.push(StatementChange::Deleted(
-                self.positions[next_index].0.clone(),
-            ));
-            self.positions.remove(next_index);
-        }
-        for idx in affected_indices.iter().rev() {
-            changed.push(StatementChange::Deleted(self.positions[*idx].0.clone()));
-            self.positions.remove(*idx);
-        }
-        if let Some(prev_index) = prev_index {
-            changed.push(StatementChange::Deleted(
-                self.positions[prev_index].0.clone(),
-            ));
-            self.positions.remove(prev_index);
-        }
+        // remove all statements within the full affected range
+        let mut removed: Vec<(StatementId, String)> = next_index
+            .into_iter()
+            .chain(affected_indices.iter().rev().copied())
+            .chain(prev_index)
+            .map(|idx| {
+                let (id, range) = self.positions.remove(idx);
+                (id, previous_content[range].to_string())
+            })
+            .collect();
 
-        new_ranges.iter().for_each(|range| {
+        // re-add them, but keep the ids of statements whose text did not change so that
+        // everything cached for them stays valid
+        let mut added = Vec::new();
+        for range in new_ranges.iter() {
             let actual_range = range.add(full_affected_range.start());
-            let new_id = self.insert_statement(actual_range);
-            changed.push(StatementChange::Added(AddedStatement {
-                stmt: new_id,
-                text: new_content[actual_range].to_string(),
-            }));
-        });
+            let text = &new_content[actual_range];
+
+            let id = match take_identical(&mut removed, text) {
+                Some(id) => id,
+                None => {
+                    let id = self.id_generator.next();
+                    added.push(StatementChange::Added(AddedStatement {
+                        stmt: id.clone(),
+                        text: text.to_string(),
+                    }));
+                    id
+                }
+            };
+
+            self.insert_statement(id, actual_range);
+        }
+
+        changed.extend(
+            removed
+                .into_iter()
+                .map(|(id, _)| StatementChange::Deleted(id)),
+        );
+        changed.extend(added);
 
         // move all statements after the afffected range
         self.move_ranges(
@@ -396,6 +435,14 @@ impl ChangeParams {
     }
 }
 
+/// Removes the first statement with the given text from `removed` and returns its id
+fn take_identical(removed: &mut Vec<(StatementId, String)>, text: &str) -> Option<StatementId> {
+    removed
+        .iter()
+        .position(|(_, t)| t == text)
+        .map(|idx| removed.remove(idx).0)
+}
+
 fn get_affected(content: &str, range: TextRange) -> &str {
     let start_byte = content
         .char_indices()
@@ -431,7 +478,7 @@ mod tests {
     }
 
     fn assert_document_integrity(d: &Document) {
-        let ranges = pgt_statement_splitter::split(&d.content)
+        let ranges = pgt_statement_splitter::split_with_options(&d.content, d.split_options)
             .expect("Unexpected scan error")
             .ranges;
 
@@ -742,6 +789,12 @@ mod tests {
 
         assert_eq!(d.positions.len(), 2);
 
+        let ids_before = d
+            .positions
+            .iter()
+            .map(|(id, _)| id.clone())
+            .collect::<Vec<_>>();
+
         let change = ChangeFileParams {
             path: path.clone(),
             version: 1,
@@ -753,21 +806,13 @@ mod tests {
 
         let changed = d.apply_file_change(&change);
 
-        assert_eq!(changed.len(), 5);
-        assert_eq!(
-            changed
-                .iter()
-                .filter(|c| matches!(c, StatementChange::Deleted(_)))
-                .count(),
-            2
-        );
-        assert_eq!(
-            changed
-                .iter()
-                .filter(|c| matches!(c, StatementChange::Added(_)))
-                .count(),
-            3
+        // the surrounding statements did not change and keep their ids
+        assert_eq!(changed.len(), 1);
+        assert!(
+            matches!(&changed[0], StatementChange::Added(AddedStatement { stmt: _, text }) if text == "select 1;")
         );
+        assert_eq!(d.positions[0].0, ids_before[0]);
+        assert_eq!(d.positions[2].0, ids_before[1]);
 
         assert_document_integrity(&d);
     }
@@ -885,20 +930,11 @@ mod tests {
         };
 
         let changed2 = d.apply_file_change(&change2);
-        assert_eq!(changed2.len(), 4);
-        assert_eq!(
-            changed2
-                .iter()
-                .filter(|c| matches!(c, StatementChange::Deleted(_)))
-                .count(),
-            2
-        );
-        assert_eq!(
-            changed2
-                .iter()
-                .filter(|c| matches!(c, StatementChange::Added(_)))
-                .count(),
-            2
+        // `alter table test` is re-split, but did not change
+        assert_eq!(changed2.len(), 2);
+        assert!(matches!(&changed2[0], StatementChange::Deleted(_)));
+        assert!(
+            matches!(&changed2[1], StatementChange::Added(AddedStatement { stmt: _, text }) if text == "select ;")
         );
         assert_document_integrity(&d);
 
@@ -1262,16 +1298,11 @@ mod tests {
             doc.content,
             "- Add new schema named \"private\"\nCREATE SCHEMA \"private\";"
         );
-        assert_eq!(changed.len(), 3);
-        assert!(matches!(&changed[0], StatementChange::Deleted(_)));
-        assert!(matches!(
-            changed[1],
-            StatementChange::Added(AddedStatement { .. })
-        ));
-        assert!(matches!(
-            changed[2],
-            StatementChange::Added(AddedStatement { .. })
-        ));
+        // the `create schema` statement did not change
+        assert_eq!(changed.len(), 1);
+        assert!(
+            matches!(&changed[0], StatementChange::Added(AddedStatement { stmt: _, text }) if text == "- Add new schema named \"private\"")
+        );
 
         let change_2 = ChangeFileParams {
             path: path.clone(),
@@ -1289,19 +1320,11 @@ mod tests {
             "-- Add new schema named \"private\"\nCREATE SCHEMA \"private\";"
         );
 
-        assert_eq!(changed_2.len(), 3);
+        assert_eq!(changed_2.len(), 1);
         assert!(matches!(
             changed_2[0],
             StatementChange::Deleted(StatementId::Root(_))
         ));
-        assert!(matches!(
-            changed_2[1],
-            StatementChange::Deleted(StatementId::Root(_))
-        ));
-        assert!(matches!(
-            changed_2[2],
-            StatementChange::Added(AddedStatement { .. })
-        ));
 
         assert_document_integrity(&doc);
     }
@@ -1410,17 +1433,8 @@ mod tests {
 
         let changes = d.apply_file_change(&change5);
 
-        assert!(matches!(
-            changes[0],
-            StatementChange::Deleted(StatementId::Root(_))
-        ));
-
-        assert!(matches!(
-            changes[1],
-            StatementChange::Added(AddedStatement { .. })
-        ));
-
-        assert_eq!(changes.len(), 2);
+        // only whitespace after the last statement was removed
+        assert!(changes.is_empty());
 
         assert_eq!(
             d.content,
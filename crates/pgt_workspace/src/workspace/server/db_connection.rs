@@ -1,12 +1,19 @@
-use std::time::Duration;
+use std::{collections::HashMap, future::Future, time::Duration};
 
-use sqlx::{PgPool, Postgres, pool::PoolOptions, postgres::PgConnectOptions};
+use sqlx::{
+    PgPool, Postgres,
+    pool::{PoolConnection, PoolOptions},
+    postgres::PgConnectOptions,
+};
 
 use crate::settings::DatabaseSettings;
 
 #[derive(Default)]
 pub struct DbConnection {
     pool: Option<PgPool>,
+
+    /// Pools for the connections a document may select, keyed by database name
+    database_pools: HashMap<String, PgPool>,
 }
 
 impl DbConnection {
@@ -15,7 +22,31 @@ impl DbConnection {
         self.pool.clone()
     }
 
+    /// Returns the pool for `database` on the configured server, for documents that declare
+    /// their database via a `-- pgt:connection=<name>` directive.
+    ///
+    /// Only the configured database and the `connections` of the settings can be selected.
+    pub(crate) fn get_pool_for_database(&self, database: &str) -> Option<PgPool> {
+        let pool = self.pool.as_ref()?;
+
+        if pool.connect_options().get_database() == Some(database) {
+            return Some(pool.clone());
+        }
+
+        let database_pool = self.database_pools.get(database);
+        if database_pool.is_none() {
+            tracing::warn!(
+                "Connection {:?} is not configured, add it to the connections of the database settings.",
+                database
+            );
+        }
+
+        database_pool.cloned()
+    }
+
     pub(crate) fn set_conn_settings(&mut self, settings: &DatabaseSettings) {
+        self.database_pools.clear();
+
         if !settings.enable_connection {
             tracing::info!("Database connection disabled.");
             return;
@@ -28,13 +59,256 @@ impl DbConnection {
             .password(&settings.password)
             .database(&settings.database);
 
-        let timeout = settings.conn_timeout_secs;
-
+        // the configured timeout applies to every attempt of `acquire_with_retry`
         let pool = PoolOptions::<Postgres>::new()
-            .acquire_timeout(timeout)
+            .acquire_timeout(settings.conn_timeout_secs)
             .acquire_slow_threshold(Duration::from_secs(2))
+            // idle connections are closed and transparently reopened on the next acquire
+            .idle_timeout(settings.idle_timeout)
             .connect_lazy_with(config);
 
+        self.database_pools = settings
+            .connections
+            .iter()
+            .filter(|name| **name != settings.database)
+            .map(|name| {
+                let config = pool.connect_options().as_ref().clone().database(name);
+                (
+                    name.clone(),
+                    pool.options().clone().connect_lazy_with(config),
+                )
+            })
+            .collect();
+
         self.pool = Some(pool);
     }
 }
+
+/// How often to retry an operation that failed because the database was momentarily unavailable
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct RetryPolicy {
+    pub max_retries: u32,
+    /// The delay before the first retry, doubled for every subsequent one
+    pub initial_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(100),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Runs `op` until it succeeds, fails with a permanent error, or the retries are used up.
+    pub(crate) async fn run<T, F, Fut>(&self, mut op: F) -> Result<T, sqlx::Error>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, sqlx::Error>>,
+    {
+        let mut backoff = self.initial_backoff;
+        let mut retries = 0;
+
+        loop {
+            match op().await {
+                Err(err) if retries < self.max_retries && is_transient(&err) => {
+                    tracing::debug!("Retrying in {:?} after transient error: {}", backoff, err);
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Acquires a connection from `pool`, retrying if the database is momentarily unavailable.
+pub(crate) async fn acquire_with_retry(
+    pool: &PgPool,
+) -> Result<PoolConnection<Postgres>, sqlx::Error> {
+    RetryPolicy::default().run(|| pool.acquire()).await
+}
+
+fn is_transient(err: &sqlx::Error) -> bool {
+    match err {
+        // sqlx retries refused connections itself until the acquire timeout, and then reports
+        // that the pool timed out
+        sqlx::Error::PoolTimedOut => true,
+        sqlx::Error::Io(_) => true,
+        // connection exceptions, and `cannot_connect_now` while the server starts up
+        sqlx::Error::Database(db_err) => db_err
+            .code()
+            .is_some_and(|code| code.starts_with("08") || code == "57P03"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        net::TcpListener,
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
+
+    use super::{DbConnection, RetryPolicy};
+    use crate::settings::DatabaseSettings;
+
+    #[tokio::test]
+    async fn configures_pool_with_idle_timeout() {
+        let settings = DatabaseSettings {
+            enable_connection: true,
+            idle_timeout: Duration::from_millis(1500),
+            ..Default::default()
+        };
+
+        let mut conn = DbConnection::default();
+        conn.set_conn_settings(&settings);
+
+        let pool = conn.get_pool().expect("Pool should be configured");
+
+        assert_eq!(
+            pool.options().get_idle_timeout(),
+            Some(Duration::from_millis(1500))
+        );
+    }
+
+    #[tokio::test]
+    async fn selects_pool_of_requested_database() {
+        let settings = DatabaseSettings {
+            enable_connection: true,
+            database: "postgres".to_string(),
+            connections: vec!["analytics".to_string()],
+            ..Default::default()
+        };
+
+        let mut conn = DbConnection::default();
+        conn.set_conn_settings(&settings);
+
+        let pool = conn
+            .get_pool_for_database("analytics")
+            .expect("Pool should be configured");
+        assert_eq!(pool.connect_options().get_database(), Some("analytics"));
+
+        let pool = conn
+            .get_pool_for_database("postgres")
+            .expect("Pool should be configured");
+        assert_eq!(pool.connect_options().get_database(), Some("postgres"));
+
+        assert!(
+            conn.get_pool_for_database("billing").is_none(),
+            "Only configured connections can be selected"
+        );
+    }
+
+    /// Fails with a connection error for the given number of attempts, then succeeds.
+    struct FlakyPool {
+        failures: u32,
+        attempts: AtomicU32,
+    }
+
+    impl FlakyPool {
+        fn new(failures: u32) -> Self {
+            Self {
+                failures,
+                attempts: AtomicU32::new(0),
+            }
+        }
+
+        async fn acquire(&self) -> Result<(), sqlx::Error> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+
+            if attempt < self.failures {
+                Err(sqlx::Error::Io(io::ErrorKind::ConnectionRefused.into()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        max_retries: 2,
+        initial_backoff: Duration::from_millis(1),
+    };
+
+    #[tokio::test]
+    async fn recovers_from_transient_errors_within_retry_budget() {
+        let pool = FlakyPool::new(2);
+
+        let result = POLICY.run(|| pool.acquire()).await;
+
+        assert!(result.is_ok());
+        assert_eq!(pool.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn applies_connection_timeout_to_every_attempt() {
+        let settings = DatabaseSettings {
+            enable_connection: true,
+            conn_timeout_secs: Duration::from_secs(9),
+            ..Default::default()
+        };
+
+        let mut conn = DbConnection::default();
+        conn.set_conn_settings(&settings);
+
+        let pool = conn.get_pool().expect("Pool should be configured");
+
+        assert_eq!(pool.options().get_acquire_timeout(), Duration::from_secs(9));
+    }
+
+    #[tokio::test]
+    async fn retries_refused_connections_until_retries_are_exhausted() {
+        // nothing listens on the port once the listener is dropped
+        let port = {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            listener.local_addr().unwrap().port()
+        };
+
+        let settings = DatabaseSettings {
+            enable_connection: true,
+            host: "127.0.0.1".to_string(),
+            port,
+            conn_timeout_secs: Duration::from_millis(300),
+            ..Default::default()
+        };
+
+        let mut conn = DbConnection::default();
+        conn.set_conn_settings(&settings);
+        let pool = conn.get_pool().expect("Pool should be configured");
+
+        let attempts = AtomicU32::new(0);
+        let result = POLICY
+            .run(|| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                pool.acquire()
+            })
+            .await;
+
+        assert!(
+            matches!(result, Err(sqlx::Error::PoolTimedOut)),
+            "Expected the pool to time out, got {:?}",
+            result.as_ref().err()
+        );
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_permanent_errors() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), sqlx::Error> = POLICY
+            .run(|| async {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                Err(sqlx::Error::PoolClosed)
+            })
+            .await;
+
+        assert!(matches!(result, Err(sqlx::Error::PoolClosed)));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+}
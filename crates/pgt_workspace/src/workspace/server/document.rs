@@ -1,5 +1,13 @@
+use std::{
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
 use pgt_diagnostics::{Diagnostic, DiagnosticExt, Severity, serde::Diagnostic as SDiagnostic};
+use pgt_statement_splitter::SplitOptions;
 use pgt_text_size::{TextRange, TextSize};
+use rustc_hash::FxHasher;
 
 use super::statement_identifier::{StatementId, StatementIdGenerator};
 
@@ -14,13 +22,37 @@ pub(crate) struct Document {
     pub(super) positions: Vec<StatementPos>,
 
     pub(super) id_generator: StatementIdGenerator,
+
+    /// Options used whenever the content is split into statements
+    pub(super) split_options: SplitOptions,
+
+    /// Recent splits, e.g. to skip splitting again when a change is undone or a file is reopened.
+    /// It is shared by all documents of a workspace.
+    pub(super) split_cache: Arc<SplitCache>,
 }
 
 impl Document {
     pub(crate) fn new(content: String, version: i32) -> Self {
+        Self::with_split_options(content, version, SplitOptions::default())
+    }
+
+    pub(crate) fn with_split_options(
+        content: String,
+        version: i32,
+        split_options: SplitOptions,
+    ) -> Self {
+        Self::with_split_cache(content, version, split_options, Arc::default())
+    }
+
+    pub(crate) fn with_split_cache(
+        content: String,
+        version: i32,
+        split_options: SplitOptions,
+        split_cache: Arc<SplitCache>,
+    ) -> Self {
         let mut id_generator = StatementIdGenerator::new();
 
-        let (ranges, diagnostics) = split_with_diagnostics(&content, None);
+        let (ranges, diagnostics) = split_cache.split(&content, None, split_options);
 
         Self {
             positions: ranges
@@ -31,6 +63,8 @@ impl Document {
             version,
             diagnostics,
             id_generator,
+            split_options,
+            split_cache,
         }
     }
 
@@ -48,14 +82,93 @@ impl Document {
     }
 }
 
-/// Helper function that wraps the statement splitter and returns the ranges with unified
-/// diagnostics
-pub(crate) fn split_with_diagnostics(
+type SplitResult = Arc<(Vec<TextRange>, Vec<SDiagnostic>)>;
+
+struct SplitCacheEntry {
+    /// The hash of the split content, see [content_hash]
+    content_hash: u64,
+    offset: Option<TextSize>,
+    options: SplitOptions,
+    result: SplitResult,
+}
+
+/// Caches the results of the most recent splits, keyed by the hash of the split content.
+///
+/// The workspace keeps a single cache for all documents, so it outlives closing a document.
+#[derive(Default)]
+pub(crate) struct SplitCache {
+    /// The most recently used entry first
+    entries: Mutex<VecDeque<SplitCacheEntry>>,
+}
+
+impl SplitCache {
+    /// Maximum number of cached results. The least recently used one is evicted once it is full.
+    const CAPACITY: usize = 32;
+
+    /// Wraps the statement splitter and returns the ranges with unified diagnostics
+    pub(crate) fn split(
+        &self,
+        content: &str,
+        offset: Option<TextSize>,
+        options: SplitOptions,
+    ) -> (Vec<TextRange>, Vec<SDiagnostic>) {
+        let (ranges, diagnostics) = &*self.get_or_split(content, offset, options);
+        (ranges.clone(), diagnostics.clone())
+    }
+
+    pub(super) fn get_or_split(
+        &self,
+        content: &str,
+        offset: Option<TextSize>,
+        options: SplitOptions,
+    ) -> SplitResult {
+        let hash = content_hash(content);
+
+        let mut entries = self.entries.lock().unwrap();
+
+        let position = entries.iter().position(|entry| {
+            entry.content_hash == hash && entry.offset == offset && entry.options == options
+        });
+
+        if let Some(entry) = position.and_then(|idx| entries.remove(idx)) {
+            let result = entry.result.clone();
+            entries.push_front(entry);
+            return result;
+        }
+
+        if entries.len() >= Self::CAPACITY {
+            entries.pop_back();
+        }
+
+        let result = Arc::new(split_uncached(content, offset, options));
+        entries.push_front(SplitCacheEntry {
+            content_hash: hash,
+            offset,
+            options,
+            result: result.clone(),
+        });
+        result
+    }
+
+    #[cfg(test)]
+    pub(super) fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+}
+
+fn content_hash(content: &str) -> u64 {
+    let mut hasher = FxHasher::default();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn split_uncached(
     content: &str,
     offset: Option<TextSize>,
+    options: SplitOptions,
 ) -> (Vec<TextRange>, Vec<SDiagnostic>) {
     let o = offset.unwrap_or_else(|| 0.into());
-    match pgt_statement_splitter::split(content) {
+    match pgt_statement_splitter::split_with_options(content, options) {
         Ok(parse) => (
             parse.ranges,
             parse
@@ -109,3 +222,53 @@ impl<'a> Iterator for StatementIterator<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use pgt_statement_splitter::SplitOptions;
+
+    use super::SplitCache;
+
+    #[test]
+    fn serves_identical_content_from_cache() {
+        let cache = SplitCache::default();
+        let content = "select 1;\nselect 2;";
+
+        let first = cache.get_or_split(content, None, SplitOptions::default());
+        let second = cache.get_or_split(content, None, SplitOptions::default());
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.0.len(), 2);
+
+        let other = cache.get_or_split("select 3;", None, SplitOptions::default());
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[test]
+    fn evicts_least_recently_used_split() {
+        let cache = SplitCache::default();
+        let options = SplitOptions::default();
+
+        let first = cache.get_or_split("select 0;", None, options);
+        let second = cache.get_or_split("select 1;", None, options);
+
+        for i in 2..SplitCache::CAPACITY {
+            cache.get_or_split(&format!("select {i};"), None, options);
+        }
+
+        // using the first split again makes the second one the least recently used
+        cache.get_or_split("select 0;", None, options);
+        cache.get_or_split("select 100;", None, options);
+
+        assert!(Arc::ptr_eq(
+            &first,
+            &cache.get_or_split("select 0;", None, options)
+        ));
+        assert!(!Arc::ptr_eq(
+            &second,
+            &cache.get_or_split("select 1;", None, options)
+        ));
+    }
+}
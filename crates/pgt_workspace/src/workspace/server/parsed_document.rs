@@ -1,18 +1,24 @@
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    sync::{Arc, OnceLock},
+};
 
 use pgt_diagnostics::serde::Diagnostic as SDiagnostic;
 use pgt_fs::PgTPath;
+use pgt_lexer::{SyntaxKind, WHITESPACE_TOKENS};
 use pgt_query_ext::diagnostics::SyntaxDiagnostic;
+use pgt_statement_splitter::SplitOptions;
 use pgt_text_size::{TextRange, TextSize};
 
-use crate::workspace::ChangeFileParams;
+use crate::{WorkspaceError, workspace::ChangeFileParams};
 
 use super::{
     annotation::AnnotationStore,
     change::StatementChange,
-    document::{Document, StatementIterator},
+    document::{Document, SplitCache, StatementIterator},
     pg_query::PgQueryStore,
     sql_function::SQLFunctionBodyStore,
+    statement_diagnostics::{AnalysisKey, StatementAnalysis, StatementDiagnosticsStore},
     statement_identifier::StatementId,
     tree_sitter::TreeSitterStore,
 };
@@ -26,21 +32,54 @@ pub struct ParsedDocument {
     cst_db: TreeSitterStore,
     sql_fn_db: SQLFunctionBodyStore,
     annotation_db: AnnotationStore,
+    diagnostics_db: StatementDiagnosticsStore,
+
+    /// The database named by a `-- pgt:connection=<name>` directive in the leading comments
+    connection_directive: Option<String>,
+    /// The text before the first statement the directive was parsed from
+    leading_comments: String,
+
+    /// The snapshot of the current version, taken on first request
+    snapshot: OnceLock<ParsedDocumentSnapshot>,
 }
 
 impl ParsedDocument {
     pub fn new(path: PgTPath, content: String, version: i32) -> ParsedDocument {
-        let doc = Document::new(content, version);
+        Self::with_split_options(path, content, version, SplitOptions::default())
+    }
+
+    pub fn with_split_options(
+        path: PgTPath,
+        content: String,
+        version: i32,
+        split_options: SplitOptions,
+    ) -> ParsedDocument {
+        Self::with_split_cache(path, content, version, split_options, Arc::default())
+    }
+
+    /// Creates a document whose splits are cached in `split_cache`, e.g. the cache of the workspace
+    pub(crate) fn with_split_cache(
+        path: PgTPath,
+        content: String,
+        version: i32,
+        split_options: SplitOptions,
+        split_cache: Arc<SplitCache>,
+    ) -> ParsedDocument {
+        let doc = Document::with_split_cache(content, version, split_options, split_cache);
 
         let cst_db = TreeSitterStore::new();
         let ast_db = PgQueryStore::new();
         let sql_fn_db = SQLFunctionBodyStore::new();
         let annotation_db = AnnotationStore::new();
+        let diagnostics_db = StatementDiagnosticsStore::new();
 
         doc.iter().for_each(|(stmt, _, content)| {
             cst_db.add_statement(&stmt, content);
         });
 
+        let leading_comments = leading_comments(&doc).to_string();
+        let connection_directive = parse_connection_directive(&leading_comments);
+
         ParsedDocument {
             path,
             doc,
@@ -48,6 +87,10 @@ impl ParsedDocument {
             cst_db,
             sql_fn_db,
             annotation_db,
+            diagnostics_db,
+            connection_directive,
+            leading_comments,
+            snapshot: OnceLock::new(),
         }
     }
 
@@ -56,8 +99,19 @@ impl ParsedDocument {
     /// Note that only tree-sitter cares about statement modifications vs remove + add.
     /// Hence, we just clear the AST for the old statements and lazily load them when requested.
     ///
+    /// Changes must be applied in order, so a change whose version is not greater than the
+    /// version of the document is rejected.
+    ///
     /// * `params`: ChangeFileParams - The parameters for the change to be applied.
-    pub fn apply_change(&mut self, params: ChangeFileParams) {
+    pub fn apply_change(&mut self, params: ChangeFileParams) -> Result<(), WorkspaceError> {
+        if params.version <= self.doc.version {
+            return Err(WorkspaceError::stale_change(
+                params.path.as_path().display().to_string(),
+                params.version,
+                self.doc.version,
+            ));
+        }
+
         for c in &self.doc.apply_file_change(&params) {
             match c {
                 StatementChange::Added(added) => {
@@ -74,6 +128,7 @@ impl ParsedDocument {
                     self.ast_db.clear_statement(s);
                     self.sql_fn_db.clear_statement(s);
                     self.annotation_db.clear_statement(s);
+                    self.diagnostics_db.clear_statement(s);
                 }
                 StatementChange::Modified(s) => {
                     tracing::debug!(
@@ -90,15 +145,51 @@ impl ParsedDocument {
                     self.ast_db.clear_statement(&s.old_stmt);
                     self.sql_fn_db.clear_statement(&s.old_stmt);
                     self.annotation_db.clear_statement(&s.old_stmt);
+                    self.diagnostics_db.clear_statement(&s.old_stmt);
                 }
             }
         }
+
+        // most changes are made to statements, which cannot change the directive
+        if leading_comments(&self.doc) != self.leading_comments {
+            self.leading_comments = leading_comments(&self.doc).to_string();
+            self.connection_directive = parse_connection_directive(&self.leading_comments);
+        }
+        self.snapshot = OnceLock::new();
+
+        Ok(())
     }
 
     pub fn get_document_content(&self) -> &str {
         &self.doc.content
     }
 
+    /// Whether a single newline terminates a statement in this document
+    pub fn newline_terminates_statements(&self) -> bool {
+        self.doc.split_options.newline_terminates_statements
+    }
+
+    /// Splits the document again if the options differ from those it was split with, e.g. after
+    /// the settings changed
+    pub(crate) fn set_split_options(&mut self, split_options: SplitOptions) {
+        if self.doc.split_options == split_options {
+            return;
+        }
+
+        *self = Self::with_split_cache(
+            self.path.clone(),
+            std::mem::take(&mut self.doc.content),
+            self.doc.version,
+            split_options,
+            self.doc.split_cache.clone(),
+        );
+    }
+
+    /// The database this document declares via a `-- pgt:connection=<name>` directive, if any
+    pub fn connection_directive(&self) -> Option<&str> {
+        self.connection_directive.as_deref()
+    }
+
     pub fn document_diagnostics(&self) -> &Vec<SDiagnostic> {
         &self.doc.diagnostics
     }
@@ -110,6 +201,41 @@ impl ParsedDocument {
         self.iter_with_filter(mapper, IdFilter::new(id)).next()
     }
 
+    /// Returns the current range of the statement with the given id.
+    ///
+    /// Returns `None` if the statement has been removed or modified since the id was obtained.
+    pub fn range_of(&self, id: StatementId) -> Option<TextRange> {
+        self.find(id, DefaultMapper).map(|(_, range, _)| range)
+    }
+
+    /// Returns the statements that intersect with `range`, e.g. a selection spanning several
+    /// statements.
+    ///
+    /// The bodies of sql functions are returned as separate child statements.
+    #[allow(dead_code)]
+    pub fn statements_in_range(&self, range: TextRange) -> Vec<(StatementId, TextRange)> {
+        self.iter_with_filter(DefaultMapper, RangeFilter::new(range))
+            .map(|(id, range, _)| (id, range))
+            .collect()
+    }
+
+    /// Returns the last analysis of the statement, with spans relative to it.
+    ///
+    /// Returns `None` if the statement was not analysed with `key` since it was added or last
+    /// modified.
+    pub fn cached_analysis(
+        &self,
+        id: &StatementId,
+        key: &AnalysisKey,
+    ) -> Option<StatementAnalysis> {
+        self.diagnostics_db.get_analysis(id, key)
+    }
+
+    /// Remembers an analysis of the statement made with `key`, with spans relative to it.
+    pub fn cache_analysis(&self, id: StatementId, key: &AnalysisKey, analysis: StatementAnalysis) {
+        self.diagnostics_db.set_analysis(id, key, analysis);
+    }
+
     pub fn iter<'a, M>(&'a self, mapper: M) -> ParseIterator<'a, M, NoFilter>
     where
         M: StatementMapper<'a>,
@@ -129,6 +255,104 @@ impl ParsedDocument {
     pub fn count(&self) -> usize {
         self.iter(DefaultMapper).count()
     }
+
+    /// Returns an immutable view of the current statements that does not borrow the document.
+    ///
+    /// The statements are copied once per version of the document, later snapshots of the
+    /// same version share them.
+    pub fn snapshot(&self) -> ParsedDocumentSnapshot {
+        self.snapshot
+            .get_or_init(|| {
+                let statements = self
+                    .iter(GetCompletionsMapper)
+                    .map(|(id, range, content, cst)| StatementSnapshot {
+                        id,
+                        range,
+                        content,
+                        cst,
+                    })
+                    .collect();
+
+                ParsedDocumentSnapshot {
+                    content: self.doc.content.as_str().into(),
+                    newline_terminates_statements: self.newline_terminates_statements(),
+                    connection_directive: self.connection_directive.as_deref().map(Arc::from),
+                    statements,
+                }
+            })
+            .clone()
+    }
+}
+
+/// An immutable view of a [ParsedDocument] at the time it was taken.
+///
+/// It shares its content with the document and the other snapshots of the same version, and
+/// can be used after the lock on the document was released, so that slow operations like
+/// loading the schema cache do not block changes to it.
+#[derive(Clone)]
+pub struct ParsedDocumentSnapshot {
+    content: Arc<str>,
+    newline_terminates_statements: bool,
+    connection_directive: Option<Arc<str>>,
+    statements: Arc<[StatementSnapshot]>,
+}
+
+/// A statement of a [ParsedDocumentSnapshot], including the bodies of sql functions.
+pub struct StatementSnapshot {
+    pub id: StatementId,
+    pub range: TextRange,
+    pub content: String,
+    pub cst: Arc<tree_sitter::Tree>,
+}
+
+impl ParsedDocumentSnapshot {
+    pub fn get_document_content(&self) -> &str {
+        &self.content
+    }
+
+    /// Whether a single newline terminates a statement in this document
+    pub fn newline_terminates_statements(&self) -> bool {
+        self.newline_terminates_statements
+    }
+
+    /// The database this document declares via a `-- pgt:connection=<name>` directive, if any
+    pub fn connection_directive(&self) -> Option<&str> {
+        self.connection_directive.as_deref()
+    }
+
+    pub fn statements(&self) -> &[StatementSnapshot] {
+        &self.statements
+    }
+}
+
+/// Returns the text before the first statement, which consists of comments and whitespace
+fn leading_comments(doc: &Document) -> &str {
+    let end = doc
+        .positions
+        .first()
+        .map_or(doc.content.len(), |(_, range)| range.start().into());
+
+    &doc.content[..end]
+}
+
+/// Looks for a `pgt:connection=<name>` directive in the leading comments of a document
+fn parse_connection_directive(leading_comments: &str) -> Option<String> {
+    let tokens = pgt_lexer::lex(leading_comments).ok()?;
+
+    tokens
+        .iter()
+        .take_while(|t| WHITESPACE_TOKENS.contains(&t.kind))
+        .find_map(|t| {
+            let comment = match t.kind {
+                SyntaxKind::SqlComment => t.text.strip_prefix("--")?,
+                SyntaxKind::CComment => t.text.strip_prefix("/*")?.strip_suffix("*/")?,
+                _ => return None,
+            };
+
+            let name = comment.trim().strip_prefix("pgt:connection=")?.trim();
+
+            (!name.is_empty()).then(|| name.to_string())
+        })
 }
 
 pub trait StatementMapper<'a> {
@@ -302,6 +526,7 @@ impl<'a> StatementMapper<'a> for SyncDiagnosticsMapper {
     type Output = (
         StatementId,
         TextRange,
+        String,
         Option<pgt_query_ext::NodeEnum>,
         Option<SyntaxDiagnostic>,
     );
@@ -320,7 +545,7 @@ impl<'a> StatementMapper<'a> for SyncDiagnosticsMapper {
             Err(diag) => (None, Some(diag.clone())),
         };
 
-        (id, range, ast_option, diagnostics)
+        (id, range, content.to_string(), ast_option, diagnostics)
     }
 }
 
@@ -350,10 +575,14 @@ impl<'a> StatementMapper<'a> for GetCompletionsMapper {
  *
  * select * from users; | <-- no autocompletions here.
  */
-pub struct GetCompletionsFilter {
+pub struct GetCompletionsFilter<'a> {
     pub cursor_position: TextSize,
+    /// If true, a statement without a semicolon ends at the end of its line
+    pub newline_terminates_statements: bool,
+    /// The content of the whole document
+    pub document_content: &'a str,
 }
-impl StatementFilter<'_> for GetCompletionsFilter {
+impl<'a> StatementFilter<'a> for GetCompletionsFilter<'a> {
     fn predicate(&self, _id: &StatementId, range: &TextRange, content: &str) -> bool {
         let is_terminated_by_semi = content.chars().last().is_some_and(|c| c == ';');
 
@@ -362,7 +591,20 @@ impl StatementFilter<'_> for GetCompletionsFilter {
         } else {
             range.checked_expand_end(2.into()).unwrap_or(*range)
         };
-        measuring_range.contains(self.cursor_position)
+
+        if !measuring_range.contains(self.cursor_position) {
+            return false;
+        }
+
+        // the allowance after the statement must not reach into the next line
+        if self.newline_terminates_statements && self.cursor_position > range.end() {
+            return !self
+                .document_content
+                .get(usize::from(range.end())..usize::from(self.cursor_position))
+                .is_some_and(|between| between.contains('\n'));
+        }
+
+        true
     }
 }
 
@@ -389,6 +631,49 @@ impl StatementFilter<'_> for CursorPositionFilter {
     }
 }
 
+/// Matches the statements that overlap with a range.
+///
+/// An empty range matches the statement that contains it, just like [CursorPositionFilter].
+pub struct RangeFilter {
+    range: TextRange,
+}
+
+impl RangeFilter {
+    pub fn new(range: TextRange) -> Self {
+        Self { range }
+    }
+}
+
+impl StatementFilter<'_> for RangeFilter {
+    fn predicate(&self, _id: &StatementId, range: &TextRange, _content: &str) -> bool {
+        if self.range.is_empty() {
+            return range.contains(self.range.start());
+        }
+
+        // ranges that merely touch, e.g. a selection ending right before a statement, don't count
+        range
+            .intersect(self.range)
+            .is_some_and(|intersection| !intersection.is_empty())
+    }
+}
+
+/// Matches the statements with one of the given ids.
+pub struct IdSetFilter<'b> {
+    ids: &'b HashSet<StatementId>,
+}
+
+impl<'b> IdSetFilter<'b> {
+    pub fn new(ids: &'b HashSet<StatementId>) -> Self {
+        Self { ids }
+    }
+}
+
+impl StatementFilter<'_> for IdSetFilter<'_> {
+    fn predicate(&self, id: &StatementId, _range: &TextRange, _content: &str) -> bool {
+        self.ids.contains(id)
+    }
+}
+
 pub struct IdFilter {
     id: StatementId,
 }
@@ -411,6 +696,8 @@ mod tests {
 
     use pgt_fs::PgTPath;
 
+    use crate::workspace::ChangeParams;
+
     #[test]
     fn sql_function_body() {
         let input = "CREATE FUNCTION add(integer, integer) RETURNS integer
@@ -428,4 +715,219 @@ mod tests {
         assert_eq!(stmts.len(), 2);
         assert_eq!(stmts[1].2, "select $1 + $2;");
     }
+
+    #[test]
+    fn rejects_stale_changes() {
+        let path = PgTPath::new("test.sql");
+
+        let mut d = ParsedDocument::new(path.clone(), "select 1;".to_string(), 1);
+
+        d.apply_change(ChangeFileParams {
+            path: path.clone(),
+            version: 3,
+            changes: vec![ChangeParams {
+                range: None,
+                text: "select 3;".to_string(),
+            }],
+        })
+        .expect("Newer changes should be applied");
+
+        let result = d.apply_change(ChangeFileParams {
+            path,
+            version: 2,
+            changes: vec![ChangeParams {
+                range: None,
+                text: "select 2;".to_string(),
+            }],
+        });
+
+        assert!(matches!(result, Err(WorkspaceError::StaleChange(_))));
+        assert_eq!(d.get_document_content(), "select 3;");
+    }
+
+    #[test]
+    fn range_of_follows_edits() {
+        let path = PgTPath::new("test.sql");
+
+        let mut d = ParsedDocument::new(path.clone(), "select 1;\nselect 2;".to_string(), 1);
+
+        let (id, range, _) = d
+            .iter(DefaultMapper)
+            .find(|(_, _, content)| content == "select 2;")
+            .expect("Expected statement");
+
+        assert_eq!(d.range_of(id.clone()), Some(range));
+
+        d.apply_change(ChangeFileParams {
+            path,
+            version: 2,
+            changes: vec![ChangeParams {
+                range: Some(TextRange::new(7.into(), 8.into())),
+                text: "100".to_string(),
+            }],
+        })
+        .expect("Change should be applied");
+
+        assert_eq!(d.get_document_content(), "select 100;\nselect 2;");
+        assert_eq!(d.range_of(id), Some(TextRange::new(12.into(), 21.into())));
+    }
+
+    #[test]
+    fn keeps_ast_of_unchanged_statements() {
+        let path = PgTPath::new("test.sql");
+        let input = "select id from users;\n\n\n\nselect * from contacts;";
+
+        let mut d = ParsedDocument::new(path.clone(), input.to_string(), 1);
+
+        let (id, _, content) = d
+            .iter(DefaultMapper)
+            .find(|(_, _, content)| content == "select * from contacts;")
+            .expect("Expected statement");
+        let ast = d.ast_db.get_or_cache_ast(&id, &content);
+
+        // adds a statement in between, which re-splits the surrounding statements
+        d.apply_change(ChangeFileParams {
+            path,
+            version: 2,
+            changes: vec![ChangeParams {
+                range: Some(TextRange::new(23.into(), 23.into())),
+                text: "select 1;".to_string(),
+            }],
+        })
+        .expect("Change should be applied");
+
+        assert_eq!(
+            d.get_document_content(),
+            "select id from users;\n\nselect 1;\n\nselect * from contacts;"
+        );
+
+        let (new_id, _, content) = d
+            .iter(DefaultMapper)
+            .find(|(_, _, content)| content == "select * from contacts;")
+            .expect("Expected statement");
+
+        assert_eq!(new_id, id);
+        assert!(Arc::ptr_eq(
+            &ast,
+            &d.ast_db.get_or_cache_ast(&new_id, &content)
+        ));
+    }
+
+    #[test]
+    fn parses_connection_directive() {
+        let path = PgTPath::new("test.sql");
+
+        let input = "-- some description\n-- pgt:connection=analytics\nselect 1;";
+        let mut d = ParsedDocument::new(path.clone(), input.to_string(), 0);
+        assert_eq!(d.connection_directive(), Some("analytics"));
+
+        // directives after the first statement are ignored
+        let input = "select 1;\n-- pgt:connection=analytics\nselect 2;";
+        let other = ParsedDocument::new(path.clone(), input.to_string(), 0);
+        assert_eq!(other.connection_directive(), None);
+
+        d.apply_change(ChangeFileParams {
+            path: path.clone(),
+            version: 1,
+            changes: vec![ChangeParams {
+                range: None,
+                text: "/* pgt:connection=billing */\nselect 1;".to_string(),
+            }],
+        })
+        .unwrap();
+        assert_eq!(d.connection_directive(), Some("billing"));
+
+        // a change to a statement keeps the directive
+        let one = TextSize::try_from(d.get_document_content().find('1').unwrap()).unwrap();
+        d.apply_change(ChangeFileParams {
+            path: path.clone(),
+            version: 2,
+            changes: vec![ChangeParams {
+                range: Some(TextRange::at(one, 1.into())),
+                text: "2".to_string(),
+            }],
+        })
+        .unwrap();
+        assert_eq!(d.connection_directive(), Some("billing"));
+
+        d.apply_change(ChangeFileParams {
+            path,
+            version: 3,
+            changes: vec![ChangeParams {
+                range: Some(TextRange::new(0.into(), one)),
+                text: String::new(),
+            }],
+        })
+        .unwrap();
+        assert_eq!(d.get_document_content(), "select 2;");
+        assert_eq!(d.connection_directive(), None);
+    }
+
+    #[test]
+    fn statements_in_range_returns_overlapping_statements() {
+        let path = PgTPath::new("test.sql");
+
+        let d = ParsedDocument::new(path, "select 1;\nselect 2;\nselect 3;".to_string(), 1);
+
+        // from within the first statement into the second one
+        let selection = TextRange::new(TextSize::new(7), TextSize::new(14));
+
+        let ranges: Vec<TextRange> = d
+            .statements_in_range(selection)
+            .into_iter()
+            .map(|(_, range)| range)
+            .collect();
+
+        assert_eq!(
+            ranges,
+            vec![
+                TextRange::new(TextSize::new(0), TextSize::new(9)),
+                TextRange::new(TextSize::new(10), TextSize::new(19)),
+            ]
+        );
+    }
+
+    #[test]
+    fn snapshot_is_unaffected_by_later_changes() {
+        let path = PgTPath::new("test.sql");
+
+        let mut d = ParsedDocument::new(path.clone(), "select 1;\nselect 2;".to_string(), 1);
+
+        let snapshot = d.snapshot();
+
+        d.apply_change(ChangeFileParams {
+            path,
+            version: 2,
+            changes: vec![ChangeParams {
+                range: None,
+                text: "select 3;".to_string(),
+            }],
+        })
+        .expect("Change should be applied");
+
+        assert_eq!(snapshot.get_document_content(), "select 1;\nselect 2;");
+        assert_eq!(
+            snapshot
+                .statements()
+                .iter()
+                .map(|s| s.content.as_str())
+                .collect::<Vec<_>>(),
+            vec!["select 1;", "select 2;"]
+        );
+        assert_eq!(d.snapshot().statements().len(), 1);
+    }
+
+    #[test]
+    fn snapshots_of_the_same_version_share_the_statements() {
+        let d = ParsedDocument::new(
+            PgTPath::new("test.sql"),
+            "select 1;\nselect 2;".to_string(),
+            1,
+        );
+
+        assert!(Arc::ptr_eq(
+            &d.snapshot().statements,
+            &d.snapshot().statements
+        ));
+    }
 }
@@ -1,74 +1,244 @@
-use std::sync::{RwLock, RwLockReadGuard};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
+use futures::future::BoxFuture;
 use pgt_schema_cache::SchemaCache;
 use sqlx::PgPool;
 
 use crate::WorkspaceError;
 
-use super::async_helper::run_async;
+use super::{async_helper::spawn_async, db_connection::acquire_with_retry};
 
-pub(crate) struct SchemaCacheHandle<'a> {
-    inner: RwLockReadGuard<'a, SchemaCacheManagerInner>,
+/// The schema cache of a connection at the time it was requested.
+///
+/// It does not hold a lock, so a refresh that finishes in the meantime is not blocked by it.
+pub(crate) struct SchemaCacheHandle {
+    cache: Arc<SchemaCache>,
 }
 
-impl<'a> SchemaCacheHandle<'a> {
-    pub(crate) fn new(cache: &'a RwLock<SchemaCacheManagerInner>) -> Self {
-        Self {
-            inner: cache.read().unwrap(),
-        }
+impl AsRef<SchemaCache> for SchemaCacheHandle {
+    fn as_ref(&self) -> &SchemaCache {
+        &self.cache
     }
+}
 
-    pub(crate) fn wrap(inner: RwLockReadGuard<'a, SchemaCacheManagerInner>) -> Self {
-        Self { inner }
-    }
+/// The schema cache of a single connection
+#[derive(Default)]
+pub(crate) struct SchemaCacheManagerInner {
+    cache: Arc<SchemaCache>,
+
+    /// Whether the cache was loaded at least once
+    loaded: bool,
+
+    /// Whether a refresh is currently running
+    loading: bool,
+
+    /// Incremented whenever the cache is replaced
+    generation: u64,
+
+    /// The last refresh that failed, if the cache was not refreshed since.
+    failure: Option<LoadFailure>,
 }
 
-impl AsRef<SchemaCache> for SchemaCacheHandle<'_> {
-    fn as_ref(&self) -> &SchemaCache {
-        &self.inner.cache
+impl SchemaCacheManagerInner {
+    fn handle(&self) -> SchemaCacheHandle {
+        SchemaCacheHandle {
+            cache: Arc::clone(&self.cache),
+        }
     }
 }
 
-#[derive(Default)]
-pub(crate) struct SchemaCacheManagerInner {
-    cache: SchemaCache,
-    conn_str: String,
+/// The delay before the first retry of a failed refresh; it doubles with every further failure.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+struct LoadFailure {
+    /// The error, until it was returned by [SchemaCacheManager::load]
+    error: Option<sqlx::Error>,
+    /// The number of consecutive failed refreshes of the connection
+    attempts: u32,
+    /// The refresh is not retried before this point in time
+    retry_at: Instant,
 }
 
-#[derive(Default)]
+type SchemaCacheLoader =
+    Arc<dyn Fn(PgPool) -> BoxFuture<'static, Result<SchemaCache, sqlx::Error>> + Send + Sync>;
+
+/// The caches of the connections, keyed by connection string
+type SchemaCaches = Arc<RwLock<HashMap<String, SchemaCacheManagerInner>>>;
+
+/// Holds a schema cache for every connection, so that documents using different databases
+/// (e.g. via a `-- pgt:connection=<name>` directive) do not evict each other's cache.
 pub struct SchemaCacheManager {
-    inner: RwLock<SchemaCacheManagerInner>,
+    caches: SchemaCaches,
+    loader: SchemaCacheLoader,
+}
+
+impl Default for SchemaCacheManager {
+    fn default() -> Self {
+        Self::with_loader(|pool| {
+            Box::pin(async move {
+                acquire_with_retry(&pool).await?;
+                SchemaCache::load(&pool).await
+            })
+        })
+    }
 }
 
 impl SchemaCacheManager {
+    fn with_loader<F>(loader: F) -> Self
+    where
+        F: Fn(PgPool) -> BoxFuture<'static, Result<SchemaCache, sqlx::Error>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            caches: Arc::default(),
+            loader: Arc::new(loader),
+        }
+    }
+
+    /// Returns a number that changes whenever the cached schema of the connection is replaced
+    pub fn generation(&self, pool: &PgPool) -> u64 {
+        self.caches
+            .read()
+            .unwrap()
+            .get(&pool_to_conn_str(pool))
+            .map_or(0, |inner| inner.generation)
+    }
+
+    /// Returns the cached schema of the connection and loads it in the background if it was
+    /// not loaded yet.
+    ///
+    /// Loading the schema of a large database takes a while, so the returned cache might still be
+    /// empty until the refresh finished.
+    ///
+    /// If a refresh failed, its error is returned once. The refresh is then retried with an
+    /// exponential backoff, and the stale cache is returned in the meantime.
     pub fn load(&self, pool: PgPool) -> Result<SchemaCacheHandle, WorkspaceError> {
-        let new_conn_str = pool_to_conn_str(&pool);
+        let conn_str = pool_to_conn_str(&pool);
 
         {
-            // return early if the connection string is the same
-            let inner = self.inner.read().unwrap();
-            if new_conn_str == inner.conn_str {
-                tracing::info!("Same connection string, no updates.");
-                return Ok(SchemaCacheHandle::wrap(inner));
+            // return early if the cache was loaded or is already being loaded
+            let caches = self.caches.read().unwrap();
+            if let Some(inner) = caches.get(&conn_str) {
+                if inner.loaded {
+                    tracing::info!("Same connection string, no updates.");
+                    return Ok(inner.handle());
+                }
+
+                if inner.loading {
+                    tracing::info!("Schema cache is still loading, returning stale cache.");
+                    return Ok(inner.handle());
+                }
             }
         }
 
-        let maybe_refreshed = run_async(async move { SchemaCache::load(&pool).await })?;
-        let refreshed = maybe_refreshed?;
+        let mut caches = self.caches.write().unwrap();
+        let inner = caches.entry(conn_str.clone()).or_default();
 
-        {
-            // write lock must be dropped before we return the reference below, hence the block
-            let mut inner = self.inner.write().unwrap();
+        if let Some(failure) = inner.failure.as_mut() {
+            if let Some(err) = failure.error.take() {
+                return Err(err.into());
+            }
+
+            if Instant::now() < failure.retry_at {
+                tracing::debug!("Schema cache failed to load, waiting before retrying.");
+                return Ok(inner.handle());
+            }
+        }
+
+        // Double-check that we still need to refresh (another thread might have started it)
+        if !inner.loaded && !inner.loading {
+            inner.loading = true;
+            spawn_async(refresh(
+                Arc::clone(&self.caches),
+                (self.loader)(pool),
+                conn_str,
+            ));
+        }
+
+        Ok(inner.handle())
+    }
+
+    /// Reloads the schema of the connection in the background if it was loaded before, e.g.
+    /// because the settings changed. The changes to the schema are logged once it is reloaded.
+    ///
+    /// A connection that was not loaded yet is loaded by the next [SchemaCacheManager::load].
+    pub fn refresh(&self, pool: PgPool) {
+        let conn_str = pool_to_conn_str(&pool);
+
+        let mut caches = self.caches.write().unwrap();
+        let Some(inner) = caches.get_mut(&conn_str) else {
+            return;
+        };
+
+        if !inner.loaded || inner.loading {
+            return;
+        }
+
+        inner.loading = true;
+        spawn_async(refresh(
+            Arc::clone(&self.caches),
+            (self.loader)(pool),
+            conn_str,
+        ));
+    }
+}
 
-            // Double-check that we still need to refresh (another thread might have done it)
-            if new_conn_str != inner.conn_str {
-                inner.cache = refreshed;
-                inner.conn_str = new_conn_str;
+async fn refresh(
+    caches: SchemaCaches,
+    load: BoxFuture<'static, Result<SchemaCache, sqlx::Error>>,
+    conn_str: String,
+) {
+    let result = load.await;
+
+    let replaced = {
+        let mut caches = caches.write().unwrap();
+        let inner = caches.entry(conn_str).or_default();
+
+        inner.loading = false;
+
+        match result {
+            Ok(cache) => {
+                let cache = Arc::new(cache);
+                let previous = std::mem::replace(&mut inner.cache, Arc::clone(&cache));
+                inner.loaded = true;
+                inner.generation += 1;
+                inner.failure = None;
                 tracing::info!("Refreshed connection.");
+
+                Some((cache, previous))
+            }
+            Err(err) => {
+                tracing::error!("Failed to load schema cache: {}", err);
+
+                let attempts = inner.failure.as_ref().map_or(1, |f| f.attempts + 1);
+                let backoff = INITIAL_BACKOFF
+                    .saturating_mul(2u32.saturating_pow(attempts - 1))
+                    .min(MAX_BACKOFF);
+
+                inner.failure = Some(LoadFailure {
+                    error: Some(err),
+                    attempts,
+                    retry_at: Instant::now() + backoff,
+                });
+
+                None
             }
         }
+    };
 
-        Ok(SchemaCacheHandle::new(&self.inner))
+    // the diff of a large schema takes a while, so the caches are not locked meanwhile
+    if let Some((cache, previous)) = replaced {
+        let diff = cache.diff(&previous);
+        if !diff.is_empty() {
+            tracing::info!("Schema cache changed: {}", diff);
+        }
     }
 }
 
@@ -91,3 +261,163 @@ fn pool_to_conn_str(pool: &PgPool) -> String {
         ),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use pgt_schema_cache::{SchemaCache, Table};
+    use sqlx::{PgPool, Postgres, pool::PoolOptions, postgres::PgConnectOptions};
+
+    use super::SchemaCacheManager;
+
+    fn lazy_pool() -> PgPool {
+        lazy_pool_for("postgres")
+    }
+
+    fn lazy_pool_for(database: &str) -> PgPool {
+        PoolOptions::<Postgres>::new()
+            .connect_lazy_with(PgConnectOptions::new().host("localhost").database(database))
+    }
+
+    fn wait_for_refresh(manager: &SchemaCacheManager) {
+        let start = Instant::now();
+        while manager
+            .caches
+            .read()
+            .unwrap()
+            .values()
+            .any(|inner| inner.loading)
+        {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_stale_cache_while_loading() {
+        // a load that never finishes
+        let manager = SchemaCacheManager::with_loader(|_| Box::pin(std::future::pending()));
+
+        let start = Instant::now();
+        let handle = manager
+            .load(lazy_pool())
+            .expect("Loading a cold cache should not fail");
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(handle.as_ref().tables.is_empty());
+        drop(handle);
+
+        // subsequent calls do not wait for the running refresh either
+        let start = Instant::now();
+        manager
+            .load(lazy_pool())
+            .expect("Loading a cold cache should not fail");
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn returns_the_load_error_once_and_backs_off() {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&attempts);
+        let manager = SchemaCacheManager::with_loader(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Err(sqlx::Error::PoolTimedOut) })
+        });
+
+        assert!(manager.load(lazy_pool()).is_ok());
+        wait_for_refresh(&manager);
+
+        assert!(
+            manager.load(lazy_pool()).is_err(),
+            "The failed refresh should be reported"
+        );
+
+        // within the backoff, the stale cache is returned without retrying
+        let handle = manager
+            .load(lazy_pool())
+            .expect("The error should only be reported once");
+        assert!(handle.as_ref().tables.is_empty());
+        drop(handle);
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn keeps_a_cache_per_connection() {
+        // every database has a single table named like the database
+        let manager = SchemaCacheManager::with_loader(|pool| {
+            let database = pool
+                .connect_options()
+                .get_database()
+                .unwrap_or_default()
+                .to_string();
+
+            Box::pin(async move {
+                Ok(SchemaCache {
+                    tables: vec![Table {
+                        name: database,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                })
+            })
+        });
+
+        let table_names = |pool| {
+            manager
+                .load(pool)
+                .unwrap()
+                .as_ref()
+                .tables
+                .iter()
+                .map(|t| t.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        table_names(lazy_pool_for("analytics"));
+        table_names(lazy_pool_for("billing"));
+        wait_for_refresh(&manager);
+
+        // loading one connection does not replace the cache of the other one
+        assert_eq!(table_names(lazy_pool_for("analytics")), vec!["analytics"]);
+        assert_eq!(table_names(lazy_pool_for("billing")), vec!["billing"]);
+        assert_eq!(table_names(lazy_pool_for("analytics")), vec!["analytics"]);
+
+        assert_eq!(manager.generation(&lazy_pool_for("analytics")), 1);
+        assert_eq!(manager.generation(&lazy_pool_for("unknown")), 0);
+    }
+
+    #[tokio::test]
+    async fn refreshes_loaded_connections_only() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&loads);
+        let manager = SchemaCacheManager::with_loader(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Box::pin(async { Ok(SchemaCache::default()) })
+        });
+
+        // a connection that was never loaded is left for the next load
+        manager.refresh(lazy_pool());
+        wait_for_refresh(&manager);
+        assert_eq!(loads.load(Ordering::SeqCst), 0);
+
+        manager.load(lazy_pool()).unwrap();
+        wait_for_refresh(&manager);
+        assert_eq!(manager.generation(&lazy_pool()), 1);
+
+        // a loaded connection is reloaded although the connection did not change
+        manager.refresh(lazy_pool());
+        wait_for_refresh(&manager);
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+        assert_eq!(manager.generation(&lazy_pool()), 2);
+    }
+}
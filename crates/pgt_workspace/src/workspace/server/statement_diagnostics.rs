@@ -0,0 +1,89 @@
+use std::sync::RwLock;
+
+use dashmap::DashMap;
+use pgt_analyse::{RuleCategories, RuleFix};
+use pgt_configuration::RuleSelector;
+use pgt_diagnostics::serde::Diagnostic as SDiagnostic;
+
+use super::statement_identifier::StatementId;
+
+/// The result of the analysis of a statement, with spans relative to it.
+#[derive(Debug, Clone, Default)]
+pub struct StatementAnalysis {
+    pub diagnostics: Vec<SDiagnostic>,
+    /// The fixes of the reported diagnostics, with edits relative to the statement
+    pub fixes: Vec<RuleFix>,
+}
+
+/// Everything besides the statement itself that its analysis depends on.
+///
+/// An analysis is only reused by a pull with the same key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalysisKey {
+    pub categories: RuleCategories,
+    pub only: Vec<RuleSelector>,
+    pub skip: Vec<RuleSelector>,
+    /// The database the statements are checked against, if any
+    pub database: Option<String>,
+    /// Changes whenever the settings, and with them the connection, are updated
+    pub settings_generation: u64,
+    /// Changes whenever the schema cache is refreshed
+    pub schema_cache_generation: u64,
+}
+
+/// Remembers the last analysis of every statement, so that statements that did not change do
+/// not have to be analysed again.
+///
+/// Spans are relative to the statement, because unchanged statements still move when the text
+/// before them is edited.
+pub struct StatementDiagnosticsStore {
+    /// The key all analyses in `db` were made with
+    key: RwLock<Option<AnalysisKey>>,
+    db: DashMap<StatementId, StatementAnalysis>,
+}
+
+impl StatementDiagnosticsStore {
+    pub fn new() -> StatementDiagnosticsStore {
+        StatementDiagnosticsStore {
+            key: RwLock::new(None),
+            db: DashMap::new(),
+        }
+    }
+
+    /// Returns the analysis of the statement if it was made with `key`
+    pub fn get_analysis(
+        &self,
+        statement: &StatementId,
+        key: &AnalysisKey,
+    ) -> Option<StatementAnalysis> {
+        if self.key.read().unwrap().as_ref() != Some(key) {
+            return None;
+        }
+
+        self.db.get(statement).map(|x| x.clone())
+    }
+
+    /// Remembers the analysis of the statement, forgetting all analyses made with another key
+    pub fn set_analysis(
+        &self,
+        statement: StatementId,
+        key: &AnalysisKey,
+        analysis: StatementAnalysis,
+    ) {
+        let mut current_key = self.key.write().unwrap();
+        if current_key.as_ref() != Some(key) {
+            self.db.clear();
+            *current_key = Some(key.clone());
+        }
+
+        self.db.insert(statement, analysis);
+    }
+
+    pub fn clear_statement(&self, id: &StatementId) {
+        self.db.remove(id);
+
+        if let Some(child_id) = id.get_child_id() {
+            self.db.remove(&child_id);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tracing::{Instrument, debug, debug_span};
+
+use super::StatementId;
+
+/// Receives how long every stage of the analysis of a statement took
+pub(crate) type TimingHook = Arc<dyn Fn(&StatementId, Stage, Duration) + Send + Sync>;
+
+/// A stage of the analysis of a statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stage {
+    /// The database round-trip of the typecheck
+    Typecheck,
+    /// The checks that run without a database, e.g. the linter rules
+    Analysis,
+}
+
+/// Runs the typecheck of statement `id` in its own tracing span and records how long it took.
+///
+/// The duration is emitted as a debug event within the span, and passed to `hook` if set.
+pub(crate) async fn timed<F: Future>(
+    id: StatementId,
+    hook: Option<TimingHook>,
+    future: F,
+) -> F::Output {
+    let span = debug_span!("typecheck_statement", statement = id.raw());
+
+    let start = Instant::now();
+    let output = future.instrument(span.clone()).await;
+    let elapsed = start.elapsed();
+
+    span.in_scope(|| {
+        debug!(
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Typechecked statement"
+        )
+    });
+
+    if let Some(hook) = hook {
+        hook(&id, Stage::Typecheck, elapsed);
+    }
+
+    output
+}
+
+/// Runs the analysis of statement `id` like [timed] runs its typecheck.
+pub(crate) fn timed_analysis<T>(
+    id: &StatementId,
+    hook: Option<&TimingHook>,
+    analyse: impl FnOnce() -> T,
+) -> T {
+    let span = debug_span!("analyse_statement", statement = id.raw());
+
+    let start = Instant::now();
+    let output = span.in_scope(analyse);
+    let elapsed = start.elapsed();
+
+    span.in_scope(|| {
+        debug!(
+            elapsed_ms = elapsed.as_millis() as u64,
+            "Analysed statement"
+        )
+    });
+
+    if let Some(hook) = hook {
+        hook(id, Stage::Analysis, elapsed);
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use super::super::statement_identifier::{StatementId, StatementIdGenerator};
+    use super::{Stage, TimingHook, timed};
+
+    #[test]
+    fn records_a_timing_for_every_statement() {
+        let recorded = Arc::new(Mutex::new(Vec::new()));
+
+        let hook: TimingHook = {
+            let recorded = recorded.clone();
+            Arc::new(move |id: &StatementId, stage: Stage, elapsed: Duration| {
+                assert_eq!(stage, Stage::Typecheck);
+                recorded.lock().unwrap().push((id.clone(), elapsed))
+            })
+        };
+
+        let mut ids = StatementIdGenerator::new();
+        let statements = vec![ids.next(), ids.next(), ids.next()];
+
+        for id in statements.iter() {
+            let output =
+                futures::executor::block_on(timed(id.clone(), Some(hook.clone()), async {
+                    std::thread::sleep(Duration::from_millis(5));
+                    id.raw()
+                }));
+            assert_eq!(output, id.raw());
+        }
+
+        let recorded = recorded.lock().unwrap();
+        assert_eq!(
+            recorded
+                .iter()
+                .map(|(id, _)| id.clone())
+                .collect::<Vec<_>>(),
+            statements
+        );
+        assert!(
+            recorded
+                .iter()
+                .all(|(_, elapsed)| *elapsed >= Duration::from_millis(5))
+        );
+    }
+}
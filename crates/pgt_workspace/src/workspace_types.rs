@@ -7,11 +7,18 @@ use rustc_hash::FxHashSet;
 use schemars::{
     JsonSchema,
     r#gen::{SchemaGenerator, SchemaSettings},
-    schema::{InstanceType, RootSchema, Schema, SchemaObject, SingleOrVec},
+    schema::{InstanceType, Metadata, RootSchema, Schema, SchemaObject, SingleOrVec},
 };
 use serde_json::Value;
 
-use crate::{WorkspaceError, workspace::*};
+use crate::{
+    WorkspaceError,
+    features::{
+        code_actions::CodeActionsResult, completions::CompletionsResult,
+        diagnostics::PullDiagnosticsResult,
+    },
+    workspace::*,
+};
 use biome_js_factory::{
     make,
     syntax::{AnyJsObjectMemberName, AnyTsName, AnyTsType, AnyTsTypeMember, T},
@@ -457,15 +464,69 @@ macro_rules! workspace_method {
 }
 
 /// Returns a list of signature for all the methods in the [Workspace] trait
-pub fn methods() -> [WorkspaceMethod; 8] {
+pub fn methods() -> [WorkspaceMethod; 12] {
     [
         workspace_method!(is_path_ignored),
         workspace_method!(get_file_content),
         workspace_method!(pull_diagnostics),
         workspace_method!(get_completions),
+        workspace_method!(record_completion_outcome),
+        workspace_method!(rename_symbol),
         workspace_method!(update_settings),
+        workspace_method!(validate_settings),
         workspace_method!(open_file),
         workspace_method!(change_file),
         workspace_method!(close_file),
+        workspace_method!(close_all),
     ]
 }
+
+/// Returns a JSON Schema describing the results of the [Workspace] requests
+///
+/// The root schema carries no constraints of its own: the result types are
+/// emitted as named `definitions`, so that clients written in other languages
+/// can generate bindings for them.
+pub fn results_schema() -> RootSchema {
+    let mut generator = SchemaGenerator::from(SchemaSettings::draft07());
+
+    generator.subschema_for::<CompletionsResult>();
+    generator.subschema_for::<PullDiagnosticsResult>();
+    generator.subschema_for::<CodeActionsResult>();
+
+    RootSchema {
+        meta_schema: generator.settings().meta_schema.clone(),
+        schema: SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                title: Some("WorkspaceResults".to_string()),
+                ..Default::default()
+            })),
+            ..Default::default()
+        },
+        definitions: generator.take_definitions(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::results_schema;
+
+    #[test]
+    fn results_schema_contains_result_types() {
+        let schema = results_schema();
+
+        for name in [
+            "CompletionsResult",
+            "PullDiagnosticsResult",
+            "CodeActionsResult",
+        ] {
+            assert!(
+                schema.definitions.contains_key(name),
+                "missing definition for {name}"
+            );
+        }
+
+        let json = serde_json::to_value(&schema).unwrap();
+        assert_eq!(json["title"], "WorkspaceResults");
+        assert!(json["definitions"]["CompletionsResult"].is_object());
+    }
+}
@@ -86,6 +86,12 @@ fn extract_group_metadata(group: &str) -> (&str, Markup) {
                 "Rules that detect potential safety issues in your code."
             },
         ),
+        "style" => (
+            "Style",
+            markup! {
+                "Rules that enforce a consistent and less error-prone way of writing your code."
+            },
+        ),
         _ => panic!("Unknown group ID {group:?}"),
     }
 }
@@ -24,6 +24,12 @@ export interface GetFileContentParams {
 }
 export interface PullDiagnosticsParams {
 	categories: RuleCategories;
+	/**
+	* The range of the document that changed since the last pull.
+
+If set, only the statements overlapping it are analysed again and the diagnostics of all other statements are taken from the last pull, unless it used other filters or the settings or the schema changed in the meantime. 
+	 */
+	changed_range?: TextRange;
 	max_diagnostics: number;
 	only: RuleCode[];
 	path: PgTPath;
@@ -35,6 +41,10 @@ export type RuleCategory = "Lint" | "Action" | "Transformation";
 export interface PullDiagnosticsResult {
 	diagnostics: Diagnostic[];
 	errors: number;
+	/**
+	 * The fixes of the reported diagnostics, as edits of the document
+	 */
+	fixes?: CodeAction[];
 	skipped_diagnostics: number;
 }
 /**
@@ -51,6 +61,11 @@ export interface Diagnostic {
 	tags: DiagnosticTags;
 	verboseAdvices: Advices;
 }
+export interface CodeAction {
+	disabled_reason?: string;
+	kind: CodeActionKind;
+	title: string;
+}
 /**
  * Implementation of [Visitor] collecting serializable [Advice] into a vector.
  */
@@ -62,8 +77,10 @@ export type Category =
 	| "lint/safety/banDropColumn"
 	| "lint/safety/banDropNotNull"
 	| "lint/safety/banDropTable"
+	| "lint/style/reservedIdentifier"
 	| "stdin"
 	| "check"
+	| "complete"
 	| "configuration"
 	| "database/connection"
 	| "internalError/io"
@@ -77,7 +94,8 @@ export type Category =
 	| "dummy"
 	| "lint"
 	| "lint/performance"
-	| "lint/safety";
+	| "lint/safety"
+	| "lint/style";
 export interface Location {
 	path?: Resource_for_String;
 	sourceCode?: string;
@@ -89,6 +107,10 @@ export type MarkupBuf = MarkupNodeBuf[];
  */
 export type Severity = "hint" | "information" | "warning" | "error" | "fatal";
 export type DiagnosticTags = DiagnosticTag[];
+export type CodeActionKind =
+	| { Edit: EditAction }
+	| { Command: CommandAction }
+	| { EditAndCommand: [EditAction, CommandAction] };
 /**
 	* Serializable representation of a [Diagnostic](super::Diagnostic) advice
 
@@ -124,11 +146,41 @@ export type DiagnosticTag =
  * The category for a log advice, defines how the message should be presented to the user.
  */
 export type LogCategory = "none" | "info" | "warn" | "error";
+export interface EditAction {
+	edits: TextEdit2[];
+	/**
+	 * Whether the edits can be applied without review, e.g. by `check --write`
+	 */
+	is_safe: boolean;
+}
+export interface CommandAction {
+	category: CommandActionCategory;
+}
 export interface TextEdit {
 	dictionary: string;
 	ops: CompressedOp[];
 }
 export type Backtrace = BacktraceFrame[];
+export interface TextEdit2 {
+	new_text: string;
+	range: TextRange;
+}
+export type CommandActionCategory = { ExecuteStatement: StatementId };
+/**
+	* `StatementId` can represent IDs for nested statements.
+
+For example, an SQL function really consist of two statements; the function creation and the body:
+
+```sql create or replace function get_product_name(product_id INT) -- the root statement returns varchar as $$ select * from … -- the child statement $$ LANGUAGE plpgsql; ```
+
+For now, we only support SQL functions – no complex, nested statements.
+
+An SQL function only ever has ONE child, that's why the inner `RootId` of a `Root` is the same as the one of its `Child`. 
+	 */
+export type StatementId = { Root: RootId } | { Child: RootId };
+export interface RootId {
+	inner: number;
+}
 export type TextSize = number;
 /**
  * Enumeration of all the supported markup elements
@@ -178,13 +230,28 @@ export interface GetCompletionsParams {
 	 * The Cursor position in the file for which a completion is requested.
 	 */
 	position: TextSize;
+	/**
+	 * The maximum number of completion items, `completions.limit` if not set.
+	 */
+	limit?: number;
 }
 export interface CompletionsResult {
 	items: CompletionItem[];
+	/**
+	 * Whether `completions.telemetry` is enabled, i.e. whether the outcome of the completion should be recorded.
+	 */
+	telemetry?: boolean;
 }
 export interface CompletionItem {
-	completion_text?: CompletionText;
+	/**
+	 * Replaces the token that is being completed, so editors don't duplicate the typed prefix.
+	 */
+	completion_text: CompletionText;
 	description: string;
+	/**
+	 * The comment of the object, e.g. from `COMMENT ON TABLE`, if it has one.
+	 */
+	documentation?: string;
 	kind: CompletionItemKind;
 	label: string;
 	preselected: boolean;
@@ -194,7 +261,7 @@ export interface CompletionItem {
 	sort_text: string;
 }
 /**
-	* The text that the editor should fill in. If `None`, the `label` should be used. Tables, for example, might have different completion_texts:
+	* The text that the editor should fill in. Unless a provider sets a different text, this is the `label`. Tables, for example, might have different completion_texts:
 
 label: "users", description: "Schema: auth", completion_text: "auth.users". 
 	 */
@@ -205,7 +272,49 @@ export interface CompletionText {
 	range: TextRange;
 	text: string;
 }
-export type CompletionItemKind = "table" | "function" | "column" | "schema";
+export type CompletionItemKind =
+	| "table"
+	| "function"
+	| "column"
+	| "schema"
+	| "keyword"
+	| "setting"
+	| "field"
+	| "collation"
+	| "extension"
+	| "alias"
+	| "parameter"
+	| "operatorClass"
+	| "window"
+	| "constraint"
+	| "type"
+	| "policy"
+	| "enumValue";
+export interface RecordCompletionOutcomeParams {
+	/**
+	 * The label of the completion item that was accepted.
+	 */
+	accepted_label: string;
+	/**
+	 * The position of the accepted item among the items that were shown, starting at 0.
+	 */
+	shown_order: number;
+}
+export interface RenameSymbolParams {
+	new_name: string;
+	path: PgTPath;
+	/**
+	 * The position of the table or column name to rename
+	 */
+	position: TextSize;
+}
+export interface RenameSymbolResult {
+	edits: RenameEdit[];
+}
+export interface RenameEdit {
+	new_text: string;
+	range: TextRange;
+}
 export interface UpdateSettingsParams {
 	configuration: PartialConfiguration;
 	gitignore_matches: string[];
@@ -220,6 +329,10 @@ export interface PartialConfiguration {
 	 * A field for the [JSON schema](https://json-schema.org/) specification
 	 */
 	$schema?: string;
+	/**
+	 * The configuration of the completions
+	 */
+	completions?: PartialCompletionsConfiguration;
 	/**
 	 * The configuration of the database connection
 	 */
@@ -241,6 +354,39 @@ export interface PartialConfiguration {
 	 */
 	vcs?: PartialVcsConfiguration;
 }
+/**
+ * The configuration of the completions
+ */
+export interface PartialCompletionsConfiguration {
+	/**
+	 * A list of Unix shell style patterns. Objects whose name matches one of these patterns, e.g. `_deprecated*`, are not suggested.
+	 */
+	hiddenObjects?: StringSet;
+	/**
+	 * Tables and columns whose comment contains this marker, e.g. `@deprecated`, are not suggested.
+	 */
+	hiddenObjectsCommentMarker?: string;
+	/**
+	 * Whether the objects matched by `hiddenObjects` and `hiddenObjectsCommentMarker` are suggested anyway. Defaults to false
+	 */
+	includeHiddenObjects?: boolean;
+	/**
+	 * Whether objects in system schemas like `pg_catalog` and `information_schema` are suggested. Defaults to true
+	 */
+	includeSystemSchemas?: boolean;
+	/**
+	 * The maximum number of completion items that are offered. Defaults to 50
+	 */
+	limit?: number;
+	/**
+	 * The number of characters that have to be typed before completions are offered. Useful for large schemas. Defaults to 0
+	 */
+	minChars?: number;
+	/**
+	 * Whether every accepted completion and its rank among the shown items are emitted as structured tracing events, to evaluate the ranking. Defaults to false
+	 */
+	telemetry?: boolean;
+}
 /**
  * The configuration of the database connection.
  */
@@ -250,6 +396,10 @@ export interface PartialDatabaseConfiguration {
 	 * The connection timeout in seconds.
 	 */
 	connTimeoutSecs?: number;
+	/**
+	 * The other databases on the same server that a file may select with a `-- pgt:connection=<name>` directive in its leading comments.
+	 */
+	connections?: StringSet;
 	/**
 	 * The name of the database.
 	 */
@@ -258,6 +408,10 @@ export interface PartialDatabaseConfiguration {
 	 * The host of the database. Required if you want database-related features. All else falls back to sensible defaults.
 	 */
 	host?: string;
+	/**
+	 * The time in milliseconds after which an idle connection is closed. A new connection is opened on the next database access.
+	 */
+	idleTimeoutMs?: number;
 	/**
 	 * The password to connect to the database.
 	 */
@@ -287,6 +441,14 @@ export interface PartialFilesConfiguration {
 	 * The maximum allowed size for source code files in bytes. Files above this limit will be ignored for performance reasons. Defaults to 1 MiB
 	 */
 	maxSize?: number;
+	/**
+	 * Whether a single newline terminates a statement. Useful for files that do not use semicolons. By default, only blank lines and semicolons end a statement.
+	 */
+	newlineTerminatesStatements?: boolean;
+	/**
+	 * The file extensions of SQL files, without the leading dot. Defaults to `sql` and `pg`.
+	 */
+	sqlExtensions?: StringSet;
 }
 export interface PartialLinterConfiguration {
 	/**
@@ -357,6 +519,7 @@ export interface Rules {
 	 */
 	recommended?: boolean;
 	safety?: Safety;
+	style?: Style;
 }
 export type VcsClientKind = "git";
 /**
@@ -388,6 +551,23 @@ export interface Safety {
 	 */
 	recommended?: boolean;
 }
+/**
+ * A list of rules that belong to this group
+ */
+export interface Style {
+	/**
+	 * It enables ALL rules for this group.
+	 */
+	all?: boolean;
+	/**
+	 * It enables the recommended rules for this group
+	 */
+	recommended?: boolean;
+	/**
+	 * Object names should not be reserved words.
+	 */
+	reservedIdentifier?: RuleConfiguration_for_Null;
+}
 export type RuleConfiguration_for_Null =
 	| RulePlainConfiguration
 	| RuleWithOptions_for_Null;
@@ -422,6 +602,7 @@ export interface ChangeParams {
 export interface CloseFileParams {
 	path: PgTPath;
 }
+export interface CloseAllParams {}
 export type Configuration = PartialConfiguration;
 export interface Workspace {
 	isPathIgnored(params: IsPathIgnoredParams): Promise<boolean>;
@@ -430,10 +611,14 @@ export interface Workspace {
 		params: PullDiagnosticsParams,
 	): Promise<PullDiagnosticsResult>;
 	getCompletions(params: GetCompletionsParams): Promise<CompletionsResult>;
+	recordCompletionOutcome(params: RecordCompletionOutcomeParams): Promise<void>;
+	renameSymbol(params: RenameSymbolParams): Promise<RenameSymbolResult>;
 	updateSettings(params: UpdateSettingsParams): Promise<void>;
+	validateSettings(params: UpdateSettingsParams): Promise<void>;
 	openFile(params: OpenFileParams): Promise<void>;
 	changeFile(params: ChangeFileParams): Promise<void>;
 	closeFile(params: CloseFileParams): Promise<void>;
+	closeAll(params: CloseAllParams): Promise<void>;
 	destroy(): void;
 }
 export function createWorkspace(transport: Transport): Workspace {
@@ -450,9 +635,18 @@ export function createWorkspace(transport: Transport): Workspace {
 		getCompletions(params) {
 			return transport.request("pgt/get_completions", params);
 		},
+		recordCompletionOutcome(params) {
+			return transport.request("pgt/record_completion_outcome", params);
+		},
+		renameSymbol(params) {
+			return transport.request("pgt/rename_symbol", params);
+		},
 		updateSettings(params) {
 			return transport.request("pgt/update_settings", params);
 		},
+		validateSettings(params) {
+			return transport.request("pgt/validate_settings", params);
+		},
 		openFile(params) {
 			return transport.request("pgt/open_file", params);
 		},
@@ -462,6 +656,9 @@ export function createWorkspace(transport: Transport): Workspace {
 		closeFile(params) {
 			return transport.request("pgt/close_file", params);
 		},
+		closeAll(params) {
+			return transport.request("pgt/close_all", params);
+		},
 		destroy() {
 			transport.destroy();
 		},
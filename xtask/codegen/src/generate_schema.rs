@@ -0,0 +1,16 @@
+use crate::update;
+use pgt_workspace::workspace_types::results_schema;
+use xtask::{project_root, Mode, Result};
+
+/// Writes the JSON Schema of the workspace results next to the TypeScript bindings
+pub fn generate_schema(mode: Mode) -> Result<()> {
+    let schema_path =
+        project_root().join("packages/@postgrestools/backend-jsonrpc/src/workspace.schema.json");
+
+    let mut content = serde_json::to_string_pretty(&results_schema())?;
+    content.push('\n');
+
+    update(&schema_path, &content, &mode)?;
+
+    Ok(())
+}
@@ -5,12 +5,14 @@ mod generate_bindings;
 mod generate_configuration;
 mod generate_crate;
 mod generate_new_analyser_rule;
+mod generate_schema;
 
 pub use self::generate_analyser::generate_analyser;
 pub use self::generate_bindings::generate_bindings;
 pub use self::generate_configuration::generate_rules_configuration;
 pub use self::generate_crate::generate_crate;
 pub use self::generate_new_analyser_rule::generate_new_analyser_rule;
+pub use self::generate_schema::generate_schema;
 use bpaf::Bpaf;
 use generate_new_analyser_rule::Category;
 use std::path::Path;
@@ -57,6 +59,9 @@ pub enum TaskCommand {
     /// Generate TypeScript definitions for the JavaScript bindings to the Workspace API
     #[bpaf(command)]
     Bindings,
+    /// Generate the JSON Schema of the Workspace API results for clients in other languages
+    #[bpaf(command)]
+    Schema,
     /// Generate factory functions for the analyser and the configuration of the analysers
     #[bpaf(command)]
     Analyser,
@@ -132,8 +132,10 @@ fn assert_lint(
             for stmt in stmts.ranges {
                 match pgt_query_ext::parse(&code[stmt]) {
                     Ok(ast) => {
-                        for rule_diag in analyser.run(pgt_analyser::AnalyserContext { root: &ast })
-                        {
+                        for rule_diag in analyser.run(pgt_analyser::AnalyserContext {
+                            root: &ast,
+                            text: &code[stmt],
+                        }) {
                             let diag = pgt_diagnostics::serde::Diagnostic::new(rule_diag);
 
                             let category = diag.category().expect("linter diagnostic has no code");